@@ -0,0 +1,20 @@
+#version 430
+
+in vec3 EyePosition;
+in vec3 EyeNormal;
+in vec2 OutVertexTexCoord;
+
+layout( location = 0 ) out vec3 gPosition;
+layout( location = 1 ) out vec3 gNormal;
+layout( location = 2 ) out vec4 gAlbedoSpec;
+
+uniform sampler2D tex;
+
+void main()
+{
+    gPosition = EyePosition;
+    gNormal = normalize( EyeNormal );
+    gAlbedoSpec.rgb = texture( tex, OutVertexTexCoord ).rgb;
+    // specular intensity
+    gAlbedoSpec.a = 0.5;
+}
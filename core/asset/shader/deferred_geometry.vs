@@ -0,0 +1,23 @@
+#version 430
+
+layout (location = 0) in vec3 VertexPosition;
+layout (location = 1) in vec3 VertexNormal;
+layout (location = 2) in vec2 VertexTexCoord;
+
+out vec3 EyePosition;
+out vec3 EyeNormal;
+out vec2 OutVertexTexCoord;
+
+uniform mat4 ModelViewMatrix;
+uniform mat3 NormalMatrix;
+uniform mat4 ProjectionMatrix;
+uniform mat4 MVP;
+
+void main()
+{
+    // position and normal in eye space, as lights of the lighting pass
+    EyePosition = vec3( ModelViewMatrix * vec4( VertexPosition, 1.0 ) );
+    EyeNormal = normalize( NormalMatrix * VertexNormal );
+    OutVertexTexCoord = VertexTexCoord;
+    gl_Position = MVP * vec4( VertexPosition, 1.0 );
+}
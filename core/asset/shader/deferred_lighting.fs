@@ -0,0 +1,54 @@
+#version 430
+
+in vec2 OutVertexTexCoord;
+
+layout( location = 0 ) out vec4 FragColor;
+
+uniform sampler2D gPosition;
+uniform sampler2D gNormal;
+uniform sampler2D gAlbedoSpec;
+
+struct LightInfo {
+    vec3 Position; // Light position in eye coords.
+    vec3 Color;
+    float Linear;
+    float Quadratic;
+    float Radius; // Distance beyond which the light is ignored.
+};
+
+// matches deferred::MAX_LIGHTS_PER_PASS
+const int MAX_LIGHTS = 32;
+uniform LightInfo lights[MAX_LIGHTS];
+uniform float LightCount;
+// applied by the first light batch only
+uniform vec3 Ambient;
+
+void main()
+{
+    vec3 position = texture( gPosition, OutVertexTexCoord ).rgb;
+    vec3 normal = texture( gNormal, OutVertexTexCoord ).rgb;
+    vec3 albedo = texture( gAlbedoSpec, OutVertexTexCoord ).rgb;
+    float specular = texture( gAlbedoSpec, OutVertexTexCoord ).a;
+
+    // no geometry written, keep the window content
+    if( dot( normal, normal ) == 0.0 )
+	discard;
+
+    vec3 lighting = albedo * Ambient;
+    vec3 v = normalize( -position );
+    for( int i = 0; i < MAX_LIGHTS; ++i )
+    {
+	if( float(i) >= LightCount )
+	    break;
+	float d = length( lights[i].Position - position );
+	if( d > lights[i].Radius )
+	    continue;
+	vec3 s = normalize( lights[i].Position - position );
+	vec3 diffuse = max( dot( normal, s ), 0.0 ) * albedo * lights[i].Color;
+	vec3 h = normalize( s + v );
+	vec3 spec = lights[i].Color * specular * pow( max( dot( normal, h ), 0.0 ), 16.0 );
+	float attenuation = 1.0 / ( 1.0 + lights[i].Linear * d + lights[i].Quadratic * d * d );
+	lighting += ( diffuse + spec ) * attenuation;
+    }
+    FragColor = vec4( lighting, 1.0 );
+}
@@ -0,0 +1,13 @@
+#version 430
+
+layout (location = 0) in vec3 VertexPosition;
+layout (location = 2) in vec2 VertexTexCoord;
+
+out vec2 OutVertexTexCoord;
+
+void main()
+{
+    // screen quad in normalized device coordinates
+    OutVertexTexCoord = VertexTexCoord;
+    gl_Position = vec4( VertexPosition, 1.0 );
+}
@@ -1,3 +1,5 @@
+//raw gl prototype, superseded by implement::render::deferred and RenderGraph::deferred
+
 extern crate gl;
 extern crate glutin;
 extern crate rand;
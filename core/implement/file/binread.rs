@@ -0,0 +1,67 @@
+use std::str;
+
+///little endian readers for binary model formats, offsets in bytes
+
+pub fn read_u8( buf: &[u8], offset: usize ) -> Result< u8, & 'static str > {
+    match buf.get( offset ) {
+        Some( x ) => Ok( *x ),
+        None => Err( "read out of bounds" ),
+    }
+}
+
+pub fn read_u16( buf: &[u8], offset: usize ) -> Result< u16, & 'static str > {
+    if offset + 2 > buf.len() {
+        return Err( "read out of bounds" )
+    }
+    Ok( buf[offset] as u16 | ( buf[offset+1] as u16 ) << 8 )
+}
+
+pub fn read_i16( buf: &[u8], offset: usize ) -> Result< i16, & 'static str > {
+    Ok( read_u16( buf, offset )? as i16 )
+}
+
+pub fn read_u32( buf: &[u8], offset: usize ) -> Result< u32, & 'static str > {
+    if offset + 4 > buf.len() {
+        return Err( "read out of bounds" )
+    }
+    Ok( buf[offset] as u32 |
+        ( buf[offset+1] as u32 ) << 8 |
+        ( buf[offset+2] as u32 ) << 16 |
+        ( buf[offset+3] as u32 ) << 24 )
+}
+
+pub fn read_i32( buf: &[u8], offset: usize ) -> Result< i32, & 'static str > {
+    Ok( read_u32( buf, offset )? as i32 )
+}
+
+pub fn read_f32( buf: &[u8], offset: usize ) -> Result< f32, & 'static str > {
+    Ok( f32::from_bits( read_u32( buf, offset )? ) )
+}
+
+pub fn read_vec3( buf: &[u8], offset: usize ) -> Result< [f32;3], & 'static str > {
+    Ok( [ read_f32( buf, offset )?,
+          read_f32( buf, offset + 4 )?,
+          read_f32( buf, offset + 8 )? ] )
+}
+
+///reads a fixed length null padded string
+pub fn read_str( buf: &[u8], offset: usize, len: usize ) -> Result< String, & 'static str > {
+    if offset + len > buf.len() {
+        return Err( "read out of bounds" )
+    }
+    let s = &buf[ offset..offset + len ];
+    let end = s.iter().position( |&x| x == 0 ).unwrap_or( len );
+    match str::from_utf8( &s[..end] ) {
+        Ok( o ) => Ok( String::from( o ) ),
+        Err( _ ) => Err( "invalid string encoding" ),
+    }
+}
+
+///reads a non-negative count or offset field
+pub fn read_count( buf: &[u8], offset: usize ) -> Result< usize, & 'static str > {
+    let v = read_i32( buf, offset )?;
+    if v < 0 {
+        return Err( "negative count or offset" )
+    }
+    Ok( v as usize )
+}
@@ -0,0 +1,241 @@
+use interface::i_file::IParseBytes;
+use interface::i_md2::model::*;
+use interface::i_md5::compute::{ ComputeCollection, SubMesh };
+
+use implement::file::binread::*;
+
+const MD2_IDENT: &[u8] = b"IDP2";
+const MD2_VERSION: i32 = 8;
+const MD2_HEADER_LEN: usize = 68;
+
+///precomputed vertex normals indexed by the normal index stored with each compressed vertex
+pub const ANORMS: [ [f32;3]; 162 ] = [
+    [ -0.525731,  0.000000,  0.850651 ], [ -0.442863,  0.238856,  0.864188 ], [ -0.295242,  0.000000,  0.955423 ],
+    [ -0.309017,  0.500000,  0.809017 ], [ -0.162460,  0.262866,  0.951056 ], [  0.000000,  0.000000,  1.000000 ],
+    [  0.000000,  0.850651,  0.525731 ], [ -0.147621,  0.716567,  0.681718 ], [  0.147621,  0.716567,  0.681718 ],
+    [  0.000000,  0.525731,  0.850651 ], [  0.309017,  0.500000,  0.809017 ], [  0.525731,  0.000000,  0.850651 ],
+    [  0.295242,  0.000000,  0.955423 ], [  0.442863,  0.238856,  0.864188 ], [  0.162460,  0.262866,  0.951056 ],
+    [ -0.681718,  0.147621,  0.716567 ], [ -0.809017,  0.309017,  0.500000 ], [ -0.587785,  0.425325,  0.688191 ],
+    [ -0.850651,  0.525731,  0.000000 ], [ -0.864188,  0.442863,  0.238856 ], [ -0.716567,  0.681718,  0.147621 ],
+    [ -0.688191,  0.587785,  0.425325 ], [ -0.500000,  0.809017,  0.309017 ], [ -0.238856,  0.864188,  0.442863 ],
+    [ -0.425325,  0.688191,  0.587785 ], [ -0.716567,  0.681718, -0.147621 ], [ -0.500000,  0.809017, -0.309017 ],
+    [ -0.525731,  0.850651,  0.000000 ], [  0.000000,  0.850651, -0.525731 ], [ -0.238856,  0.864188, -0.442863 ],
+    [  0.000000,  0.955423, -0.295242 ], [ -0.262866,  0.951056, -0.162460 ], [  0.000000,  1.000000,  0.000000 ],
+    [  0.000000,  0.955423,  0.295242 ], [ -0.262866,  0.951056,  0.162460 ], [  0.238856,  0.864188,  0.442863 ],
+    [  0.262866,  0.951056,  0.162460 ], [  0.500000,  0.809017,  0.309017 ], [  0.238856,  0.864188, -0.442863 ],
+    [  0.262866,  0.951056, -0.162460 ], [  0.500000,  0.809017, -0.309017 ], [  0.850651,  0.525731,  0.000000 ],
+    [  0.716567,  0.681718,  0.147621 ], [  0.716567,  0.681718, -0.147621 ], [  0.525731,  0.850651,  0.000000 ],
+    [  0.425325,  0.688191,  0.587785 ], [  0.864188,  0.442863,  0.238856 ], [  0.688191,  0.587785,  0.425325 ],
+    [  0.809017,  0.309017,  0.500000 ], [  0.681718,  0.147621,  0.716567 ], [  0.587785,  0.425325,  0.688191 ],
+    [  0.955423,  0.295242,  0.000000 ], [  1.000000,  0.000000,  0.000000 ], [  0.951056,  0.162460,  0.262866 ],
+    [  0.850651, -0.525731,  0.000000 ], [  0.955423, -0.295242,  0.000000 ], [  0.864188, -0.442863,  0.238856 ],
+    [  0.951056, -0.162460,  0.262866 ], [  0.809017, -0.309017,  0.500000 ], [  0.681718, -0.147621,  0.716567 ],
+    [  0.850651,  0.000000,  0.525731 ], [  0.864188,  0.442863, -0.238856 ], [  0.809017,  0.309017, -0.500000 ],
+    [  0.951056,  0.162460, -0.262866 ], [  0.525731,  0.000000, -0.850651 ], [  0.681718,  0.147621, -0.716567 ],
+    [  0.681718, -0.147621, -0.716567 ], [  0.850651,  0.000000, -0.525731 ], [  0.809017, -0.309017, -0.500000 ],
+    [  0.864188, -0.442863, -0.238856 ], [  0.951056, -0.162460, -0.262866 ], [  0.147621,  0.716567, -0.681718 ],
+    [  0.309017,  0.500000, -0.809017 ], [  0.425325,  0.688191, -0.587785 ], [  0.442863,  0.238856, -0.864188 ],
+    [  0.587785,  0.425325, -0.688191 ], [  0.688191,  0.587785, -0.425325 ], [ -0.147621,  0.716567, -0.681718 ],
+    [ -0.309017,  0.500000, -0.809017 ], [  0.000000,  0.525731, -0.850651 ], [ -0.525731,  0.000000, -0.850651 ],
+    [ -0.442863,  0.238856, -0.864188 ], [ -0.295242,  0.000000, -0.955423 ], [ -0.162460,  0.262866, -0.951056 ],
+    [  0.000000,  0.000000, -1.000000 ], [  0.295242,  0.000000, -0.955423 ], [  0.162460,  0.262866, -0.951056 ],
+    [ -0.442863, -0.238856, -0.864188 ], [ -0.309017, -0.500000, -0.809017 ], [ -0.162460, -0.262866, -0.951056 ],
+    [  0.000000, -0.850651, -0.525731 ], [ -0.147621, -0.716567, -0.681718 ], [  0.147621, -0.716567, -0.681718 ],
+    [  0.000000, -0.525731, -0.850651 ], [  0.309017, -0.500000, -0.809017 ], [  0.442863, -0.238856, -0.864188 ],
+    [  0.162460, -0.262866, -0.951056 ], [  0.238856, -0.864188, -0.442863 ], [  0.500000, -0.809017, -0.309017 ],
+    [  0.425325, -0.688191, -0.587785 ], [  0.716567, -0.681718, -0.147621 ], [  0.688191, -0.587785, -0.425325 ],
+    [  0.587785, -0.425325, -0.688191 ], [  0.000000, -0.955423, -0.295242 ], [  0.000000, -1.000000,  0.000000 ],
+    [  0.262866, -0.951056, -0.162460 ], [  0.000000, -0.850651,  0.525731 ], [  0.000000, -0.955423,  0.295242 ],
+    [  0.238856, -0.864188,  0.442863 ], [  0.262866, -0.951056,  0.162460 ], [  0.500000, -0.809017,  0.309017 ],
+    [  0.716567, -0.681718,  0.147621 ], [  0.525731, -0.850651,  0.000000 ], [ -0.238856, -0.864188, -0.442863 ],
+    [ -0.500000, -0.809017, -0.309017 ], [ -0.262866, -0.951056, -0.162460 ], [ -0.850651, -0.525731,  0.000000 ],
+    [ -0.716567, -0.681718, -0.147621 ], [ -0.716567, -0.681718,  0.147621 ], [ -0.525731, -0.850651,  0.000000 ],
+    [ -0.500000, -0.809017,  0.309017 ], [ -0.238856, -0.864188,  0.442863 ], [ -0.262866, -0.951056,  0.162460 ],
+    [ -0.864188, -0.442863,  0.238856 ], [ -0.809017, -0.309017,  0.500000 ], [ -0.688191, -0.587785,  0.425325 ],
+    [ -0.681718, -0.147621,  0.716567 ], [ -0.442863, -0.238856,  0.864188 ], [ -0.587785, -0.425325,  0.688191 ],
+    [ -0.309017, -0.500000,  0.809017 ], [ -0.147621, -0.716567,  0.681718 ], [ -0.425325, -0.688191,  0.587785 ],
+    [ -0.162460, -0.262866,  0.951056 ], [  0.442863, -0.238856,  0.864188 ], [  0.162460, -0.262866,  0.951056 ],
+    [  0.309017, -0.500000,  0.809017 ], [  0.147621, -0.716567,  0.681718 ], [  0.000000, -0.525731,  0.850651 ],
+    [  0.425325, -0.688191,  0.587785 ], [  0.587785, -0.425325,  0.688191 ], [  0.688191, -0.587785,  0.425325 ],
+    [ -0.955423,  0.295242,  0.000000 ], [ -0.951056,  0.162460,  0.262866 ], [ -1.000000,  0.000000,  0.000000 ],
+    [ -0.850651,  0.000000,  0.525731 ], [ -0.955423, -0.295242,  0.000000 ], [ -0.951056, -0.162460,  0.262866 ],
+    [ -0.864188,  0.442863, -0.238856 ], [ -0.951056,  0.162460, -0.262866 ], [ -0.809017,  0.309017, -0.500000 ],
+    [ -0.864188, -0.442863, -0.238856 ], [ -0.951056, -0.162460, -0.262866 ], [ -0.809017, -0.309017, -0.500000 ],
+    [ -0.681718,  0.147621, -0.716567 ], [ -0.681718, -0.147621, -0.716567 ], [ -0.850651,  0.000000, -0.525731 ],
+    [ -0.688191,  0.587785, -0.425325 ], [ -0.587785,  0.425325, -0.688191 ], [ -0.425325,  0.688191, -0.587785 ],
+    [ -0.425325, -0.688191, -0.587785 ], [ -0.587785, -0.425325, -0.688191 ], [ -0.688191, -0.587785, -0.425325 ],
+];
+
+pub struct Md2Parser {}
+
+impl IParseBytes for Md2Parser {
+    type output = Md2Root;
+
+    fn parse( buf: &[u8] ) -> Result< Self::output, & 'static str > {
+
+        if buf.len() < MD2_HEADER_LEN || &buf[0..4] != MD2_IDENT {
+            return Err( "md2 ident invalid" )
+        }
+        if read_i32( buf, 4 )? != MD2_VERSION {
+            return Err( "md2 version unsupported" )
+        }
+        let skin_width = read_count( buf, 8 )?;
+        let skin_height = read_count( buf, 12 )?;
+        let frame_size = read_count( buf, 16 )?;
+        let num_skins = read_count( buf, 20 )?;
+        let num_verts = read_count( buf, 24 )?;
+        let num_st = read_count( buf, 28 )?;
+        let num_tris = read_count( buf, 32 )?;
+        let _num_glcmds = read_count( buf, 36 )?;
+        let num_frames = read_count( buf, 40 )?;
+        let ofs_skins = read_count( buf, 44 )?;
+        let ofs_st = read_count( buf, 48 )?;
+        let ofs_tris = read_count( buf, 52 )?;
+        let ofs_frames = read_count( buf, 56 )?;
+
+        if frame_size < 40 + num_verts * 4 {
+            return Err( "md2 frame size invalid" )
+        }
+
+        let mut m = Md2Root {
+            _skin_width: skin_width as u32,
+            _skin_height: skin_height as u32,
+            _skins: vec![],
+            _tex_coords: vec![],
+            _tris: vec![],
+            _frames: vec![],
+        };
+
+        for i in 0..num_skins {
+            m._skins.push( read_str( buf, ofs_skins + i * 64, 64 )? );
+        }
+        for i in 0..num_st {
+            let o = ofs_st + i * 4;
+            m._tex_coords.push( [ read_i16( buf, o )?, read_i16( buf, o + 2 )? ] );
+        }
+        for i in 0..num_tris {
+            let o = ofs_tris + i * 12;
+            let t = Md2Tri {
+                _vert_indices: [ read_u16( buf, o )?, read_u16( buf, o + 2 )?, read_u16( buf, o + 4 )? ],
+                _tc_indices: [ read_u16( buf, o + 6 )?, read_u16( buf, o + 8 )?, read_u16( buf, o + 10 )? ],
+            };
+            for j in 0..3 {
+                if t._vert_indices[j] as usize >= num_verts {
+                    return Err( "md2 triangle vertex index out of bounds" )
+                }
+                if t._tc_indices[j] as usize >= num_st {
+                    return Err( "md2 triangle tex coord index out of bounds" )
+                }
+            }
+            m._tris.push( t );
+        }
+        for i in 0..num_frames {
+            let o = ofs_frames + i * frame_size;
+            let scale = read_vec3( buf, o )?;
+            let translate = read_vec3( buf, o + 12 )?;
+            let mut f = Md2Frame {
+                _name: read_str( buf, o + 24, 16 )?,
+                _verts: vec![],
+                _normal_indices: vec![],
+            };
+            //compressed vertex: 3 x u8 position, u8 normal index
+            for j in 0..num_verts {
+                let ov = o + 40 + j * 4;
+                let mut v = [0f32;3];
+                for k in 0..3 {
+                    v[k] = read_u8( buf, ov + k )? as f32 * scale[k] + translate[k];
+                }
+                let n = read_u8( buf, ov + 3 )?;
+                if n as usize >= ANORMS.len() {
+                    return Err( "md2 normal index out of bounds" )
+                }
+                f._verts.push( v );
+                f._normal_indices.push( n );
+            }
+            m._frames.push( f );
+        }
+        Ok( m )
+    }
+}
+
+///interpolates between 2 frames into batch vertex, vertex normal, and tex coord data
+pub fn interpolate( m: & Md2Root, frame_start: usize, frame_end: usize, interp: f32 ) -> Result< ComputeCollection, & 'static str > {
+    if frame_start >= m._frames.len() {
+        return Err( "frame_start out of bounds." )
+    }
+    if frame_end >= m._frames.len() {
+        return Err( "frame_end out of bounds." )
+    }
+    let t = interp.max( 0f32 ).min( 1f32 );
+    let fs = & m._frames[ frame_start ];
+    let fe = & m._frames[ frame_end ];
+
+    let mut cc = ComputeCollection {
+        _bbox_lower: [0f32;3],
+        _bbox_upper: [0f32;3],
+        _batch_vert: vec![],
+        _batch_normal: vec![],
+        _batch_tc: vec![],
+        _submeshes: vec![],
+    };
+
+    let verts = fs._verts.iter().zip( fe._verts.iter() )
+        .map( |(a, b)| [ a[0] + ( b[0] - a[0] ) * t,
+                         a[1] + ( b[1] - a[1] ) * t,
+                         a[2] + ( b[2] - a[2] ) * t ] )
+        .collect::< Vec< _ > >();
+
+    let normals = fs._normal_indices.iter().zip( fe._normal_indices.iter() )
+        .map( |(a, b)| {
+            let ( na, nb ) = ( ANORMS[ *a as usize ], ANORMS[ *b as usize ] );
+            let n = [ na[0] + ( nb[0] - na[0] ) * t,
+                      na[1] + ( nb[1] - na[1] ) * t,
+                      na[2] + ( nb[2] - na[2] ) * t ];
+            let l = ( n[0] * n[0] + n[1] * n[1] + n[2] * n[2] ).sqrt();
+            if l > 0. { [ n[0] / l, n[1] / l, n[2] / l ] } else { na }
+        } )
+        .collect::< Vec< _ > >();
+
+    let sw = if m._skin_width > 0 { m._skin_width as f32 } else { 1f32 };
+    let sh = if m._skin_height > 0 { m._skin_height as f32 } else { 1f32 };
+
+    for i in m._tris.iter() {
+        for j in 0..3 {
+            let idx = i._vert_indices[j] as usize;
+            cc._batch_vert.extend_from_slice( &verts[ idx ][..] );
+            cc._batch_normal.extend_from_slice( &normals[ idx ][..] );
+        }
+        for j in 0..3 {
+            let tc = m._tex_coords[ i._tc_indices[j] as usize ];
+            cc._batch_tc.push( tc[0] as f32 / sw );
+            cc._batch_tc.push( tc[1] as f32 / sh );
+        }
+    }
+
+    update_bbox( & mut cc );
+
+    cc._submeshes.push( SubMesh {
+        _shader: m._skins.get( 0 ).cloned().unwrap_or( String::new() ),
+        _texture: None,
+        _vert_offset: 0,
+        _vert_count: cc._batch_vert.len() / 3,
+    } );
+
+    Ok( cc )
+}
+
+pub fn update_bbox( cc: & mut ComputeCollection ) {
+    if cc._batch_vert.len() < 3 {
+        return
+    }
+    let mut lower = [ cc._batch_vert[0], cc._batch_vert[1], cc._batch_vert[2] ];
+    let mut upper = lower;
+    for v in cc._batch_vert.chunks( 3 ) {
+        for h in 0..3 {
+            lower[h] = lower[h].min( v[h] );
+            upper[h] = upper[h].max( v[h] );
+        }
+    }
+    cc._bbox_lower = lower;
+    cc._bbox_upper = upper;
+}
@@ -0,0 +1,264 @@
+extern crate mazth;
+
+use std::f32;
+
+use self::mazth::mat::Mat4;
+
+use interface::i_file::IParseBytes;
+use interface::i_md3::model::*;
+use interface::i_md5::compute::{ ComputeCollection, SubMesh };
+
+use implement::file::binread::*;
+use implement::file::md2;
+
+const MD3_IDENT: &[u8] = b"IDP3";
+const MD3_VERSION: i32 = 15;
+const MD3_HEADER_LEN: usize = 108;
+const MD3_FRAME_LEN: usize = 56;
+const MD3_TAG_LEN: usize = 112;
+const MD3_SURFACE_HEADER_LEN: usize = 108;
+const MD3_XYZ_SCALE: f32 = 1. / 64.;
+
+pub struct Md3Parser {}
+
+impl IParseBytes for Md3Parser {
+    type output = Md3Root;
+
+    fn parse( buf: &[u8] ) -> Result< Self::output, & 'static str > {
+
+        if buf.len() < MD3_HEADER_LEN || &buf[0..4] != MD3_IDENT {
+            return Err( "md3 ident invalid" )
+        }
+        if read_i32( buf, 4 )? != MD3_VERSION {
+            return Err( "md3 version unsupported" )
+        }
+        let name = read_str( buf, 8, 64 )?;
+        let num_frames = read_count( buf, 76 )?;
+        let num_tags = read_count( buf, 80 )?;
+        let num_surfaces = read_count( buf, 84 )?;
+        let _num_skins = read_count( buf, 88 )?;
+        let ofs_frames = read_count( buf, 92 )?;
+        let ofs_tags = read_count( buf, 96 )?;
+        let ofs_surfaces = read_count( buf, 100 )?;
+
+        let mut m = Md3Root {
+            _name: name,
+            _frames: vec![],
+            _tags: vec![],
+            _surfaces: vec![],
+        };
+
+        for i in 0..num_frames {
+            let o = ofs_frames + i * MD3_FRAME_LEN;
+            m._frames.push( Md3Frame {
+                _bound_min: read_vec3( buf, o )?,
+                _bound_max: read_vec3( buf, o + 12 )?,
+                _origin: read_vec3( buf, o + 24 )?,
+                _radius: read_f32( buf, o + 36 )?,
+                _name: read_str( buf, o + 40, 16 )?,
+            } );
+        }
+
+        //tags are stored frame major
+        for i in 0..num_frames {
+            let mut tags = vec![];
+            for j in 0..num_tags {
+                let o = ofs_tags + ( i * num_tags + j ) * MD3_TAG_LEN;
+                tags.push( Md3Tag {
+                    _name: read_str( buf, o, 64 )?,
+                    _origin: read_vec3( buf, o + 64 )?,
+                    _axis: [ read_vec3( buf, o + 76 )?,
+                             read_vec3( buf, o + 88 )?,
+                             read_vec3( buf, o + 100 )? ],
+                } );
+            }
+            m._tags.push( tags );
+        }
+
+        //surfaces are chained, each with offsets relative to its own start
+        let mut o = ofs_surfaces;
+        for _ in 0..num_surfaces {
+            let s = parse_surface( buf, o )?;
+            m._surfaces.push( s.0 );
+            o += s.1;
+        }
+
+        Ok( m )
+    }
+}
+
+///returns surface and its length in bytes
+fn parse_surface( buf: &[u8], o: usize ) -> Result< ( Md3Surface, usize ), & 'static str > {
+    if o + MD3_SURFACE_HEADER_LEN > buf.len() || &buf[ o..o + 4 ] != MD3_IDENT {
+        return Err( "md3 surface ident invalid" )
+    }
+    let name = read_str( buf, o + 4, 64 )?;
+    let num_frames = read_count( buf, o + 72 )?;
+    let num_shaders = read_count( buf, o + 76 )?;
+    let num_verts = read_count( buf, o + 80 )?;
+    let num_tris = read_count( buf, o + 84 )?;
+    let ofs_tris = o + read_count( buf, o + 88 )?;
+    let ofs_shaders = o + read_count( buf, o + 92 )?;
+    let ofs_st = o + read_count( buf, o + 96 )?;
+    let ofs_xyznormal = o + read_count( buf, o + 100 )?;
+    let len = read_count( buf, o + 104 )?;
+    if len == 0 {
+        return Err( "md3 surface length invalid" )
+    }
+
+    let mut s = Md3Surface {
+        _name: name,
+        _shaders: vec![],
+        _tris: vec![],
+        _tex_coords: vec![],
+        _frames: vec![],
+    };
+    for i in 0..num_shaders {
+        s._shaders.push( read_str( buf, ofs_shaders + i * 68, 64 )? );
+    }
+    for i in 0..num_tris {
+        let ot = ofs_tris + i * 12;
+        let t = [ read_u32( buf, ot )?, read_u32( buf, ot + 4 )?, read_u32( buf, ot + 8 )? ];
+        for j in 0..3 {
+            if t[j] as usize >= num_verts {
+                return Err( "md3 triangle vertex index out of bounds" )
+            }
+        }
+        s._tris.push( t );
+    }
+    for i in 0..num_verts {
+        let ot = ofs_st + i * 8;
+        s._tex_coords.push( [ read_f32( buf, ot )?, read_f32( buf, ot + 4 )? ] );
+    }
+    for i in 0..num_frames {
+        let mut verts = vec![];
+        for j in 0..num_verts {
+            let ov = ofs_xyznormal + ( i * num_verts + j ) * 8;
+            verts.push( Md3Vert {
+                _pos: [ read_i16( buf, ov )? as f32 * MD3_XYZ_SCALE,
+                        read_i16( buf, ov + 2 )? as f32 * MD3_XYZ_SCALE,
+                        read_i16( buf, ov + 4 )? as f32 * MD3_XYZ_SCALE ],
+                _normal: decode_normal( read_u16( buf, ov + 6 )? ),
+            } );
+        }
+        s._frames.push( verts );
+    }
+    Ok( ( s, len ) )
+}
+
+///normal is encoded as latitude in high byte and longitude in low byte
+pub fn decode_normal( n: u16 ) -> [f32;3] {
+    let lat = ( ( n >> 8 ) & 0xff ) as f32 * 2. * f32::consts::PI / 255.;
+    let lng = ( n & 0xff ) as f32 * 2. * f32::consts::PI / 255.;
+    [ lat.cos() * lng.sin(),
+      lat.sin() * lng.sin(),
+      lng.cos() ]
+}
+
+///interpolates between 2 frames into batch vertex, normal, and tex coord data with a submesh per surface
+pub fn interpolate( m: & Md3Root, frame_start: usize, frame_end: usize, interp: f32 ) -> Result< ComputeCollection, & 'static str > {
+    if frame_start >= m._frames.len() {
+        return Err( "frame_start out of bounds." )
+    }
+    if frame_end >= m._frames.len() {
+        return Err( "frame_end out of bounds." )
+    }
+    let t = interp.max( 0f32 ).min( 1f32 );
+
+    let mut cc = ComputeCollection {
+        _bbox_lower: [0f32;3],
+        _bbox_upper: [0f32;3],
+        _batch_vert: vec![],
+        _batch_normal: vec![],
+        _batch_tc: vec![],
+        _submeshes: vec![],
+    };
+
+    for s in m._surfaces.iter() {
+        if frame_start >= s._frames.len() || frame_end >= s._frames.len() {
+            return Err( "surface frame count mismatch." )
+        }
+        let vert_offset = cc._batch_vert.len() / 3;
+        let fs = & s._frames[ frame_start ];
+        let fe = & s._frames[ frame_end ];
+        for tri in s._tris.iter() {
+            for &idx in tri.iter() {
+                let ( a, b ) = ( & fs[ idx as usize ], & fe[ idx as usize ] );
+                let mut n = [0f32;3];
+                for h in 0..3 {
+                    cc._batch_vert.push( a._pos[h] + ( b._pos[h] - a._pos[h] ) * t );
+                    n[h] = a._normal[h] + ( b._normal[h] - a._normal[h] ) * t;
+                }
+                let l = ( n[0] * n[0] + n[1] * n[1] + n[2] * n[2] ).sqrt();
+                for h in 0..3 {
+                    cc._batch_normal.push( if l > 0. { n[h] / l } else { n[h] } );
+                }
+                cc._batch_tc.extend_from_slice( & s._tex_coords[ idx as usize ][..] );
+            }
+        }
+        cc._submeshes.push( SubMesh {
+            _shader: s._shaders.get( 0 ).cloned().unwrap_or( s._name.clone() ),
+            _texture: None,
+            _vert_offset: vert_offset,
+            _vert_count: cc._batch_vert.len() / 3 - vert_offset,
+        } );
+    }
+
+    md2::update_bbox( & mut cc );
+
+    Ok( cc )
+}
+
+///interpolated tag transform in row major form, for attaching another model to this one
+pub fn tag_xform( m: & Md3Root, name: &str, frame_start: usize, frame_end: usize, interp: f32 ) -> Result< Mat4< f32 >, & 'static str > {
+    if frame_start >= m._tags.len() {
+        return Err( "frame_start out of bounds." )
+    }
+    if frame_end >= m._tags.len() {
+        return Err( "frame_end out of bounds." )
+    }
+    let t = interp.max( 0f32 ).min( 1f32 );
+    let a = match m._tags[ frame_start ].iter().find( |x| x._name == name ) {
+        Some( o ) => o,
+        None => return Err( "tag not found." ),
+    };
+    let b = match m._tags[ frame_end ].iter().find( |x| x._name == name ) {
+        Some( o ) => o,
+        None => return Err( "tag not found." ),
+    };
+    let mut origin = [0f32;3];
+    let mut axis = [[0f32;3];3];
+    for i in 0..3 {
+        origin[i] = a._origin[i] + ( b._origin[i] - a._origin[i] ) * t;
+        for j in 0..3 {
+            axis[i][j] = a._axis[i][j] + ( b._axis[i][j] - a._axis[i][j] ) * t;
+        }
+    }
+    let axis = orthonormalize( axis );
+    //basis vectors form the columns of the rotation
+    Ok( Mat4::init( [ axis[0][0], axis[1][0], axis[2][0], origin[0],
+                      axis[0][1], axis[1][1], axis[2][1], origin[1],
+                      axis[0][2], axis[1][2], axis[2][2], origin[2],
+                      0f32, 0f32, 0f32, 1f32 ], true ) )
+}
+
+///gram-schmidt on linearly blended axes
+fn orthonormalize( a: [[f32;3];3] ) -> [[f32;3];3] {
+    let dot = |u: & [f32;3], v: & [f32;3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+    let norm = |u: [f32;3]| {
+        let l = ( u[0] * u[0] + u[1] * u[1] + u[2] * u[2] ).sqrt();
+        if l > 0. { [ u[0] / l, u[1] / l, u[2] / l ] } else { u }
+    };
+    let x = norm( a[0] );
+    let d = dot( & a[1], & x );
+    let y = norm( [ a[1][0] - d * x[0], a[1][1] - d * x[1], a[1][2] - d * x[2] ] );
+    let z = [ x[1] * y[2] - x[2] * y[1],
+              x[2] * y[0] - x[0] * y[2],
+              x[0] * y[1] - x[1] * y[0] ];
+    //preserve handedness of source axes
+    if dot( & z, & a[2] ) < 0. {
+        [ x, y, [ -z[0], -z[1], -z[2] ] ]
+    } else {
+        [ x, y, z ]
+    }
+}
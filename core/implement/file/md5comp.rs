@@ -2,12 +2,16 @@ extern crate mazth;
 
 use std::str;
 use std::f32;
+use std::collections::HashMap;
 
 use self::mazth::{ mat::Mat3x1, quat::Quat };
 
 use interface::i_md5::compute::*;
 use interface::i_md5::rig::{ PoseCollection, PoseJoints };
 use interface::i_md5::mesh::Md5MeshRoot;
+use interface::i_md5::morph::MorphTarget;
+
+use implement::math::quat;
 
 pub fn process( pc: & PoseCollection, m: & Md5MeshRoot, pose_index_start: u64, pose_index_end: u64, interp: f32 ) -> Result< ComputeCollection, & 'static str > {
     if pose_index_start >= pc._frames.len() as u64 {
@@ -29,6 +33,14 @@ pub fn process( pc: & PoseCollection, m: & Md5MeshRoot, pose_index_start: u64, p
 }
 
 pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & PoseJoints, interp: f32 ) -> Result< ComputeCollection, & 'static str > {
+    interpolate_with_morphs( m, pose_start, pose_end, interp, &[], &[] )
+}
+
+///skinning with weighted morph targets applied in bind pose prior to joint transformation
+pub fn interpolate_with_morphs( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & PoseJoints, interp: f32, targets: &[ MorphTarget ], weights: &[ f32 ] ) -> Result< ComputeCollection, & 'static str > {
+    if targets.len() != weights.len() {
+        return Err( "morph target and weight count mismatch." )
+    }
     let mut cc = ComputeCollection {
         // _meshcomputes: vec![],
         _bbox_lower: [0f32;3],
@@ -36,19 +48,23 @@ pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & Pose
         _batch_vert: vec![],
         _batch_normal: vec![],
         _batch_tc: vec![],
+        _submeshes: vec![],
     };
 
     let mut max_pos = [0., 0., 0.];
     let mut min_pos = [0., 0., 0.];
     
-    for i in &m._meshes {
+    for ( mesh_index, i ) in m._meshes.iter().enumerate() {
+        let vert_offset = cc._batch_vert.len() / 3;
+        let morph_pos = accumulate_morphs( mesh_index, i._verts.len(), targets, weights )?;
+        let has_morph = morph_pos.len() > 0;
         let mut mc = MeshCompute {
             _verts: vec![],
             // _tris: vec![],
         };
         mc._verts.reserve_exact( i._verts.len() );
             
-        for j in &i._verts {
+        for ( vert_index, j ) in i._verts.iter().enumerate() {
             let mut vc = VertCompute {
                 _pos: [0f32;3],
                 _normal: [0f32;3],
@@ -76,6 +92,18 @@ pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & Pose
                 vc._pos[0] += ( pose_start_rigjoint._pos[0] + pos_transform._x ) * w._weight_bias;
                 vc._pos[1] += ( pose_start_rigjoint._pos[1] + pos_transform._y ) * w._weight_bias;
                 vc._pos[2] += ( pose_start_rigjoint._pos[2] + pos_transform._z ) * w._weight_bias;
+
+                if has_morph {
+                    //bind pose delta is carried to pose via the joint's rotation relative to bind pose
+                    if joint_index >= m._joints.len() as u64 {
+                        return Err( "joint index out of bounds in mesh joints." )
+                    }
+                    let rot = orient_interp.mul( m._joints[ joint_index as usize ]._rot.inverse().normalize() );
+                    let dp = quat::rotate( & rot, morph_pos[ vert_index ] );
+                    for h in 0..3 {
+                        vc._pos[h] += dp[h] * w._weight_bias;
+                    }
+                }
             }
 
             for h in 0..3 {
@@ -112,8 +140,8 @@ pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & Pose
                 _val: mc._verts[ v2_index as usize ]._pos,
             };
 
-            let tc0 = mc._verts[ v2_index as usize ]._tc;
-            let tc1 = mc._verts[ v2_index as usize ]._tc;
+            let tc0 = mc._verts[ v0_index as usize ]._tc;
+            let tc1 = mc._verts[ v1_index as usize ]._tc;
             let tc2 = mc._verts[ v2_index as usize ]._tc;
             
             let v01 = v1.minus( &v0 ).unwrap();
@@ -131,6 +159,7 @@ pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & Pose
             cc._batch_vert.extend_from_slice( &v0._val[..] );
             cc._batch_vert.extend_from_slice( &v1._val[..] );
             cc._batch_vert.extend_from_slice( &v2._val[..] );
+            //face normal of the morphed positions
             let ns = n._val.into_iter().cycle().cloned().take(9).collect::<Vec<f32>>();
             cc._batch_normal.extend_from_slice( &ns[..] );
             // cc._batch_tc.extend_from_slice( &[0., 0., 0., 0., 0., 0.] );
@@ -138,6 +167,12 @@ pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & Pose
                                                tc1[0], tc1[1],
                                                tc2[0], tc2[1] ] );
         }
+        cc._submeshes.push( SubMesh {
+            _shader: i._shader.clone(),
+            _texture: None,
+            _vert_offset: vert_offset,
+            _vert_count: cc._batch_vert.len() / 3 - vert_offset,
+        } );
         //don't need to save these
         // mc._tris.extend_from_slice( &i._tris[..] );
         // cc._meshcomputes.push( mc );
@@ -147,3 +182,37 @@ pub fn interpolate( m: & Md5MeshRoot, pose_start: & PoseJoints, pose_end: & Pose
     cc._bbox_upper = max_pos;
     Ok( cc )
 }
+
+///sums weighted bind pose position deltas of targets for a mesh, normal deltas are ignored as faces are flat shaded. returns an empty vector if nothing applies
+fn accumulate_morphs( mesh_index: usize, num_verts: usize, targets: &[ MorphTarget ], weights: &[ f32 ] ) -> Result< Vec< [f32;3] >, & 'static str > {
+    let mut pos = vec![];
+    for ( t, &w ) in targets.iter().zip( weights.iter() ) {
+        if t._mesh_index != mesh_index || w == 0. {
+            continue
+        }
+        if pos.len() == 0 {
+            pos = vec![ [0f32;3]; num_verts ];
+        }
+        for d in t._deltas.iter() {
+            if d._vert_index >= num_verts as u64 {
+                return Err( "morph delta vertex index out of bounds." )
+            }
+            for h in 0..3 {
+                pos[ d._vert_index as usize ][h] += d._pos[h] * w;
+            }
+        }
+    }
+    Ok( pos )
+}
+
+///resolves each submesh's shader name to a texture description. returns number of submeshes left unresolved
+pub fn map_submesh_textures( cc: & mut ComputeCollection, materials: & HashMap< String, String > ) -> usize {
+    let mut unresolved = 0;
+    for i in cc._submeshes.iter_mut() {
+        i._texture = materials.get( &i._shader ).cloned();
+        if i._texture.is_none() {
+            unresolved += 1;
+        }
+    }
+    unresolved
+}
@@ -0,0 +1,264 @@
+extern crate mazth;
+
+use std::f32;
+
+use self::mazth::quat::Quat;
+
+use interface::i_md5::anim::Md5AnimRoot;
+use interface::i_md5::rig::PoseJoints;
+use interface::i_md5::compress::{ CompressSettings, CompressStat, KeyPos, KeyRot, JointTrack, Md5AnimCompressed };
+
+use implement::file::md5rig;
+use implement::math::quat;
+
+const QUANT_RANGE: f32 = f32::consts::FRAC_1_SQRT_2;
+const QUANT_MAX: f32 = 65535f32;
+
+///compresses animation by removing keyframes per channel that are reproducible within tolerance
+///via interpolation of neighbouring keys, and storing orientations as quantised quaternions
+pub fn compress( anim: & Md5AnimRoot, settings: & CompressSettings ) -> Result< Md5AnimCompressed, & 'static str > {
+
+    if anim._frames.len() == 0 {
+        return Err( "md5anim has no frames" )
+    }
+    if anim._frames.len() > u16::max_value() as usize + 1 {
+        return Err( "md5anim has too many frames to compress" )
+    }
+    if anim._hierarchy.len() != anim._baseframe.len() {
+        return Err( "md5anim hierarchy and baseframe length mismatch" )
+    }
+
+    let mut tracks = vec![];
+    let mut err_pos = vec![];
+    let mut err_angle = vec![];
+
+    for i in 0..anim._hierarchy.len() {
+
+        //gather local channel values for all frames
+        let mut pos : [ Vec< f32 >; 3 ] = [ vec![], vec![], vec![] ];
+        let mut rot : Vec< Quat< f32 > > = vec![];
+        for f in anim._frames.iter() {
+            let ( p, r ) = md5rig::local_joint( f, & anim._hierarchy[i], & anim._baseframe[i] );
+            for j in 0..3 {
+                pos[j].push( p[j] );
+            }
+            let mut q = quat::normalize( & Quat::<f32>::init_from_vals_auto_w( r[0], r[1], r[2] ) );
+            //keep consecutive orientations in the same hemisphere for interpolation
+            if let Some( prev ) = rot.last() {
+                if quat::dot( prev, & q ) < 0. {
+                    q = quat::scale( & q, -1. );
+                }
+            }
+            rot.push( q );
+        }
+
+        let pos_keys = [ reduce_pos( & pos[0], settings._tolerance_pos ),
+                         reduce_pos( & pos[1], settings._tolerance_pos ),
+                         reduce_pos( & pos[2], settings._tolerance_pos ) ];
+        let rot_keys = reduce_rot( & rot, settings._tolerance_angle );
+
+        //measure actual error introduced
+        let mut e_pos = 0f32;
+        let mut e_angle = 0f32;
+        for k in 0..anim._frames.len() {
+            for j in 0..3 {
+                let v = sample_pos( & pos_keys[j], k as f32 );
+                e_pos = e_pos.max( ( v - pos[j][k] ).abs() );
+            }
+            let q = sample_rot( & rot_keys, k as f32 );
+            e_angle = e_angle.max( quat_angle( & q, & rot[k] ) );
+        }
+        err_pos.push( e_pos );
+        err_angle.push( e_angle );
+
+        tracks.push( JointTrack {
+            _name: anim._hierarchy[i]._name.clone(),
+            _parent: anim._hierarchy[i]._parent,
+            _pos: pos_keys,
+            _rot: rot_keys,
+        } );
+    }
+
+    let bytes_original = anim._frames.iter().fold( 0u64, |acc, f| acc + f._data.len() as u64 * 4 );
+    let bytes_compressed = compressed_size( & tracks );
+    let ratio = if bytes_compressed > 0 { bytes_original as f32 / bytes_compressed as f32 } else { 0f32 };
+
+    Ok( Md5AnimCompressed {
+        _numframes: anim._frames.len() as u64,
+        _framerate: anim._framerate,
+        _tracks: tracks,
+        _stat: CompressStat {
+            _bytes_original: bytes_original,
+            _bytes_compressed: bytes_compressed,
+            _ratio: ratio,
+            _joint_err_pos: err_pos,
+            _joint_err_angle: err_angle,
+        },
+    } )
+}
+
+///samples pose at time in seconds, clamped to animation duration
+pub fn sample( anim: & Md5AnimCompressed, t: f32 ) -> PoseJoints {
+    sample_frame( anim, t * anim._framerate as f32 )
+}
+
+///samples pose at fractional frame index, clamped to valid frame range
+pub fn sample_frame( anim: & Md5AnimCompressed, frame: f32 ) -> PoseJoints {
+    let f = if anim._numframes == 0 { 0f32 } else { frame.max( 0f32 ).min( ( anim._numframes - 1 ) as f32 ) };
+    let mut pj = PoseJoints {
+        _joints: vec![],
+    };
+    for t in anim._tracks.iter() {
+        let pos = [ sample_pos( & t._pos[0], f ),
+                    sample_pos( & t._pos[1], f ),
+                    sample_pos( & t._pos[2], f ) ];
+        let orient = sample_rot( & t._rot, f );
+        let rj = md5rig::compose_joint( &pj._joints[..], &t._name, t._parent, pos, orient );
+        pj._joints.push( rj );
+    }
+    pj
+}
+
+///size in bytes of compressed tracks: position key = frame(u16) + f32, rotation key = frame(u16) + index(u8) + 3 x u16
+pub fn compressed_size( tracks: & [ JointTrack ] ) -> u64 {
+    tracks.iter().fold( 0u64, |acc, t| {
+        let n_pos = t._pos.iter().fold( 0u64, |a, k| a + k.len() as u64 );
+        acc + n_pos * 6 + t._rot.len() as u64 * 9
+    } )
+}
+
+///greedily extends linear segments while all skipped samples are within tolerance
+fn reduce_pos( v: & [f32], tol: f32 ) -> Vec< KeyPos > {
+    let n = v.len();
+    if v.iter().all( |x| ( x - v[0] ).abs() <= tol ) {
+        return vec![ KeyPos { _frame: 0, _val: v[0] } ]
+    }
+    let fits = |a: usize, b: usize| {
+        ( a + 1..b ).all( |k| {
+            let u = ( k - a ) as f32 / ( b - a ) as f32;
+            ( v[a] + ( v[b] - v[a] ) * u - v[k] ).abs() <= tol
+        } )
+    };
+    let mut keys = vec![ KeyPos { _frame: 0, _val: v[0] } ];
+    let mut start = 0;
+    while start + 1 < n {
+        let mut end = start + 1;
+        while end + 1 < n && fits( start, end + 1 ) {
+            end += 1;
+        }
+        keys.push( KeyPos { _frame: end as u16, _val: v[end] } );
+        start = end;
+    }
+    keys
+}
+
+///same as position reduction but error is measured against slerp of dequantised keys
+fn reduce_rot( v: & [ Quat< f32 > ], tol: f32 ) -> Vec< KeyRot > {
+    let n = v.len();
+    let quantised : Vec< KeyRot > = v.iter().enumerate().map( |(i, q)| quantise( i as u16, q ) ).collect();
+    let dequantised : Vec< Quat< f32 > > = quantised.iter().map( |k| dequantise( k ) ).collect();
+    if v.iter().all( |q| quat_angle( & dequantised[0], q ) <= tol ) {
+        return vec![ quantised[0] ]
+    }
+    let fits = |a: usize, b: usize| {
+        ( a + 1..b ).all( |k| {
+            let u = ( k - a ) as f32 / ( b - a ) as f32;
+            quat_angle( & quat::slerp( & dequantised[a], & dequantised[b], u ), & v[k] ) <= tol
+        } )
+    };
+    let mut keys = vec![ quantised[0] ];
+    let mut start = 0;
+    while start + 1 < n {
+        let mut end = start + 1;
+        while end + 1 < n && fits( start, end + 1 ) {
+            end += 1;
+        }
+        keys.push( quantised[end] );
+        start = end;
+    }
+    keys
+}
+
+///index of the key segment containing frame: keys[idx]._frame <= frame
+fn find_segment< F >( n: usize, frame: f32, key_frame: F ) -> usize where F: Fn( usize ) -> u16 {
+    let ( mut lo, mut hi ) = ( 0usize, n );
+    while hi - lo > 1 {
+        let mid = ( lo + hi ) / 2;
+        if key_frame( mid ) as f32 <= frame {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn sample_pos( keys: & [ KeyPos ], frame: f32 ) -> f32 {
+    let idx = find_segment( keys.len(), frame, |i| keys[i]._frame );
+    if idx + 1 >= keys.len() {
+        return keys[idx]._val
+    }
+    let ( a, b ) = ( & keys[idx], & keys[idx+1] );
+    let u = ( ( frame - a._frame as f32 ) / ( b._frame - a._frame ) as f32 ).max( 0f32 ).min( 1f32 );
+    a._val + ( b._val - a._val ) * u
+}
+
+fn sample_rot( keys: & [ KeyRot ], frame: f32 ) -> Quat< f32 > {
+    let idx = find_segment( keys.len(), frame, |i| keys[i]._frame );
+    if idx + 1 >= keys.len() {
+        return dequantise( & keys[idx] )
+    }
+    let ( a, b ) = ( & keys[idx], & keys[idx+1] );
+    let u = ( ( frame - a._frame as f32 ) / ( b._frame - a._frame ) as f32 ).max( 0f32 ).min( 1f32 );
+    quat::slerp( & dequantise( a ), & dequantise( b ), u )
+}
+
+///smallest three encoding: drop largest magnitude component, made positive, and store remaining three
+fn quantise( frame: u16, q: & Quat< f32 > ) -> KeyRot {
+    let q = [ q._x, q._y, q._z, q._w ];
+    let mut largest = 0;
+    for i in 1..4 {
+        if q[i].abs() > q[largest].abs() {
+            largest = i;
+        }
+    }
+    let sign = if q[largest] < 0. { -1f32 } else { 1f32 };
+    let mut val = [0u16;3];
+    let mut j = 0;
+    for i in 0..4 {
+        if i != largest {
+            let c = ( q[i] * sign ).max( -QUANT_RANGE ).min( QUANT_RANGE );
+            val[j] = ( ( c + QUANT_RANGE ) / ( 2. * QUANT_RANGE ) * QUANT_MAX ).round() as u16;
+            j += 1;
+        }
+    }
+    KeyRot {
+        _frame: frame,
+        _largest: largest as u8,
+        _val: val,
+    }
+}
+
+fn dequantise( k: & KeyRot ) -> Quat< f32 > {
+    let mut q = [0f32;4];
+    let mut j = 0;
+    let mut sum = 0f32;
+    for i in 0..4 {
+        if i != k._largest as usize {
+            q[i] = k._val[j] as f32 / QUANT_MAX * 2. * QUANT_RANGE - QUANT_RANGE;
+            sum += q[i] * q[i];
+            j += 1;
+        }
+    }
+    q[ k._largest as usize ] = ( 1. - sum ).max( 0f32 ).sqrt();
+    quat::normalize( & Quat::<f32>::init_from_vals_raw( q[0], q[1], q[2], q[3] ) )
+}
+
+///rotation angle between orientations, independent of quaternion sign.
+///uses atan2 of chord lengths for precision when orientations are close
+pub fn quat_angle( a: & Quat< f32 >, b: & Quat< f32 > ) -> f32 {
+    let b = if quat::dot( a, b ) < 0. { quat::scale( b, -1. ) } else { *b };
+    let diff = Quat::<f32>::init_from_vals_raw( a._x - b._x, a._y - b._y, a._z - b._z, a._w - b._w );
+    let sum = Quat::<f32>::init_from_vals_raw( a._x + b._x, a._y + b._y, a._z + b._z, a._w + b._w );
+    4. * quat::dot( & diff, & diff ).sqrt().atan2( quat::dot( & sum, & sum ).sqrt() )
+}
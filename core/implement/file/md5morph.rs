@@ -0,0 +1,32 @@
+use interface::i_md5::morph::{ MorphTarget, MorphTrack };
+
+///weight of a track at time in seconds, linearly interpolated between keys sorted by time and held at either end.
+///a nan time holds the first key
+pub fn weight_at( track: & MorphTrack, t: f32 ) -> f32 {
+    let keys = & track._keys;
+    if keys.len() == 0 {
+        return 0f32
+    }
+    if t.is_nan() || t <= keys[0]._time {
+        return keys[0]._weight
+    }
+    let last = & keys[ keys.len() - 1 ];
+    if t >= last._time {
+        return last._weight
+    }
+    //first key after t, at least the second key since t is past the first
+    let idx = keys.iter().position( |k| k._time > t ).unwrap_or( keys.len() - 1 );
+    let ( a, b ) = ( & keys[ idx - 1 ], & keys[ idx ] );
+    let u = ( t - a._time ) / ( b._time - a._time );
+    a._weight + ( b._weight - a._weight ) * u
+}
+
+///weight for each target at time in seconds, matched to tracks by target name. targets without a track get zero weight
+pub fn weights_at( targets: &[ MorphTarget ], tracks: &[ MorphTrack ], t: f32 ) -> Vec< f32 > {
+    targets.iter().map( |x| {
+        match tracks.iter().find( |y| y._target == x._name ) {
+            Some( track ) => weight_at( track, t ),
+            None => 0f32,
+        }
+    } ).collect()
+}
@@ -0,0 +1,173 @@
+use interface::i_md5::rig::{ PoseCollection, PoseJoints };
+use interface::i_md5::mesh::Md5MeshRoot;
+use interface::i_md5::compute::ComputeCollection;
+use interface::i_md5::morph::{ MorphTarget, MorphTrack };
+
+use implement::file::md5rig;
+use implement::file::md5comp;
+use implement::file::md5morph;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopMode {
+    ///wraps around, blending last frame into first frame
+    Loop,
+    ///stops at either end
+    Clamp,
+    ///alternates between forward and backward playback
+    PingPong,
+}
+
+///time based playback of a pose collection
+#[derive(Debug, Clone)]
+pub struct AnimPlayer {
+    pub _numframes: u64,
+    pub _framerate: u64,
+    pub _loop_mode: LoopMode,
+    ///playback rate multiplier, negative for reverse playback
+    pub _speed: f32,
+    pub _playing: bool,
+    ///accumulated playback time in seconds, wrapped into a single period
+    _time: f32,
+}
+
+impl AnimPlayer {
+
+    pub fn init( numframes: u64, framerate: u64 ) -> AnimPlayer {
+        AnimPlayer {
+            _numframes: numframes,
+            _framerate: framerate,
+            _loop_mode: LoopMode::Loop,
+            _speed: 1f32,
+            _playing: true,
+            _time: 0f32,
+        }
+    }
+
+    pub fn init_from_pose_collection( pc: & PoseCollection ) -> AnimPlayer {
+        AnimPlayer::init( pc._frames.len() as u64, pc._framerate )
+    }
+
+    pub fn play( & mut self ) {
+        self._playing = true;
+    }
+
+    pub fn pause( & mut self ) {
+        self._playing = false;
+    }
+
+    pub fn is_playing( & self ) -> bool {
+        self._playing
+    }
+
+    pub fn set_speed( & mut self, speed: f32 ) {
+        self._speed = speed;
+    }
+
+    pub fn set_loop_mode( & mut self, mode: LoopMode ) {
+        self._loop_mode = mode;
+        let t = self._time;
+        self._time = self.wrap( t );
+    }
+
+    ///playback length in seconds. looping includes the blend from last frame back to first frame
+    pub fn duration( & self ) -> f32 {
+        if self._framerate == 0 || self._numframes == 0 {
+            return 0f32
+        }
+        let n = match self._loop_mode {
+            LoopMode::Loop => self._numframes,
+            _ => self._numframes - 1,
+        };
+        n as f32 / self._framerate as f32
+    }
+
+    ///sets playback position in seconds
+    pub fn seek( & mut self, t: f32 ) {
+        self._time = self.wrap( t );
+    }
+
+    ///sets playback position as a fraction of duration
+    pub fn seek_normalized( & mut self, t: f32 ) {
+        let d = self.duration();
+        self.seek( t * d );
+    }
+
+    ///advances playback by elapsed seconds and returns the normalized time
+    pub fn update( & mut self, dt: f32 ) -> f32 {
+        if self._playing {
+            let t = self._time + dt * self._speed;
+            self._time = self.wrap( t );
+        }
+        self.normalized_time()
+    }
+
+    ///current animation time in seconds within [0, duration]
+    pub fn time( & self ) -> f32 {
+        let d = self.duration();
+        match self._loop_mode {
+            LoopMode::PingPong if self._time > d => 2. * d - self._time,
+            _ => self._time,
+        }
+    }
+
+    ///current animation time in [0, 1]
+    pub fn normalized_time( & self ) -> f32 {
+        let d = self.duration();
+        if d > 0. {
+            ( self.time() / d ).max( 0f32 ).min( 1f32 )
+        } else {
+            0f32
+        }
+    }
+
+    ///returns start frame index, end frame index, and interpolation fraction between them
+    pub fn frame_sample( & self ) -> ( u64, u64, f32 ) {
+        if self._numframes == 0 {
+            return ( 0, 0, 0f32 )
+        }
+        let f = self.time() * self._framerate as f32;
+        let start = ( f.floor() as u64 ).min( self._numframes - 1 );
+        let interp = ( f - start as f32 ).max( 0f32 ).min( 1f32 );
+        let end = match self._loop_mode {
+            LoopMode::Loop => ( start + 1 ) % self._numframes,
+            _ => ( start + 1 ).min( self._numframes - 1 ),
+        };
+        ( start, end, interp )
+    }
+
+    ///interpolated skeleton pose at current time
+    pub fn pose( & self, pc: & PoseCollection ) -> Result< PoseJoints, & 'static str > {
+        if pc._frames.len() as u64 != self._numframes {
+            return Err( "pose collection frame count mismatch." )
+        }
+        let ( s, e, interp ) = self.frame_sample();
+        md5rig::interpolate( & pc._frames[ s as usize ], & pc._frames[ e as usize ], interp )
+    }
+
+    ///skinned mesh at current time
+    pub fn compute( & self, pc: & PoseCollection, m: & Md5MeshRoot ) -> Result< ComputeCollection, & 'static str > {
+        let pose = self.pose( pc )?;
+        md5comp::interpolate( m, & pose, & pose, 0f32 )
+    }
+
+    ///skinned mesh at current time with morph target weights sampled from tracks at the same time
+    pub fn compute_with_morphs( & self, pc: & PoseCollection, m: & Md5MeshRoot, targets: &[ MorphTarget ], tracks: &[ MorphTrack ] ) -> Result< ComputeCollection, & 'static str > {
+        let pose = self.pose( pc )?;
+        let weights = md5morph::weights_at( targets, tracks, self.time() );
+        md5comp::interpolate_with_morphs( m, & pose, & pose, 0f32, targets, &weights[..] )
+    }
+
+    fn wrap( & self, t: f32 ) -> f32 {
+        let d = self.duration();
+        if d <= 0. {
+            return 0f32
+        }
+        let period = match self._loop_mode {
+            LoopMode::Loop => d,
+            LoopMode::PingPong => 2. * d,
+            LoopMode::Clamp => { return t.max( 0f32 ).min( d ) },
+        };
+        let w = t % period;
+        if w < 0. { w + period } else { w }
+    }
+}
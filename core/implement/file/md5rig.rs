@@ -32,55 +32,86 @@ fn process_posejoints( f: & Frame, _bbox: & Bound, hier: & Vec< JointHierarchy >
         // _bbox_upper: [0f32;3],
     };
     for i in 0..hier.len() {
-        let start = hier[i]._start_index;
-        let flag = hier[i]._flags;
-        //obtain rotation and position from baseframe
-        let mut bf_pos = baseframe[i]._pos;
-        let mut bf_rot = baseframe[i]._orient;
-        let mut offset = 0;
-        for j in 0..3 { //override data if required from flag
-            if (flag & (1u64 << j)) > 0 {
-                assert!( start + offset < f._data.len() as u64 );
-                bf_pos[j] = f._data[ ( start + offset ) as usize ];
-                offset += 1;
-            }
-        }
-        for j in 0..3 {
-            if (flag & (8u64 << j)) > 0 {
-                assert!( start + offset < f._data.len() as u64 );
-                bf_rot[j] = f._data[ ( start + offset ) as usize ];
-                offset += 1;
-            }
-        }
+        let ( bf_pos, bf_rot ) = local_joint( f, & hier[i], & baseframe[i] );
         //compute rotation quaternion
         let bf_orient = Quat::<f32>::init_from_vals_auto_w( bf_rot[0], bf_rot[1], bf_rot[2] ).normalize();
-        let parent_joint_index = hier[i]._parent;
-        let rj = if parent_joint_index >= 0 { //need to chain transformation from parent joint
-            assert!( parent_joint_index < pj._joints.len() as i64 ); //the referenced parent joint is required to be alrady processed
-            let parent_joint_frame = & pj._joints[ parent_joint_index as usize ];
-            //update position and rotation
-            let pos_quat = Quat::<f32>::init_from_vals_raw( bf_pos[0], bf_pos[1], bf_pos[2], 0. );
-            let orient_inv = parent_joint_frame._orient.inverse().normalize();
-
-            let res = parent_joint_frame._orient.mul( pos_quat ).mul( orient_inv );
-            
-            RigJoint {
-                _name: hier[i]._name.clone(),
-                _parent: hier[i]._parent,
-                _pos: [ parent_joint_frame._pos[0] + res._x,
-                        parent_joint_frame._pos[1] + res._y,
-                        parent_joint_frame._pos[2] + res._z, ],
-                _orient: parent_joint_frame._orient.mul( bf_orient ).normalize(),
-            }
-        }else{ //root joint
-            RigJoint {
-                _name: hier[i]._name.clone(),
-                _parent: hier[i]._parent,
-                _pos: bf_pos,
-                _orient: bf_orient,
-            }
-        };
+        let rj = compose_joint( &pj._joints[..], &hier[i]._name, hier[i]._parent, bf_pos, bf_orient );
         pj._joints.push( rj );
     }
     Ok( pj )
 }
+
+///obtain local position and rotation components of a joint from baseframe, overridden by frame data where flagged
+pub fn local_joint( f: & Frame, hier: & JointHierarchy, baseframe: & FrameJoint ) -> ( [f32;3], [f32;3] ) {
+    let start = hier._start_index;
+    let flag = hier._flags;
+    let mut bf_pos = baseframe._pos;
+    let mut bf_rot = baseframe._orient;
+    let mut offset = 0;
+    for j in 0..3 { //override data if required from flag
+        if (flag & (1u64 << j)) > 0 {
+            assert!( start + offset < f._data.len() as u64 );
+            bf_pos[j] = f._data[ ( start + offset ) as usize ];
+            offset += 1;
+        }
+    }
+    for j in 0..3 {
+        if (flag & (8u64 << j)) > 0 {
+            assert!( start + offset < f._data.len() as u64 );
+            bf_rot[j] = f._data[ ( start + offset ) as usize ];
+            offset += 1;
+        }
+    }
+    ( bf_pos, bf_rot )
+}
+
+///chains a joint's local position and orientation with its already processed parent joint
+pub fn compose_joint( processed: &[ RigJoint ], name: &str, parent_joint_index: i64, pos: [f32;3], orient: Quat<f32> ) -> RigJoint {
+    if parent_joint_index >= 0 { //need to chain transformation from parent joint
+        assert!( parent_joint_index < processed.len() as i64 ); //the referenced parent joint is required to be alrady processed
+        let parent_joint_frame = & processed[ parent_joint_index as usize ];
+        //update position and rotation
+        let pos_quat = Quat::<f32>::init_from_vals_raw( pos[0], pos[1], pos[2], 0. );
+        let orient_inv = parent_joint_frame._orient.inverse().normalize();
+
+        let res = parent_joint_frame._orient.mul( pos_quat ).mul( orient_inv );
+        
+        RigJoint {
+            _name: String::from( name ),
+            _parent: parent_joint_index,
+            _pos: [ parent_joint_frame._pos[0] + res._x,
+                    parent_joint_frame._pos[1] + res._y,
+                    parent_joint_frame._pos[2] + res._z, ],
+            _orient: parent_joint_frame._orient.mul( orient ).normalize(),
+        }
+    }else{ //root joint
+        RigJoint {
+            _name: String::from( name ),
+            _parent: parent_joint_index,
+            _pos: pos,
+            _orient: orient,
+        }
+    }
+}
+
+///blends two poses of the same skeleton: linear for position, slerp for orientation
+pub fn interpolate( pose_start: & PoseJoints, pose_end: & PoseJoints, interp: f32 ) -> Result< PoseJoints, & 'static str > {
+    if pose_start._joints.len() != pose_end._joints.len() {
+        return Err( "pose joint count mismatch." )
+    }
+    let t = interp.max( 0f32 ).min( 1f32 );
+    let mut pj = PoseJoints {
+        _joints: vec![],
+    };
+    for ( a, b ) in pose_start._joints.iter().zip( pose_end._joints.iter() ) {
+        pj._joints.push( RigJoint {
+            _name: a._name.clone(),
+            _parent: a._parent,
+            _pos: [ a._pos[0] + ( b._pos[0] - a._pos[0] ) * t,
+                    a._pos[1] + ( b._pos[1] - a._pos[1] ) * t,
+                    a._pos[2] + ( b._pos[2] - a._pos[2] ) * t, ],
+            _orient: Quat::<f32>::interpolate_slerp( a._orient, b._orient, t ).normalize(),
+        } );
+    }
+    Ok( pj )
+}
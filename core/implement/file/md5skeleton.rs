@@ -0,0 +1,127 @@
+extern crate mazth;
+
+use std::collections::HashMap;
+
+use self::mazth::mat::{ Mat4, Mat4x1 };
+use self::mazth::quat::Quat;
+
+use interface::i_md5::rig::PoseJoints;
+use interface::i_md5::mesh::Md5MeshRoot;
+
+use implement::math::util;
+use implement::math::quat;
+
+///joint name lookup for evaluating poses of a fixed joint hierarchy
+#[derive(Debug, Clone)]
+pub struct Skeleton {
+    pub _joint_names: Vec< String >,
+    _name_to_index: HashMap< String, usize >,
+}
+
+///attachment point relative to a joint
+#[derive(Debug, Clone)]
+pub struct Socket {
+    pub _name: String,
+    pub _joint: String,
+    pub _offset_pos: [f32;3],
+    pub _offset_orient: Quat<f32>,
+}
+
+impl Skeleton {
+    pub fn init( names: &[ String ] ) -> Skeleton {
+        Skeleton {
+            _joint_names: names.to_vec(),
+            _name_to_index: names.iter().enumerate().map( |(i, n)| ( n.clone(), i ) ).collect(),
+        }
+    }
+    pub fn init_from_pose( pose: & PoseJoints ) -> Skeleton {
+        let names = pose._joints.iter().map( |x| x._name.clone() ).collect::< Vec< _ > >();
+        Skeleton::init( &names[..] )
+    }
+    pub fn init_from_mesh( m: & Md5MeshRoot ) -> Skeleton {
+        let names = m._joints.iter().map( |x| x._name.clone() ).collect::< Vec< _ > >();
+        Skeleton::init( &names[..] )
+    }
+    pub fn joint_index( & self, name: &str ) -> Option< usize > {
+        self._name_to_index.get( name ).cloned()
+    }
+    ///joint transform in model space
+    pub fn joint_xform( & self, pose: & PoseJoints, name: &str ) -> Result< Mat4< f32 >, & 'static str > {
+        match self.joint_index( name ) {
+            Some( i ) => joint_xform( pose, i ),
+            None => Err( "joint name not found" ),
+        }
+    }
+    ///joint transform in world space given model to world transform
+    pub fn joint_world_xform( & self, pose: & PoseJoints, name: &str, model: & Mat4< f32 > ) -> Result< Mat4< f32 >, & 'static str > {
+        match self.joint_index( name ) {
+            Some( i ) => joint_world_xform( pose, i, model ),
+            None => Err( "joint name not found" ),
+        }
+    }
+    ///joint position and orientation in world space given model position and orientation
+    pub fn joint_world( & self, pose: & PoseJoints, name: &str, model_pos: [f32;3], model_orient: & Quat<f32> ) -> Result< ( [f32;3], Quat<f32> ), & 'static str > {
+        match self.joint_index( name ) {
+            Some( i ) => joint_world( pose, i, model_pos, model_orient ),
+            None => Err( "joint name not found" ),
+        }
+    }
+}
+
+impl Socket {
+    pub fn init( name: &str, joint: &str, offset_pos: [f32;3], offset_orient: Quat<f32> ) -> Socket {
+        Socket {
+            _name: String::from( name ),
+            _joint: String::from( joint ),
+            _offset_pos: offset_pos,
+            _offset_orient: offset_orient,
+        }
+    }
+    ///world transform of the socket, suitable for placing attached renderables
+    pub fn xform( & self, skel: & Skeleton, pose: & PoseJoints, model: & Mat4< f32 > ) -> Result< Mat4< f32 >, & 'static str > {
+        let joint = skel.joint_world_xform( pose, self._joint.as_str(), model )?;
+        let offset = util::xform_from_pos_quat( self._offset_pos, & self._offset_orient );
+        Ok( joint.mul( & offset ).unwrap() )
+    }
+    ///world position and orientation of the socket
+    pub fn pos_orient( & self, skel: & Skeleton, pose: & PoseJoints, model_pos: [f32;3], model_orient: & Quat<f32> ) -> Result< ( [f32;3], Quat<f32> ), & 'static str > {
+        let ( p, q ) = skel.joint_world( pose, self._joint.as_str(), model_pos, model_orient )?;
+        let offset = quat::rotate( & q, self._offset_pos );
+        Ok( ( [ p[0] + offset[0], p[1] + offset[1], p[2] + offset[2] ],
+              q.mul( self._offset_orient ).normalize() ) )
+    }
+}
+
+pub fn joint_xform( pose: & PoseJoints, index: usize ) -> Result< Mat4< f32 >, & 'static str > {
+    if index >= pose._joints.len() {
+        return Err( "joint index out of bounds" )
+    }
+    let j = & pose._joints[ index ];
+    Ok( util::xform_from_pos_quat( j._pos, & j._orient ) )
+}
+
+pub fn joint_world_xform( pose: & PoseJoints, index: usize, model: & Mat4< f32 > ) -> Result< Mat4< f32 >, & 'static str > {
+    let x = joint_xform( pose, index )?;
+    Ok( model.mul( & x ).unwrap() )
+}
+
+pub fn joint_world( pose: & PoseJoints, index: usize, model_pos: [f32;3], model_orient: & Quat<f32> ) -> Result< ( [f32;3], Quat<f32> ), & 'static str > {
+    if index >= pose._joints.len() {
+        return Err( "joint index out of bounds" )
+    }
+    let j = & pose._joints[ index ];
+    let p = quat::rotate( model_orient, j._pos );
+    Ok( ( [ model_pos[0] + p[0], model_pos[1] + p[1], model_pos[2] + p[2] ],
+          model_orient.mul( j._orient ).normalize() ) )
+}
+
+///world transforms of all joints
+pub fn world_xforms( pose: & PoseJoints, model: & Mat4< f32 > ) -> Vec< Mat4< f32 > > {
+    ( 0..pose._joints.len() ).map( |i| joint_world_xform( pose, i, model ).unwrap() ).collect()
+}
+
+///transforms a point by a row major transform
+pub fn xform_point( m: & Mat4< f32 >, p: [f32;3] ) -> [f32;3] {
+    let v = m.mul_mat4x1( & Mat4x1 { _val: [ p[0], p[1], p[2], 1. ] } ).unwrap();
+    [ v[0], v[1], v[2] ]
+}
@@ -5,7 +5,15 @@ pub mod md5mesh_nom; //alternative parser for md5mesh
 pub mod md5anim_nom; //alternative parser for md5anim
 pub mod md5rig;
 pub mod md5comp;
+pub mod md5compress;
+pub mod md5player;
+pub mod md5skeleton;
+pub mod md5morph;
+pub mod binread;
+pub mod md2;
+pub mod md3;
 pub mod ppm;
 pub mod wavefrontobj;
 pub mod wavefrontcomp;
 
+pub mod wavefrontmtl;
@@ -17,6 +17,7 @@ pub fn process( input: & obj::Collection ) -> Result< compute::ComputeCollection
     let mut batch_vert = vec![];
     let mut batch_normal = vec![];
     let mut batch_tc = vec![];
+    let mut submeshes = vec![];
     let mut bbox_upper = [ 0f32; 3 ];
     let mut bbox_lower = [ 0f32; 3 ];
 
@@ -31,6 +32,7 @@ pub fn process( input: & obj::Collection ) -> Result< compute::ComputeCollection
     } );
 
     for i in input._groups.iter() {
+        let vert_offset = batch_vert.len() / 3;
         for j in i._faces.iter() {
             for k in 0..3 {
                 let v_idx = j._vert_index[ k ] - 1;
@@ -71,6 +73,14 @@ pub fn process( input: & obj::Collection ) -> Result< compute::ComputeCollection
                 batch_tc.extend_from_slice( tc );
             }
         }
+        let vert_count = batch_vert.len() / 3 - vert_offset;
+        if vert_count > 0 {
+            submeshes.push( compute::SubMesh {
+                _material: i._material.clone(),
+                _vert_offset: vert_offset,
+                _vert_count: vert_count,
+            } );
+        }
     }
 
     Ok(
@@ -80,6 +90,7 @@ pub fn process( input: & obj::Collection ) -> Result< compute::ComputeCollection
             _batch_vert: batch_vert,
             _batch_normal: batch_normal,
             _batch_tc: batch_tc,
+            _submeshes: submeshes,
         }
     )
 }
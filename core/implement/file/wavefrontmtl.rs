@@ -0,0 +1,74 @@
+use std::str::FromStr;
+
+use interface::i_wavefront::mtl::Material;
+
+///parses newmtl entries in file order, unsupported statements are ignored
+pub fn parse( input: & str ) -> Result< Vec< Material >, & 'static str > {
+    let mut materials : Vec< Material > = vec![];
+    for line in input.lines() {
+        let line = match line.find( '#' ) {
+            Some( i ) => &line[ ..i ],
+            None => line,
+        };
+        let mut tokens = line.split_whitespace();
+        let key = match tokens.next() {
+            Some( k ) => k,
+            None => { continue },
+        };
+        let args : Vec< & str > = tokens.collect();
+        if key == "newmtl" {
+            if args.is_empty() {
+                return Err( "newmtl name missing" )
+            }
+            materials.push( Material::init( &args.join( " " ) ) );
+            continue
+        }
+        let m = match materials.last_mut() {
+            Some( m ) => m,
+            None => { return Err( "mtl statement before newmtl" ) },
+        };
+        match key {
+            "Ka" => { m._ka = parse_rgb( &args[..] )? },
+            "Kd" => { m._kd = parse_rgb( &args[..] )? },
+            "Ks" => { m._ks = parse_rgb( &args[..] )? },
+            "Ke" => { m._ke = parse_rgb( &args[..] )? },
+            "Ns" => { m._ns = parse_num( &args[..] )? },
+            "d" => { m._d = parse_num( &args[..] )? },
+            "Tr" => { m._d = 1. - parse_num::< f32 >( &args[..] )? },
+            "Ni" => { m._ni = parse_num( &args[..] )? },
+            "illum" => { m._illum = parse_num( &args[..] )? },
+            "map_Kd" => {
+                if args.is_empty() {
+                    return Err( "map_Kd file missing" )
+                }
+                m._map_kd = Some( args.join( " " ) );
+            },
+            "Pr" => { m._pr = Some( parse_num( &args[..] )? ) },
+            "Pm" => { m._pm = Some( parse_num( &args[..] )? ) },
+            "Ps" => { m._ps = Some( parse_num( &args[..] )? ) },
+            "Pc" => { m._pc = Some( parse_num( &args[..] )? ) },
+            "Pcr" => { m._pcr = Some( parse_num( &args[..] )? ) },
+            "aniso" => { m._aniso = Some( parse_num( &args[..] )? ) },
+            "anisor" => { m._anisor = Some( parse_num( &args[..] )? ) },
+            _ => {},
+        }
+    }
+    Ok( materials )
+}
+
+fn parse_num< T: FromStr >( args: &[ & str ] ) -> Result< T, & 'static str > {
+    match args.first() {
+        Some( a ) => a.parse().or( Err( "mtl number invalid" ) ),
+        None => Err( "mtl number missing" ),
+    }
+}
+
+///a single value is used for all channels
+fn parse_rgb( args: &[ & str ] ) -> Result< [ f32; 3 ], & 'static str > {
+    let c : f32 = parse_num( args )?;
+    match args.len() {
+        1 => Ok( [ c, c, c ] ),
+        3 => Ok( [ c, parse_num( &args[ 1.. ] )?, parse_num( &args[ 2.. ] )? ] ),
+        _ => Err( "mtl color component count invalid" ),
+    }
+}
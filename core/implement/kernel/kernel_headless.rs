@@ -0,0 +1,109 @@
+///kernel without a display, driven by scripted input and rendered in software
+
+use interface::i_window::IWindow;
+use interface::i_game_logic::IGameLogic;
+use interface::i_kernel::IKernel;
+use interface::i_ui::{ IUi, InputFiltered };
+use interface::i_renderer::IRenderer;
+
+use implement::window::winheadless::WinHeadless;
+use implement::render::renderer_sw::RendererSw;
+use implement::ui::input_headless::InputHeadless;
+
+///fixed time step in seconds handed to game logic each frame, independent of wall clock time
+pub const FRAME_DELTA: f32 = 1. / 60.;
+
+pub struct KernelHeadless < GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                      EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+    pub _windowing: WinHeadless,
+    pub _input: InputHeadless,
+    pub _game_logic: GameLogic,
+    pub _renderer: RendererSw,
+    pub _frame_delta: f32,
+}
+
+impl < GameLogic > KernelHeadless< GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                             EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+    ///creates a kernel with given frame dimension and ( frame, event ) input script
+    pub fn init( w: u64, h: u64, script: & [ ( u64, InputFiltered ) ] ) -> Result< Self, & 'static str > {
+
+        info!("headless kernel creation." );
+
+        let r = RendererSw::init_with_size( w as usize, h as usize )?;
+
+        Ok( KernelHeadless {
+            _windowing: WinHeadless::with_script( w, h, script ),
+            _input: InputHeadless::new(),
+            _game_logic: GameLogic::new(),
+            _renderer: r,
+            _frame_delta: FRAME_DELTA,
+        } )
+    }
+}
+
+///use default implementation for run method
+impl < GameLogic > IKernel< WinHeadless, InputHeadless, GameLogic, RendererSw > for KernelHeadless< GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                                                                                              EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+        
+    fn new() -> Result< Self, & 'static str > where Self: Sized {
+        KernelHeadless::init( 500, 500, &[] )
+    }
+    fn init_hook( & mut self ) -> Result< (), & 'static str > {
+        
+        self._windowing.make_current()?;
+        
+        self._game_logic.run_init_hook()?;
+
+        Ok( () )
+    }
+    fn deinit_hook( & mut self ) -> Result< (), & 'static str > {
+        Ok( () )
+    }
+    fn frame_delta( & mut self, _t_measured: f32 ) -> f32 {
+        self._frame_delta
+    }
+}
+
+impl < GameLogic > AsMut< WinHeadless > for KernelHeadless< GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                                                      EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+    fn as_mut( & mut self ) -> & mut WinHeadless {
+        & mut self._windowing
+    }
+}
+
+impl < GameLogic > AsMut< GameLogic > for KernelHeadless < GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                                                     EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+    fn as_mut( & mut self ) -> & mut GameLogic {
+        & mut self._game_logic
+    }
+}
+
+impl < GameLogic > AsMut< RendererSw > for KernelHeadless < GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                                                      EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+   fn as_mut( & mut self ) -> & mut RendererSw {
+        & mut self._renderer
+    }
+
+}
+    
+impl < GameLogic > AsMut< InputHeadless > for KernelHeadless < GameLogic > where GameLogic : IGameLogic< EventInput = <InputHeadless as IUi>::EventInputFiltered,
+                                                                                                         EventRender = <RendererSw as IRenderer>::EventRender >,
+<GameLogic as IGameLogic>::RenderObj : Into< Vec< <GameLogic as IGameLogic>::EventRender > >
+{
+   fn as_mut( & mut self ) -> & mut InputHeadless {
+        & mut self._input
+    }
+
+}
@@ -1 +1,2 @@
 pub mod kernel_impl_001;
+pub mod kernel_headless;
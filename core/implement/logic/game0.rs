@@ -22,6 +22,7 @@ use implement::render::camera;
 use implement::render::light;
 use implement::render::mesh;
 use implement::render::primitive;
+use implement::render::culling::{ ICullable, BoundingVolume };
 
 use self::mazth::mat;
 
@@ -152,6 +153,13 @@ pub enum RenderObj {
 }
 
 
+impl ICullable for RenderObj {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        //test geometry carries the camera and light, keep it always
+        BoundingVolume::Unbounded
+    }
+}
+
 impl From< RenderObj > for Vec< renderer_gl::Event > {
     fn from( _r: RenderObj ) -> Self {
         match _r {
@@ -394,11 +402,6 @@ impl IGameLogic for GameLogic {
 
         v
     }
-    fn filter_renderables( & mut self, _r: Vec< RenderObj > ) -> Vec< RenderObj > {
-        //todo: add spatial accelerator algo here
-        _r
-    }
-
     fn should_exit( & mut self ) -> bool {
         self._state._exit
     }
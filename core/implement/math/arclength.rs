@@ -0,0 +1,254 @@
+extern crate mazth;
+
+use std::cmp;
+use std::cmp::Ordering;
+
+use interface::i_interpolate::*;
+
+use implement::math::piecewise::Piecewise;
+
+use self::mazth::mat::*;
+
+///arc length parameterisation of a path of segments. lengths are measured over the xyz components using chords
+///between evenly spaced segment fractions
+#[derive(Debug, Clone)]
+pub struct ArcLength< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    pub _segments: Vec< T >,
+    pub _samples: usize,
+    ///cumulative length within each segment at fractions i / _samples
+    pub _tables: Vec< Vec< f64 > >,
+    ///path distance at the start of each segment
+    pub _offsets: Vec< f64 >,
+    pub _length: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClosestPoint {
+    pub _segment: usize,
+    pub _fraction: f64,
+    ///distance along the path
+    pub _distance: f64,
+    pub _point: Mat4x1< f64 >,
+    ///separation between the query point and the path
+    pub _separation: f64,
+}
+
+impl < T > ArcLength< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    pub fn init( segments: Vec< T >, samples: usize ) -> Result< ArcLength< T >, & 'static str > {
+        if segments.is_empty() {
+            return Err( "arc length requires at least 1 segment." )
+        }
+        if samples == 0 {
+            return Err( "arc length samples must be non-zero." )
+        }
+        let mut tables = vec![];
+        let mut offsets = vec![];
+        let mut total = 0f64;
+        for s in segments.iter() {
+            let mut t = vec![ 0f64 ];
+            let mut prev = s.eval( 0f64 );
+            let mut acc = 0f64;
+            for i in 1..samples + 1 {
+                let p = s.eval( i as f64 / samples as f64 );
+                acc += dist( & prev, & p );
+                t.push( acc );
+                prev = p;
+            }
+            offsets.push( total );
+            total += acc;
+            tables.push( t );
+        }
+        if !total.is_finite() {
+            return Err( "arc length is not finite." )
+        }
+        Ok( ArcLength {
+            _segments: segments,
+            _samples: samples,
+            _tables: tables,
+            _offsets: offsets,
+            _length: total,
+        } )
+    }
+
+    pub fn init_from_piecewise( p: & Piecewise< T, Mat4x1< f64 > >, samples: usize ) -> Result< ArcLength< T >, & 'static str > where T: IInterpolate< Mat4x1< f64 > > {
+        ArcLength::init( p._pieces.clone(), samples )
+    }
+
+    pub fn length( & self ) -> f64 {
+        self._length
+    }
+
+    pub fn segment_length( & self, i: usize ) -> f64 {
+        self._tables[i][ self._samples ]
+    }
+
+    ///segment index and segment fraction at distance along the path, clamped to the path
+    pub fn distance_to_param( & self, d: f64 ) -> ( usize, f64 ) {
+        let d = d.max( 0f64 ).min( self._length );
+        //last segment whose start is not past d
+        let seg = match self._offsets.binary_search_by( |x| x.partial_cmp( & d ).unwrap_or( Ordering::Equal ) ) {
+            Ok( i ) => i,
+            Err( i ) => i - 1,
+        };
+        let seg = cmp::min( seg, self._segments.len() - 1 );
+        let local = d - self._offsets[ seg ];
+        let t = & self._tables[ seg ];
+        if t[ self._samples ] <= 0f64 {
+            return ( seg, 0f64 )
+        }
+        let i = match t.binary_search_by( |x| x.partial_cmp( & local ).unwrap_or( Ordering::Equal ) ) {
+            Ok( i ) => { return ( seg, i as f64 / self._samples as f64 ) },
+            Err( i ) => cmp::min( cmp::max( i, 1 ), self._samples ) - 1,
+        };
+        let span = t[ i + 1 ] - t[ i ];
+        let frac = if span > 0f64 { ( local - t[i] ) / span } else { 0f64 };
+        ( seg, ( ( i as f64 + frac ) / self._samples as f64 ).min( 1f64 ) )
+    }
+
+    ///distance along the path at a segment fraction
+    pub fn param_to_distance( & self, seg: usize, fraction: f64 ) -> Result< f64, & 'static str > {
+        if seg >= self._segments.len() {
+            return Err( "segment index out of range." )
+        }
+        let f = fraction.max( 0f64 ).min( 1f64 ) * self._samples as f64;
+        let i = cmp::min( f.floor() as usize, self._samples - 1 );
+        let t = & self._tables[ seg ];
+        Ok( self._offsets[ seg ] + t[i] + ( t[ i + 1 ] - t[i] ) * ( f - i as f64 ) )
+    }
+
+    pub fn point_at_distance( & self, d: f64 ) -> Mat4x1< f64 > {
+        let ( seg, f ) = self.distance_to_param( d );
+        self._segments[ seg ].eval( f )
+    }
+
+    ///closest point on the path to p by scanning table samples then refining around the best sample
+    pub fn closest_point( & self, p: & Mat4x1< f64 > ) -> ClosestPoint {
+        let mut best = ( 0usize, 0f64, ::std::f64::INFINITY );
+        for ( si, s ) in self._segments.iter().enumerate() {
+            for i in 0..self._samples + 1 {
+                let f = i as f64 / self._samples as f64;
+                let d = dist( & s.eval( f ), p );
+                if d < best.2 {
+                    best = ( si, f, d );
+                }
+            }
+        }
+        let ( seg, f0, _ ) = best;
+        let h = 1f64 / self._samples as f64;
+        let s = & self._segments[ seg ];
+        let ( mut lo, mut hi ) = ( ( f0 - h ).max( 0f64 ), ( f0 + h ).min( 1f64 ) );
+        for _ in 0..40 {
+            let a = lo + ( hi - lo ) / 3f64;
+            let b = hi - ( hi - lo ) / 3f64;
+            if dist( & s.eval( a ), p ) < dist( & s.eval( b ), p ) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        let f = ( lo + hi ) * 0.5f64;
+        let point = s.eval( f );
+        ClosestPoint {
+            _segment: seg,
+            _fraction: f,
+            _distance: self.param_to_distance( seg, f ).unwrap(),
+            _point: point,
+            _separation: dist( & point, p ),
+        }
+    }
+}
+
+///steps along a path at constant speed, each step covering equal path distance
+#[derive(Debug, Clone)]
+pub struct ArcLengthInterp< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    pub _path: ArcLength< T >,
+    pub _point: Mat4x1< f64 >,
+    pub _steps: i64,
+    pub _step_current: i64,
+}
+
+impl < T > ArcLengthInterp< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    pub fn init( s: u64, path: ArcLength< T > ) -> ArcLengthInterp< T > {
+        assert!( s > 2 );
+        let start = path.point_at_distance( 0f64 );
+        ArcLengthInterp {
+            _path: path,
+            _point: start,
+            _steps: s as _,
+            _step_current: -1i64,
+        }
+    }
+
+    ///path distance covered by a single step
+    pub fn step_length( & self ) -> f64 {
+        self._path.length() / ( self._steps - 1 ) as f64
+    }
+
+    fn point_at_step( & self, step: i64 ) -> Mat4x1< f64 > {
+        let clamp = cmp::min( cmp::max( step, 0 ), self._steps - 1 );
+        self._path.point_at_distance( clamp as f64 * self.step_length() )
+    }
+}
+
+impl < T > IInterpolateEval< Mat4x1< f64 > > for ArcLengthInterp< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    ///fraction of total path length
+    fn eval( & self, fraction: f64 ) -> Mat4x1< f64 > {
+        self._path.point_at_distance( fraction * self._path.length() )
+    }
+}
+
+impl < T > IInterpolate< Mat4x1< f64 > > for ArcLengthInterp< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    fn num_steps( & self ) -> u64 {
+        self._steps as u64
+    }
+    fn interp_delta( & mut self, steps: i64 ) -> Option< Mat4x1< f64 > > {
+        self._step_current = cmp::min( self._step_current + steps, self._steps );
+        self._step_current = cmp::max( self._step_current, -1 );
+        self._point = self.point_at_step( self._step_current );
+        Some( self._point )
+    }
+    fn interp_current( & self ) -> Mat4x1< f64 > {
+        self._point
+    }
+    fn interp_is_end( & self ) -> bool {
+        self._step_current == self._steps
+    }
+    fn interp_is_start( & self ) -> bool {
+        self._step_current == -1
+    }
+    fn reset( & mut self ){
+        self._step_current = -1i64;
+        self._point = self.point_at_step( 0 );
+    }
+}
+
+//required by IInterpolate
+impl < T > Iterator for ArcLengthInterp< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    type Item = Mat4x1< f64 >;
+    fn next( & mut self ) -> Option< Mat4x1< f64 > > {
+        self._step_current = cmp::min( self._step_current + 1, self._steps );
+        if self._step_current == self._steps {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+//required by IInterpolate
+impl < T > DoubleEndedIterator for ArcLengthInterp< T > where T: IInterpolateEval< Mat4x1< f64 > > + Clone {
+    fn next_back( & mut self ) -> Option< Mat4x1< f64 > > {
+        self._step_current = cmp::max( self._step_current - 1, -1 );
+        if self._step_current == -1 {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+fn dist( a: & Mat4x1< f64 >, b: & Mat4x1< f64 > ) -> f64 {
+    ( 0..3 ).fold( 0f64, |acc, i| acc + ( a._val[i] - b._val[i] ) * ( a._val[i] - b._val[i] ) ).sqrt()
+}
@@ -0,0 +1,444 @@
+use std::f32;
+
+use interface::i_stat_tree::IStatTree;
+
+use implement::math::intersect::{ ray_aabb, ray_triangle, aabb_overlap, sphere_aabb, aabb_union, aabb_surface_area };
+
+///number of bins per axis evaluated for the surface area heuristic
+const SAH_BINS: usize = 12;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BvhNode {
+    pub _lower: [f32;3],
+    pub _upper: [f32;3],
+    ///child node indices of an interior node
+    pub _left: usize,
+    pub _right: usize,
+    ///range into the primitive index list of a leaf, count of 0 marks an interior node
+    pub _first: usize,
+    pub _count: usize,
+}
+
+impl BvhNode {
+    pub fn is_leaf( & self ) -> bool {
+        self._count > 0
+    }
+}
+
+///binary bounding volume hierarchy over primitive bounds, built with binned surface area heuristic
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    ///root at index 0, children always follow their parent
+    pub _nodes: Vec< BvhNode >,
+    ///primitive indices grouped by leaf
+    pub _indices: Vec< usize >,
+    ///bounds per primitive
+    pub _bounds: Vec< ( [f32;3], [f32;3] ) >,
+    pub _max_leaf: usize,
+}
+
+impl Bvh {
+    pub fn init( bounds: &[ ( [f32;3], [f32;3] ) ], max_leaf: usize ) -> Bvh {
+        let mut b = Bvh {
+            _nodes: vec![],
+            _indices: ( 0..bounds.len() ).collect(),
+            _bounds: bounds.to_vec(),
+            _max_leaf: max_leaf.max( 1 ),
+        };
+        if !bounds.is_empty() {
+            let centroids : Vec< [f32;3] > = bounds.iter()
+                .map( |&( l, u )| [ ( l[0] + u[0] ) * 0.5, ( l[1] + u[1] ) * 0.5, ( l[2] + u[2] ) * 0.5 ] )
+                .collect();
+            let n = bounds.len();
+            build( & mut b._nodes, & mut b._indices, &b._bounds[..], &centroids[..], 0, n, b._max_leaf );
+        }
+        b
+    }
+
+    pub fn len( & self ) -> usize {
+        self._bounds.len()
+    }
+
+    pub fn is_empty( & self ) -> bool {
+        self._bounds.is_empty()
+    }
+
+    ///updates primitive bounds keeping the tree topology, for animated content
+    pub fn refit( & mut self, bounds: &[ ( [f32;3], [f32;3] ) ] ) -> Result< (), & 'static str > {
+        if bounds.len() != self._bounds.len() {
+            return Err( "refit primitive count mismatch." )
+        }
+        self._bounds.clear();
+        self._bounds.extend_from_slice( bounds );
+        for i in ( 0..self._nodes.len() ).rev() {
+            let n = self._nodes[i];
+            let ( l, u ) = if n.is_leaf() {
+                bound_of( &self._indices[ n._first..n._first + n._count ], &self._bounds[..] )
+            } else {
+                let ( a, b ) = ( self._nodes[ n._left ], self._nodes[ n._right ] );
+                aabb_union( a._lower, a._upper, b._lower, b._upper )
+            };
+            self._nodes[i]._lower = l;
+            self._nodes[i]._upper = u;
+        }
+        Ok( () )
+    }
+
+    ///closest primitive hit within [0, t_max]. hit is given a primitive index and the current closest distance and returns the hit distance if any
+    pub fn ray_first_hit< F >( & self, origin: [f32;3], dir: [f32;3], t_max: f32, mut hit: F ) -> Option< ( usize, f32 ) > where F: FnMut( usize, f32 ) -> Option< f32 > {
+        let mut ret = None;
+        self.traverse_ray( origin, dir, t_max, false, |p, closest| {
+            match hit( p, closest ) {
+                Some( t ) if t >= 0. && t <= closest => {
+                    ret = Some( ( p, t ) );
+                    Some( t )
+                },
+                _ => None,
+            }
+        } );
+        ret
+    }
+
+    ///any primitive hit within [0, t_max], stops at the first one found
+    pub fn ray_any_hit< F >( & self, origin: [f32;3], dir: [f32;3], t_max: f32, mut hit: F ) -> Option< usize > where F: FnMut( usize, f32 ) -> Option< f32 > {
+        let mut ret = None;
+        self.traverse_ray( origin, dir, t_max, true, |p, _| {
+            match hit( p, t_max ) {
+                Some( t ) if t >= 0. && t <= t_max => {
+                    ret = Some( p );
+                    Some( t )
+                },
+                _ => None,
+            }
+        } );
+        ret
+    }
+
+    ///closest primitive bound hit, for scene objects described only by their bounds
+    pub fn ray_first_hit_bounds( & self, origin: [f32;3], dir: [f32;3], t_max: f32 ) -> Option< ( usize, f32 ) > {
+        let bounds = &self._bounds;
+        self.ray_first_hit( origin, dir, t_max, |p, _| {
+            let ( l, u ) = bounds[p];
+            match ray_aabb( origin, dir, l, u ) {
+                Some( ( tn, tf ) ) if tf >= 0. => Some( tn.max( 0. ) ),
+                _ => None,
+            }
+        } )
+    }
+
+    ///primitives whose bounds overlap the box, sorted
+    pub fn query_aabb( & self, lower: [f32;3], upper: [f32;3] ) -> Vec< usize > {
+        self.query( |l, u| aabb_overlap( l, u, lower, upper ) )
+    }
+
+    ///primitives whose bounds overlap the sphere, sorted
+    pub fn query_sphere( & self, center: [f32;3], radius: f32 ) -> Vec< usize > {
+        self.query( |l, u| sphere_aabb( center, radius, l, u ) )
+    }
+
+    fn query< F >( & self, f: F ) -> Vec< usize > where F: Fn( [f32;3], [f32;3] ) -> bool {
+        let mut ret = vec![];
+        if self._nodes.is_empty() {
+            return ret
+        }
+        let mut stack = vec![ 0 ];
+        while let Some( i ) = stack.pop() {
+            let n = & self._nodes[i];
+            if !f( n._lower, n._upper ) {
+                continue
+            }
+            if n.is_leaf() {
+                for &p in self._indices[ n._first..n._first + n._count ].iter() {
+                    let ( l, u ) = self._bounds[p];
+                    if f( l, u ) {
+                        ret.push( p );
+                    }
+                }
+            } else {
+                stack.push( n._left );
+                stack.push( n._right );
+            }
+        }
+        ret.sort();
+        ret
+    }
+
+    ///visits leaves along the ray nearest first, pruning nodes entered beyond the closest accepted hit. stops after the first hit if any_hit
+    fn traverse_ray< H >( & self, origin: [f32;3], dir: [f32;3], t_max: f32, any_hit: bool, mut hit: H ) where H: FnMut( usize, f32 ) -> Option< f32 > {
+        if self._nodes.is_empty() {
+            return
+        }
+        let mut closest = t_max;
+        let mut stack = vec![ 0 ];
+        while let Some( i ) = stack.pop() {
+            let n = & self._nodes[i];
+            match ray_aabb( origin, dir, n._lower, n._upper ) {
+                Some( ( tn, tf ) ) if tf >= 0. && tn <= closest => {},
+                _ => { continue },
+            }
+            if n.is_leaf() {
+                for &p in self._indices[ n._first..n._first + n._count ].iter() {
+                    if let Some( t ) = hit( p, closest ) {
+                        if any_hit {
+                            return
+                        }
+                        closest = t;
+                    }
+                }
+            } else {
+                let entry = |c: usize| {
+                    let m = & self._nodes[c];
+                    ray_aabb( origin, dir, m._lower, m._upper ).map( |x| x.0 ).unwrap_or( f32::INFINITY )
+                };
+                //push far child first so the near child is visited first
+                if entry( n._left ) <= entry( n._right ) {
+                    stack.push( n._right );
+                    stack.push( n._left );
+                } else {
+                    stack.push( n._left );
+                    stack.push( n._right );
+                }
+            }
+        }
+    }
+}
+
+///tree of depth 0 at root, leaves determine depth statistics
+impl IStatTree for Bvh {
+    fn sum_subtree_child_count( & self ) -> Option< u64 > {
+        if self._nodes.is_empty() {
+            None
+        } else {
+            Some( self._nodes.len() as u64 - 1 )
+        }
+    }
+    fn node_degree( & self ) -> Option< u64 > {
+        Some( 2 )
+    }
+    fn max_depth( & self ) -> Option< u64 > {
+        self.leaf_depths().into_iter().max()
+    }
+    fn mean_depth( & self ) -> Option< u64 > {
+        let d = self.leaf_depths();
+        if d.is_empty() {
+            None
+        } else {
+            Some( d.iter().sum::< u64 >() / d.len() as u64 )
+        }
+    }
+    fn min_depth( & self ) -> Option< u64 > {
+        self.leaf_depths().into_iter().min()
+    }
+    ///min over max leaf depth, 1 for a perfectly balanced tree
+    fn balance_ratio( & self ) -> Option< f64 > {
+        match ( self.min_depth(), self.max_depth() ) {
+            ( Some( _ ), Some( 0 ) ) => Some( 1. ),
+            ( Some( a ), Some( b ) ) => Some( a as f64 / b as f64 ),
+            _ => None,
+        }
+    }
+}
+
+impl Bvh {
+    fn leaf_depths( & self ) -> Vec< u64 > {
+        let mut ret = vec![];
+        if self._nodes.is_empty() {
+            return ret
+        }
+        let mut stack = vec![ ( 0, 0u64 ) ];
+        while let Some( ( i, d ) ) = stack.pop() {
+            let n = & self._nodes[i];
+            if n.is_leaf() {
+                ret.push( d );
+            } else {
+                stack.push( ( n._left, d + 1 ) );
+                stack.push( ( n._right, d + 1 ) );
+            }
+        }
+        ret
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriHit {
+    pub _tri: usize,
+    pub _t: f32,
+    ///barycentric coordinates of the hit relative to the 2nd and 3rd vertex
+    pub _u: f32,
+    pub _v: f32,
+}
+
+///hierarchy over a triangle soup in batch layout, 3 consecutive xyz vertices per triangle as in ComputeCollection::_batch_vert
+#[derive(Debug, Clone)]
+pub struct TriMeshBvh {
+    pub _bvh: Bvh,
+    pub _positions: Vec< f32 >,
+}
+
+impl TriMeshBvh {
+    pub fn init( positions: &[ f32 ], max_leaf: usize ) -> Result< TriMeshBvh, & 'static str > {
+        if positions.len() % 9 != 0 {
+            return Err( "positions do not form whole triangles." )
+        }
+        Ok( TriMeshBvh {
+            _bvh: Bvh::init( &tri_bounds( positions )[..], max_leaf ),
+            _positions: positions.to_vec(),
+        } )
+    }
+
+    pub fn num_tris( & self ) -> usize {
+        self._positions.len() / 9
+    }
+
+    ///updates vertex positions of a deformed mesh with the same triangle count
+    pub fn refit( & mut self, positions: &[ f32 ] ) -> Result< (), & 'static str > {
+        if positions.len() != self._positions.len() {
+            return Err( "refit position count mismatch." )
+        }
+        self._positions.clear();
+        self._positions.extend_from_slice( positions );
+        self._bvh.refit( &tri_bounds( positions )[..] )
+    }
+
+    pub fn tri( & self, i: usize ) -> [ [f32;3]; 3 ] {
+        let p = & self._positions[ i * 9..i * 9 + 9 ];
+        [ [ p[0], p[1], p[2] ], [ p[3], p[4], p[5] ], [ p[6], p[7], p[8] ] ]
+    }
+
+    pub fn ray_first_hit( & self, origin: [f32;3], dir: [f32;3], t_max: f32 ) -> Option< TriHit > {
+        let mut bary = ( 0f32, 0f32 );
+        let found = self._bvh.ray_first_hit( origin, dir, t_max, |p, closest| {
+            let v = self.tri( p );
+            match ray_triangle( origin, dir, v[0], v[1], v[2] ) {
+                Some( ( t, u, w ) ) if t >= 0. && t <= closest => {
+                    //accepted hits only get closer, so the last one is kept
+                    bary = ( u, w );
+                    Some( t )
+                },
+                _ => None,
+            }
+        } );
+        found.map( |( p, t )| TriHit { _tri: p, _t: t, _u: bary.0, _v: bary.1 } )
+    }
+
+    pub fn ray_any_hit( & self, origin: [f32;3], dir: [f32;3], t_max: f32 ) -> bool {
+        self._bvh.ray_any_hit( origin, dir, t_max, |p, _| {
+            let v = self.tri( p );
+            ray_triangle( origin, dir, v[0], v[1], v[2] ).map( |x| x.0 )
+        } ).is_some()
+    }
+
+    ///triangles whose bounds overlap the box
+    pub fn query_aabb( & self, lower: [f32;3], upper: [f32;3] ) -> Vec< usize > {
+        self._bvh.query_aabb( lower, upper )
+    }
+
+    ///triangles whose bounds overlap the sphere
+    pub fn query_sphere( & self, center: [f32;3], radius: f32 ) -> Vec< usize > {
+        self._bvh.query_sphere( center, radius )
+    }
+}
+
+fn tri_bounds( positions: &[ f32 ] ) -> Vec< ( [f32;3], [f32;3] ) > {
+    positions.chunks( 9 )
+        .map( |p| {
+            let mut l = [ p[0], p[1], p[2] ];
+            let mut u = l;
+            for v in 1..3 {
+                for h in 0..3 {
+                    l[h] = l[h].min( p[ v * 3 + h ] );
+                    u[h] = u[h].max( p[ v * 3 + h ] );
+                }
+            }
+            ( l, u )
+        } )
+        .collect()
+}
+
+fn bound_of( indices: &[ usize ], bounds: &[ ( [f32;3], [f32;3] ) ] ) -> ( [f32;3], [f32;3] ) {
+    indices.iter().fold( ( [ f32::INFINITY; 3 ], [ f32::NEG_INFINITY; 3 ] ), |acc, &i| {
+        aabb_union( acc.0, acc.1, bounds[i].0, bounds[i].1 )
+    } )
+}
+
+///builds the subtree over indices[start..end] and returns its node index
+fn build( nodes: & mut Vec< BvhNode >, indices: & mut Vec< usize >, bounds: &[ ( [f32;3], [f32;3] ) ], centroids: &[ [f32;3] ], start: usize, end: usize, max_leaf: usize ) -> usize {
+    let ( lower, upper ) = bound_of( &indices[ start..end ], bounds );
+    let idx = nodes.len();
+    nodes.push( BvhNode { _lower: lower, _upper: upper, _left: 0, _right: 0, _first: start, _count: end - start } );
+    let n = end - start;
+    if n <= 1 {
+        return idx
+    }
+    let mut cl = [ f32::INFINITY; 3 ];
+    let mut cu = [ f32::NEG_INFINITY; 3 ];
+    for &i in indices[ start..end ].iter() {
+        for h in 0..3 {
+            cl[h] = cl[h].min( centroids[i][h] );
+            cu[h] = cu[h].max( centroids[i][h] );
+        }
+    }
+    let bin_of = |c: [f32;3], axis: usize| {
+        let ext = cu[axis] - cl[axis];
+        ( ( ( c[axis] - cl[axis] ) / ext * SAH_BINS as f32 ) as usize ).min( SAH_BINS - 1 )
+    };
+    //( cost, axis, first bin of right side )
+    let mut best : Option< ( f32, usize, usize ) > = None;
+    for axis in 0..3 {
+        if !( cu[axis] - cl[axis] > 0. ) {
+            continue
+        }
+        let mut bins = [ ( 0usize, [ f32::INFINITY; 3 ], [ f32::NEG_INFINITY; 3 ] ); SAH_BINS ];
+        for &i in indices[ start..end ].iter() {
+            let b = bin_of( centroids[i], axis );
+            let ( l, u ) = aabb_union( bins[b].1, bins[b].2, bounds[i].0, bounds[i].1 );
+            bins[b] = ( bins[b].0 + 1, l, u );
+        }
+        for split in 1..SAH_BINS {
+            let side = |r: ::std::ops::Range< usize >| {
+                bins[r].iter().fold( ( 0usize, [ f32::INFINITY; 3 ], [ f32::NEG_INFINITY; 3 ] ), |acc, b| {
+                    let ( l, u ) = aabb_union( acc.1, acc.2, b.1, b.2 );
+                    ( acc.0 + b.0, l, u )
+                } )
+            };
+            let ( nl, ll, lu ) = side( 0..split );
+            let ( nr, rl, ru ) = side( split..SAH_BINS );
+            if nl == 0 || nr == 0 {
+                continue
+            }
+            let cost = aabb_surface_area( ll, lu ) * nl as f32 + aabb_surface_area( rl, ru ) * nr as f32;
+            if best.map_or( true, |x| cost < x.0 ) {
+                best = Some( ( cost, axis, split ) );
+            }
+        }
+    }
+    let leaf_cost = aabb_surface_area( lower, upper ) * n as f32;
+    let mid = match best {
+        Some( ( cost, axis, split ) ) => {
+            if n <= max_leaf && cost >= leaf_cost {
+                return idx
+            }
+            let mut mid = start;
+            for i in start..end {
+                if bin_of( centroids[ indices[i] ], axis ) < split {
+                    indices.swap( i, mid );
+                    mid += 1;
+                }
+            }
+            mid
+        },
+        None => {
+            //coincident centroids, split evenly only to bound leaf size
+            if n <= max_leaf {
+                return idx
+            }
+            start + n / 2
+        },
+    };
+    let left = build( nodes, indices, bounds, centroids, start, mid, max_leaf );
+    let right = build( nodes, indices, bounds, centroids, mid, end, max_leaf );
+    nodes[idx]._left = left;
+    nodes[idx]._right = right;
+    nodes[idx]._count = 0;
+    idx
+}
@@ -1,7 +1,339 @@
+use std::collections::{ HashMap, HashSet };
+use std::cmp::Ordering;
+use std::f32;
+
+use interface::i_stat_tree::IStatTree;
+
+use implement::math::intersect::{ ray_aabb, aabb_overlap, sphere_aabb };
+
+#[derive(Debug, Clone)]
+struct Instance {
+    _lower: [f32;3],
+    _upper: [f32;3],
+    ///inclusive range of covered cells
+    _cell_lo: [usize;3],
+    _cell_hi: [usize;3],
+}
+
 #[derive(Debug, Clone)]
 pub struct ClusterBox {
     ///stores instance ids into grid box clusters
+    pub _origin: [f32;3],
     pub _dim: [f32;3],
     pub _divisions: [usize;3],
+    _cells: Vec< Vec< u64 > >,
+    _instances: HashMap< u64, Instance >,
+}
+
+impl ClusterBox {
+    ///uniform grid spanning origin to origin + dim. instances extending past the grid are clamped into boundary cells
+    pub fn init( origin: [f32;3], dim: [f32;3], divisions: [usize;3] ) -> Result< ClusterBox, & 'static str > {
+        if divisions.iter().any( |x| *x == 0 ) {
+            return Err( "divisions must be non-zero." )
+        }
+        if dim.iter().any( |x| !( *x > 0. ) ) {
+            return Err( "dimension must be positive." )
+        }
+        Ok( ClusterBox {
+            _origin: origin,
+            _dim: dim,
+            _divisions: divisions,
+            _cells: vec![ vec![]; divisions[0] * divisions[1] * divisions[2] ],
+            _instances: HashMap::new(),
+        } )
+    }
+
+    pub fn len( & self ) -> usize {
+        self._instances.len()
+    }
+
+    pub fn is_empty( & self ) -> bool {
+        self._instances.is_empty()
+    }
+
+    pub fn clear( & mut self ) {
+        for c in self._cells.iter_mut() {
+            c.clear();
+        }
+        self._instances.clear();
+    }
+
+    pub fn cell_size( & self ) -> [f32;3] {
+        [ self._dim[0] / self._divisions[0] as f32,
+          self._dim[1] / self._divisions[1] as f32,
+          self._dim[2] / self._divisions[2] as f32 ]
+    }
+
+    ///cell coordinate containing point, clamped to the grid
+    pub fn cell_coord( & self, p: [f32;3] ) -> [usize;3] {
+        let s = self.cell_size();
+        let mut c = [0usize;3];
+        for h in 0..3 {
+            let f = ( ( p[h] - self._origin[h] ) / s[h] ).floor();
+            c[h] = if f <= 0. { 0 } else { ( f as usize ).min( self._divisions[h] - 1 ) };
+        }
+        c
+    }
+
+    pub fn get_bound( & self, id: u64 ) -> Option< ( [f32;3], [f32;3] ) > {
+        self._instances.get( & id ).map( |x| ( x._lower, x._upper ) )
+    }
+
+    ///instance ids stored in a cell
+    pub fn cell( & self, c: [usize;3] ) -> &[ u64 ] {
+        &self._cells[ self.cell_index( c ) ][..]
+    }
+
+    pub fn insert( & mut self, id: u64, lower: [f32;3], upper: [f32;3] ) -> Result< (), & 'static str > {
+        if self._instances.contains_key( & id ) {
+            return Err( "instance id already exists." )
+        }
+        if ( 0..3 ).any( |h| !( lower[h] <= upper[h] ) ) {
+            return Err( "invalid bound." )
+        }
+        let inst = Instance {
+            _lower: lower,
+            _upper: upper,
+            _cell_lo: self.cell_coord( lower ),
+            _cell_hi: self.cell_coord( upper ),
+        };
+        self.add_to_cells( id, & inst );
+        self._instances.insert( id, inst );
+        Ok( () )
+    }
+
+    ///moves an existing instance, only touching cells when its cell range changes
+    pub fn update( & mut self, id: u64, lower: [f32;3], upper: [f32;3] ) -> Result< (), & 'static str > {
+        if ( 0..3 ).any( |h| !( lower[h] <= upper[h] ) ) {
+            return Err( "invalid bound." )
+        }
+        let lo = self.cell_coord( lower );
+        let hi = self.cell_coord( upper );
+        let old = match self._instances.get( & id ) {
+            Some( x ) => x.clone(),
+            None => { return Err( "instance id not found." ) },
+        };
+        let inst = Instance { _lower: lower, _upper: upper, _cell_lo: lo, _cell_hi: hi };
+        if old._cell_lo != lo || old._cell_hi != hi {
+            self.remove_from_cells( id, & old );
+            self.add_to_cells( id, & inst );
+        }
+        self._instances.insert( id, inst );
+        Ok( () )
+    }
+
+    pub fn remove( & mut self, id: u64 ) -> Result< (), & 'static str > {
+        match self._instances.remove( & id ) {
+            Some( inst ) => {
+                self.remove_from_cells( id, & inst );
+                Ok( () )
+            },
+            None => Err( "instance id not found." ),
+        }
+    }
+
+    ///ids whose bound contains the point, sorted
+    pub fn query_point( & self, p: [f32;3] ) -> Vec< u64 > {
+        let c = self.cell_coord( p );
+        let mut ret : Vec< u64 > = self._cells[ self.cell_index( c ) ].iter()
+            .cloned()
+            .filter( |id| {
+                let b = & self._instances[ id ];
+                ( 0..3 ).all( |h| b._lower[h] <= p[h] && p[h] <= b._upper[h] )
+            } )
+            .collect();
+        ret.sort();
+        ret
+    }
+
+    ///ids whose bound overlaps the box, sorted
+    pub fn query_box( & self, lower: [f32;3], upper: [f32;3] ) -> Vec< u64 > {
+        self.query_cells( self.cell_coord( lower ), self.cell_coord( upper ), |b| {
+            aabb_overlap( b._lower, b._upper, lower, upper )
+        } )
+    }
+
+    ///ids whose bound overlaps the sphere, sorted
+    pub fn query_sphere( & self, center: [f32;3], radius: f32 ) -> Vec< u64 > {
+        let lower = [ center[0] - radius, center[1] - radius, center[2] - radius ];
+        let upper = [ center[0] + radius, center[1] + radius, center[2] + radius ];
+        self.query_cells( self.cell_coord( lower ), self.cell_coord( upper ), |b| {
+            sphere_aabb( center, radius, b._lower, b._upper )
+        } )
+    }
+
+    ///ids whose bound is hit by the ray within [0, t_max], sorted by entry distance along dir.
+    ///traverses cells inside the grid only. a zero or non-finite direction hits nothing
+    pub fn query_ray( & self, origin: [f32;3], dir: [f32;3], t_max: f32 ) -> Vec< ( u64, f32 ) > {
+        if dir.iter().chain( origin.iter() ).any( |x| !x.is_finite() ) || dir.iter().all( |x| *x == 0. ) || t_max.is_nan() {
+            return vec![]
+        }
+        let grid_upper = [ self._origin[0] + self._dim[0], self._origin[1] + self._dim[1], self._origin[2] + self._dim[2] ];
+        let ( t0, t1 ) = match ray_aabb( origin, dir, self._origin, grid_upper ) {
+            Some( x ) => x,
+            None => { return vec![] },
+        };
+        let t0 = t0.max( 0. );
+        let t1 = t1.min( t_max );
+        if t0 > t1 {
+            return vec![]
+        }
+        let s = self.cell_size();
+        let p = [ origin[0] + dir[0] * t0, origin[1] + dir[1] * t0, origin[2] + dir[2] * t0 ];
+        let mut c = self.cell_coord( p );
+        let mut step = [ 0isize; 3 ];
+        let mut t_next = [ f32::INFINITY; 3 ];
+        let mut t_delta = [ f32::INFINITY; 3 ];
+        for h in 0..3 {
+            if dir[h] > 0. {
+                step[h] = 1;
+                let boundary = self._origin[h] + ( c[h] + 1 ) as f32 * s[h];
+                t_next[h] = ( boundary - origin[h] ) / dir[h];
+                t_delta[h] = s[h] / dir[h];
+            } else if dir[h] < 0. {
+                step[h] = -1;
+                let boundary = self._origin[h] + c[h] as f32 * s[h];
+                t_next[h] = ( boundary - origin[h] ) / dir[h];
+                t_delta[h] = -s[h] / dir[h];
+            }
+        }
+        let mut candidates = HashSet::new();
+        loop {
+            for id in self._cells[ self.cell_index( c ) ].iter() {
+                candidates.insert( *id );
+            }
+            let axis = if t_next[0] <= t_next[1] && t_next[0] <= t_next[2] { 0 } else if t_next[1] <= t_next[2] { 1 } else { 2 };
+            //axes not stepped by the ray never reach a boundary
+            if step[axis] == 0 || t_next[axis] > t1 {
+                break
+            }
+            let n = c[axis] as isize + step[axis];
+            if n < 0 || n >= self._divisions[axis] as isize {
+                break
+            }
+            c[axis] = n as usize;
+            t_next[axis] += t_delta[axis];
+        }
+        let mut ret : Vec< ( u64, f32 ) > = candidates.into_iter()
+            .filter_map( |id| {
+                let b = & self._instances[ & id ];
+                match ray_aabb( origin, dir, b._lower, b._upper ) {
+                    Some( ( tn, tf ) ) if tf >= 0. && tn <= t_max => Some( ( id, tn.max( 0. ) ) ),
+                    _ => None,
+                }
+            } )
+            .collect();
+        ret.sort_by( |a, b| a.1.partial_cmp( & b.1 ).unwrap_or( Ordering::Equal ).then( a.0.cmp( & b.0 ) ) );
+        ret
+    }
+
+    ///broadphase pairs (a < b) sharing a cell whose bounds overlap, sorted
+    pub fn overlapping_pairs( & self ) -> Vec< ( u64, u64 ) > {
+        let mut pairs = HashSet::new();
+        for cell in self._cells.iter() {
+            for i in 0..cell.len() {
+                for j in i+1..cell.len() {
+                    let ( a, b ) = if cell[i] < cell[j] { ( cell[i], cell[j] ) } else { ( cell[j], cell[i] ) };
+                    if pairs.contains( & ( a, b ) ) {
+                        continue
+                    }
+                    let ( ia, ib ) = ( & self._instances[ & a ], & self._instances[ & b ] );
+                    if aabb_overlap( ia._lower, ia._upper, ib._lower, ib._upper ) {
+                        pairs.insert( ( a, b ) );
+                    }
+                }
+            }
+        }
+        let mut ret : Vec< _ > = pairs.into_iter().collect();
+        ret.sort();
+        ret
+    }
+
+    ///number of cells holding at least one instance
+    pub fn occupied_cells( & self ) -> usize {
+        self._cells.iter().filter( |x| !x.is_empty() ).count()
+    }
+
+    fn cell_index( & self, c: [usize;3] ) -> usize {
+        ( c[2] * self._divisions[1] + c[1] ) * self._divisions[0] + c[0]
+    }
+
+    fn cell_range( lo: [usize;3], hi: [usize;3] ) -> Vec< [usize;3] > {
+        let mut v = vec![];
+        for z in lo[2]..hi[2]+1 {
+            for y in lo[1]..hi[1]+1 {
+                for x in lo[0]..hi[0]+1 {
+                    v.push( [ x, y, z ] );
+                }
+            }
+        }
+        v
+    }
+
+    fn add_to_cells( & mut self, id: u64, inst: & Instance ) {
+        for c in ClusterBox::cell_range( inst._cell_lo, inst._cell_hi ) {
+            let i = self.cell_index( c );
+            self._cells[i].push( id );
+        }
+    }
+
+    fn remove_from_cells( & mut self, id: u64, inst: & Instance ) {
+        for c in ClusterBox::cell_range( inst._cell_lo, inst._cell_hi ) {
+            let i = self.cell_index( c );
+            self._cells[i].retain( |x| *x != id );
+        }
+    }
+
+    fn query_cells< F >( & self, lo: [usize;3], hi: [usize;3], f: F ) -> Vec< u64 > where F: Fn( & Instance ) -> bool {
+        let mut found = HashSet::new();
+        for c in ClusterBox::cell_range( lo, hi ) {
+            for id in self._cells[ self.cell_index( c ) ].iter() {
+                if !found.contains( id ) && f( & self._instances[ id ] ) {
+                    found.insert( *id );
+                }
+            }
+        }
+        let mut ret : Vec< _ > = found.into_iter().collect();
+        ret.sort();
+        ret
+    }
 }
 
+///the grid is treated as a single level tree with cells as children and cell occupancy as depth
+impl IStatTree for ClusterBox {
+    ///total instance references over all cells
+    fn sum_subtree_child_count( & self ) -> Option< u64 > {
+        Some( self._cells.iter().fold( 0, |acc, x| acc + x.len() as u64 ) )
+    }
+    ///number of cells
+    fn node_degree( & self ) -> Option< u64 > {
+        Some( self._cells.len() as u64 )
+    }
+    ///most instances in a cell
+    fn max_depth( & self ) -> Option< u64 > {
+        self._cells.iter().map( |x| x.len() as u64 ).max()
+    }
+    ///mean instances per occupied cell
+    fn mean_depth( & self ) -> Option< u64 > {
+        let occupied = self.occupied_cells() as u64;
+        if occupied == 0 {
+            None
+        } else {
+            self.sum_subtree_child_count().map( |x| x / occupied )
+        }
+    }
+    ///fewest instances in an occupied cell
+    fn min_depth( & self ) -> Option< u64 > {
+        self._cells.iter().filter( |x| !x.is_empty() ).map( |x| x.len() as u64 ).min()
+    }
+    ///mean over max occupancy of occupied cells, 1 for evenly spread instances
+    fn balance_ratio( & self ) -> Option< f64 > {
+        let occupied = self.occupied_cells();
+        match ( self.sum_subtree_child_count(), self.max_depth() ) {
+            ( Some( sum ), Some( max ) ) if occupied > 0 && max > 0 => {
+                Some( sum as f64 / occupied as f64 / max as f64 )
+            },
+            _ => None,
+        }
+    }
+}
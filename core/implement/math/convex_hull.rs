@@ -0,0 +1,492 @@
+extern crate mazth;
+
+use std::f64;
+use std::collections::HashMap;
+
+use self::mazth::i_shape::{ ShapeType, IShape };
+use self::mazth::i_bound::IBound;
+use self::mazth::i_vicinity::IVicinity;
+
+use self::mazth::mat::Mat3x1;
+use self::mazth::bound::AxisAlignedBBox;
+
+use implement::math::vec3::{ add, sub, scale, dot, cross, length, normalize };
+use implement::math::intersect::convex_overlap;
+
+///dimension of the hull, lower dimensions result from coincident, colinear or coplanar input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HullKind {
+    Point,
+    Segment,
+    ///flat hull, faces are given for both sides
+    Polygon,
+    Polyhedron,
+}
+
+///convex hull of a point set computed with quickhull
+#[derive(Debug, Clone)]
+pub struct ConvexHull {
+    pub _kind: HullKind,
+    pub _verts: Vec< Mat3x1< f64 > >,
+    ///counter clockwise triangles viewed from outside, indexing into _verts
+    pub _faces: Vec< [usize;3] >,
+    ///outward unit normal per face
+    pub _normals: Vec< Mat3x1< f64 > >,
+    ///bounding planes n.x <= d, including edge planes of flat hulls
+    pub _planes: Vec< ( Mat3x1< f64 >, f64 ) >,
+    ///centroid of the hull vertices
+    pub _ori: Mat3x1< f64 >,
+    pub _bound: AxisAlignedBBox,
+    pub _vicinity: f64,
+}
+
+struct HullFace {
+    _v: [usize;3],
+    _n: [f64;3],
+    _d: f64,
+    ///points in front of the face not yet on the hull
+    _outside: Vec< usize >,
+    _alive: bool,
+}
+
+impl ConvexHull {
+    ///hull of packed xyz positions, eg: mesh vertex data
+    pub fn init_from_positions( pos: &[ f32 ] ) -> Result< ConvexHull, & 'static str > {
+        let p : Vec< [f64;3] > = pos.chunks( 3 )
+            .filter( |x| x.len() == 3 )
+            .map( |x| [ x[0] as f64, x[1] as f64, x[2] as f64 ] )
+            .collect();
+        ConvexHull::init( &p[..] )
+    }
+
+    pub fn init( points: &[ [f64;3] ] ) -> Result< ConvexHull, & 'static str > {
+        if points.is_empty() {
+            return Err( &"convex hull requires at least 1 point" )
+        }
+        if points.iter().any( |p| p.iter().any( |x| !x.is_finite() ) ) {
+            return Err( &"convex hull input not finite" )
+        }
+        let mut lower = points[0];
+        let mut upper = points[0];
+        for p in points.iter() {
+            for h in 0..3 {
+                lower[h] = lower[h].min( p[h] );
+                upper[h] = upper[h].max( p[h] );
+            }
+        }
+        let extent = ( 0..3 ).fold( 0f64, |acc, h| acc.max( upper[h] - lower[h] ) );
+        let scale_max = ( 0..3 ).fold( 0f64, |acc, h| acc.max( lower[h].abs() ).max( upper[h].abs() ) );
+        let eps = 1e-10 * extent.max( scale_max );
+
+        //pair of axis extremes furthest apart
+        let mut extremes = vec![];
+        for h in 0..3 {
+            let mut lo = 0;
+            let mut hi = 0;
+            for ( i, p ) in points.iter().enumerate() {
+                if p[h] < points[lo][h] { lo = i; }
+                if p[h] > points[hi][h] { hi = i; }
+            }
+            extremes.push( lo );
+            extremes.push( hi );
+        }
+        let mut i0 = 0;
+        let mut i1 = 0;
+        let mut best = -1f64;
+        for &a in extremes.iter() {
+            for &b in extremes.iter() {
+                let d = length( sub( points[a], points[b] ) );
+                if d > best {
+                    best = d;
+                    i0 = a;
+                    i1 = b;
+                }
+            }
+        }
+        if best <= eps {
+            return Ok( ConvexHull::from_parts( HullKind::Point, vec![ points[0] ], vec![], vec![], vec![], eps ) )
+        }
+
+        //point furthest from the line
+        let dir = normalize( sub( points[i1], points[i0] ) );
+        let ( i2, d2 ) = furthest( points, |p| length( cross( sub( p, points[i0] ), dir ) ) );
+        if d2 <= eps {
+            let ( lo, _ ) = furthest( points, |p| -dot( p, dir ) );
+            let ( hi, _ ) = furthest( points, |p| dot( p, dir ) );
+            return Ok( ConvexHull::from_parts( HullKind::Segment, vec![ points[lo], points[hi] ], vec![], vec![], vec![], eps ) )
+        }
+
+        //point furthest from the plane
+        let n = normalize( cross( sub( points[i1], points[i0] ), sub( points[i2], points[i0] ) ) );
+        let ( i3, d3 ) = furthest( points, |p| dot( sub( p, points[i0] ), n ).abs() );
+        if d3 <= eps {
+            return Ok( ConvexHull::polygon( points, points[i0], dir, n, eps ) )
+        }
+
+        Ok( ConvexHull::polyhedron( points, [ i0, i1, i2, i3 ], eps ) )
+    }
+
+    fn from_parts( kind: HullKind, verts: Vec< [f64;3] >, faces: Vec< [usize;3] >, normals: Vec< [f64;3] >, planes: Vec< ( [f64;3], f64 ) >, eps: f64 ) -> ConvexHull {
+        let mut lower = verts[0];
+        let mut upper = verts[0];
+        let mut centroid = [ 0f64; 3 ];
+        for v in verts.iter() {
+            for h in 0..3 {
+                lower[h] = lower[h].min( v[h] );
+                upper[h] = upper[h].max( v[h] );
+            }
+            centroid = add( centroid, scale( *v, 1. / verts.len() as f64 ) );
+        }
+        //conservative cube enclosing the vertices
+        let center = scale( add( lower, upper ), 0.5 );
+        let half = ( 0..3 ).fold( 0f64, |acc, h| acc.max( ( upper[h] - lower[h] ) / 2. ) );
+        ConvexHull {
+            _kind: kind,
+            _verts: verts.into_iter().map( |x| Mat3x1 { _val: x } ).collect(),
+            _faces: faces,
+            _normals: normals.into_iter().map( |x| Mat3x1 { _val: x } ).collect(),
+            _planes: planes.into_iter().map( |( n, d )| ( Mat3x1 { _val: n }, d ) ).collect(),
+            _ori: Mat3x1 { _val: centroid },
+            _bound: AxisAlignedBBox::init( ShapeType::BOX, &[ center[0], center[1], center[2], half ] ),
+            _vicinity: eps.max( 0.000001f64 ),
+        }
+    }
+
+    ///2d hull within the plane through origin with normal n using a monotone chain
+    fn polygon( points: &[ [f64;3] ], origin: [f64;3], u: [f64;3], n: [f64;3], eps: f64 ) -> ConvexHull {
+        let w = cross( n, u );
+        let mut proj : Vec< ( f64, f64, usize ) > = points.iter().enumerate()
+            .map( |( i, p )| ( dot( sub( *p, origin ), u ), dot( sub( *p, origin ), w ), i ) )
+            .collect();
+        proj.sort_by( |a, b| a.partial_cmp( b ).unwrap() );
+        //distance of a to the left of the line from o to b
+        let turn = |o: &( f64, f64, usize ), a: &( f64, f64, usize ), b: &( f64, f64, usize )| {
+            let l = ( ( b.0 - o.0 ) * ( b.0 - o.0 ) + ( b.1 - o.1 ) * ( b.1 - o.1 ) ).sqrt();
+            if l <= 0. {
+                0.
+            } else {
+                ( ( a.0 - o.0 ) * ( b.1 - o.1 ) - ( a.1 - o.1 ) * ( b.0 - o.0 ) ) / l
+            }
+        };
+        let mut chain : Vec< ( f64, f64, usize ) > = vec![];
+        for pass in 0..2 {
+            let start = chain.len();
+            let it : Box< Iterator< Item = &( f64, f64, usize ) > > = if pass == 0 { Box::new( proj.iter() ) } else { Box::new( proj.iter().rev() ) };
+            for p in it {
+                //drop colinear points along with right turns
+                while chain.len() >= start + 2 && turn( &chain[ chain.len() - 2 ], &chain[ chain.len() - 1 ], p ) <= eps {
+                    chain.pop();
+                }
+                chain.push( *p );
+            }
+            chain.pop();
+        }
+        let verts : Vec< [f64;3] > = chain.iter().map( |x| points[ x.2 ] ).collect();
+        let d = dot( n, origin );
+        let mut faces = vec![];
+        let mut normals = vec![];
+        for i in 1..verts.len() - 1 {
+            faces.push( [ 0, i, i + 1 ] );
+            normals.push( n );
+            faces.push( [ 0, i + 1, i ] );
+            normals.push( scale( n, -1. ) );
+        }
+        let mut planes = vec![ ( n, d ), ( scale( n, -1. ), -d ) ];
+        for i in 0..verts.len() {
+            let ( a, b ) = ( verts[i], verts[ ( i + 1 ) % verts.len() ] );
+            let e = normalize( cross( sub( b, a ), n ) );
+            planes.push( ( e, dot( e, a ) ) );
+        }
+        ConvexHull::from_parts( HullKind::Polygon, verts, faces, normals, planes, eps )
+    }
+
+    fn polyhedron( points: &[ [f64;3] ], simplex: [usize;4], eps: f64 ) -> ConvexHull {
+        let mut faces : Vec< HullFace > = vec![];
+        let mut edges : HashMap< ( usize, usize ), usize > = HashMap::new();
+
+        let centroid = scale( simplex.iter().fold( [ 0f64; 3 ], |acc, &i| add( acc, points[i] ) ), 0.25 );
+        for &( a, b, c ) in [ ( 0, 1, 2 ), ( 0, 3, 1 ), ( 1, 3, 2 ), ( 0, 2, 3 ) ].iter() {
+            let mut v = [ simplex[a], simplex[b], simplex[c] ];
+            let n = cross( sub( points[ v[1] ], points[ v[0] ] ), sub( points[ v[2] ], points[ v[0] ] ) );
+            if dot( n, sub( centroid, points[ v[0] ] ) ) > 0. {
+                v.swap( 1, 2 );
+            }
+            add_face( points, & mut faces, & mut edges, v );
+        }
+
+        //assign every other point to the face it lies furthest in front of
+        let all : Vec< usize > = ( 0..points.len() ).filter( |i| !simplex.contains( i ) ).collect();
+        let initial : Vec< usize > = ( 0..faces.len() ).collect();
+        assign_outside( points, & mut faces, &initial[..], all, eps );
+
+        let mut pending : Vec< usize > = ( 0..faces.len() ).collect();
+        while let Some( f ) = pending.pop() {
+            if !faces[f]._alive || faces[f]._outside.is_empty() {
+                continue;
+            }
+            let eye = *faces[f]._outside.iter().max_by( |&&a, &&b| {
+                face_dist( & faces[f], points[a] ).partial_cmp( & face_dist( & faces[f], points[b] ) ).unwrap()
+            } ).unwrap();
+            let p = points[ eye ];
+
+            //faces visible from the eye point and the horizon around them
+            let mut visible = vec![ f ];
+            let mut is_visible = HashMap::new();
+            is_visible.insert( f, true );
+            let mut horizon = vec![];
+            let mut k = 0;
+            while k < visible.len() {
+                let g = visible[k];
+                k += 1;
+                let v = faces[g]._v;
+                for e in 0..3 {
+                    let ( a, b ) = ( v[e], v[ ( e + 1 ) % 3 ] );
+                    let h = match edges.get( &( b, a ) ) {
+                        Some( &h ) => h,
+                        None => continue,
+                    };
+                    let seen = match is_visible.get( &h ) {
+                        Some( &x ) => Some( x ),
+                        None => None,
+                    };
+                    match seen {
+                        Some( true ) => {},
+                        Some( false ) => horizon.push( ( a, b ) ),
+                        None => {
+                            let vis = face_dist( & faces[h], p ) > eps;
+                            is_visible.insert( h, vis );
+                            if vis {
+                                visible.push( h );
+                            } else {
+                                horizon.push( ( a, b ) );
+                            }
+                        },
+                    }
+                }
+            }
+
+            //replace the visible faces with a cone from the horizon to the eye point
+            let mut orphans = vec![];
+            for &g in visible.iter() {
+                faces[g]._alive = false;
+                let v = faces[g]._v;
+                for e in 0..3 {
+                    edges.remove( &( v[e], v[ ( e + 1 ) % 3 ] ) );
+                }
+                orphans.extend( faces[g]._outside.drain( .. ).filter( |&x| x != eye ) );
+            }
+            let mut created = vec![];
+            for &( a, b ) in horizon.iter() {
+                created.push( add_face( points, & mut faces, & mut edges, [ a, b, eye ] ) );
+            }
+            assign_outside( points, & mut faces, &created[..], orphans, eps );
+            pending.extend( created );
+        }
+
+        //compact to the vertices in use
+        let mut remap = HashMap::new();
+        let mut verts = vec![];
+        let mut out_faces = vec![];
+        let mut normals = vec![];
+        let mut planes = vec![];
+        for f in faces.iter().filter( |x| x._alive ) {
+            let mut t = [ 0usize; 3 ];
+            for c in 0..3 {
+                let n = verts.len();
+                t[c] = *remap.entry( f._v[c] ).or_insert( n );
+                if t[c] == n {
+                    verts.push( points[ f._v[c] ] );
+                }
+            }
+            out_faces.push( t );
+            normals.push( f._n );
+            planes.push( ( f._n, f._d ) );
+        }
+        ConvexHull::from_parts( HullKind::Polyhedron, verts, out_faces, normals, planes, eps )
+    }
+
+    pub fn contains_point( & self, p: [f64;3] ) -> bool {
+        let v : Vec< [f64;3] > = self._verts.iter().map( |x| x._val ).collect();
+        match self._kind {
+            HullKind::Point => length( sub( p, v[0] ) ) <= self._vicinity,
+            HullKind::Segment => {
+                let e = sub( v[1], v[0] );
+                let t = ( dot( sub( p, v[0] ), e ) / dot( e, e ) ).max( 0. ).min( 1. );
+                length( sub( p, add( v[0], scale( e, t ) ) ) ) <= self._vicinity
+            },
+            _ => self._planes.iter().all( |&( ref n, d )| dot( n._val, p ) - d <= self._vicinity ),
+        }
+    }
+
+    ///enclosed volume, zero for flat hulls
+    pub fn volume( & self ) -> f64 {
+        if self._kind != HullKind::Polyhedron {
+            return 0.
+        }
+        let o = self._ori._val;
+        self._faces.iter().fold( 0., |acc, f| {
+            let ( a, b, c ) = ( sub( self._verts[ f[0] ]._val, o ), sub( self._verts[ f[1] ]._val, o ), sub( self._verts[ f[2] ]._val, o ) );
+            acc + dot( a, cross( b, c ) ) / 6.
+        } )
+    }
+
+    pub fn intersect_sphere( & self, center: [f64;3], radius: f64 ) -> bool {
+        match self._kind {
+            HullKind::Point | HullKind::Segment => {
+                let d = sub( self.closest_on_segment( center ), center );
+                length( d ) <= radius + self._vicinity
+            },
+            _ => self._planes.iter().all( |&( ref n, d )| dot( n._val, center ) - d <= radius + self._vicinity ),
+        }
+    }
+
+    ///separating axis test over the hull planes and the box axes
+    pub fn intersect_aabb( & self, lower: [f64;3], upper: [f64;3] ) -> bool {
+        for h in 0..3 {
+            let lo = self._verts.iter().fold( f64::INFINITY, |acc, x| acc.min( x[h] ) );
+            let hi = self._verts.iter().fold( f64::NEG_INFINITY, |acc, x| acc.max( x[h] ) );
+            if lo > upper[h] + self._vicinity || hi < lower[h] - self._vicinity {
+                return false
+            }
+        }
+        self._planes.iter().all( |&( ref n, d )| {
+            //box corner furthest against the plane normal
+            let n = n._val;
+            let p = [ if n[0] >= 0. { lower[0] } else { upper[0] },
+                      if n[1] >= 0. { lower[1] } else { upper[1] },
+                      if n[2] >= 0. { lower[2] } else { upper[2] } ];
+            dot( n, p ) - d <= self._vicinity
+        } )
+    }
+
+    ///gjk test against the support mapping of an arbitrary convex shape. shapes without a support mapping are
+    ///assumed to intersect
+    fn intersect_support( & self, other: & IShape ) -> bool {
+        if other.get_support( & Mat3x1 { _val: [ 1., 0., 0. ] } ).is_none() {
+            return true
+        }
+        let support = |s: & IShape, d: [f64;3]| s.get_support( & Mat3x1 { _val: d } ).map_or( [0f64;3], |x| x._val );
+        convex_overlap( |d| support( self, d ), |d| support( other, d ) )
+    }
+
+    fn closest_on_segment( & self, p: [f64;3] ) -> [f64;3] {
+        let a = self._verts[0]._val;
+        let b = self._verts[ self._verts.len() - 1 ]._val;
+        let e = sub( b, a );
+        let l2 = dot( e, e );
+        if l2 <= 0. {
+            return a
+        }
+        let t = ( dot( sub( p, a ), e ) / l2 ).max( 0. ).min( 1. );
+        add( a, scale( e, t ) )
+    }
+}
+
+impl IShape for ConvexHull {
+    fn get_shape_data( & self ) -> Vec< f64 > {
+        let mut d = vec![ self._ori[0], self._ori[1], self._ori[2] ];
+        for v in self._verts.iter() {
+            d.extend_from_slice( & v._val[..] );
+        }
+        d
+    }
+    fn get_type( & self ) -> ShapeType {
+        ShapeType::COMPLEX
+    }
+    fn get_bound( & self ) -> &IBound {
+        &self._bound
+    }
+    ///returns intersection status, no intersection point is computed
+    fn get_intersect( & self, other: & IShape ) -> ( bool, Option< Mat3x1< f64 > > ){
+        let d = other.get_shape_data();
+        let hit = match other.get_type() {
+            ShapeType::SPHERE if d.len() >= 4 => {
+                self.intersect_sphere( [ d[0], d[1], d[2] ], d[3] )
+            },
+            ShapeType::BOX if d.len() >= 4 => {
+                self.intersect_aabb( [ d[0] - d[3], d[1] - d[3], d[2] - d[3] ],
+                                     [ d[0] + d[3], d[1] + d[3], d[2] + d[3] ] )
+            },
+            ShapeType::POINT if d.len() >= 3 => {
+                self.contains_point( [ d[0], d[1], d[2] ] )
+            },
+            _ => {
+                self.intersect_support( other )
+            },
+        };
+        ( hit, None )
+    }
+    ///furthest hull vertex along v
+    fn get_support( & self, v: & Mat3x1< f64 > ) -> Option< Mat3x1< f64 > > {
+        let mut best = self._verts[0];
+        let mut best_d = dot( best._val, v._val );
+        for c in self._verts.iter().skip( 1 ) {
+            let d = dot( c._val, v._val );
+            if d > best_d {
+                best_d = d;
+                best = *c;
+            }
+        }
+        Some( best )
+    }
+}
+
+impl IVicinity< f64 > for ConvexHull {
+    fn set_vicinity( & mut self, epsilon: f64 ) {
+        self._vicinity = epsilon.abs();
+    }
+    fn within_vicinity( & self, a: f64, b: f64 ) -> bool {
+        if a + self._vicinity >= b &&
+           a - self._vicinity <= b {
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn add_face( points: &[ [f64;3] ], faces: & mut Vec< HullFace >, edges: & mut HashMap< ( usize, usize ), usize >, v: [usize;3] ) -> usize {
+    let n = normalize( cross( sub( points[ v[1] ], points[ v[0] ] ), sub( points[ v[2] ], points[ v[0] ] ) ) );
+    let idx = faces.len();
+    faces.push( HullFace {
+        _v: v,
+        _n: n,
+        _d: dot( n, points[ v[0] ] ),
+        _outside: vec![],
+        _alive: true,
+    } );
+    for e in 0..3 {
+        edges.insert( ( v[e], v[ ( e + 1 ) % 3 ] ), idx );
+    }
+    idx
+}
+
+fn face_dist( f: & HullFace, p: [f64;3] ) -> f64 {
+    dot( f._n, p ) - f._d
+}
+
+///moves each point into the outside set of the candidate face it lies furthest in front of, points behind all are dropped
+fn assign_outside( points: &[ [f64;3] ], faces: & mut Vec< HullFace >, candidates: &[ usize ], pts: Vec< usize >, eps: f64 ) {
+    for i in pts {
+        let mut best = None;
+        let mut best_d = eps;
+        for &f in candidates.iter() {
+            let d = face_dist( & faces[f], points[i] );
+            if d > best_d {
+                best_d = d;
+                best = Some( f );
+            }
+        }
+        if let Some( f ) = best {
+            faces[f]._outside.push( i );
+        }
+    }
+}
+
+///index and value of the point maximizing f
+fn furthest< F >( points: &[ [f64;3] ], f: F ) -> ( usize, f64 ) where F: Fn( [f64;3] ) -> f64 {
+    points.iter().enumerate().fold( ( 0, f64::NEG_INFINITY ), |acc, ( i, p )| {
+        let d = f( *p );
+        if d > acc.1 { ( i, d ) } else { acc }
+    } )
+}
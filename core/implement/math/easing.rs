@@ -0,0 +1,144 @@
+use std::f64::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EaseMode {
+    In,
+    Out,
+    InOut,
+}
+
+///easing curves mapping a linear fraction in [0,1] to an eased fraction with ease(0) = 0 and ease(1) = 1.
+///back and elastic curves overshoot outside of [0,1] in between
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ease {
+    Linear,
+    Quad( EaseMode ),
+    Cubic( EaseMode ),
+    Expo( EaseMode ),
+    Elastic( EaseMode ),
+    Bounce( EaseMode ),
+    Back( EaseMode ),
+    ///css style cubic-bezier( x1, y1, x2, y2 ) through (0,0) and (1,1), x1 and x2 in [0,1]
+    CubicBezier( f64, f64, f64, f64 ),
+}
+
+impl Default for Ease {
+    fn default() -> Ease {
+        Ease::Linear
+    }
+}
+
+impl Ease {
+    ///css ease
+    pub fn css_ease() -> Ease {
+        Ease::CubicBezier( 0.25, 0.1, 0.25, 1. )
+    }
+    pub fn css_ease_in() -> Ease {
+        Ease::CubicBezier( 0.42, 0., 1., 1. )
+    }
+    pub fn css_ease_out() -> Ease {
+        Ease::CubicBezier( 0., 0., 0.58, 1. )
+    }
+    pub fn css_ease_in_out() -> Ease {
+        Ease::CubicBezier( 0.42, 0., 0.58, 1. )
+    }
+
+    ///eased fraction, input is clamped to [0,1]
+    pub fn apply( & self, t: f64 ) -> f64 {
+        let t = t.max( 0. ).min( 1. );
+        match *self {
+            Ease::Linear => t,
+            Ease::Quad( m ) => with_mode( m, t, |x| x * x ),
+            Ease::Cubic( m ) => with_mode( m, t, |x| x * x * x ),
+            Ease::Expo( m ) => with_mode( m, t, expo_in ),
+            Ease::Elastic( m ) => with_mode( m, t, elastic_in ),
+            Ease::Bounce( m ) => with_mode( m, t, bounce_in ),
+            Ease::Back( m ) => with_mode( m, t, back_in ),
+            Ease::CubicBezier( x1, y1, x2, y2 ) => cubic_bezier( x1, y1, x2, y2, t ),
+        }
+    }
+}
+
+///derives out and in-out variants from an ease-in curve
+fn with_mode< F >( m: EaseMode, t: f64, f: F ) -> f64 where F: Fn( f64 ) -> f64 {
+    match m {
+        EaseMode::In => f( t ),
+        EaseMode::Out => 1. - f( 1. - t ),
+        EaseMode::InOut => {
+            if t < 0.5 {
+                f( 2. * t ) * 0.5
+            } else {
+                1. - f( 2. - 2. * t ) * 0.5
+            }
+        },
+    }
+}
+
+fn expo_in( t: f64 ) -> f64 {
+    if t <= 0. { 0. } else { 2f64.powf( 10. * t - 10. ) }
+}
+
+fn elastic_in( t: f64 ) -> f64 {
+    if t <= 0. {
+        0.
+    } else if t >= 1. {
+        1.
+    } else {
+        -2f64.powf( 10. * t - 10. ) * ( ( 10. * t - 10.75 ) * 2. * PI / 3. ).sin()
+    }
+}
+
+fn bounce_out( t: f64 ) -> f64 {
+    let n = 7.5625;
+    let d = 2.75;
+    if t < 1. / d {
+        n * t * t
+    } else if t < 2. / d {
+        let t = t - 1.5 / d;
+        n * t * t + 0.75
+    } else if t < 2.5 / d {
+        let t = t - 2.25 / d;
+        n * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / d;
+        n * t * t + 0.984375
+    }
+}
+
+fn bounce_in( t: f64 ) -> f64 {
+    1. - bounce_out( 1. - t )
+}
+
+fn back_in( t: f64 ) -> f64 {
+    let c1 = 1.70158;
+    ( c1 + 1. ) * t * t * t - c1 * t * t
+}
+
+///solves for the bezier parameter at x with newton iterations, falling back to bisection
+fn cubic_bezier( x1: f64, y1: f64, x2: f64, y2: f64, x: f64 ) -> f64 {
+    let bez = |a: f64, b: f64, s: f64| 3. * a * ( 1. - s ) * ( 1. - s ) * s + 3. * b * ( 1. - s ) * s * s + s * s * s;
+    let bez_d = |a: f64, b: f64, s: f64| 3. * a * ( 1. - s ) * ( 1. - s ) + 6. * ( b - a ) * ( 1. - s ) * s + 3. * ( 1. - b ) * s * s;
+    let mut s = x;
+    for _ in 0..8 {
+        let err = bez( x1, x2, s ) - x;
+        if err.abs() < 1e-9 {
+            return bez( y1, y2, s )
+        }
+        let d = bez_d( x1, x2, s );
+        if d.abs() < 1e-9 {
+            break;
+        }
+        s -= err / d;
+    }
+    let ( mut lo, mut hi ) = ( 0f64, 1f64 );
+    s = x;
+    for _ in 0..60 {
+        let v = bez( x1, x2, s );
+        if ( v - x ).abs() < 1e-9 {
+            break;
+        }
+        if v < x { lo = s; } else { hi = s; }
+        s = ( lo + hi ) * 0.5;
+    }
+    bez( y1, y2, s )
+}
@@ -1,5 +1,7 @@
 extern crate mazth;
 
+use std::f64;
+
 use self::mazth::i_shape::{ ShapeType, IShape };
 use self::mazth::i_bound::IBound;
 use self::mazth::i_vicinity::IVicinity;
@@ -7,30 +9,154 @@ use self::mazth::i_vicinity::IVicinity;
 use self::mazth::mat::Mat3x1;
 use self::mazth::bound::AxisAlignedBBox;
 
+use implement::math::vec3::{ add, sub, scale, dot, cross, normalize };
+use implement::math::intersect::convex_overlap;
+
 #[derive(Debug, Clone)]
 pub struct Frustum {
-    ///a frustum of square top and bottom caps
-    ///work in progress
+    ///a frustum of rectangular top and bottom caps, described by its corners and inward facing planes
     pub _ori: Mat3x1< f64 >,
     pub _halfedge_small: f64, //smaller of the 2 caps
     pub _halfedge_big: f64, //bigger of the 2 caps
     pub _halflength: f64, //center half length of the frustum
+    ///corners of small cap followed by corners of big cap, each in the same winding
+    pub _corners: [ Mat3x1< f64 >; 8 ],
+    ///plane normal n and offset d such that n.x + d >= 0 for points inside
+    ///order: small cap, big cap, left, right, bottom, top
+    pub _planes: [ ( Mat3x1< f64 >, f64 ); 6 ],
     pub _bound: AxisAlignedBBox,
     pub _vicinity: f64,
 }
-    
+
 impl Frustum {
+    ///square capped frustum centered at origin with its axis along +z, small cap toward -z
     pub fn init( origin: &[f64], halflength: f64, halfedge_small: f64, halfedge_big: f64 ) -> Frustum {
         assert!( origin.len() == 3 );
+        let o = [ origin[0], origin[1], origin[2] ];
+        let mut corners = [ [0f64;3]; 8 ];
+        for ( cap, &( z, h ) ) in [ ( -halflength, halfedge_small ), ( halflength, halfedge_big ) ].iter().enumerate() {
+            for ( k, &( sx, sy ) ) in [ ( -1f64, -1f64 ), ( 1., -1. ), ( 1., 1. ), ( -1., 1. ) ].iter().enumerate() {
+                corners[ cap * 4 + k ] = [ o[0] + sx * h, o[1] + sy * h, o[2] + z ];
+            }
+        }
+        Frustum::init_from_corners( corners, halflength, halfedge_small, halfedge_big )
+    }
+
+    ///view frustum of a perspective camera. fov is the full vertical field of view in degrees
+    pub fn init_from_view( fov: f64, aspect: f64, near: f64, far: f64, pos: [f64;3], focus: [f64;3], up: [f64;3] ) -> Frustum {
+        let f = normalize( sub( focus, pos ) );
+        let r = normalize( cross( f, up ) );
+        let u = cross( r, f );
+        let half_tan = ( fov * f64::consts::PI / 360. ).tan();
+        let mut corners = [ [0f64;3]; 8 ];
+        for ( cap, &dist ) in [ near, far ].iter().enumerate() {
+            let c = add( pos, scale( f, dist ) );
+            let h = dist * half_tan;
+            let w = h * aspect;
+            for ( k, &( sx, sy ) ) in [ ( -1f64, -1f64 ), ( 1., -1. ), ( 1., 1. ), ( -1., 1. ) ].iter().enumerate() {
+                corners[ cap * 4 + k ] = add( c, add( scale( r, sx * w ), scale( u, sy * h ) ) );
+            }
+        }
+        Frustum::init_from_corners( corners, ( far - near ) / 2., near * half_tan, far * half_tan )
+    }
+
+    fn init_from_corners( corners: [ [f64;3]; 8 ], halflength: f64, halfedge_small: f64, halfedge_big: f64 ) -> Frustum {
+        let mut centroid = [0f64;3];
+        for c in corners.iter() {
+            centroid = add( centroid, scale( *c, 1. / 8. ) );
+        }
+        //corner indices spanning each face
+        let faces = [ [ 0, 1, 2 ], [ 4, 5, 6 ], [ 0, 3, 7 ], [ 1, 2, 6 ], [ 0, 1, 5 ], [ 3, 2, 6 ] ];
+        let mut planes = [ ( Mat3x1 { _val: [0f64;3] }, 0f64 ); 6 ];
+        for ( i, f ) in faces.iter().enumerate() {
+            let ( a, b, c ) = ( corners[ f[0] ], corners[ f[1] ], corners[ f[2] ] );
+            let mut n = normalize( cross( sub( b, a ), sub( c, a ) ) );
+            //orient toward inside
+            if dot( n, sub( centroid, a ) ) < 0. {
+                n = scale( n, -1. );
+            }
+            planes[i] = ( Mat3x1 { _val: n }, -dot( n, a ) );
+        }
+        let mut lower = corners[0];
+        let mut upper = corners[0];
+        for c in corners.iter() {
+            for h in 0..3 {
+                lower[h] = lower[h].min( c[h] );
+                upper[h] = upper[h].max( c[h] );
+            }
+        }
+        //conservative cube enclosing the corners
+        let center = scale( add( lower, upper ), 0.5 );
+        let half = ( 0..3 ).fold( 0f64, |acc, h| acc.max( ( upper[h] - lower[h] ) / 2. ) );
+        let mut c = [ Mat3x1 { _val: [0f64;3] }; 8 ];
+        for i in 0..8 {
+            c[i] = Mat3x1 { _val: corners[i] };
+        }
         Frustum {
-            _ori: Mat3x1 { _val: [ origin[0], origin[1], origin[2] ] },
+            _ori: Mat3x1 { _val: centroid },
             _halflength: halflength,
             _halfedge_small: halfedge_small,
             _halfedge_big: halfedge_big,
-            _bound: AxisAlignedBBox::init( ShapeType::FRUSTUM, &[ &origin[0..3], &[ halflength ] ].concat() ),
+            _corners: c,
+            _planes: planes,
+            _bound: AxisAlignedBBox::init( ShapeType::BOX, &[ center[0], center[1], center[2], half ] ),
             _vicinity: 0.000001f64,
         }
-        
+    }
+
+    fn furthest_corner( & self, v: [f64;3] ) -> [f64;3] {
+        let mut best = self._corners[0]._val;
+        let mut best_d = dot( best, v );
+        for c in self._corners.iter().skip( 1 ) {
+            let d = dot( c._val, v );
+            if d > best_d {
+                best_d = d;
+                best = c._val;
+            }
+        }
+        best
+    }
+
+    ///signed distance to a plane, positive toward inside
+    fn plane_dist( & self, i: usize, p: [f64;3] ) -> f64 {
+        dot( self._planes[i].0._val, p ) + self._planes[i].1
+    }
+
+    pub fn contains_point( & self, p: [f64;3] ) -> bool {
+        ( 0..6 ).all( |i| self.plane_dist( i, p ) >= -self._vicinity )
+    }
+
+    pub fn intersect_sphere( & self, center: [f64;3], radius: f64 ) -> bool {
+        ( 0..6 ).all( |i| self.plane_dist( i, center ) >= -radius - self._vicinity )
+    }
+
+    ///conservative: may report intersection for boxes near frustum edges that are outside
+    pub fn intersect_aabb( & self, lower: [f64;3], upper: [f64;3] ) -> bool {
+        ( 0..6 ).all( |i| {
+            //box corner furthest along plane normal
+            let n = self._planes[i].0._val;
+            let p = [ if n[0] >= 0. { upper[0] } else { lower[0] },
+                      if n[1] >= 0. { upper[1] } else { lower[1] },
+                      if n[2] >= 0. { upper[2] } else { lower[2] } ];
+            self.plane_dist( i, p ) >= -self._vicinity
+        } )
+    }
+
+    ///separating plane test over both frusta's planes
+    pub fn intersect_frustum( & self, other: & Frustum ) -> bool {
+        let sep_self = ( 0..6 ).any( |i| other._corners.iter().all( |c| self.plane_dist( i, c._val ) < -self._vicinity ) );
+        let sep_other = ( 0..6 ).any( |i| self._corners.iter().all( |c| other.plane_dist( i, c._val ) < -other._vicinity ) );
+        !sep_self && !sep_other
+    }
+
+    ///gjk test against the support mapping of an arbitrary convex shape. shapes without a support mapping are
+    ///assumed to intersect
+    fn intersect_support( & self, other: & IShape ) -> bool {
+        if other.get_support( & Mat3x1 { _val: [ 1., 0., 0. ] } ).is_none() {
+            return true
+        }
+        let support_other = |d: [f64;3]| other.get_support( & Mat3x1 { _val: d } ).map_or( [0f64;3], |x| x._val );
+        convex_overlap( |d| self.furthest_corner( d ), support_other )
     }
 }
 
@@ -39,16 +165,35 @@ impl IShape for Frustum {
         vec![ self._ori[0], self._ori[1], self._ori[2], self._halflength, self._halfedge_small, self._halfedge_big ]
     }
     fn get_type( & self ) -> ShapeType {
-        ShapeType::SPHERE
+        ShapeType::FRUSTUM
     }
     fn get_bound( & self ) -> &IBound {
         &self._bound
     }
-    fn get_intersect( & self, _other: & IShape ) -> ( bool, Option< Mat3x1< f64 > > ){
-        unimplemented!();
+    ///returns intersection status, no intersection point is computed
+    fn get_intersect( & self, other: & IShape ) -> ( bool, Option< Mat3x1< f64 > > ){
+        let d = other.get_shape_data();
+        let hit = match other.get_type() {
+            ShapeType::SPHERE if d.len() >= 4 => {
+                self.intersect_sphere( [ d[0], d[1], d[2] ], d[3] )
+            },
+            ShapeType::BOX if d.len() >= 4 => {
+                self.intersect_aabb( [ d[0] - d[3], d[1] - d[3], d[2] - d[3] ],
+                                     [ d[0] + d[3], d[1] + d[3], d[2] + d[3] ] )
+            },
+            ShapeType::POINT if d.len() >= 3 => {
+                self.contains_point( [ d[0], d[1], d[2] ] )
+            },
+            _ => {
+                //shape data does not fully describe other frusta or complex shapes, rely on support mapping
+                self.intersect_support( other )
+            },
+        };
+        ( hit, None )
     }
-    fn get_support( & self, _v: & Mat3x1< f64 > ) -> Option< Mat3x1< f64 > > {
-        unimplemented!();
+    ///furthest corner along v
+    fn get_support( & self, v: & Mat3x1< f64 > ) -> Option< Mat3x1< f64 > > {
+        Some( Mat3x1 { _val: self.furthest_corner( v._val ) } )
     }
 }
 
@@ -0,0 +1,215 @@
+///intersection tests over plain arrays. ray tests are computed in f64, the f32 variants convert their arguments
+
+use std::f64;
+
+use implement::math::vec3::{ sub, scale, dot, cross };
+
+///entry and exit of a line through a box, with the axis and side ( -1 for lower, 1 for upper ) of the faces crossed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlabHit {
+    pub _t_near: f64,
+    pub _t_far: f64,
+    pub _face_near: ( usize, f64 ),
+    pub _face_far: ( usize, f64 ),
+}
+
+///slab test along the infinite line, entry may be negative
+pub fn ray_aabb_f64( origin: [f64;3], dir: [f64;3], lower: [f64;3], upper: [f64;3] ) -> Option< SlabHit > {
+    let mut hit = SlabHit {
+        _t_near: f64::NEG_INFINITY,
+        _t_far: f64::INFINITY,
+        _face_near: ( 0, 0. ),
+        _face_far: ( 0, 0. ),
+    };
+    for h in 0..3 {
+        if dir[h] == 0. {
+            if origin[h] < lower[h] || origin[h] > upper[h] {
+                return None
+            }
+            continue;
+        }
+        let a = ( lower[h] - origin[h] ) / dir[h];
+        let b = ( upper[h] - origin[h] ) / dir[h];
+        let ( near, far, side ) = if a < b { ( a, b, -1. ) } else { ( b, a, 1. ) };
+        if near > hit._t_near {
+            hit._t_near = near;
+            hit._face_near = ( h, side );
+        }
+        if far < hit._t_far {
+            hit._t_far = far;
+            hit._face_far = ( h, -side );
+        }
+    }
+    if hit._t_near <= hit._t_far { Some( hit ) } else { None }
+}
+
+///double sided Moller-Trumbore test, returns distance and barycentric coordinates u, v of the hit
+pub fn ray_triangle_f64( origin: [f64;3], dir: [f64;3], v0: [f64;3], v1: [f64;3], v2: [f64;3] ) -> Option< ( f64, f64, f64 ) > {
+    let e1 = sub( v1, v0 );
+    let e2 = sub( v2, v0 );
+    let p = cross( dir, e2 );
+    let det = dot( e1, p );
+    if det.abs() < 1e-20 {
+        return None
+    }
+    let inv = 1. / det;
+    let s = sub( origin, v0 );
+    let u = dot( s, p ) * inv;
+    if u < 0. || u > 1. {
+        return None
+    }
+    let q = cross( s, e1 );
+    let v = dot( dir, q ) * inv;
+    if v < 0. || u + v > 1. {
+        return None
+    }
+    Some( ( dot( e2, q ) * inv, u, v ) )
+}
+
+///ordered distances where the infinite line enters and leaves a sphere, entry may be negative
+pub fn ray_sphere_f64( origin: [f64;3], dir: [f64;3], center: [f64;3], radius: f64 ) -> Option< ( f64, f64 ) > {
+    let oc = sub( origin, center );
+    let a = dot( dir, dir );
+    if a == 0. {
+        return None
+    }
+    let b = dot( oc, dir );
+    let c = dot( oc, oc ) - radius * radius;
+    let disc = b * b - a * c;
+    if disc < 0. {
+        return None
+    }
+    let s = disc.sqrt();
+    //numerically stable roots
+    let q = if b > 0. { -b - s } else { -b + s };
+    let ( t0, t1 ) = if q == 0. { ( 0., 0. ) } else { ( q / a, c / q ) };
+    if t0 > t1 { Some( ( t1, t0 ) ) } else { Some( ( t0, t1 ) ) }
+}
+
+pub fn ray_aabb( origin: [f32;3], dir: [f32;3], lower: [f32;3], upper: [f32;3] ) -> Option< ( f32, f32 ) > {
+    ray_aabb_f64( to_f64( origin ), to_f64( dir ), to_f64( lower ), to_f64( upper ) ).map( |x| ( x._t_near as f32, x._t_far as f32 ) )
+}
+
+pub fn ray_triangle( origin: [f32;3], dir: [f32;3], v0: [f32;3], v1: [f32;3], v2: [f32;3] ) -> Option< ( f32, f32, f32 ) > {
+    ray_triangle_f64( to_f64( origin ), to_f64( dir ), to_f64( v0 ), to_f64( v1 ), to_f64( v2 ) ).map( |x| ( x.0 as f32, x.1 as f32, x.2 as f32 ) )
+}
+
+pub fn ray_sphere( origin: [f32;3], dir: [f32;3], center: [f32;3], radius: f32 ) -> Option< ( f32, f32 ) > {
+    ray_sphere_f64( to_f64( origin ), to_f64( dir ), to_f64( center ), radius as f64 ).map( |x| ( x.0 as f32, x.1 as f32 ) )
+}
+
+pub fn to_f64( a: [f32;3] ) -> [f64;3] {
+    [ a[0] as f64, a[1] as f64, a[2] as f64 ]
+}
+
+pub fn aabb_overlap( l0: [f32;3], u0: [f32;3], l1: [f32;3], u1: [f32;3] ) -> bool {
+    ( 0..3 ).all( |h| l0[h] <= u1[h] && l1[h] <= u0[h] )
+}
+
+pub fn sphere_aabb( center: [f32;3], radius: f32, lower: [f32;3], upper: [f32;3] ) -> bool {
+    let d2 = ( 0..3 ).fold( 0f32, |acc, h| {
+        let v = center[h].max( lower[h] ).min( upper[h] ) - center[h];
+        acc + v * v
+    } );
+    d2 <= radius * radius
+}
+
+pub fn aabb_union( l0: [f32;3], u0: [f32;3], l1: [f32;3], u1: [f32;3] ) -> ( [f32;3], [f32;3] ) {
+    ( [ l0[0].min( l1[0] ), l0[1].min( l1[1] ), l0[2].min( l1[2] ) ],
+      [ u0[0].max( u1[0] ), u0[1].max( u1[1] ), u0[2].max( u1[2] ) ] )
+}
+
+pub fn aabb_surface_area( lower: [f32;3], upper: [f32;3] ) -> f32 {
+    let d = sub( upper, lower );
+    if d[0] < 0. || d[1] < 0. || d[2] < 0. {
+        return 0.
+    }
+    2. * ( d[0] * d[1] + d[1] * d[2] + d[2] * d[0] )
+}
+
+///gjk overlap test of 2 convex shapes given their support mappings, touching counts as overlap
+pub fn convex_overlap< A, B >( support_a: A, support_b: B ) -> bool where A: Fn( [f64;3] ) -> [f64;3], B: Fn( [f64;3] ) -> [f64;3] {
+    //support of the minkowski difference a - b
+    let support = |d: [f64;3]| sub( support_a( d ), support_b( scale( d, -1. ) ) );
+    let mut simplex = vec![ support( [ 1., 0., 0. ] ) ];
+    let mut d = scale( simplex[0], -1. );
+    for _ in 0..64 {
+        if dot( d, d ) == 0. {
+            return true
+        }
+        let a = support( d );
+        if dot( a, d ) < 0. {
+            return false
+        }
+        simplex.push( a );
+        match gjk_simplex( & mut simplex ) {
+            Some( n ) => { d = n; },
+            None => { return true },
+        }
+    }
+    //no convergence, treat as touching
+    true
+}
+
+///reduces the simplex to the feature closest to the origin, newest point last. returns the next search direction
+///or none if the origin is enclosed
+fn gjk_simplex( s: & mut Vec< [f64;3] > ) -> Option< [f64;3] > {
+    let a = s[ s.len() - 1 ];
+    let ao = scale( a, -1. );
+    match s.len() {
+        2 => {
+            let ab = sub( s[0], a );
+            if dot( ab, ao ) > 0. {
+                let n = cross( cross( ab, ao ), ab );
+                if dot( n, n ) == 0. { None } else { Some( n ) }
+            } else {
+                *s = vec![ a ];
+                Some( ao )
+            }
+        },
+        3 => {
+            let ( b, c ) = ( s[1], s[0] );
+            let ab = sub( b, a );
+            let ac = sub( c, a );
+            let abc = cross( ab, ac );
+            if dot( cross( abc, ac ), ao ) > 0. {
+                if dot( ac, ao ) > 0. {
+                    *s = vec![ c, a ];
+                    Some( cross( cross( ac, ao ), ac ) )
+                } else {
+                    *s = vec![ b, a ];
+                    gjk_simplex( s )
+                }
+            } else if dot( cross( ab, abc ), ao ) > 0. {
+                *s = vec![ b, a ];
+                gjk_simplex( s )
+            } else {
+                let side = dot( abc, ao );
+                if side > 0. {
+                    Some( abc )
+                } else if side < 0. {
+                    *s = vec![ b, c, a ];
+                    Some( scale( abc, -1. ) )
+                } else {
+                    None
+                }
+            }
+        },
+        _ => {
+            let ( b, c, d ) = ( s[2], s[1], s[0] );
+            let ab = sub( b, a );
+            let ac = sub( c, a );
+            let ad = sub( d, a );
+            if dot( cross( ab, ac ), ao ) > 0. {
+                *s = vec![ c, b, a ];
+            } else if dot( cross( ac, ad ), ao ) > 0. {
+                *s = vec![ d, c, a ];
+            } else if dot( cross( ad, ab ), ao ) > 0. {
+                *s = vec![ b, d, a ];
+            } else {
+                return None
+            }
+            gjk_simplex( s )
+        },
+    }
+}
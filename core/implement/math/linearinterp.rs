@@ -62,6 +62,13 @@ impl IInterpolate< Mat4x1< f64 > > for LinearInterp {
     }
 }
 
+impl IInterpolateEval< Mat4x1< f64 > > for LinearInterp {
+    fn eval( & self, fraction: f64 ) -> Mat4x1< f64 > {
+        let d = self._ctl[1].minus( &self._ctl[0] ).unwrap();
+        self._ctl[0].plus( & d.scale( fraction ).unwrap() ).unwrap()
+    }
+}
+
 impl Iterator for LinearInterp { //required by IInterpolate
     type Item = Mat4x1< f64 >;
     fn next( & mut self ) -> Option< Mat4x1< f64 > > {
@@ -1,10 +1,22 @@
 pub mod util;
+pub mod vec3;
 pub mod spline_bezier;
 pub mod linearinterp;
 pub mod piecewise;
+pub mod spline_cubic;
+pub mod arclength;
+pub mod quat;
+pub mod quat_interp;
+pub mod easing;
 pub mod frustum;
+pub mod intersect;
+pub mod convex_hull;
+pub mod surf_interact;
 ///spatial accelerators
 pub mod clusterbox;
+pub mod bvh;
+///mesh processing
+pub mod simplify;
 // pub mod svo;
 ///probabilistic algorithms
 pub mod sim_anneal;
@@ -51,6 +51,19 @@ impl < T, V > IInterpolate< V > for Piecewise< T, V > where T: IInterpolate< V >
     }
     fn interp_delta( & mut self, steps: i64 ) -> Option< V > {
         let mut ret = None;
+        if steps < 0 {
+            for i in 0..-steps {
+                match self.next_back() {
+                    Some(x) => {
+                        ret = Some( self.interp_current() );
+                    },
+                    None => {
+                        return Some( self.interp_current() )
+                    },
+                }
+            }
+            return ret
+        }
         for i in 0..steps {
             match self.next() {
                 Some(x) => {
@@ -118,7 +131,24 @@ impl < T, V > Iterator for Piecewise< T, V > where T: IInterpolate< V >, V : Clo
 
 impl < T, V > DoubleEndedIterator for Piecewise< T, V > where T: IInterpolate< V > , V : Clone {
     fn next_back( & mut self ) -> Option< V > {
-        unimplemented!();
+        if self._pieces.is_empty() {
+            return None
+        }
+        if self._current_piece as usize >= self._pieces.len() {
+            self._current_piece = self._pieces.len() as u64 - 1;
+        }
+        match self._pieces[ self._current_piece as usize ].next_back() {
+            Some( s ) => Some( s ),
+            None => {
+                if self._current_piece == 0 {
+                    None
+                } else {
+                    //earlier pieces are left at their end by forward iteration
+                    self._current_piece -= 1;
+                    self.next_back()
+                }
+            },
+        }
     }
 }
 
@@ -0,0 +1,94 @@
+extern crate mazth;
+
+///quaternion helpers shared by interpolation, skinning and animation compression
+
+use self::mazth::quat::Quat;
+
+pub fn dot( a: & Quat< f32 >, b: & Quat< f32 > ) -> f32 {
+    a._x * b._x + a._y * b._y + a._z * b._z + a._w * b._w
+}
+
+pub fn scale( a: & Quat< f32 >, s: f32 ) -> Quat< f32 > {
+    Quat::<f32>::init_from_vals_raw( a._x * s, a._y * s, a._z * s, a._w * s )
+}
+
+///zero quaternions are returned unchanged
+pub fn normalize( a: & Quat< f32 > ) -> Quat< f32 > {
+    let l = dot( a, a ).sqrt();
+    if l > 0. { scale( a, 1. / l ) } else { *a }
+}
+
+pub fn conjugate( a: & Quat< f32 > ) -> Quat< f32 > {
+    Quat::<f32>::init_from_vals_raw( -a._x, -a._y, -a._z, a._w )
+}
+
+pub fn mul( a: & Quat< f32 >, b: & Quat< f32 > ) -> Quat< f32 > {
+    Quat::<f32>::init_from_vals_raw( a._w * b._x + a._x * b._w + a._y * b._z - a._z * b._y,
+                                     a._w * b._y - a._x * b._z + a._y * b._w + a._z * b._x,
+                                     a._w * b._z + a._x * b._y - a._y * b._x + a._z * b._w,
+                                     a._w * b._w - a._x * b._x - a._y * b._y - a._z * b._z )
+}
+
+///rotates v by the normalized orientation q
+pub fn rotate( q: & Quat< f32 >, v: [f32;3] ) -> [f32;3] {
+    let q = normalize( q );
+    let p = Quat::<f32>::init_from_vals_raw( v[0], v[1], v[2], 0. );
+    let r = mul( & mul( & q, & p ), & conjugate( & q ) );
+    [ r._x, r._y, r._z ]
+}
+
+///log of a unit quaternion as a pure quaternion
+pub fn log( a: & Quat< f32 > ) -> Quat< f32 > {
+    let v = ( a._x * a._x + a._y * a._y + a._z * a._z ).sqrt();
+    if v < 1e-7 {
+        return Quat::<f32>::init_from_vals_raw( 0., 0., 0., 0. )
+    }
+    let theta = v.atan2( a._w );
+    let k = theta / v;
+    Quat::<f32>::init_from_vals_raw( a._x * k, a._y * k, a._z * k, 0. )
+}
+
+///exp of a pure quaternion
+pub fn exp( a: & Quat< f32 > ) -> Quat< f32 > {
+    let theta = ( a._x * a._x + a._y * a._y + a._z * a._z ).sqrt();
+    if theta < 1e-7 {
+        return Quat::<f32>::init_from_vals_raw( a._x, a._y, a._z, 1. )
+    }
+    let k = theta.sin() / theta;
+    Quat::<f32>::init_from_vals_raw( a._x * k, a._y * k, a._z * k, theta.cos() )
+}
+
+///spherical interpolation along the shorter arc
+pub fn slerp( a: & Quat< f32 >, b: & Quat< f32 >, t: f32 ) -> Quat< f32 > {
+    let b = if dot( a, b ) < 0. { scale( b, -1. ) } else { *b };
+    slerp_arc( a, & b, t )
+}
+
+///normalized linear blend along the shorter arc
+pub fn nlerp( a: & Quat< f32 >, b: & Quat< f32 >, t: f32 ) -> Quat< f32 > {
+    let b = if dot( a, b ) < 0. { scale( b, -1. ) } else { *b };
+    lerp_normalized( a, & b, t )
+}
+
+///slerp without choosing the shorter arc, as required between squad control points
+pub fn slerp_arc( a: & Quat< f32 >, b: & Quat< f32 >, t: f32 ) -> Quat< f32 > {
+    let d = dot( a, b );
+    if d.abs() > 0.9995 { //nearly parallel, fall back to normalized lerp
+        return lerp_normalized( a, b, t )
+    }
+    let theta = d.acos();
+    let s = theta.sin();
+    let wa = ( ( 1. - t ) * theta ).sin() / s;
+    let wb = ( t * theta ).sin() / s;
+    Quat::<f32>::init_from_vals_raw( a._x * wa + b._x * wb,
+                                     a._y * wa + b._y * wb,
+                                     a._z * wa + b._z * wb,
+                                     a._w * wa + b._w * wb )
+}
+
+fn lerp_normalized( a: & Quat< f32 >, b: & Quat< f32 >, t: f32 ) -> Quat< f32 > {
+    normalize( & Quat::<f32>::init_from_vals_raw( a._x + ( b._x - a._x ) * t,
+                                                  a._y + ( b._y - a._y ) * t,
+                                                  a._z + ( b._z - a._z ) * t,
+                                                  a._w + ( b._w - a._w ) * t ) )
+}
@@ -0,0 +1,370 @@
+extern crate mazth;
+
+use std::cmp;
+
+use self::mazth::mat::*;
+use self::mazth::quat::Quat;
+
+use interface::i_interpolate::*;
+
+use implement::math::piecewise::Piecewise;
+use implement::math::quat::{ dot, scale, normalize, conjugate, mul, log, exp, slerp, nlerp, slerp_arc };
+use implement::math::linearinterp::LinearInterp;
+use implement::math::spline_cubic::{ self, SplineCubic, CatmullRomParam };
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuatInterpMode {
+    ///constant angular velocity
+    Slerp,
+    ///normalized linear blend, cheaper with non-constant angular velocity
+    Nlerp,
+}
+
+///interpolation between 2 orientations along the shorter arc, evaluated at evenly spaced steps
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct QuatInterp {
+    pub _ctl: [ Quat< f32 >; 2 ],
+    pub _mode: QuatInterpMode,
+    pub _point: Quat< f32 >,
+    pub _steps: i64,
+    pub _step_current: i64,
+}
+
+impl QuatInterp {
+    pub fn init( s: u64, q0: Quat< f32 >, q1: Quat< f32 >, mode: QuatInterpMode ) -> QuatInterp {
+        assert!( s > 2 );
+        let q0 = normalize( & q0 );
+        let q1 = normalize( & q1 );
+        //flip end so the shorter arc is taken
+        let q1 = if dot( & q0, & q1 ) < 0. { scale( & q1, -1. ) } else { q1 };
+        QuatInterp {
+            _ctl: [ q0, q1 ],
+            _mode: mode,
+            _point: q0,
+            _steps: s as _,
+            _step_current: -1i64,
+        }
+    }
+    pub fn init_slerp( s: u64, q0: Quat< f32 >, q1: Quat< f32 > ) -> QuatInterp {
+        QuatInterp::init( s, q0, q1, QuatInterpMode::Slerp )
+    }
+    pub fn init_nlerp( s: u64, q0: Quat< f32 >, q1: Quat< f32 > ) -> QuatInterp {
+        QuatInterp::init( s, q0, q1, QuatInterpMode::Nlerp )
+    }
+    fn point_at_step( & self, step: i64 ) -> Quat< f32 > {
+        let clamp = cmp::min( cmp::max( step, 0 ), self._steps - 1 );
+        self.eval( clamp as f64 / ( self._steps - 1 ) as f64 )
+    }
+}
+
+impl IInterpolateEval< Quat< f32 > > for QuatInterp {
+    fn eval( & self, t: f64 ) -> Quat< f32 > {
+        match self._mode {
+            QuatInterpMode::Slerp => slerp( & self._ctl[0], & self._ctl[1], t as f32 ),
+            QuatInterpMode::Nlerp => nlerp( & self._ctl[0], & self._ctl[1], t as f32 ),
+        }
+    }
+}
+
+impl IInterpolate< Quat< f32 > > for QuatInterp {
+    fn num_steps( & self ) -> u64 {
+        self._steps as u64
+    }
+    fn interp_delta( & mut self, steps: i64 ) -> Option< Quat< f32 > > {
+        self._step_current = cmp::min( self._step_current + steps, self._steps );
+        self._step_current = cmp::max( self._step_current, -1 );
+        self._point = self.point_at_step( self._step_current );
+        Some( self._point )
+    }
+    fn interp_current( & self ) -> Quat< f32 > {
+        self._point
+    }
+    fn interp_is_end( & self ) -> bool {
+        self._step_current == self._steps
+    }
+    fn interp_is_start( & self ) -> bool {
+        self._step_current == -1
+    }
+    fn reset( & mut self ){
+        self._step_current = -1i64;
+        self._point = self._ctl[0];
+    }
+}
+
+//required by IInterpolate
+impl Iterator for QuatInterp {
+    type Item = Quat< f32 >;
+    fn next( & mut self ) -> Option< Quat< f32 > > {
+        self._step_current = cmp::min( self._step_current + 1, self._steps );
+        if self._step_current == self._steps {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+//required by IInterpolate
+impl DoubleEndedIterator for QuatInterp {
+    fn next_back( & mut self ) -> Option< Quat< f32 > > {
+        self._step_current = cmp::max( self._step_current - 1, -1 );
+        if self._step_current == -1 {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+///spherical quadrangle segment between keys q0 and q1 with inner control orientations s0 and s1,
+///giving continuous angular velocity across consecutive segments
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Squad {
+    ///q0, s0, s1, q1
+    pub _ctl: [ Quat< f32 >; 4 ],
+    pub _point: Quat< f32 >,
+    pub _steps: i64,
+    pub _step_current: i64,
+}
+
+impl Squad {
+    pub fn init( s: u64, q0: Quat< f32 >, s0: Quat< f32 >, s1: Quat< f32 >, q1: Quat< f32 > ) -> Squad {
+        assert!( s > 2 );
+        Squad {
+            _ctl: [ q0, s0, s1, q1 ],
+            _point: q0,
+            _steps: s as _,
+            _step_current: -1i64,
+        }
+    }
+    fn point_at_step( & self, step: i64 ) -> Quat< f32 > {
+        let clamp = cmp::min( cmp::max( step, 0 ), self._steps - 1 );
+        self.eval( clamp as f64 / ( self._steps - 1 ) as f64 )
+    }
+}
+
+impl IInterpolateEval< Quat< f32 > > for Squad {
+    fn eval( & self, t: f64 ) -> Quat< f32 > {
+        let t = t as f32;
+        let a = slerp_arc( & self._ctl[0], & self._ctl[3], t );
+        let b = slerp_arc( & self._ctl[1], & self._ctl[2], t );
+        normalize( & slerp_arc( & a, & b, 2. * t * ( 1. - t ) ) )
+    }
+}
+
+impl IInterpolate< Quat< f32 > > for Squad {
+    fn num_steps( & self ) -> u64 {
+        self._steps as u64
+    }
+    fn interp_delta( & mut self, steps: i64 ) -> Option< Quat< f32 > > {
+        self._step_current = cmp::min( self._step_current + steps, self._steps );
+        self._step_current = cmp::max( self._step_current, -1 );
+        self._point = self.point_at_step( self._step_current );
+        Some( self._point )
+    }
+    fn interp_current( & self ) -> Quat< f32 > {
+        self._point
+    }
+    fn interp_is_end( & self ) -> bool {
+        self._step_current == self._steps
+    }
+    fn interp_is_start( & self ) -> bool {
+        self._step_current == -1
+    }
+    fn reset( & mut self ){
+        self._step_current = -1i64;
+        self._point = self._ctl[0];
+    }
+}
+
+//required by IInterpolate
+impl Iterator for Squad {
+    type Item = Quat< f32 >;
+    fn next( & mut self ) -> Option< Quat< f32 > > {
+        self._step_current = cmp::min( self._step_current + 1, self._steps );
+        if self._step_current == self._steps {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+//required by IInterpolate
+impl DoubleEndedIterator for Squad {
+    fn next_back( & mut self ) -> Option< Quat< f32 > > {
+        self._step_current = cmp::max( self._step_current - 1, -1 );
+        if self._step_current == -1 {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+///squad path through all key orientations. keys are sign aligned to their predecessor so each segment takes the shorter arc
+pub fn squad_path( steps_per_segment: u64, keys: &[ Quat< f32 > ] ) -> Result< Piecewise< Squad, Quat< f32 > >, & 'static str > {
+    if keys.len() < 2 {
+        return Err( "squad path requires at least 2 keys." )
+    }
+    let mut q : Vec< Quat< f32 > > = vec![ normalize( & keys[0] ) ];
+    for k in keys[1..].iter() {
+        let k = normalize( k );
+        let prev = q[ q.len() - 1 ];
+        q.push( if dot( & prev, & k ) < 0. { scale( & k, -1. ) } else { k } );
+    }
+    let n = q.len();
+    //inner control points, end keys are their own controls
+    let inner : Vec< Quat< f32 > > = ( 0..n ).map( |i| {
+        if i == 0 || i == n - 1 {
+            q[i]
+        } else {
+            let inv = conjugate( & q[i] );
+            let a = log( & mul( & inv, & q[ i + 1 ] ) );
+            let b = log( & mul( & inv, & q[ i - 1 ] ) );
+            let v = Quat::<f32>::init_from_vals_raw( -( a._x + b._x ) / 4., -( a._y + b._y ) / 4., -( a._z + b._z ) / 4., 0. );
+            normalize( & mul( & q[i], & exp( & v ) ) )
+        }
+    } ).collect();
+    let mut path = Piecewise::init();
+    for i in 0..n - 1 {
+        path.add( Squad::init( steps_per_segment, q[i], inner[i], inner[ i + 1 ], q[ i + 1 ] ) );
+    }
+    Ok( path )
+}
+
+///full pose of a transform track
+#[derive(Debug, Clone, Copy)]
+pub struct Pose {
+    pub _pos: Mat4x1< f64 >,
+    pub _orient: Quat< f32 >,
+    pub _scale: Mat4x1< f64 >,
+}
+
+impl Pose {
+    pub fn init( pos: [ f64; 3 ], orient: Quat< f32 >, scale: [ f64; 3 ] ) -> Pose {
+        Pose {
+            _pos: Mat4x1 { _val: [ pos[0], pos[1], pos[2], 1f64 ] },
+            _orient: orient,
+            _scale: Mat4x1 { _val: [ scale[0], scale[1], scale[2], 1f64 ] },
+        }
+    }
+    ///row major translation * rotation * scale
+    pub fn xform( & self ) -> Mat4< f32 > {
+        let q = normalize( & self._orient );
+        let ( x, y, z, w ) = ( q._x, q._y, q._z, q._w );
+        let ( sx, sy, sz ) = ( self._scale._val[0] as f32, self._scale._val[1] as f32, self._scale._val[2] as f32 );
+        Mat4::<f32>::init( [ ( 1. - 2.*(y*y + z*z) ) * sx, 2.*(x*y - z*w) * sy,        2.*(x*z + y*w) * sz,        self._pos._val[0] as f32,
+                             2.*(x*y + z*w) * sx,        ( 1. - 2.*(x*x + z*z) ) * sy, 2.*(y*z - x*w) * sz,        self._pos._val[1] as f32,
+                             2.*(x*z - y*w) * sx,        2.*(y*z + x*w) * sy,        ( 1. - 2.*(x*x + y*y) ) * sz, self._pos._val[2] as f32,
+                             0f32,                       0f32,                       0f32,                       1f32 ],
+                           true )
+    }
+}
+
+///translation, rotation and scale tracks stepped in lockstep
+#[derive(Clone)]
+pub struct TransformTrack< T > where T: IInterpolate< Mat4x1< f64 > > {
+    pub _translation: Piecewise< T, Mat4x1< f64 > >,
+    pub _rotation: Piecewise< Squad, Quat< f32 > >,
+    pub _scale: Piecewise< LinearInterp, Mat4x1< f64 > >,
+}
+
+impl < T > TransformTrack< T > where T: IInterpolate< Mat4x1< f64 > > {
+    pub fn init( translation: Piecewise< T, Mat4x1< f64 > >, rotation: Piecewise< Squad, Quat< f32 > >, scale: Piecewise< LinearInterp, Mat4x1< f64 > > ) -> Result< TransformTrack< T >, & 'static str > {
+        if translation._pieces.is_empty() || rotation._pieces.is_empty() || scale._pieces.is_empty() {
+            return Err( "transform track requires non-empty tracks." )
+        }
+        let n = translation._pieces.len();
+        if rotation._pieces.len() != n || scale._pieces.len() != n {
+            return Err( "transform track piece count mismatch." )
+        }
+        for i in 0..n {
+            let s = translation._pieces[i].num_steps();
+            if rotation._pieces[i].num_steps() != s || scale._pieces[i].num_steps() != s {
+                return Err( "transform track step count mismatch." )
+            }
+        }
+        Ok( TransformTrack {
+            _translation: translation,
+            _rotation: rotation,
+            _scale: scale,
+        } )
+    }
+    fn current( & self ) -> Pose {
+        Pose {
+            _pos: self._translation.interp_current(),
+            _orient: self._rotation.interp_current(),
+            _scale: self._scale.interp_current(),
+        }
+    }
+}
+
+///transform track through key poses using centripetal catmull-rom translation, squad rotation and linear scale
+pub fn transform_track_from_keys( steps_per_segment: u64, keys: &[ Pose ] ) -> Result< TransformTrack< SplineCubic >, & 'static str > {
+    if keys.len() < 2 {
+        return Err( "transform track requires at least 2 key poses." )
+    }
+    let pos : Vec< _ > = keys.iter().map( |x| x._pos ).collect();
+    let orient : Vec< _ > = keys.iter().map( |x| x._orient ).collect();
+    let translation = spline_cubic::catmull_rom_path( steps_per_segment, &pos[..], CatmullRomParam::Centripetal )?;
+    let rotation = squad_path( steps_per_segment, &orient[..] )?;
+    let mut scale = Piecewise::init();
+    for w in keys.windows( 2 ) {
+        scale.add( LinearInterp::init( steps_per_segment as i64, w[0]._scale, w[1]._scale ) );
+    }
+    TransformTrack::init( translation, rotation, scale )
+}
+
+impl < T > IInterpolate< Pose > for TransformTrack< T > where T: IInterpolate< Mat4x1< f64 > > {
+    fn num_steps( & self ) -> u64 {
+        self._translation.num_steps()
+    }
+    fn interp_delta( & mut self, steps: i64 ) -> Option< Pose > {
+        self._translation.interp_delta( steps );
+        self._rotation.interp_delta( steps );
+        self._scale.interp_delta( steps );
+        Some( self.current() )
+    }
+    fn interp_current( & self ) -> Pose {
+        self.current()
+    }
+    fn interp_is_end( & self ) -> bool {
+        self._translation.interp_is_end()
+    }
+    fn interp_is_start( & self ) -> bool {
+        self._translation.interp_is_start()
+    }
+    fn reset( & mut self ){
+        self._translation.reset();
+        self._rotation.reset();
+        self._scale.reset();
+    }
+}
+
+//required by IInterpolate
+impl < T > Iterator for TransformTrack< T > where T: IInterpolate< Mat4x1< f64 > > {
+    type Item = Pose;
+    fn next( & mut self ) -> Option< Pose > {
+        match ( self._translation.next(), self._rotation.next(), self._scale.next() ) {
+            ( Some( p ), Some( r ), Some( s ) ) => Some( Pose { _pos: p, _orient: r, _scale: s } ),
+            _ => None,
+        }
+    }
+}
+
+//required by IInterpolate
+impl < T > DoubleEndedIterator for TransformTrack< T > where T: IInterpolate< Mat4x1< f64 > > {
+    fn next_back( & mut self ) -> Option< Pose > {
+        match ( self._translation.next_back(), self._rotation.next_back(), self._scale.next_back() ) {
+            ( Some( p ), Some( r ), Some( s ) ) => Some( Pose { _pos: p, _orient: r, _scale: s } ),
+            _ => None,
+        }
+    }
+}
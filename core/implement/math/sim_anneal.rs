@@ -1,38 +1,203 @@
 extern crate rand;
 
 use std::f32;
+use std::f64;
+use self::rand::{ Rng, SeedableRng, StdRng };
 use self::rand::distributions::{IndependentSample, Range};
 
-pub fn compute_discrete< F0, F1, F2 >( domain: &[ f32 ], candidate_generator: F0, temperature_function: F1, energy_function: F2, max_iter: usize, initial: usize ) -> usize
-    where F0: Fn( &[ f32 ], usize ) -> ( usize ), F1: Fn( f32 ) -> f32, F2: Fn( &[ f32 ], usize )-> f32
+///state of a running anneal handed to temperature functions
+#[derive(Debug, Clone, Copy)]
+pub struct AnnealProgress {
+    pub _iter: usize,
+    pub _max_iter: usize,
+    ///index of the current restart
+    pub _restart: usize,
+    ///temperature of the previous iteration
+    pub _temperature: f64,
+    ///moving average of the acceptance rate
+    pub _accept_rate: f64,
+}
+
+impl AnnealProgress {
+    ///fraction of the current run completed, in [0,1)
+    pub fn fraction( & self ) -> f64 {
+        self._iter as f64 / self._max_iter as f64
+    }
+}
+
+///cooling schedules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cooling {
+    ///t0 * alpha^k
+    Exponential { _t0: f64, _alpha: f64 },
+    ///t0 / ln( k + e )
+    Logarithmic { _t0: f64 },
+    ///scales the previous temperature down when the acceptance rate is above target and up otherwise,
+    ///bounded by a linear decay from t0 so the run still converges
+    Adaptive { _t0: f64, _target_accept: f64, _rate: f64 },
+}
+
+impl Cooling {
+    pub fn temperature( & self, p: & AnnealProgress ) -> f64 {
+        match *self {
+            Cooling::Exponential { _t0, _alpha } => _t0 * _alpha.powi( p._iter as i32 ),
+            Cooling::Logarithmic { _t0 } => _t0 / ( p._iter as f64 + f64::consts::E ).ln(),
+            Cooling::Adaptive { _t0, _target_accept, _rate } => {
+                let adj = if p._accept_rate > _target_accept { 1. - _rate } else { 1. + _rate };
+                ( p._temperature * adj ).min( _t0 * ( 1. - p.fraction() ) ).max( 0. )
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnealConfig {
+    ///iterations per run
+    pub _max_iter: usize,
+    ///additional runs, each reheating from the best state found so far
+    pub _restarts: usize,
+    pub _seed: usize,
+}
+
+impl Default for AnnealConfig {
+    fn default() -> AnnealConfig {
+        AnnealConfig {
+            _max_iter: 1000,
+            _restarts: 0,
+            _seed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnnealStats {
+    pub _iterations: usize,
+    pub _accepted: usize,
+    ///accepted moves that improved on the best energy
+    pub _improved: usize,
+    ///best energy after each iteration over all runs
+    pub _best_trace: Vec< f64 >,
+    ///best energy at the end of each run
+    pub _restart_best: Vec< f64 >,
+}
+
+impl AnnealStats {
+    pub fn acceptance_rate( & self ) -> f64 {
+        if self._iterations == 0 {
+            0.
+        } else {
+            self._accepted as f64 / self._iterations as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnealResult< S > {
+    pub _best: S,
+    pub _best_energy: f64,
+    ///state at the end of the last run
+    pub _final: S,
+    pub _stats: AnnealStats,
+}
 
+///metropolis acceptance: worse candidates are taken with probability exp( -e_diff / t )
+pub fn accept_metropolis( e_diff: f64, t: f64, r: f64 ) -> bool {
+    e_diff <= 0. || ( t > 0. && r < ( -e_diff / t ).exp() )
+}
+
+///anneals over an arbitrary state type using metropolis acceptance.
+///neighbour proposes a candidate given the current state and temperature, temperature maps progress to a temperature.
+///runs are reproducible for a given seed
+pub fn anneal< S, FN, FE, FT >( initial: S, neighbour: FN, energy: FE, temperature: FT, config: & AnnealConfig ) -> AnnealResult< S >
+    where S: Clone, FN: FnMut( & S, f64, & mut StdRng ) -> S, FE: FnMut( & S ) -> f64, FT: FnMut( & AnnealProgress ) -> f64
 {
-    assert!( initial < domain.len() );
+    anneal_accept( initial, neighbour, energy, temperature, accept_metropolis, config )
+}
 
-    let between = Range::new(0f32, 1f32);
-    let mut rng = rand::thread_rng();
+///anneal with a custom acceptance rule taking the energy difference, temperature and a uniform sample in [0,1)
+pub fn anneal_accept< S, FN, FE, FT, FA >( initial: S, mut neighbour: FN, mut energy: FE, mut temperature: FT, mut accept: FA, config: & AnnealConfig ) -> AnnealResult< S >
+    where S: Clone, FN: FnMut( & S, f64, & mut StdRng ) -> S, FE: FnMut( & S ) -> f64, FT: FnMut( & AnnealProgress ) -> f64, FA: FnMut( f64, f64, f64 ) -> bool
+{
+    let seed = [ config._seed ];
+    let mut rng : StdRng = SeedableRng::from_seed( &seed[..] );
 
-    let mut state = initial; //state is the index within the domain slice
-
-    for i in 0..max_iter {
-        // println!( "state: {:?}", state );
-        let k = i as f32/ max_iter as f32;
-        let t = temperature_function( k ); //output range between [0,1]
-        let candidate = candidate_generator( &domain[..], i ); //candidate is the index within the domain slice
-        let r = between.ind_sample(&mut rng); //generate from a uniform distribution [0,1]
-        let e_current = energy_function( &domain[..], state );
-        let e_candidate = energy_function( &domain[..], candidate );
-        let e_diff = e_candidate - e_current; // diff is between [-1, 1]
-        if e_diff < 0f32 { //candidate energy is lower than current
-            state = candidate;
-        } else { //diff is between [0, 1]
-            let a = t * e_diff;
-            if a > r {
-                state = candidate;                
+    let mut best_energy = energy( & initial );
+    let mut best = initial;
+    let mut state = best.clone();
+    let mut stats = AnnealStats::default();
+
+    for restart in 0..config._restarts + 1 {
+        state = best.clone();
+        let mut e_state = best_energy;
+        let mut progress = AnnealProgress {
+            _iter: 0,
+            _max_iter: config._max_iter,
+            _restart: restart,
+            _temperature: f64::INFINITY,
+            _accept_rate: 1.,
+        };
+        for i in 0..config._max_iter {
+            progress._iter = i;
+            let t = temperature( & progress );
+            let candidate = neighbour( & state, t, & mut rng );
+            let e_candidate = energy( & candidate );
+            let e_diff = e_candidate - e_state;
+            let r = rng.next_f64();
+            let accepted = accept( e_diff, t, r );
+            if accepted {
+                state = candidate;
+                e_state = e_candidate;
+                stats._accepted += 1;
+                if e_state < best_energy {
+                    best_energy = e_state;
+                    best = state.clone();
+                    stats._improved += 1;
+                }
             }
+            progress._temperature = t;
+            progress._accept_rate = progress._accept_rate * 0.95 + if accepted { 0.05 } else { 0. };
+            stats._iterations += 1;
+            stats._best_trace.push( best_energy );
         }
+        stats._restart_best.push( best_energy );
+    }
+
+    AnnealResult {
+        _best: best,
+        _best_energy: best_energy,
+        _final: state,
+        _stats: stats,
     }
-    state
+}
+
+///anneal using one of the provided cooling schedules
+pub fn anneal_cooling< S, FN, FE >( initial: S, neighbour: FN, energy: FE, cooling: Cooling, config: & AnnealConfig ) -> AnnealResult< S >
+    where S: Clone, FN: FnMut( & S, f64, & mut StdRng ) -> S, FE: FnMut( & S ) -> f64
+{
+    anneal( initial, neighbour, energy, |p: & AnnealProgress| cooling.temperature( p ), config )
+}
+
+///searches for the index into domain with the lowest energy. a worse candidate is accepted when t * e_diff exceeds
+///a uniform sample, and the final state is returned
+pub fn compute_discrete< F0, F1, F2 >( domain: &[ f32 ], candidate_generator: F0, temperature_function: F1, energy_function: F2, max_iter: usize, initial: usize ) -> usize
+    where F0: Fn( &[ f32 ], usize ) -> ( usize ), F1: Fn( f32 ) -> f32, F2: Fn( &[ f32 ], usize )-> f32
+
+{
+    assert!( initial < domain.len() );
+
+    let config = AnnealConfig {
+        _max_iter: max_iter,
+        _restarts: 0,
+        _seed: rand::thread_rng().gen(),
+    };
+    let mut iter = 0;
+    let r = anneal_accept( initial,
+                           |_, _, _| { let c = candidate_generator( domain, iter ); iter += 1; c },
+                           |s: & usize| energy_function( domain, *s ) as f64,
+                           |p: & AnnealProgress| temperature_function( p.fraction() as f32 ) as f64,
+                           |e_diff, t, r| e_diff < 0. || t * e_diff > r,
+                           & config );
+    r._final
 }
 
 pub fn temperature_function_default( fraction: f32 ) -> f32 {
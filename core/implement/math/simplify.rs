@@ -0,0 +1,619 @@
+///quadric error metric mesh simplification (garland-heckbert) of triangle soups.
+///vertices are welded by position for connectivity while normals and texture coordinates stay per corner.
+///boundary vertices only slide along the boundary and seam vertices only along their seam,
+///with penalty planes keeping both in place
+
+use std::collections::{ HashMap, BinaryHeap };
+use std::cmp::Ordering;
+use std::f64;
+
+use implement::math::vec3::{ sub, dot, cross };
+
+///symmetric 4x4 error quadric, upper triangle stored row major
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Quadric {
+    pub _q: [f64;10],
+}
+
+impl Quadric {
+    ///squared distance to the plane n.x + d = 0 scaled by weight, n is of unit length
+    pub fn from_plane( n: [f64;3], d: f64, weight: f64 ) -> Quadric {
+        let v = [ n[0], n[1], n[2], d ];
+        let mut q = [ 0f64; 10 ];
+        let mut k = 0;
+        for i in 0..4 {
+            for j in i..4 {
+                q[k] = v[i] * v[j] * weight;
+                k += 1;
+            }
+        }
+        Quadric { _q: q }
+    }
+    pub fn add( & self, other: & Quadric ) -> Quadric {
+        let mut q = self._q;
+        for i in 0..10 {
+            q[i] += other._q[i];
+        }
+        Quadric { _q: q }
+    }
+    pub fn error( & self, p: [f64;3] ) -> f64 {
+        let q = & self._q;
+        let ( x, y, z ) = ( p[0], p[1], p[2] );
+        q[0] * x * x + 2. * q[1] * x * y + 2. * q[2] * x * z + 2. * q[3] * x
+            + q[4] * y * y + 2. * q[5] * y * z + 2. * q[6] * y
+            + q[7] * z * z + 2. * q[8] * z
+            + q[9]
+    }
+    ///position minimizing the error, none if the system is singular
+    pub fn optimal( & self ) -> Option< [f64;3] > {
+        let q = & self._q;
+        let a = [ [ q[0], q[1], q[2] ],
+                  [ q[1], q[4], q[5] ],
+                  [ q[2], q[5], q[7] ] ];
+        let b = [ -q[3], -q[6], -q[8] ];
+        let det3 = |m: & [[f64;3];3]| {
+            m[0][0] * ( m[1][1] * m[2][2] - m[1][2] * m[2][1] )
+                - m[0][1] * ( m[1][0] * m[2][2] - m[1][2] * m[2][0] )
+                + m[0][2] * ( m[1][0] * m[2][1] - m[1][1] * m[2][0] )
+        };
+        let det = det3( & a );
+        let scale = a.iter().fold( 0f64, |acc, r| acc.max( r[0].abs() ).max( r[1].abs() ).max( r[2].abs() ) );
+        if scale == 0. || det.abs() <= 1e-10 * scale * scale * scale {
+            return None
+        }
+        //cramer's rule
+        let mut p = [ 0f64; 3 ];
+        for c in 0..3 {
+            let mut m = a;
+            for r in 0..3 {
+                m[r][c] = b[r];
+            }
+            p[c] = det3( & m ) / det;
+        }
+        Some( p )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimplifyConfig {
+    ///stop once the triangle count is at or below this
+    pub _target_tris: usize,
+    ///stop before a collapse exceeds this error
+    pub _max_error: f64,
+    ///weight of the constraint planes along open boundaries
+    pub _boundary_weight: f64,
+    ///weight of the constraint planes along texture coordinate and normal seams
+    pub _seam_weight: f64,
+    ///largest difference for corner attributes to be considered equal
+    pub _attr_epsilon: f32,
+}
+
+impl Default for SimplifyConfig {
+    fn default() -> SimplifyConfig {
+        SimplifyConfig {
+            _target_tris: 0,
+            _max_error: f64::INFINITY,
+            _boundary_weight: 10.,
+            _seam_weight: 10.,
+            _attr_epsilon: 1e-5,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimplifyResult {
+    pub _batch_pos: Vec< f32 >,
+    pub _batch_normal: Vec< f32 >,
+    pub _batch_tc: Vec< f32 >,
+    pub _tri_count: usize,
+    ///largest quadric error of the applied collapses
+    pub _error: f64,
+}
+
+///reduces a triangle soup of xyz positions with optional per vertex normals and texture coordinates
+pub fn simplify( pos: &[ f32 ], normal: &[ f32 ], tc: &[ f32 ], config: & SimplifyConfig ) -> Result< SimplifyResult, & 'static str > {
+    if pos.len() % 9 != 0 {
+        return Err( &"position data not a multiple of triangles" )
+    }
+    let n_corner = pos.len() / 3;
+    if !normal.is_empty() && normal.len() != pos.len() {
+        return Err( &"inconsistent length for position, normal data" )
+    }
+    if !tc.is_empty() && tc.len() != n_corner * 2 {
+        return Err( &"inconsistent length for position, texture coordinate data" )
+    }
+    let mut m = SimplifyMesh::init( pos, normal, tc, config );
+    m.run( config );
+    let ( p, n, t ) = m.to_soup();
+    Ok( SimplifyResult {
+        _batch_pos: p,
+        _batch_normal: if normal.is_empty() { vec![] } else { n },
+        _batch_tc: if tc.is_empty() { vec![] } else { t },
+        _tri_count: m._tri_count,
+        _error: m._error,
+    } )
+}
+
+///normal and texture coordinate of a triangle corner
+type Attr = [ f32; 5 ];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VertKind {
+    Interior,
+    Border,
+    Seam,
+    ///non-manifold or where seams and borders meet
+    Locked,
+}
+
+struct Collapse {
+    ///removed vertex
+    _from: usize,
+    _to: usize,
+    _pos: [f64;3],
+    ///both vertices move to the new position with blended attributes, otherwise _from moves onto _to
+    _merge: bool,
+    _cost: f64,
+}
+
+///heap entry ordered by lowest cost first
+struct Candidate {
+    _cost: f64,
+    _a: usize,
+    _b: usize,
+    _ver_a: u32,
+    _ver_b: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq( & self, other: & Candidate ) -> bool {
+        self._cost == other._cost
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp( & self, other: & Candidate ) -> Option< Ordering > {
+        Some( self.cmp( other ) )
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp( & self, other: & Candidate ) -> Ordering {
+        other._cost.partial_cmp( & self._cost ).unwrap_or( Ordering::Equal )
+    }
+}
+
+struct SimplifyMesh {
+    _verts: Vec< [f64;3] >,
+    _quadrics: Vec< Quadric >,
+    _kind: Vec< VertKind >,
+    _version: Vec< u32 >,
+    _removed: Vec< bool >,
+    _tris: Vec< [usize;3] >,
+    _attrs: Vec< [Attr;3] >,
+    _alive: Vec< bool >,
+    ///incident triangles per vertex, may contain dead triangles
+    _vert_tris: Vec< Vec< usize > >,
+    _tri_count: usize,
+    _error: f64,
+    _eps: f32,
+}
+
+impl SimplifyMesh {
+    fn init( pos: &[ f32 ], normal: &[ f32 ], tc: &[ f32 ], config: & SimplifyConfig ) -> SimplifyMesh {
+        //weld by exact position
+        let mut lookup : HashMap< [u32;3], usize > = HashMap::new();
+        let mut verts = vec![];
+        let mut tris = vec![];
+        let mut attrs = vec![];
+        for t in 0..pos.len() / 9 {
+            let mut tri = [ 0usize; 3 ];
+            let mut attr = [ [ 0f32; 5 ]; 3 ];
+            for c in 0..3 {
+                let i = t * 3 + c;
+                let p = [ pos[ i*3 ], pos[ i*3 + 1 ], pos[ i*3 + 2 ] ];
+                //+0 and -0 weld together
+                let key = [ ( p[0] + 0. ).to_bits(), ( p[1] + 0. ).to_bits(), ( p[2] + 0. ).to_bits() ];
+                let n = verts.len();
+                tri[c] = *lookup.entry( key ).or_insert( n );
+                if tri[c] == n {
+                    verts.push( [ p[0] as f64, p[1] as f64, p[2] as f64 ] );
+                }
+                if !normal.is_empty() {
+                    attr[c][0] = normal[ i*3 ];
+                    attr[c][1] = normal[ i*3 + 1 ];
+                    attr[c][2] = normal[ i*3 + 2 ];
+                }
+                if !tc.is_empty() {
+                    attr[c][3] = tc[ i*2 ];
+                    attr[c][4] = tc[ i*2 + 1 ];
+                }
+            }
+            //drop triangles that are degenerate after welding
+            if tri[0] == tri[1] || tri[1] == tri[2] || tri[0] == tri[2] {
+                continue;
+            }
+            tris.push( tri );
+            attrs.push( attr );
+        }
+
+        let nv = verts.len();
+        let mut m = SimplifyMesh {
+            _quadrics: vec![ Quadric::default(); nv ],
+            _kind: vec![ VertKind::Interior; nv ],
+            _version: vec![ 0; nv ],
+            _removed: vec![ false; nv ],
+            _vert_tris: vec![ vec![]; nv ],
+            _tri_count: tris.len(),
+            _alive: vec![ true; tris.len() ],
+            _verts: verts,
+            _tris: tris,
+            _attrs: attrs,
+            _error: 0.,
+            _eps: config._attr_epsilon,
+        };
+        for ( t, tri ) in m._tris.iter().enumerate() {
+            for &v in tri.iter() {
+                m._vert_tris[v].push( t );
+            }
+        }
+
+        //face quadrics weighted by area
+        for t in 0..m._tris.len() {
+            let ( n, area ) = m.face_normal( t );
+            if area <= 0. {
+                continue;
+            }
+            let d = -dot( n, m._verts[ m._tris[t][0] ] );
+            let q = Quadric::from_plane( n, d, area );
+            for c in 0..3 {
+                let v = m._tris[t][c];
+                m._quadrics[v] = m._quadrics[v].add( & q );
+            }
+        }
+
+        //classify edges
+        let mut edges : HashMap< ( usize, usize ), Vec< ( usize, usize, usize ) > > = HashMap::new();
+        for ( t, tri ) in m._tris.iter().enumerate() {
+            for c in 0..3 {
+                let ( a, b ) = ( tri[c], tri[ ( c + 1 ) % 3 ] );
+                let ( key, ca, cb ) = if a < b { ( ( a, b ), c, ( c + 1 ) % 3 ) } else { ( ( b, a ), ( c + 1 ) % 3, c ) };
+                edges.entry( key ).or_insert( vec![] ).push( ( t, ca, cb ) );
+            }
+        }
+        let mut border_edges = vec![ 0usize; nv ];
+        let mut seam_edges = vec![ 0usize; nv ];
+        let mut non_manifold = vec![ false; nv ];
+        for ( &( a, b ), e ) in edges.iter() {
+            let ( t0, ca0, cb0 ) = e[0];
+            match e.len() {
+                1 => {
+                    border_edges[a] += 1;
+                    border_edges[b] += 1;
+                    m.add_edge_constraint( a, b, t0, config._boundary_weight );
+                },
+                2 => {
+                    let ( t1, ca1, cb1 ) = e[1];
+                    let seam = !attr_eq( & m._attrs[t0][ca0], & m._attrs[t1][ca1], m._eps ) ||
+                        !attr_eq( & m._attrs[t0][cb0], & m._attrs[t1][cb1], m._eps );
+                    if seam {
+                        seam_edges[a] += 1;
+                        seam_edges[b] += 1;
+                        m.add_edge_constraint( a, b, t0, config._seam_weight * 0.5 );
+                        m.add_edge_constraint( a, b, t1, config._seam_weight * 0.5 );
+                    }
+                },
+                _ => {
+                    non_manifold[a] = true;
+                    non_manifold[b] = true;
+                },
+            }
+        }
+        for v in 0..nv {
+            m._kind[v] = if non_manifold[v] {
+                VertKind::Locked
+            } else if border_edges[v] > 0 && seam_edges[v] > 0 {
+                VertKind::Locked
+            } else if border_edges[v] > 0 {
+                if border_edges[v] == 2 { VertKind::Border } else { VertKind::Locked }
+            } else if seam_edges[v] > 0 {
+                if seam_edges[v] == 2 { VertKind::Seam } else { VertKind::Locked }
+            } else {
+                VertKind::Interior
+            };
+        }
+        m
+    }
+
+    ///plane through edge ab perpendicular to triangle t
+    fn add_edge_constraint( & mut self, a: usize, b: usize, t: usize, weight: f64 ) {
+        let ( n, _ ) = self.face_normal( t );
+        let e = sub( self._verts[b], self._verts[a] );
+        let len2 = dot( e, e );
+        let c = cross( e, n );
+        let l = dot( c, c ).sqrt();
+        if l <= 0. || len2 <= 0. {
+            return
+        }
+        let c = [ c[0] / l, c[1] / l, c[2] / l ];
+        let q = Quadric::from_plane( c, -dot( c, self._verts[a] ), weight * len2 );
+        self._quadrics[a] = self._quadrics[a].add( & q );
+        self._quadrics[b] = self._quadrics[b].add( & q );
+    }
+
+    ///unit normal and area of a triangle
+    fn face_normal( & self, t: usize ) -> ( [f64;3], f64 ) {
+        let tri = self._tris[t];
+        let n = cross( sub( self._verts[ tri[1] ], self._verts[ tri[0] ] ), sub( self._verts[ tri[2] ], self._verts[ tri[0] ] ) );
+        let l = dot( n, n ).sqrt();
+        if l <= 0. {
+            ( [ 0., 0., 0. ], 0. )
+        } else {
+            ( [ n[0] / l, n[1] / l, n[2] / l ], l * 0.5 )
+        }
+    }
+
+    fn tris_around( & self, v: usize ) -> Vec< usize > {
+        self._vert_tris[v].iter().cloned().filter( |&t| self._alive[t] ).collect()
+    }
+
+    fn neighbours( & self, v: usize ) -> Vec< usize > {
+        let mut n : Vec< usize > = self.tris_around( v ).iter()
+            .flat_map( |&t| self._tris[t].iter().cloned().collect::< Vec< _ > >() )
+            .filter( |&x| x != v )
+            .collect();
+        n.sort();
+        n.dedup();
+        n
+    }
+
+    ///alive triangles containing both vertices
+    fn shared_tris( & self, a: usize, b: usize ) -> Vec< usize > {
+        self.tris_around( a ).into_iter().filter( |&t| self._tris[t].contains( &b ) ).collect()
+    }
+
+    fn corner( & self, t: usize, v: usize ) -> usize {
+        self._tris[t].iter().position( |&x| x == v ).unwrap()
+    }
+
+    fn is_seam_edge( & self, shared: &[ usize ], a: usize, b: usize ) -> bool {
+        if shared.len() != 2 {
+            return false
+        }
+        let ( t0, t1 ) = ( shared[0], shared[1] );
+        !attr_eq( & self._attrs[t0][ self.corner( t0, a ) ], & self._attrs[t1][ self.corner( t1, a ) ], self._eps ) ||
+            !attr_eq( & self._attrs[t0][ self.corner( t0, b ) ], & self._attrs[t1][ self.corner( t1, b ) ], self._eps )
+    }
+
+    ///whether vertex v may move onto u
+    fn can_move( & self, v: usize, u: usize, shared: &[ usize ] ) -> bool {
+        match self._kind[v] {
+            VertKind::Interior => true,
+            VertKind::Border => shared.len() == 1,
+            VertKind::Seam => self.is_seam_edge( shared, v, u ),
+            VertKind::Locked => false,
+        }
+    }
+
+    ///collapse options for edge ab sorted by cost
+    fn options( & self, a: usize, b: usize ) -> Vec< Collapse > {
+        let q = self._quadrics[a].add( & self._quadrics[b] );
+        let mut opts = vec![];
+        if self._kind[a] == VertKind::Interior && self._kind[b] == VertKind::Interior {
+            let ( pa, pb ) = ( self._verts[a], self._verts[b] );
+            let mid = [ ( pa[0] + pb[0] ) * 0.5, ( pa[1] + pb[1] ) * 0.5, ( pa[2] + pb[2] ) * 0.5 ];
+            let mut targets = vec![ pa, pb, mid ];
+            if let Some( p ) = q.optimal() {
+                targets.insert( 0, p );
+            }
+            for p in targets {
+                opts.push( Collapse { _from: b, _to: a, _pos: p, _merge: true, _cost: q.error( p ).max( 0. ) } );
+            }
+        } else {
+            let shared = self.shared_tris( a, b );
+            for &( from, to ) in [ ( b, a ), ( a, b ) ].iter() {
+                if self.can_move( from, to, & shared[..] ) {
+                    let p = self._verts[to];
+                    opts.push( Collapse { _from: from, _to: to, _pos: p, _merge: false, _cost: q.error( p ).max( 0. ) } );
+                }
+            }
+        }
+        opts.sort_by( |x, y| x._cost.partial_cmp( &y._cost ).unwrap_or( Ordering::Equal ) );
+        opts
+    }
+
+    ///new attribute for each corner of the removed vertex on surviving triangles, none if a wedge has no match
+    fn wedge_map( & self, c: & Collapse ) -> Option< Vec< ( usize, Attr ) > > {
+        let ( v, u ) = ( c._from, c._to );
+        let shared = self.shared_tris( v, u );
+        let mut out = vec![];
+        for t in self.tris_around( v ) {
+            if shared.contains( &t ) {
+                continue;
+            }
+            let a = self._attrs[t][ self.corner( t, v ) ];
+            let m = shared.iter().find( |&&s| attr_eq( & self._attrs[s][ self.corner( s, v ) ], & a, self._eps ) )?;
+            out.push( ( t, self._attrs[*m][ self.corner( *m, u ) ] ) );
+        }
+        Some( out )
+    }
+
+    ///link condition and triangle flips
+    fn is_valid( & self, c: & Collapse ) -> bool {
+        let ( v, u ) = ( c._from, c._to );
+        let shared = self.shared_tris( v, u );
+        if shared.is_empty() {
+            return false
+        }
+        let nu = self.neighbours( u );
+        let common = self.neighbours( v ).iter().filter( |x| nu.binary_search( x ).is_ok() ).count();
+        if common != shared.len() {
+            return false
+        }
+        let mut around = self.tris_around( v );
+        around.extend( self.tris_around( u ) );
+        for t in around {
+            if shared.contains( &t ) {
+                continue;
+            }
+            let tri = self._tris[t];
+            let old = [ self._verts[ tri[0] ], self._verts[ tri[1] ], self._verts[ tri[2] ] ];
+            let mut new = old;
+            for k in 0..3 {
+                if tri[k] == v || tri[k] == u {
+                    new[k] = c._pos;
+                }
+            }
+            let n0 = cross( sub( old[1], old[0] ), sub( old[2], old[0] ) );
+            let n1 = cross( sub( new[1], new[0] ), sub( new[2], new[0] ) );
+            let l0 = dot( n0, n0 ).sqrt();
+            let l1 = dot( n1, n1 ).sqrt();
+            if l1 <= 1e-12 * l0 || dot( n0, n1 ) <= 0. {
+                return false
+            }
+        }
+        true
+    }
+
+    fn apply( & mut self, c: & Collapse, wedges: Vec< ( usize, Attr ) > ) {
+        let ( v, u ) = ( c._from, c._to );
+        let merged_attr = if c._merge {
+            let s = {
+                let e = sub( self._verts[v], self._verts[u] );
+                let l2 = dot( e, e );
+                if l2 > 0. { ( dot( sub( c._pos, self._verts[u] ), e ) / l2 ).max( 0. ).min( 1. ) } else { 0. }
+            };
+            let t0 = self.tris_around( u )[0];
+            let t1 = self.tris_around( v )[0];
+            Some( attr_lerp( & self._attrs[t0][ self.corner( t0, u ) ], & self._attrs[t1][ self.corner( t1, v ) ], s as f32 ) )
+        } else {
+            None
+        };
+        for t in self.tris_around( v ) {
+            let k = self.corner( t, v );
+            if self._tris[t].contains( &u ) {
+                self._alive[t] = false;
+                self._tri_count -= 1;
+            } else {
+                self._tris[t][k] = u;
+                self._vert_tris[u].push( t );
+            }
+        }
+        for ( t, a ) in wedges {
+            let k = self.corner( t, u );
+            self._attrs[t][k] = a;
+        }
+        if let Some( a ) = merged_attr {
+            for t in self.tris_around( u ) {
+                let k = self.corner( t, u );
+                self._attrs[t][k] = a;
+            }
+        }
+        self._vert_tris[v].clear();
+        let live = self.tris_around( u );
+        self._vert_tris[u] = live;
+        self._verts[u] = c._pos;
+        self._quadrics[u] = self._quadrics[u].add( & self._quadrics[v] );
+        self._removed[v] = true;
+        self._version[u] += 1;
+        self._error = self._error.max( c._cost );
+    }
+
+    fn push_edges( & self, heap: & mut BinaryHeap< Candidate >, a: usize ) {
+        for b in self.neighbours( a ) {
+            if let Some( o ) = self.options( a, b ).first() {
+                heap.push( Candidate { _cost: o._cost, _a: a, _b: b, _ver_a: self._version[a], _ver_b: self._version[b] } );
+            }
+        }
+    }
+
+    fn run( & mut self, config: & SimplifyConfig ) {
+        let mut heap = BinaryHeap::new();
+        for a in 0..self._verts.len() {
+            for b in self.neighbours( a ) {
+                if a < b {
+                    if let Some( o ) = self.options( a, b ).first() {
+                        heap.push( Candidate { _cost: o._cost, _a: a, _b: b, _ver_a: 0, _ver_b: 0 } );
+                    }
+                }
+            }
+        }
+        while self._tri_count > config._target_tris {
+            let cand = match heap.pop() {
+                Some( x ) => x,
+                None => break,
+            };
+            if cand._cost > config._max_error {
+                break;
+            }
+            let ( a, b ) = ( cand._a, cand._b );
+            if self._removed[a] || self._removed[b] || self._version[a] != cand._ver_a || self._version[b] != cand._ver_b {
+                continue;
+            }
+            //cheapest valid option, retried later if it costs more than this entry
+            let mut chosen = None;
+            for o in self.options( a, b ) {
+                if !self.is_valid( & o ) {
+                    continue;
+                }
+                let wedges = if o._merge { Some( vec![] ) } else { self.wedge_map( & o ) };
+                if let Some( w ) = wedges {
+                    chosen = Some( ( o, w ) );
+                    break;
+                }
+            }
+            match chosen {
+                Some( ( o, w ) ) => {
+                    if o._cost > cand._cost + 1e-12 * cand._cost.abs().max( 1. ) {
+                        heap.push( Candidate { _cost: o._cost, .. cand } );
+                        continue;
+                    }
+                    let u = o._to;
+                    self.apply( & o, w );
+                    self.push_edges( & mut heap, u );
+                },
+                None => {},
+            }
+        }
+    }
+
+    fn to_soup( & self ) -> ( Vec< f32 >, Vec< f32 >, Vec< f32 > ) {
+        let mut p = vec![];
+        let mut n = vec![];
+        let mut t = vec![];
+        for ( i, tri ) in self._tris.iter().enumerate() {
+            if !self._alive[i] {
+                continue;
+            }
+            for c in 0..3 {
+                let v = self._verts[ tri[c] ];
+                let a = self._attrs[i][c];
+                p.extend_from_slice( &[ v[0] as f32, v[1] as f32, v[2] as f32 ] );
+                n.extend_from_slice( &a[0..3] );
+                t.extend_from_slice( &a[3..5] );
+            }
+        }
+        ( p, n, t )
+    }
+}
+
+fn attr_eq( a: & Attr, b: & Attr, eps: f32 ) -> bool {
+    a.iter().zip( b.iter() ).all( |( x, y )| ( x - y ).abs() <= eps )
+}
+
+///blends texture coordinates linearly and renormalizes the normal
+fn attr_lerp( a: & Attr, b: & Attr, s: f32 ) -> Attr {
+    let mut out = [ 0f32; 5 ];
+    for i in 0..5 {
+        out[i] = a[i] + ( b[i] - a[i] ) * s;
+    }
+    let l = ( out[0] * out[0] + out[1] * out[1] + out[2] * out[2] ).sqrt();
+    if l > 0. {
+        for i in 0..3 {
+            out[i] /= l;
+        }
+    }
+    out
+}
@@ -69,6 +69,24 @@ impl SplineBezier {
                         _step_current: -1i64,
         }
     }
+
+    ///recomputes point and forward differences at the current step so stepping can resume after a jump
+    fn sync( & mut self ) {
+        if self._step_current >= self._steps {
+            self._point = self._ctl[3];
+            return
+        }
+        let h = 1f64 / ( self._steps as f64 - 1f64 );
+        let t = cmp::max( self._step_current, 0 ) as f64 * h;
+        let p0 = self.eval( t );
+        let p1 = self.eval( t + h );
+        let p2 = self.eval( t + 2f64 * h );
+        for i in 0..4 {
+            self._point_d._val[i] = p1._val[i] - p0._val[i];
+            self._point_dd._val[i] = p2._val[i] - 2f64 * p1._val[i] + p0._val[i];
+        }
+        self._point = p0;
+    }
 }
 #[allow(unused_variables)]
 impl IInterpolate< Mat4x1< f64 > > for SplineBezier {
@@ -97,7 +115,9 @@ impl IInterpolate< Mat4x1< f64 > > for SplineBezier {
             }
             return Some( self._point )
         } else {
-            unimplemented!();
+            self._step_current = cmp::max( self._step_current + steps, -1 );
+            self.sync();
+            return Some( self._point )
         }
     }
     fn interp_current( & self ) -> Mat4x1< f64 > {
@@ -114,6 +134,19 @@ impl IInterpolate< Mat4x1< f64 > > for SplineBezier {
     }
 }
 
+impl IInterpolateEval< Mat4x1< f64 > > for SplineBezier {
+    ///direct evaluation via bernstein polynomials
+    fn eval( & self, t: f64 ) -> Mat4x1< f64 > {
+        let u = 1f64 - t;
+        let w = [ u * u * u, 3f64 * u * u * t, 3f64 * u * t * t, t * t * t ];
+        let mut p = Mat4x1::default();
+        for i in 0..4 {
+            p._val[i] = ( 0..4 ).fold( 0f64, |acc, j| acc + w[j] * self._ctl[j]._val[i] );
+        }
+        p
+    }
+}
+
 //required by IInterpolate
 impl Iterator for SplineBezier {
     type Item = Mat4x1< f64 >;
@@ -138,7 +171,13 @@ impl Iterator for SplineBezier {
 //required by IInterpolate
 impl DoubleEndedIterator for SplineBezier {
     fn next_back( & mut self ) -> Option< Mat4x1< f64 > > {
-        unimplemented!();
+        self._step_current = cmp::max( self._step_current - 1, -1 );
+        self.sync();
+        if self._step_current == -1 {
+            None
+        } else {
+            Some( self._point )
+        }
     }
 }
 
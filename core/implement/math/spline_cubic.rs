@@ -0,0 +1,217 @@
+extern crate mazth;
+
+use std::cmp;
+
+use interface::i_interpolate::*;
+
+use implement::math::piecewise::Piecewise;
+
+use self::mazth::mat::*;
+
+///knot parameterization of catmull-rom segments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CatmullRomParam {
+    Uniform,
+    ///avoids cusps and self intersections within a segment
+    Centripetal,
+    Chordal,
+}
+
+impl CatmullRomParam {
+    fn alpha( & self ) -> f64 {
+        match *self {
+            CatmullRomParam::Uniform => 0f64,
+            CatmullRomParam::Centripetal => 0.5f64,
+            CatmullRomParam::Chordal => 1f64,
+        }
+    }
+}
+
+///cubic polynomial segment p(t) = a t^3 + b t^2 + c t + d over t in [0,1], evaluated at evenly spaced steps.
+///hermite, catmull-rom and uniform b-spline segments are converted to this form
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct SplineCubic {
+    ///coefficients a, b, c, d
+    pub _coeff: [ Mat4x1< f64 >; 4 ],
+    pub _point: Mat4x1< f64 >,
+    pub _steps: i64,
+    pub _step_current: i64,
+}
+
+impl SplineCubic {
+    ///segment from p0 to p1 with tangents m0 and m1
+    pub fn init_hermite( s: u64, p0: Mat4x1< f64 >, m0: Mat4x1< f64 >, p1: Mat4x1< f64 >, m1: Mat4x1< f64 > ) -> SplineCubic {
+        assert!( s > 2 );
+        let mut coeff = [ Mat4x1 { _val: [0f64; 4] }; 4 ];
+        for i in 0..4 {
+            let ( a, b, c, d ) = ( p0._val[i], m0._val[i], p1._val[i], m1._val[i] );
+            coeff[0]._val[i] = 2f64 * a + b - 2f64 * c + d;
+            coeff[1]._val[i] = -3f64 * a - 2f64 * b + 3f64 * c - d;
+            coeff[2]._val[i] = b;
+            coeff[3]._val[i] = a;
+        }
+        SplineCubic {
+            _coeff: coeff,
+            _point: p0,
+            _steps: s as _,
+            _step_current: -1i64,
+        }
+    }
+
+    ///segment from p1 to p2 passing through both, with p0 and p3 shaping the tangents
+    pub fn init_catmull_rom( s: u64, p0: Mat4x1< f64 >, p1: Mat4x1< f64 >, p2: Mat4x1< f64 >, p3: Mat4x1< f64 >, param: CatmullRomParam ) -> SplineCubic {
+        let alpha = param.alpha();
+        let knot = |a: & Mat4x1< f64 >, b: & Mat4x1< f64 >| {
+            let d = ( 0..4 ).fold( 0f64, |acc, i| acc + ( b._val[i] - a._val[i] ) * ( b._val[i] - a._val[i] ) ).sqrt();
+            //coincident points fall back to a unit interval
+            if d > 1e-12 { d.powf( alpha ) } else { 1f64 }
+        };
+        let ( d0, d1, d2 ) = ( knot( &p0, &p1 ), knot( &p1, &p2 ), knot( &p2, &p3 ) );
+        let mut m1 = Mat4x1 { _val: [0f64; 4] };
+        let mut m2 = Mat4x1 { _val: [0f64; 4] };
+        for i in 0..4 {
+            let ( a, b, c, d ) = ( p0._val[i], p1._val[i], p2._val[i], p3._val[i] );
+            //tangents of the barry-goldman formulation rescaled to the [t1,t2] interval
+            m1._val[i] = ( ( b - a ) / d0 - ( c - a ) / ( d0 + d1 ) + ( c - b ) / d1 ) * d1;
+            m2._val[i] = ( ( c - b ) / d1 - ( d - b ) / ( d1 + d2 ) + ( d - c ) / d2 ) * d1;
+        }
+        SplineCubic::init_hermite( s, p1, m1, p2, m2 )
+    }
+
+    ///uniform cubic b-spline segment of 4 consecutive control points, approximating rather than passing through them
+    pub fn init_bspline( s: u64, p0: Mat4x1< f64 >, p1: Mat4x1< f64 >, p2: Mat4x1< f64 >, p3: Mat4x1< f64 > ) -> SplineCubic {
+        assert!( s > 2 );
+        let mut coeff = [ Mat4x1 { _val: [0f64; 4] }; 4 ];
+        for i in 0..4 {
+            let ( a, b, c, d ) = ( p0._val[i], p1._val[i], p2._val[i], p3._val[i] );
+            coeff[0]._val[i] = ( -a + 3f64 * b - 3f64 * c + d ) / 6f64;
+            coeff[1]._val[i] = ( 3f64 * a - 6f64 * b + 3f64 * c ) / 6f64;
+            coeff[2]._val[i] = ( -3f64 * a + 3f64 * c ) / 6f64;
+            coeff[3]._val[i] = ( a + 4f64 * b + c ) / 6f64;
+        }
+        let start = coeff[3];
+        SplineCubic {
+            _coeff: coeff,
+            _point: start,
+            _steps: s as _,
+            _step_current: -1i64,
+        }
+    }
+
+    ///first derivative with respect to the segment fraction
+    pub fn eval_tangent( & self, t: f64 ) -> Mat4x1< f64 > {
+        let mut p = Mat4x1::default();
+        for i in 0..4 {
+            p._val[i] = 3f64 * self._coeff[0]._val[i] * t * t + 2f64 * self._coeff[1]._val[i] * t + self._coeff[2]._val[i];
+        }
+        p
+    }
+
+    fn point_at_step( & self, step: i64 ) -> Mat4x1< f64 > {
+        let clamp = cmp::min( cmp::max( step, 0 ), self._steps - 1 );
+        self.eval( clamp as f64 / ( self._steps - 1 ) as f64 )
+    }
+}
+
+impl IInterpolateEval< Mat4x1< f64 > > for SplineCubic {
+    fn eval( & self, t: f64 ) -> Mat4x1< f64 > {
+        let mut p = Mat4x1::default();
+        for i in 0..4 {
+            p._val[i] = ( ( self._coeff[0]._val[i] * t + self._coeff[1]._val[i] ) * t + self._coeff[2]._val[i] ) * t + self._coeff[3]._val[i];
+        }
+        p
+    }
+}
+
+impl IInterpolate< Mat4x1< f64 > > for SplineCubic {
+    fn num_steps( & self ) -> u64 {
+        self._steps as u64
+    }
+    fn interp_delta( & mut self, steps: i64 ) -> Option< Mat4x1< f64 > > {
+        self._step_current = cmp::min( self._step_current + steps, self._steps );
+        self._step_current = cmp::max( self._step_current, -1 );
+        self._point = self.point_at_step( self._step_current );
+        Some( self._point )
+    }
+    fn interp_current( & self ) -> Mat4x1< f64 > {
+        self._point
+    }
+    fn interp_is_end( & self ) -> bool {
+        self._step_current == self._steps
+    }
+    fn interp_is_start( & self ) -> bool {
+        self._step_current == -1
+    }
+    fn reset( & mut self ){
+        self._step_current = -1i64;
+        self._point = self._coeff[3];
+    }
+}
+
+//required by IInterpolate
+impl Iterator for SplineCubic {
+    type Item = Mat4x1< f64 >;
+    fn next( & mut self ) -> Option< Mat4x1< f64 > > {
+        self._step_current = cmp::min( self._step_current + 1, self._steps );
+        if self._step_current == self._steps {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+//required by IInterpolate
+impl DoubleEndedIterator for SplineCubic {
+    fn next_back( & mut self ) -> Option< Mat4x1< f64 > > {
+        self._step_current = cmp::max( self._step_current - 1, -1 );
+        if self._step_current == -1 {
+            None
+        } else {
+            self._point = self.point_at_step( self._step_current );
+            Some( self._point )
+        }
+    }
+}
+
+///catmull-rom path through all waypoints, end segments use mirrored phantom points
+pub fn catmull_rom_path( steps_per_segment: u64, waypoints: &[ Mat4x1< f64 > ], param: CatmullRomParam ) -> Result< Piecewise< SplineCubic, Mat4x1< f64 > >, & 'static str > {
+    if waypoints.len() < 2 {
+        return Err( "catmull-rom path requires at least 2 waypoints." )
+    }
+    let n = waypoints.len();
+    let mirror = |a: & Mat4x1< f64 >, b: & Mat4x1< f64 >| {
+        let mut p = Mat4x1::default();
+        for i in 0..4 {
+            p._val[i] = 2f64 * a._val[i] - b._val[i];
+        }
+        p
+    };
+    let mut pts = vec![ mirror( &waypoints[0], &waypoints[1] ) ];
+    pts.extend_from_slice( waypoints );
+    pts.push( mirror( &waypoints[ n - 1 ], &waypoints[ n - 2 ] ) );
+    let mut path = Piecewise::init();
+    for w in pts.windows( 4 ) {
+        path.add( SplineCubic::init_catmull_rom( steps_per_segment, w[0], w[1], w[2], w[3], param ) );
+    }
+    Ok( path )
+}
+
+///uniform b-spline path over control points with clamped ends by repeating the end points
+pub fn bspline_path( steps_per_segment: u64, ctl: &[ Mat4x1< f64 > ] ) -> Result< Piecewise< SplineCubic, Mat4x1< f64 > >, & 'static str > {
+    if ctl.len() < 2 {
+        return Err( "b-spline path requires at least 2 control points." )
+    }
+    let n = ctl.len();
+    let mut pts = vec![ ctl[0], ctl[0] ];
+    pts.extend_from_slice( ctl );
+    pts.push( ctl[ n - 1 ] );
+    pts.push( ctl[ n - 1 ] );
+    let mut path = Piecewise::init();
+    for w in pts.windows( 4 ) {
+        path.add( SplineCubic::init_bspline( steps_per_segment, w[0], w[1], w[2], w[3] ) );
+    }
+    Ok( path )
+}
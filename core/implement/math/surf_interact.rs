@@ -1,13 +1,286 @@
-use implement::math::vector::Mat2x1< f64 >;
-use implement::math::vector::Mat3x1< f64 >;
-use implement::math::vector::Mat4x1< f64 >;
+///ray surface interaction records for basic primitives, built on the ray tests of intersect
 
-///todo
+extern crate mazth;
+
+use std::f64;
+
+use self::mazth::mat::{ Mat2x1, Mat3x1 };
+
+use implement::math::vec3::{ add, sub, scale, dot, cross, length, normalize };
+use implement::math::intersect;
+
+///ray with a parametric interval, distances are in units of the direction's length
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub _origin: Mat3x1< f64 >,
+    pub _dir: Mat3x1< f64 >,
+    pub _t_min: f64,
+    pub _t_max: f64,
+}
+
+impl Ray {
+    pub fn init( origin: [f64;3], dir: [f64;3] ) -> Ray {
+        Ray {
+            _origin: Mat3x1 { _val: origin },
+            _dir: Mat3x1 { _val: dir },
+            _t_min: 0.,
+            _t_max: f64::INFINITY,
+        }
+    }
+    pub fn with_range( mut self, t_min: f64, t_max: f64 ) -> Ray {
+        self._t_min = t_min;
+        self._t_max = t_max;
+        self
+    }
+    pub fn point_at( & self, t: f64 ) -> [f64;3] {
+        add( self._origin._val, scale( self._dir._val, t ) )
+    }
+    fn in_range( & self, t: f64 ) -> bool {
+        t >= self._t_min && t <= self._t_max
+    }
+}
+
+///result of a ray primitive intersection
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SurfInteract {
-    _point: Mat2x1< f64 >,
-    _dpdu: Mat3x1< f64 >,
-    _dpdv: Mat3x1< f64 >,
-    _normal: Mat3x1< f64 >,
-    _dndu: Mat3x1< f64 >,
-    _dndv: Mat3x1< f64 >,
+    ///distance along the ray
+    pub _t: f64,
+    pub _point: Mat3x1< f64 >,
+    ///unit normal of the underlying surface, outward for closed primitives and by winding for triangles
+    pub _normal_geom: Mat3x1< f64 >,
+    ///unit normal interpolated from vertex normals where available, on the same side as the geometric normal
+    pub _normal_shading: Mat3x1< f64 >,
+    pub _uv: Mat2x1< f64 >,
+    ///partial derivatives of the surface position with respect to uv
+    pub _dpdu: Mat3x1< f64 >,
+    pub _dpdv: Mat3x1< f64 >,
+    ///weights of the 3 triangle vertices for triangle hits
+    pub _barycentric: Option< [f64;3] >,
+    pub _prim_id: u64,
+    ///ray arrived against the geometric normal
+    pub _front_face: bool,
+}
+
+impl SurfInteract {
+    ///geometric normal flipped to face the incoming ray
+    pub fn normal_facing( & self ) -> [f64;3] {
+        if self._front_face { self._normal_geom._val } else { scale( self._normal_geom._val, -1. ) }
+    }
+    ///ray leaving the surface, offset along the normal to avoid hitting the same surface again
+    pub fn spawn_ray( & self, dir: [f64;3] ) -> Ray {
+        let n = self._normal_geom._val;
+        let offset = 1e-7 * ( 1. + length( self._point._val ) );
+        let side = if dot( dir, n ) >= 0. { offset } else { -offset };
+        Ray::init( add( self._point._val, scale( n, side ) ), dir )
+    }
+}
+
+///primitives that can be intersected by rays
+#[derive(Debug, Clone, PartialEq)]
+pub enum Primitive {
+    ///optional per vertex normals and texture coordinates
+    Triangle { _v: [ [f64;3]; 3 ], _n: Option< [ [f64;3]; 3 ] >, _uv: Option< [ [f64;2]; 3 ] > },
+    Sphere { _center: [f64;3], _radius: f64 },
+    ///infinite double sided plane through a point
+    Plane { _point: [f64;3], _normal: [f64;3] },
+    Aabb { _lower: [f64;3], _upper: [f64;3] },
+}
+
+impl Primitive {
+    pub fn intersect( & self, ray: & Ray, id: u64 ) -> Option< SurfInteract > {
+        match *self {
+            Primitive::Triangle { _v, _n, _uv } => ray_triangle( ray, _v, _n, _uv, id ),
+            Primitive::Sphere { _center, _radius } => ray_sphere( ray, _center, _radius, id ),
+            Primitive::Plane { _point, _normal } => ray_plane( ray, _point, _normal, id ),
+            Primitive::Aabb { _lower, _upper } => ray_aabb( ray, _lower, _upper, id ),
+        }
+    }
+    ///world space bounds, none for unbounded primitives
+    pub fn bounds( & self ) -> Option< ( [f64;3], [f64;3] ) > {
+        match *self {
+            Primitive::Triangle { _v, .. } => {
+                let mut l = _v[0];
+                let mut u = _v[0];
+                for p in _v.iter() {
+                    for h in 0..3 {
+                        l[h] = l[h].min( p[h] );
+                        u[h] = u[h].max( p[h] );
+                    }
+                }
+                Some( ( l, u ) )
+            },
+            Primitive::Sphere { _center: c, _radius: r } => Some( ( [ c[0] - r, c[1] - r, c[2] - r ], [ c[0] + r, c[1] + r, c[2] + r ] ) ),
+            Primitive::Plane { .. } => None,
+            Primitive::Aabb { _lower, _upper } => Some( ( _lower, _upper ) ),
+        }
+    }
+}
+
+///closest hit over a list of primitives, ids are the indices into the list
+pub fn closest_hit( prims: &[ Primitive ], ray: & Ray ) -> Option< SurfInteract > {
+    let mut r = *ray;
+    let mut best = None;
+    for ( i, p ) in prims.iter().enumerate() {
+        if let Some( h ) = p.intersect( & r, i as u64 ) {
+            r._t_max = h._t;
+            best = Some( h );
+        }
+    }
+    best
+}
+
+///double sided triangle test, uv defaults to the barycentric coordinates of the 2nd and 3rd vertex
+pub fn ray_triangle( ray: & Ray, v: [ [f64;3]; 3 ], normals: Option< [ [f64;3]; 3 ] >, uvs: Option< [ [f64;2]; 3 ] >, id: u64 ) -> Option< SurfInteract > {
+    let d = ray._dir._val;
+    let ( t, b1, b2 ) = intersect::ray_triangle_f64( ray._origin._val, d, v[0], v[1], v[2] )?;
+    if !ray.in_range( t ) {
+        return None
+    }
+    let e1 = sub( v[1], v[0] );
+    let e2 = sub( v[2], v[0] );
+    let b0 = 1. - b1 - b2;
+    let ng = normalize( cross( e1, e2 ) );
+
+    let uv = uvs.unwrap_or( [ [ 0., 0. ], [ 1., 0. ], [ 0., 1. ] ] );
+    let hit_uv = [ b0 * uv[0][0] + b1 * uv[1][0] + b2 * uv[2][0],
+                   b0 * uv[0][1] + b1 * uv[1][1] + b2 * uv[2][1] ];
+    let duv02 = [ uv[0][0] - uv[2][0], uv[0][1] - uv[2][1] ];
+    let duv12 = [ uv[1][0] - uv[2][0], uv[1][1] - uv[2][1] ];
+    let dp02 = sub( v[0], v[2] );
+    let dp12 = sub( v[1], v[2] );
+    let uv_det = duv02[0] * duv12[1] - duv02[1] * duv12[0];
+    let ( dpdu, dpdv ) = if uv_det.abs() < 1e-20 {
+        basis( ng )
+    } else {
+        let inv = 1. / uv_det;
+        ( scale( sub( scale( dp02, duv12[1] ), scale( dp12, duv02[1] ) ), inv ),
+          scale( sub( scale( dp12, duv02[0] ), scale( dp02, duv12[0] ) ), inv ) )
+    };
+
+    let ns = match normals {
+        Some( n ) => {
+            let i = normalize( add( add( scale( n[0], b0 ), scale( n[1], b1 ) ), scale( n[2], b2 ) ) );
+            if dot( i, ng ) < 0. { scale( i, -1. ) } else { i }
+        },
+        None => ng,
+    };
+    Some( SurfInteract {
+        _t: t,
+        _point: Mat3x1 { _val: add( add( scale( v[0], b0 ), scale( v[1], b1 ) ), scale( v[2], b2 ) ) },
+        _normal_geom: Mat3x1 { _val: ng },
+        _normal_shading: Mat3x1 { _val: ns },
+        _uv: Mat2x1 { _val: hit_uv },
+        _dpdu: Mat3x1 { _val: dpdu },
+        _dpdv: Mat3x1 { _val: dpdv },
+        _barycentric: Some( [ b0, b1, b2 ] ),
+        _prim_id: id,
+        _front_face: dot( d, ng ) < 0.,
+    } )
+}
+
+///nearest hit within the ray interval, uv is longitude and colatitude mapped to [0,1] about the z axis
+pub fn ray_sphere( ray: & Ray, center: [f64;3], radius: f64, id: u64 ) -> Option< SurfInteract > {
+    if radius <= 0. {
+        return None
+    }
+    let d = ray._dir._val;
+    let ( t0, t1 ) = intersect::ray_sphere_f64( ray._origin._val, d, center, radius )?;
+    let t = if ray.in_range( t0 ) { t0 } else if ray.in_range( t1 ) { t1 } else { return None };
+
+    let p = ray.point_at( t );
+    let l = sub( p, center );
+    let n = scale( l, 1. / radius );
+    let mut phi = l[1].atan2( l[0] );
+    if phi < 0. {
+        phi += 2. * f64::consts::PI;
+    }
+    let theta = ( l[2] / radius ).max( -1. ).min( 1. ).acos();
+    let r_xy = ( l[0] * l[0] + l[1] * l[1] ).sqrt();
+    let ( cos_phi, sin_phi ) = if r_xy > 0. { ( l[0] / r_xy, l[1] / r_xy ) } else { ( 1., 0. ) };
+    let dpdu = [ -2. * f64::consts::PI * l[1], 2. * f64::consts::PI * l[0], 0. ];
+    let dpdv = scale( [ l[2] * cos_phi, l[2] * sin_phi, -radius * theta.sin() ], f64::consts::PI );
+    Some( SurfInteract {
+        _t: t,
+        _point: Mat3x1 { _val: p },
+        _normal_geom: Mat3x1 { _val: n },
+        _normal_shading: Mat3x1 { _val: n },
+        _uv: Mat2x1 { _val: [ phi / ( 2. * f64::consts::PI ), theta / f64::consts::PI ] },
+        _dpdu: Mat3x1 { _val: dpdu },
+        _dpdv: Mat3x1 { _val: dpdv },
+        _barycentric: None,
+        _prim_id: id,
+        _front_face: dot( d, n ) < 0.,
+    } )
+}
+
+///uv are distances along an orthonormal tangent basis of the plane measured from its point
+pub fn ray_plane( ray: & Ray, point: [f64;3], normal: [f64;3], id: u64 ) -> Option< SurfInteract > {
+    let n = normalize( normal );
+    let d = ray._dir._val;
+    let denom = dot( d, n );
+    if denom.abs() < 1e-20 {
+        return None
+    }
+    let t = dot( sub( point, ray._origin._val ), n ) / denom;
+    if !ray.in_range( t ) {
+        return None
+    }
+    let p = ray.point_at( t );
+    let ( s, w ) = basis( n );
+    let rel = sub( p, point );
+    Some( SurfInteract {
+        _t: t,
+        _point: Mat3x1 { _val: p },
+        _normal_geom: Mat3x1 { _val: n },
+        _normal_shading: Mat3x1 { _val: n },
+        _uv: Mat2x1 { _val: [ dot( rel, s ), dot( rel, w ) ] },
+        _dpdu: Mat3x1 { _val: s },
+        _dpdv: Mat3x1 { _val: w },
+        _barycentric: None,
+        _prim_id: id,
+        _front_face: denom < 0.,
+    } )
+}
+
+///slab test, rays starting inside report the exit face. uv spans [0,1] over the hit face
+pub fn ray_aabb( ray: & Ray, lower: [f64;3], upper: [f64;3], id: u64 ) -> Option< SurfInteract > {
+    let d = ray._dir._val;
+    let s = intersect::ray_aabb_f64( ray._origin._val, d, lower, upper )?;
+    let ( t, ( axis, side ) ) = if ray.in_range( s._t_near ) {
+        ( s._t_near, s._face_near )
+    } else if ray.in_range( s._t_far ) {
+        ( s._t_far, s._face_far )
+    } else {
+        return None
+    };
+    let p = ray.point_at( t );
+    let mut n = [ 0f64; 3 ];
+    n[axis] = side;
+    let ( ua, va ) = ( ( axis + 1 ) % 3, ( axis + 2 ) % 3 );
+    let ext = sub( upper, lower );
+    let coord = |h: usize| if ext[h] > 0. { ( p[h] - lower[h] ) / ext[h] } else { 0. };
+    let mut dpdu = [ 0f64; 3 ];
+    let mut dpdv = [ 0f64; 3 ];
+    dpdu[ua] = ext[ua];
+    dpdv[va] = ext[va];
+    Some( SurfInteract {
+        _t: t,
+        _point: Mat3x1 { _val: p },
+        _normal_geom: Mat3x1 { _val: n },
+        _normal_shading: Mat3x1 { _val: n },
+        _uv: Mat2x1 { _val: [ coord( ua ), coord( va ) ] },
+        _dpdu: Mat3x1 { _val: dpdu },
+        _dpdv: Mat3x1 { _val: dpdv },
+        _barycentric: None,
+        _prim_id: id,
+        _front_face: dot( d, n ) < 0.,
+    } )
+}
+
+///orthonormal tangents of a unit normal
+fn basis( n: [f64;3] ) -> ( [f64;3], [f64;3] ) {
+    let a = if n[0].abs() > 0.9 { [ 0., 1., 0. ] } else { [ 1., 0., 0. ] };
+    let s = normalize( cross( a, n ) );
+    let t = cross( n, s );
+    ( s, t )
 }
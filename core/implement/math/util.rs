@@ -3,6 +3,7 @@ extern crate mazth;
 use ::std::f32;
 
 use self::mazth::mat;
+use self::mazth::quat::Quat;
 
 pub fn perspective( fov: f32, aspect: f32, near: f32, far: f32 ) -> mat::Mat4< f32 > {
     //fov is the full field of view in y-plane, eg: fovy = fov/2
@@ -57,6 +58,35 @@ pub fn look_at( eye: mat::Mat3x1< f32 >, center: mat::Mat3x1< f32 >, up: mat::Ma
     camera_view
 }
 
+///inverse of perspective, mapping clip space back to view space
+pub fn perspective_inverse( fov: f32, aspect: f32, near: f32, far: f32 ) -> mat::Mat4< f32 > {
+    let half_tan = (fov * f32::consts::PI / 360.0f32).tan();
+    let c = (far+near)/(near-far);
+    let d = (2.0f32*far*near)/(near-far);
+    let mut out : mat::Mat4<f32> = Default::default();
+    *out.index_mut(0, 0) = aspect*half_tan;
+    *out.index_mut(1, 1) = half_tan;
+    *out.index_mut(2, 3) = -1f32;
+    *out.index_mut(3, 2) = 1.0/d;
+    *out.index_mut(3, 3) = c/d;
+    out
+}
+
+///inverse of look_at, mapping camera space to world space
+pub fn look_at_inverse( eye: mat::Mat3x1< f32 >, center: mat::Mat3x1< f32 >, up: mat::Mat3x1< f32 > ) -> mat::Mat4< f32 > {
+    let view = look_at( eye, center, up );
+    //rotation part is orthonormal so its inverse is the transpose, translation is the eye position
+    let mut out : mat::Mat4<f32> = Default::default();
+    for i in 0..3 {
+        for j in 0..3 {
+            *out.index_mut( i, j ) = view.index( j, i );
+        }
+        *out.index_mut( i, 3 ) = eye[i];
+    }
+    *out.index_mut( 3, 3 ) = 1f32;
+    out
+}
+
 pub fn invert_rotate_mat4( input: mat::Mat4<f32> ) -> mat::Mat4<f32> {
     let mut out = input;
     for i in 0..3 {
@@ -88,3 +118,13 @@ pub fn identity_mat4() -> mat::Mat4<f32> {
                              0f32, 0f32, 0f32, 1f32 ],
                              true )
 }
+
+///rigid transform in row major form from a translation and a unit quaternion
+pub fn xform_from_pos_quat( pos: [f32;3], q: & Quat<f32> ) -> mat::Mat4<f32> {
+    let ( x, y, z, w ) = ( q._x, q._y, q._z, q._w );
+    mat::Mat4::<f32>::init( [ 1. - 2.*(y*y + z*z), 2.*(x*y - z*w),      2.*(x*z + y*w),      pos[0],
+                              2.*(x*y + z*w),      1. - 2.*(x*x + z*z), 2.*(y*z - x*w),      pos[1],
+                              2.*(x*z - y*w),      2.*(y*z + x*w),      1. - 2.*(x*x + y*y), pos[2],
+                              0f32,                0f32,                0f32,                1f32 ],
+                             true )
+}
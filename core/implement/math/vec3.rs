@@ -0,0 +1,55 @@
+///component wise helpers over plain 3 element arrays of f32 or f64
+
+use std::ops::{ Add, Sub, Mul, Div };
+
+pub trait Scalar : Copy + PartialOrd + Add< Output = Self > + Sub< Output = Self > + Mul< Output = Self > + Div< Output = Self > {
+    fn zero() -> Self;
+    fn sqrt( self ) -> Self;
+}
+
+impl Scalar for f32 {
+    fn zero() -> f32 { 0f32 }
+    fn sqrt( self ) -> f32 { f32::sqrt( self ) }
+}
+
+impl Scalar for f64 {
+    fn zero() -> f64 { 0f64 }
+    fn sqrt( self ) -> f64 { f64::sqrt( self ) }
+}
+
+pub fn add< T: Scalar >( a: [T;3], b: [T;3] ) -> [T;3] {
+    [ a[0] + b[0], a[1] + b[1], a[2] + b[2] ]
+}
+
+pub fn sub< T: Scalar >( a: [T;3], b: [T;3] ) -> [T;3] {
+    [ a[0] - b[0], a[1] - b[1], a[2] - b[2] ]
+}
+
+///component wise product
+pub fn mul< T: Scalar >( a: [T;3], b: [T;3] ) -> [T;3] {
+    [ a[0] * b[0], a[1] * b[1], a[2] * b[2] ]
+}
+
+pub fn scale< T: Scalar >( a: [T;3], s: T ) -> [T;3] {
+    [ a[0] * s, a[1] * s, a[2] * s ]
+}
+
+pub fn dot< T: Scalar >( a: [T;3], b: [T;3] ) -> T {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn cross< T: Scalar >( a: [T;3], b: [T;3] ) -> [T;3] {
+    [ a[1] * b[2] - a[2] * b[1],
+      a[2] * b[0] - a[0] * b[2],
+      a[0] * b[1] - a[1] * b[0] ]
+}
+
+pub fn length< T: Scalar >( a: [T;3] ) -> T {
+    dot( a, a ).sqrt()
+}
+
+///unit vector along a, zero length vectors are returned unchanged
+pub fn normalize< T: Scalar >( a: [T;3] ) -> [T;3] {
+    let l = length( a );
+    if l > T::zero() { [ a[0] / l, a[1] / l, a[2] / l ] } else { a }
+}
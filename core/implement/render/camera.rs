@@ -11,6 +11,7 @@ use interface::i_component;
 
 use self::mazth::mat::*;
 use implement::math;
+use implement::math::frustum::Frustum;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum PropKey {
@@ -75,6 +76,12 @@ impl Cam {
         self._focus = focus;
         self._view_xform = math::util::look_at( pos, self._focus, self._up );
     }
+    ///view frustum in world space
+    pub fn frustum( & self ) -> Frustum {
+        let v = |m: & Mat3x1< f32 >| [ m[0] as f64, m[1] as f64, m[2] as f64 ];
+        Frustum::init_from_view( self._fov as f64, self._aspect as f64, self._near as f64, self._far as f64,
+                                 v( & self._pos ), v( & self._focus ), v( & self._up ) )
+    }
 }
 
 impl i_ele::IObjImpl for Cam {
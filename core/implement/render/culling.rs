@@ -0,0 +1,143 @@
+///view frustum culling of renderables using coarse bounding volumes
+
+use interface::i_md5;
+use interface::i_wavefront;
+
+use implement::math::frustum::Frustum;
+use implement::render::camera::Cam;
+use implement::render::primitive::{ Poly6, SphereIcosahedron, Point };
+use implement::render::mesh::Mesh;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundingVolume {
+    Sphere { _center: [f32;3], _radius: f32 },
+    Aabb { _lower: [f32;3], _upper: [f32;3] },
+    ///never culled, eg: shader loading or camera only renderables
+    Unbounded,
+}
+
+impl BoundingVolume {
+    ///smallest box enclosing both volumes
+    pub fn union( & self, other: & BoundingVolume ) -> BoundingVolume {
+        match ( self.aabb(), other.aabb() ) {
+            ( Some( ( l0, u0 ) ), Some( ( l1, u1 ) ) ) => {
+                BoundingVolume::Aabb { _lower: [ l0[0].min( l1[0] ), l0[1].min( l1[1] ), l0[2].min( l1[2] ) ],
+                                       _upper: [ u0[0].max( u1[0] ), u0[1].max( u1[1] ), u0[2].max( u1[2] ) ] }
+            },
+            _ => BoundingVolume::Unbounded,
+        }
+    }
+
+    pub fn aabb( & self ) -> Option< ( [f32;3], [f32;3] ) > {
+        match *self {
+            BoundingVolume::Sphere { _center: c, _radius: r } => {
+                Some( ( [ c[0] - r, c[1] - r, c[2] - r ], [ c[0] + r, c[1] + r, c[2] + r ] ) )
+            },
+            BoundingVolume::Aabb { _lower, _upper } => Some( ( _lower, _upper ) ),
+            BoundingVolume::Unbounded => None,
+        }
+    }
+
+    pub fn is_visible( & self, f: & Frustum ) -> bool {
+        let v = |a: [f32;3]| [ a[0] as f64, a[1] as f64, a[2] as f64 ];
+        match *self {
+            BoundingVolume::Sphere { _center, _radius } => f.intersect_sphere( v( _center ), _radius as f64 ),
+            BoundingVolume::Aabb { _lower, _upper } => f.intersect_aabb( v( _lower ), v( _upper ) ),
+            BoundingVolume::Unbounded => true,
+        }
+    }
+}
+
+///bounding volume of a renderable in world space
+pub trait ICullable {
+    fn bounding_volume( & self ) -> BoundingVolume;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CullStats {
+    pub _visible: usize,
+    pub _culled: usize,
+}
+
+///keeps renderables intersecting the frustum, preserving order
+pub fn cull< T: ICullable >( objs: Vec< T >, f: & Frustum ) -> ( Vec< T >, CullStats ) {
+    cull_by( objs, f, |x| x.bounding_volume() )
+}
+
+///same as cull, with the bounding volume supplied by a closure
+pub fn cull_by< T, F >( objs: Vec< T >, f: & Frustum, bv: F ) -> ( Vec< T >, CullStats ) where F: Fn( & T ) -> BoundingVolume {
+    let mut stats = CullStats::default();
+    let visible = objs.into_iter()
+        .filter( |x| {
+            let keep = bv( x ).is_visible( f );
+            if keep {
+                stats._visible += 1;
+            } else {
+                stats._culled += 1;
+            }
+            keep
+        } )
+        .collect();
+    ( visible, stats )
+}
+
+///culls against the view frustum of a camera
+pub fn cull_cam< T: ICullable >( objs: Vec< T >, cam: & Cam ) -> ( Vec< T >, CullStats ) {
+    cull( objs, & cam.frustum() )
+}
+
+///bounding box of packed xyz positions
+pub fn bound_positions( pos: &[ f32 ] ) -> BoundingVolume {
+    if pos.len() < 3 {
+        return BoundingVolume::Unbounded
+    }
+    let mut lower = [ pos[0], pos[1], pos[2] ];
+    let mut upper = lower;
+    for p in pos.chunks( 3 ).filter( |p| p.len() == 3 ) {
+        for h in 0..3 {
+            lower[h] = lower[h].min( p[h] );
+            upper[h] = upper[h].max( p[h] );
+        }
+    }
+    BoundingVolume::Aabb { _lower: lower, _upper: upper }
+}
+
+impl ICullable for i_md5::compute::ComputeCollection {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        BoundingVolume::Aabb { _lower: self._bbox_lower, _upper: self._bbox_upper }
+    }
+}
+
+impl ICullable for i_wavefront::compute::ComputeCollection {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        BoundingVolume::Aabb { _lower: self._bbox_lower, _upper: self._bbox_upper }
+    }
+}
+
+impl ICullable for Poly6 {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        let h = [ self._radius / 2. * self._scale[0].abs(),
+                  self._radius / 2. * self._scale[1].abs(),
+                  self._radius / 2. * self._scale[2].abs() ];
+        BoundingVolume::Aabb { _lower: [ self._pos[0] - h[0], self._pos[1] - h[1], self._pos[2] - h[2] ],
+                               _upper: [ self._pos[0] + h[0], self._pos[1] + h[1], self._pos[2] + h[2] ] }
+    }
+}
+
+impl ICullable for SphereIcosahedron {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        BoundingVolume::Sphere { _center: self._pos._val, _radius: self._radius }
+    }
+}
+
+impl ICullable for Point {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        BoundingVolume::Sphere { _center: self._pos._val, _radius: self._radius }
+    }
+}
+
+impl ICullable for Mesh {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        bound_positions( &self._batch_pos[..] )
+    }
+}
@@ -0,0 +1,161 @@
+///deferred shading pipeline: point lights of the lighting pass, the screen quad it draws and the events selecting the pipeline
+
+extern crate pretty_env_logger;
+
+use std::f32;
+use std::collections::HashMap;
+
+use interface::i_component;
+use interface::i_renderobj;
+
+use implement::render::culling::{ ICullable, BoundingVolume };
+use implement::render::renderdevice_gl;
+use implement::render::renderdevice_gl::UniformType;
+use implement::render::renderer_gl::Event;
+use implement::render::render_graph::RenderGraph;
+use implement::render::util_gl::ShaderType;
+
+pub const SHADER_GEOMETRY_VS : & 'static str = "core/asset/shader/deferred_geometry.vs";
+pub const SHADER_GEOMETRY_FS : & 'static str = "core/asset/shader/deferred_geometry.fs";
+pub const SHADER_LIGHTING_VS : & 'static str = "core/asset/shader/deferred_lighting.vs";
+pub const SHADER_LIGHTING_FS : & 'static str = "core/asset/shader/deferred_lighting.fs";
+
+///lights per draw of the screen quad, matches MAX_LIGHTS in deferred_lighting.fs
+pub const MAX_LIGHTS_PER_PASS : usize = 32;
+
+///uniform group holding the camera matrices, read for the view matrix
+pub const UNIFORM_GROUP_CAMERA : u64 = 1;
+
+///intensity under which a light no longer contributes, bounds the light volume
+pub const LIGHT_CUTOFF : f32 = 5. / 256.;
+
+///ambient intensity applied once to the albedo of the gbuffer
+pub const AMBIENT : [f32;3] = [ 0.1, 0.1, 0.1 ];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+    ///world space, moved to eye space with the camera before shading
+    pub _pos: [f32;3],
+    pub _color: [f32;3],
+    pub _linear: f32,
+    pub _quadratic: f32,
+}
+
+impl PointLight {
+    ///light with attenuation reaching a few units
+    pub fn init( pos: [f32;3], color: [f32;3] ) -> PointLight {
+        PointLight {
+            _pos: pos,
+            _color: color,
+            _linear: 0.7,
+            _quadratic: 1.8,
+        }
+    }
+    ///distance at which the attenuated intensity of the brightest channel drops under LIGHT_CUTOFF
+    pub fn radius( & self ) -> f32 {
+        let c = self._color[0].max( self._color[1] ).max( self._color[2] );
+        if c <= LIGHT_CUTOFF {
+            return 0.
+        }
+        //1 + linear * d + quadratic * d^2 = c / cutoff
+        let k = c / LIGHT_CUTOFF - 1.;
+        if self._quadratic > 0. {
+            ( -self._linear + ( self._linear * self._linear + 4. * self._quadratic * k ).sqrt() ) / ( 2. * self._quadratic )
+        } else if self._linear > 0. {
+            k / self._linear
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+impl ICullable for PointLight {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        let r = self.radius();
+        if r.is_finite() {
+            BoundingVolume::Sphere { _center: self._pos, _radius: r }
+        } else {
+            BoundingVolume::Unbounded
+        }
+    }
+}
+
+///camera view matrix from the ModelViewMatrix of the camera uniform group
+pub fn view_matrix( uc: & renderdevice_gl::RenderUniformCollection ) -> Result< Vec< f32 >, & 'static str > {
+    let mv = uc.get_group( UNIFORM_GROUP_CAMERA ).ok().and_then( |g| {
+        g.into_iter().find( |x| x.0 == "ModelViewMatrix\0" && x.2.len() == 16 ).map( |x| x.2 )
+    } );
+    match mv {
+        Some( x ) => Ok( x ),
+        _ => Err( "camera view matrix not found" ),
+    }
+}
+
+///lights moved to eye space with a row major view matrix. lights whose volume lies entirely behind the camera are dropped
+pub fn to_eye_space( lights: & [ PointLight ], view: & [ f32 ] ) -> Vec< PointLight > {
+    lights.iter().filter_map( |l| {
+        let p = l._pos;
+        let mut e = [ 0f32; 3 ];
+        for r in 0..3 {
+            e[r] = view[r*4] * p[0] + view[r*4+1] * p[1] + view[r*4+2] * p[2] + view[r*4+3];
+        }
+        //camera looks down -z
+        if e[2] - l.radius() > 0. {
+            None
+        } else {
+            Some( PointLight { _pos: e, .. *l } )
+        }
+    } ).collect()
+}
+
+///lights split into draws of the lighting pass, at least one so that ambient is applied without lights
+pub fn batches( lights: & [ PointLight ] ) -> Vec< & [ PointLight ] > {
+    if lights.len() == 0 {
+        return vec![ lights ]
+    }
+    lights.chunks( MAX_LIGHTS_PER_PASS ).collect()
+}
+
+///sets the uniform values of a light batch and groups them under uniform_group. ambient is added by the first batch only, as batches are blended additively
+pub fn set_light_uniforms( uc: & mut renderdevice_gl::RenderUniformCollection, shader_program: u64, uniform_group: u64, batch: & [ PointLight ], is_first: bool ) -> Result< (), & 'static str > {
+    if batch.len() > MAX_LIGHTS_PER_PASS {
+        return Err( "light batch too large" )
+    }
+    let ambient = if is_first { AMBIENT } else { [ 0., 0., 0. ] };
+    let mut vals : Vec< ( String, Vec< f32 > ) > = vec![ ( String::from( "LightCount\0" ), vec![ batch.len() as f32 ] ),
+                                                         ( String::from( "Ambient\0" ), ambient.to_vec() ) ];
+    for ( i, l ) in batch.iter().enumerate() {
+        vals.push( ( format!( "lights[{}].Position\0", i ), l._pos.to_vec() ) );
+        vals.push( ( format!( "lights[{}].Color\0", i ), l._color.to_vec() ) );
+        vals.push( ( format!( "lights[{}].Linear\0", i ), vec![ l._linear ] ) );
+        vals.push( ( format!( "lights[{}].Quadratic\0", i ), vec![ l._quadratic ] ) );
+        vals.push( ( format!( "lights[{}].Radius\0", i ), vec![ l.radius().min( f32::MAX ) ] ) );
+    }
+    for &( ref k, ref v ) in vals.iter() {
+        uc.set_uniform_f( shader_program, k.as_str(), UniformType::VEC, &v[..] );
+    }
+    uc.set_group( shader_program, uniform_group, vals.into_iter().map( |x| x.0 ).collect() )
+}
+
+///two triangles covering the screen in normalized device coordinates, drawn by lighting passes
+pub fn screen_quad() -> i_component::ComponentRenderBuffer {
+    let pos = vec![ -1f32, -1., 0.,   1., -1., 0.,   1., 1., 0.,
+                    -1.,   -1., 0.,   1.,  1., 0.,  -1., 1., 0. ];
+    let tc = vec![ 0f32, 0.,   1., 0.,   1., 1.,
+                   0.,   0.,   1., 1.,   0., 1. ];
+    let normal = ( 0..6 ).flat_map( |_| vec![ 0f32, 0., 1. ] ).collect();
+    let mut data = HashMap::new();
+    data.insert( i_renderobj::BuffDataType::POS, pos );
+    data.insert( i_renderobj::BuffDataType::NORMAL, normal );
+    data.insert( i_renderobj::BuffDataType::TC, tc );
+    i_component::ComponentRenderBuffer {
+        _data_dict: data,
+    }
+}
+
+///loads the geometry and lighting shader programs and switches to the deferred render graph. shader programs are indexed in load order, so first_program is the number of programs loaded before these events
+pub fn events_pipeline( geometry: Vec< ( String, ShaderType ) >, lighting: Vec< ( String, ShaderType ) >, first_program: u64, forward_program: u64, light_group: u64 ) -> Vec< Event > {
+    vec![ Event::LoadShader( geometry ),
+          Event::LoadShader( lighting ),
+          Event::SetRenderGraph( RenderGraph::deferred( first_program, first_program + 1, forward_program, light_group ) ) ]
+}
@@ -0,0 +1,160 @@
+///level of detail chains built by mesh simplification, selected by camera distance or projected size
+
+use implement::math::simplify::{ self, SimplifyConfig };
+use implement::render::camera::Cam;
+use implement::render::mesh::Mesh;
+use implement::render::culling::{ self, ICullable, BoundingVolume };
+
+#[derive(Debug, Clone)]
+pub struct LodLevel {
+    pub _batch_pos: Vec< f32 >,
+    pub _batch_normal: Vec< f32 >,
+    pub _batch_tc: Vec< f32 >,
+    pub _tri_count: usize,
+    ///quadric error with respect to the full detail mesh
+    pub _error: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LodConfig {
+    ///maximum number of levels including the full detail mesh
+    pub _levels: usize,
+    ///fraction of triangles kept from one level to the next
+    pub _reduction: f64,
+    ///no level is reduced below this triangle count
+    pub _min_tris: usize,
+    ///seam and boundary weights used for every level
+    pub _simplify: SimplifyConfig,
+}
+
+impl Default for LodConfig {
+    fn default() -> LodConfig {
+        LodConfig {
+            _levels: 4,
+            _reduction: 0.5,
+            _min_tris: 32,
+            _simplify: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LodPolicy {
+    ///level i is used up to distance _thresholds[i] from the camera, the next level beyond it
+    Distance( Vec< f32 > ),
+    ///level i is used while the bounding sphere covers at least _thresholds[i] of the viewport height
+    ScreenSize( Vec< f32 > ),
+}
+
+///progressively coarser versions of a mesh, level 0 being the full detail mesh
+#[derive(Debug, Clone)]
+pub struct LodChain {
+    pub _levels: Vec< LodLevel >,
+    pub _center: [f32;3],
+    pub _radius: f32,
+}
+
+impl LodChain {
+    ///each level is simplified from the full detail mesh, stopping early once simplification no longer reduces the mesh
+    pub fn build( pos: &[ f32 ], normal: &[ f32 ], tc: &[ f32 ], config: & LodConfig ) -> Result< LodChain, & 'static str > {
+        if config._levels == 0 {
+            return Err( &"lod chain requires at least 1 level" )
+        }
+        if config._reduction <= 0. || config._reduction >= 1. {
+            return Err( &"lod reduction not in (0,1)" )
+        }
+        let ( center, radius ) = match culling::bound_positions( pos ).aabb() {
+            Some( ( l, u ) ) => {
+                let h = [ ( u[0] - l[0] ) * 0.5, ( u[1] - l[1] ) * 0.5, ( u[2] - l[2] ) * 0.5 ];
+                ( [ l[0] + h[0], l[1] + h[1], l[2] + h[2] ], ( h[0] * h[0] + h[1] * h[1] + h[2] * h[2] ).sqrt() )
+            },
+            None => return Err( &"lod chain requires geometry" ),
+        };
+        let mut levels = vec![ LodLevel {
+            _tri_count: pos.len() / 9,
+            _batch_pos: pos.to_vec(),
+            _batch_normal: normal.to_vec(),
+            _batch_tc: tc.to_vec(),
+            _error: 0.,
+        } ];
+        let mut target = ( pos.len() / 9 ) as f64;
+        for _ in 1..config._levels {
+            let prev = levels.last().unwrap()._tri_count;
+            if prev <= config._min_tris {
+                break;
+            }
+            target *= config._reduction;
+            let c = SimplifyConfig { _target_tris: ( target as usize ).max( config._min_tris ), .. config._simplify.clone() };
+            let r = simplify::simplify( pos, normal, tc, & c )?;
+            if r._tri_count >= prev {
+                break;
+            }
+            levels.push( LodLevel {
+                _batch_pos: r._batch_pos,
+                _batch_normal: r._batch_normal,
+                _batch_tc: r._batch_tc,
+                _tri_count: r._tri_count,
+                _error: r._error,
+            } );
+        }
+        Ok( LodChain {
+            _levels: levels,
+            _center: center,
+            _radius: radius,
+        } )
+    }
+
+    pub fn len( & self ) -> usize {
+        self._levels.len()
+    }
+
+    pub fn is_empty( & self ) -> bool {
+        self._levels.is_empty()
+    }
+
+    pub fn distance( & self, cam: & Cam ) -> f32 {
+        let d = [ self._center[0] - cam._pos[0], self._center[1] - cam._pos[1], self._center[2] - cam._pos[2] ];
+        ( d[0] * d[0] + d[1] * d[1] + d[2] * d[2] ).sqrt()
+    }
+
+    ///fraction of the viewport height covered by the bounding sphere
+    pub fn screen_size( & self, cam: & Cam ) -> f32 {
+        let d = self.distance( cam );
+        if d <= self._radius {
+            return ::std::f32::INFINITY
+        }
+        let half_tan = ( cam._fov * ::std::f32::consts::PI / 360. ).tan();
+        self._radius / ( d * half_tan )
+    }
+
+    ///level to render for the camera
+    pub fn select( & self, cam: & Cam, policy: & LodPolicy ) -> usize {
+        let level = match *policy {
+            LodPolicy::Distance( ref t ) => {
+                let d = self.distance( cam );
+                t.iter().take_while( |&&x| d > x ).count()
+            },
+            LodPolicy::ScreenSize( ref t ) => {
+                let s = self.screen_size( cam );
+                t.iter().take_while( |&&x| s < x ).count()
+            },
+        };
+        level.min( self._levels.len() - 1 )
+    }
+
+    ///renderable mesh of a level
+    pub fn mesh( & self, level: usize, id: u64 ) -> Mesh {
+        let l = & self._levels[ level.min( self._levels.len() - 1 ) ];
+        let mut m = Mesh::init( id );
+        m._batch_pos = l._batch_pos.clone();
+        m._batch_normal = l._batch_normal.clone();
+        m._batch_tc = l._batch_tc.clone();
+        m
+    }
+}
+
+impl ICullable for LodChain {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        BoundingVolume::Sphere { _center: self._center, _radius: self._radius }
+    }
+}
@@ -2,6 +2,7 @@ extern crate pretty_env_logger;
 extern crate mazth;
 
 // use self::mazth::mat::{ Mat3x1, Mat2x1 };
+use self::mazth::mat::{ Mat4, Mat4x1 };
 use std::collections::HashMap;
 use std::vec::Vec;
 use std::any::Any;
@@ -20,6 +21,8 @@ pub struct Mesh {
     pub _batch_pos: Vec< f32 >,
     pub _batch_normal: Vec< f32 >,
     pub _batch_tc: Vec< f32 >,
+    ///optional per range texture bindings, in vertex units
+    pub _submesh_textures: Vec< i_component::TextureRange >,
     // _xform: Mat4< f32 >,
 }
 
@@ -33,10 +36,40 @@ impl Mesh {
             _batch_pos: vec![],
             _batch_normal: vec![],
             _batch_tc: vec![],
+            _submesh_textures: vec![],
         }
     }
 }
 
+impl Mesh {
+    ///transforms positions by a row major transform and normals by its inverse transpose, eg: attaching to a socket
+    pub fn apply_xform( & mut self, xform: & Mat4< f32 > ) -> Result< (), & 'static str > {
+        if self._batch_pos.len() != self._batch_normal.len() {
+            return Err( &"inconsistent length for position, normal data" )
+        }
+        let normal_xform = xform.submat_mat3().inverse().map_err( |_| "transform not invertible" )?.transpose();
+        for i in 0..self._batch_pos.len() / 3 {
+            let p = xform.mul_mat4x1( & Mat4x1 { _val: [ self._batch_pos[ i*3 ],
+                                                          self._batch_pos[ i*3 + 1 ],
+                                                          self._batch_pos[ i*3 + 2 ],
+                                                          1f32 ] } ).unwrap();
+            let n = [ self._batch_normal[ i*3 ], self._batch_normal[ i*3 + 1 ], self._batch_normal[ i*3 + 2 ] ];
+            let mut n_out = [ 0f32; 3 ];
+            for r in 0..3 {
+                for c in 0..3 {
+                    n_out[r] += normal_xform._val[ r*3 + c ] * n[c];
+                }
+            }
+            let l = ( n_out[0] * n_out[0] + n_out[1] * n_out[1] + n_out[2] * n_out[2] ).sqrt();
+            for h in 0..3 {
+                self._batch_pos[ i*3 + h ] = p[h];
+                self._batch_normal[ i*3 + h ] = if l > 0. { n_out[h] / l } else { n_out[h] };
+            }
+        }
+        Ok( () )
+    }
+}
+
 impl i_ele::IObjImpl for Mesh {
     fn as_any( & self ) -> & Any {
         self
@@ -91,6 +124,13 @@ impl i_ele::IObjImpl for Mesh {
             //     temp
             // };
 
+            //texture ranges precede the buffer they apply to
+            if self._submesh_textures.len() > 0 {
+                let mut ranges = vec![];
+                mem::swap( & mut ranges, & mut self._submesh_textures );
+                components.push( Box::new( i_component::ComponentRenderTextureRanges { _ranges: ranges } ) );
+            }
+
             let mut pos = vec![];
             let mut normal = vec![];
             let mut tc = vec![];
@@ -12,3 +12,11 @@ pub mod primitive;
 pub mod render_commands;
 pub mod texture;
 pub mod texture_collection;
+pub mod culling;
+pub mod picking;
+pub mod lod;
+pub mod pathtracer;
+pub mod renderer_sw;
+pub mod renderer_record;
+pub mod render_graph;
+pub mod deferred;
@@ -0,0 +1,533 @@
+///offline cpu path tracer used as a reference for the rasterized renderers
+
+extern crate rand;
+
+use std::f64;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+
+use self::rand::{ Rng, SeedableRng, XorShiftRng };
+
+use interface::i_md5;
+use interface::i_wavefront;
+use interface::i_wavefront::mtl;
+
+use implement::file::ppm;
+use implement::math::bvh::Bvh;
+use implement::math::vec3::{ add, sub, mul, scale, dot, cross, normalize };
+use implement::math::surf_interact::{ Ray, SurfInteract, Primitive };
+use implement::render::camera::Cam;
+use implement::render::light::{ LightAdsPoint, LightAdsSpot, LightAdsDir };
+use implement::render::mesh::Mesh;
+
+///mtl style surface description: lambertian diffuse plus a normalized phong lobe, and emission
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Material {
+    pub _kd: [f64;3],
+    pub _ks: [f64;3],
+    ///phong exponent
+    pub _ns: f64,
+    ///emitted radiance from the front face
+    pub _ke: [f64;3],
+}
+
+impl Default for Material {
+    fn default() -> Material {
+        Material {
+            _kd: [ 0.8, 0.8, 0.8 ],
+            _ks: [ 0., 0., 0. ],
+            _ns: 1.,
+            _ke: [ 0., 0., 0. ],
+        }
+    }
+}
+
+impl Material {
+    ///maps an mtl entry, pbr roughness overrides Ns and metallic moves the diffuse albedo into the specular lobe
+    pub fn from_mtl( m: & mtl::Material ) -> Material {
+        let kd = to_f64( m._kd );
+        let mut ret = Material {
+            _kd: kd,
+            _ks: to_f64( m._ks ),
+            _ns: ( m._ns as f64 ).max( 0. ),
+            _ke: to_f64( m._ke ),
+        };
+        if let Some( pr ) = m._pr {
+            //phong exponent matching a ggx lobe of alpha = roughness^2
+            let alpha = ( pr as f64 ).max( 0.01 ).min( 1. ).powi( 2 );
+            ret._ns = 2. / ( alpha * alpha ) - 2.;
+        }
+        if let Some( pm ) = m._pm {
+            let pm = ( pm as f64 ).max( 0. ).min( 1. );
+            ret._kd = scale( kd, 1. - pm );
+            ret._ks = add( scale( ret._ks, 1. - pm ), scale( kd, pm ) );
+        }
+        ret
+    }
+    ///brdf for light arriving along wi and leaving along wo
+    fn eval( & self, wo: [f64;3], wi: [f64;3], n: [f64;3] ) -> [f64;3] {
+        let c = dot( wi, n );
+        if c <= 0. {
+            return [ 0., 0., 0. ]
+        }
+        let diff = scale( self._kd, f64::consts::FRAC_1_PI );
+        let spec = self.lobe( wo, wi, n ) * ( self._ns + 2. ) * 0.5 * f64::consts::FRAC_1_PI;
+        add( diff, scale( self._ks, spec ) )
+    }
+    fn pdf( & self, wo: [f64;3], wi: [f64;3], n: [f64;3] ) -> f64 {
+        let c = dot( wi, n );
+        if c <= 0. {
+            return 0.
+        }
+        let pd = self.diffuse_prob();
+        pd * c * f64::consts::FRAC_1_PI + ( 1. - pd ) * self.lobe( wo, wi, n ) * ( self._ns + 1. ) * 0.5 * f64::consts::FRAC_1_PI
+    }
+    fn lobe( & self, wo: [f64;3], wi: [f64;3], n: [f64;3] ) -> f64 {
+        dot( reflect( wo, n ), wi ).max( 0. ).powf( self._ns )
+    }
+    fn diffuse_prob( & self ) -> f64 {
+        let ( d, s ) = ( luminance( self._kd ), luminance( self._ks ) );
+        if d + s <= 0. { 1. } else { d / ( d + s ) }
+    }
+    fn sample< R: Rng >( & self, wo: [f64;3], n: [f64;3], rng: & mut R ) -> [f64;3] {
+        let ( r0, r1, r2 ) = ( rng.next_f64(), rng.next_f64(), rng.next_f64() );
+        let phi = 2. * f64::consts::PI * r1;
+        if r0 < self.diffuse_prob() {
+            //cosine weighted hemisphere
+            let r = r2.sqrt();
+            to_world( [ r * phi.cos(), r * phi.sin(), ( 1. - r2 ).sqrt() ], n )
+        } else {
+            let cos_a = r2.powf( 1. / ( self._ns + 1. ) );
+            let sin_a = ( 1. - cos_a * cos_a ).max( 0. ).sqrt();
+            to_world( [ sin_a * phi.cos(), sin_a * phi.sin(), cos_a ], reflect( wo, n ) )
+        }
+    }
+}
+
+///delta lights, intensities in radiance units at unit distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PtLight {
+    Point { _pos: [f64;3], _intensity: [f64;3] },
+    ///light within the cone around the direction, cutoff as cosine of the half angle
+    Spot { _pos: [f64;3], _dir: [f64;3], _cos_cutoff: f64, _intensity: [f64;3] },
+    ///direction light travels
+    Dir { _dir: [f64;3], _radiance: [f64;3] },
+}
+
+impl PtLight {
+    pub fn from_point( l: & LightAdsPoint ) -> PtLight {
+        PtLight::Point { _pos: to_f64( l._pos._val ), _intensity: to_f64( l._ads_val_diff._val ) }
+    }
+    ///spot direction is taken from the light's view transform
+    pub fn from_spot( l: & LightAdsSpot ) -> PtLight {
+        let v = & l._light_view_xform;
+        let back = [ v.index( 2, 0 ) as f64, v.index( 2, 1 ) as f64, v.index( 2, 2 ) as f64 ];
+        PtLight::Spot { _pos: to_f64( l._pos._val ),
+                        _dir: normalize( scale( back, -1. ) ),
+                        _cos_cutoff: ( l._fov as f64 * f64::consts::PI / 360. ).cos(),
+                        _intensity: to_f64( l._ads_val_diff._val ) }
+    }
+    pub fn from_dir( l: & LightAdsDir ) -> PtLight {
+        PtLight::Dir { _dir: normalize( to_f64( l._dir._val ) ), _radiance: to_f64( l._ads_val_diff._val ) }
+    }
+    ///unit direction towards the light, distance to it and incident radiance at p
+    fn incident( & self, p: [f64;3] ) -> Option< ( [f64;3], f64, [f64;3] ) > {
+        match *self {
+            PtLight::Point { _pos, _intensity } => {
+                let d = sub( _pos, p );
+                let l2 = dot( d, d );
+                if l2 <= 0. {
+                    return None
+                }
+                Some( ( scale( d, 1. / l2.sqrt() ), l2.sqrt(), scale( _intensity, 1. / l2 ) ) )
+            },
+            PtLight::Spot { _pos, _dir, _cos_cutoff, _intensity } => {
+                let d = sub( _pos, p );
+                let l2 = dot( d, d );
+                if l2 <= 0. {
+                    return None
+                }
+                let wi = scale( d, 1. / l2.sqrt() );
+                if -dot( wi, _dir ) < _cos_cutoff {
+                    return None
+                }
+                Some( ( wi, l2.sqrt(), scale( _intensity, 1. / l2 ) ) )
+            },
+            PtLight::Dir { _dir, _radiance } => {
+                Some( ( scale( _dir, -1. ), f64::INFINITY, _radiance ) )
+            },
+        }
+    }
+}
+
+///triangles with materials and lights, call build before rendering
+#[derive(Debug, Clone)]
+pub struct Scene {
+    pub _tris: Vec< Primitive >,
+    ///material index per triangle
+    pub _tri_material: Vec< usize >,
+    pub _materials: Vec< Material >,
+    pub _lights: Vec< PtLight >,
+    ///radiance of rays leaving the scene
+    pub _ambient: [f64;3],
+    pub _bvh: Bvh,
+}
+
+impl Default for Scene {
+    fn default() -> Scene {
+        Scene {
+            _tris: vec![],
+            _tri_material: vec![],
+            _materials: vec![],
+            _lights: vec![],
+            _ambient: [ 0., 0., 0. ],
+            _bvh: Bvh::init( &[], 4 ),
+        }
+    }
+}
+
+impl Scene {
+    pub fn init() -> Scene {
+        Default::default()
+    }
+    pub fn add_material( & mut self, m: Material ) -> usize {
+        self._materials.push( m );
+        self._materials.len() - 1
+    }
+    ///adds parsed mtl entries, returns the material index by mtl name
+    pub fn add_mtl( & mut self, materials: &[ mtl::Material ] ) -> HashMap< String, usize > {
+        materials.iter().map( |m| ( m._name.clone(), self.add_material( Material::from_mtl( m ) ) ) ).collect()
+    }
+    pub fn add_light( & mut self, l: PtLight ) {
+        self._lights.push( l );
+    }
+    ///triangle soup with 3 floats per position and optional normals of the same length
+    pub fn add_triangles( & mut self, pos: &[ f32 ], normal: &[ f32 ], material: usize ) -> Result< (), & 'static str > {
+        if pos.len() % 9 != 0 {
+            return Err( "path tracer positions not a multiple of 9." )
+        }
+        if !normal.is_empty() && normal.len() != pos.len() {
+            return Err( "path tracer normal count mismatch." )
+        }
+        if material >= self._materials.len() {
+            return Err( "path tracer material index invalid." )
+        }
+        for t in 0..pos.len() / 9 {
+            let v = |a: &[ f32 ], i: usize| { let o = t * 9 + i * 3; [ a[o] as f64, a[o+1] as f64, a[o+2] as f64 ] };
+            let n = if normal.is_empty() { None } else { Some( [ v( normal, 0 ), v( normal, 1 ), v( normal, 2 ) ] ) };
+            self._tris.push( Primitive::Triangle { _v: [ v( pos, 0 ), v( pos, 1 ), v( pos, 2 ) ], _n: n, _uv: None } );
+            self._tri_material.push( material );
+        }
+        Ok( () )
+    }
+    pub fn add_mesh( & mut self, m: & Mesh, material: usize ) -> Result< (), & 'static str > {
+        self.add_triangles( &m._batch_pos[..], &m._batch_normal[..], material )
+    }
+    ///usemtl groups use the material mapped from their mtl name, or the default material
+    pub fn add_wavefront( & mut self, c: & i_wavefront::compute::ComputeCollection, materials: & HashMap< String, usize >, default_material: usize ) -> Result< (), & 'static str > {
+        for s in c._submeshes.iter() {
+            let ( a, b ) = ( s._vert_offset * 3, ( s._vert_offset + s._vert_count ) * 3 );
+            if b > c._batch_vert.len() || b > c._batch_normal.len() {
+                return Err( "path tracer submesh range invalid." )
+            }
+            let m = s._material.as_ref().and_then( |x| materials.get( x ) ).cloned().unwrap_or( default_material );
+            self.add_triangles( &c._batch_vert[ a..b ], &c._batch_normal[ a..b ], m )?;
+        }
+        Ok( () )
+    }
+    ///submeshes use the material mapped from their shader name, or the default material
+    pub fn add_md5( & mut self, c: & i_md5::compute::ComputeCollection, materials: & HashMap< String, usize >, default_material: usize ) -> Result< (), & 'static str > {
+        for s in c._submeshes.iter() {
+            let ( a, b ) = ( s._vert_offset * 3, ( s._vert_offset + s._vert_count ) * 3 );
+            if b > c._batch_vert.len() || b > c._batch_normal.len() {
+                return Err( "path tracer submesh range invalid." )
+            }
+            let m = materials.get( &s._shader ).cloned().unwrap_or( default_material );
+            self.add_triangles( &c._batch_vert[ a..b ], &c._batch_normal[ a..b ], m )?;
+        }
+        Ok( () )
+    }
+    ///builds the acceleration structure over the current triangles
+    pub fn build( & mut self ) {
+        let bounds : Vec< ( [f32;3], [f32;3] ) > = self._tris.iter().map( |t| {
+            let ( l, u ) = t.bounds().expect( "triangle bounds" );
+            //pad so that flat triangles keep a volume
+            let pad = 1e-4 * ( 1. + ( 0..3 ).fold( 0f64, |acc, i| acc.max( u[i] - l[i] ) ) );
+            ( [ ( l[0] - pad ) as f32, ( l[1] - pad ) as f32, ( l[2] - pad ) as f32 ],
+              [ ( u[0] + pad ) as f32, ( u[1] + pad ) as f32, ( u[2] + pad ) as f32 ] )
+        } ).collect();
+        self._bvh = Bvh::init( &bounds[..], 4 );
+    }
+    pub fn is_built( & self ) -> bool {
+        self._bvh.len() == self._tris.len()
+    }
+    pub fn intersect( & self, ray: & Ray ) -> Option< SurfInteract > {
+        let mut best : Option< SurfInteract > = None;
+        let tris = &self._tris;
+        self._bvh.ray_first_hit( to_f32( ray._origin._val ), to_f32( ray._dir._val ), clamp_f32( ray._t_max ), |p, _| {
+            let t_max = best.map( |b| b._t ).unwrap_or( ray._t_max );
+            match tris[p].intersect( & ray.with_range( ray._t_min, t_max ), p as u64 ) {
+                Some( h ) => {
+                    best = Some( h );
+                    Some( h._t as f32 )
+                },
+                _ => None,
+            }
+        } );
+        best
+    }
+    pub fn occluded( & self, ray: & Ray ) -> bool {
+        let tris = &self._tris;
+        self._bvh.ray_any_hit( to_f32( ray._origin._val ), to_f32( ray._dir._val ), clamp_f32( ray._t_max ), |p, _| {
+            tris[p].intersect( ray, p as u64 ).map( |h| h._t as f32 )
+        } ).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathTracerConfig {
+    pub _width: usize,
+    pub _height: usize,
+    ///number of surface interactions per path
+    pub _max_depth: u32,
+    pub _seed: u64,
+    pub _threads: usize,
+}
+
+impl Default for PathTracerConfig {
+    fn default() -> PathTracerConfig {
+        PathTracerConfig {
+            _width: 320,
+            _height: 240,
+            _max_depth: 5,
+            _seed: 0,
+            _threads: 4,
+        }
+    }
+}
+
+///progressive accumulation of radiance samples. each sample is seeded from the seed, pixel and sample index, so
+///the image only depends on the seed and the total sample count, not on the thread count or the pass schedule
+#[derive(Debug, Clone)]
+pub struct PathTracer {
+    pub _config: PathTracerConfig,
+    ///radiance sums, rgb per pixel from the top left
+    pub _accum: Vec< f64 >,
+    pub _samples: u32,
+}
+
+impl PathTracer {
+    pub fn init( config: PathTracerConfig ) -> Result< PathTracer, & 'static str > {
+        if config._width == 0 || config._height == 0 {
+            return Err( "path tracer image size invalid." )
+        }
+        Ok( PathTracer {
+            _config: config,
+            _accum: vec![ 0.; config._width * config._height * 3 ],
+            _samples: 0,
+        } )
+    }
+    pub fn samples( & self ) -> u32 {
+        self._samples
+    }
+    pub fn reset( & mut self ) {
+        for x in self._accum.iter_mut() {
+            *x = 0.;
+        }
+        self._samples = 0;
+    }
+    ///adds spp samples per pixel, rows are split across worker threads
+    pub fn render_pass( & mut self, scene: & Arc< Scene >, cam: & Cam, spp: u32 ) -> Result< (), & 'static str > {
+        if !scene.is_built() {
+            return Err( "path tracer scene not built." )
+        }
+        let c = self._config;
+        let view = View::init( cam, c._width, c._height );
+        let threads = c._threads.max( 1 ).min( c._height );
+        let row_len = c._width * 3;
+        let mut handles = vec![];
+        for i in 0..threads {
+            let ( row_begin, row_end ) = ( c._height * i / threads, c._height * ( i + 1 ) / threads );
+            let mut accum = self._accum[ row_begin * row_len..row_end * row_len ].to_vec();
+            let scene = scene.clone();
+            let sample_begin = self._samples;
+            handles.push( ( row_begin, thread::spawn( move || {
+                for y in row_begin..row_end {
+                    for x in 0..c._width {
+                        let pixel = y * c._width + x;
+                        let o = ( y - row_begin ) * row_len + x * 3;
+                        for s in sample_begin..sample_begin + spp {
+                            let mut rng = pixel_rng( c._seed, pixel as u64, s as u64 );
+                            let ( jx, jy ) = ( rng.next_f64(), rng.next_f64() );
+                            let l = radiance( & scene, & view.ray( x as f64 + jx, y as f64 + jy ), c._max_depth, & mut rng );
+                            for k in 0..3 {
+                                accum[ o + k ] += l[k];
+                            }
+                        }
+                    }
+                }
+                accum
+            } ) ) );
+        }
+        for ( row_begin, h ) in handles.into_iter() {
+            let rows = match h.join() {
+                Ok( x ) => x,
+                _ => { return Err( "path tracer worker failed." ) },
+            };
+            let o = row_begin * row_len;
+            self._accum[ o..o + rows.len() ].copy_from_slice( &rows[..] );
+        }
+        self._samples += spp;
+        Ok( () )
+    }
+    ///mean radiance per pixel
+    pub fn image( & self ) -> Vec< f64 > {
+        let s = if self._samples == 0 { 0. } else { 1. / self._samples as f64 };
+        self._accum.iter().map( |x| x * s ).collect()
+    }
+    ///clamped and gamma encoded 8 bit rgb
+    pub fn to_rgb8( & self ) -> Vec< u8 > {
+        self.image().iter().map( |x| ( x.max( 0. ).min( 1. ).powf( 1. / 2.2 ) * 255. ).round() as u8 ).collect()
+    }
+    pub fn write_ppm( & self, file_path: & str ) -> Result< (), & 'static str > {
+        ppm::write( file_path, self._config._width as u64, self._config._height as u64, & self.to_rgb8() )
+    }
+}
+
+///camera basis for generating primary rays
+#[derive(Debug, Clone, Copy)]
+struct View {
+    _pos: [f64;3],
+    _forward: [f64;3],
+    _right: [f64;3],
+    _up: [f64;3],
+    _half_h: f64,
+    _half_w: f64,
+    _width: f64,
+    _height: f64,
+}
+
+impl View {
+    fn init( cam: & Cam, width: usize, height: usize ) -> View {
+        let pos = to_f64( cam._pos._val );
+        let forward = normalize( sub( to_f64( cam._focus._val ), pos ) );
+        let right = normalize( cross( forward, to_f64( cam._up._val ) ) );
+        let half_h = ( cam._fov as f64 * f64::consts::PI / 360. ).tan();
+        View {
+            _pos: pos,
+            _forward: forward,
+            _right: right,
+            _up: cross( right, forward ),
+            _half_h: half_h,
+            _half_w: half_h * cam._aspect as f64,
+            _width: width as f64,
+            _height: height as f64,
+        }
+    }
+    ///ray through a position in pixels from the top left
+    fn ray( & self, x: f64, y: f64 ) -> Ray {
+        let sx = ( 2. * x / self._width - 1. ) * self._half_w;
+        let sy = ( 1. - 2. * y / self._height ) * self._half_h;
+        let d = add( self._forward, add( scale( self._right, sx ), scale( self._up, sy ) ) );
+        Ray::init( self._pos, normalize( d ) )
+    }
+}
+
+fn radiance< R: Rng >( scene: & Scene, primary: & Ray, max_depth: u32, rng: & mut R ) -> [f64;3] {
+    let mut ray = *primary;
+    let mut beta = [ 1., 1., 1. ];
+    let mut l = [ 0., 0., 0. ];
+    for depth in 0..max_depth {
+        let hit = match scene.intersect( & ray ) {
+            Some( h ) => h,
+            _ => {
+                l = add( l, mul( beta, scene._ambient ) );
+                break
+            },
+        };
+        let m = & scene._materials[ scene._tri_material[ hit._prim_id as usize ] ];
+        if hit._front_face {
+            l = add( l, mul( beta, m._ke ) );
+        }
+        let wo = scale( ray._dir._val, -1. );
+        let ng = hit.normal_facing();
+        let ns = if hit._front_face { hit._normal_shading._val } else { scale( hit._normal_shading._val, -1. ) };
+
+        //direct lighting from delta lights
+        for light in scene._lights.iter() {
+            if let Some( ( wi, dist, li ) ) = light.incident( hit._point._val ) {
+                if dot( wi, ng ) <= 0. || dot( wi, ns ) <= 0. {
+                    continue
+                }
+                let shadow = hit.spawn_ray( wi );
+                if !scene.occluded( & shadow.with_range( 0., dist * ( 1. - 1e-6 ) ) ) {
+                    l = add( l, mul( beta, scale( mul( m.eval( wo, wi, ns ), li ), dot( wi, ns ) ) ) );
+                }
+            }
+        }
+        if depth + 1 == max_depth {
+            break
+        }
+
+        //continue the path by sampling the surface
+        let wi = m.sample( wo, ns, rng );
+        let pdf = m.pdf( wo, wi, ns );
+        if pdf <= 0. || dot( wi, ng ) <= 0. {
+            break
+        }
+        beta = mul( beta, scale( m.eval( wo, wi, ns ), dot( wi, ns ) / pdf ) );
+        if depth >= 3 {
+            //russian roulette
+            let q = beta[0].max( beta[1] ).max( beta[2] ).min( 0.95 );
+            if rng.next_f64() >= q {
+                break
+            }
+            beta = scale( beta, 1. / q );
+        }
+        ray = hit.spawn_ray( wi );
+    }
+    l
+}
+
+fn pixel_rng( seed: u64, pixel: u64, sample: u64 ) -> XorShiftRng {
+    let a = splitmix( seed ^ splitmix( pixel ^ splitmix( sample ) ) );
+    let b = splitmix( a );
+    //xorshift requires a non zero seed
+    XorShiftRng::from_seed( [ a as u32 | 1, ( a >> 32 ) as u32, b as u32, ( b >> 32 ) as u32 ] )
+}
+
+fn splitmix( x: u64 ) -> u64 {
+    let mut z = x.wrapping_add( 0x9e3779b97f4a7c15 );
+    z = ( z ^ ( z >> 30 ) ).wrapping_mul( 0xbf58476d1ce4e5b9 );
+    z = ( z ^ ( z >> 27 ) ).wrapping_mul( 0x94d049bb133111eb );
+    z ^ ( z >> 31 )
+}
+
+///local direction about the z axis rotated to be about n
+fn to_world( v: [f64;3], n: [f64;3] ) -> [f64;3] {
+    let a = if n[0].abs() > 0.9 { [ 0., 1., 0. ] } else { [ 1., 0., 0. ] };
+    let t = normalize( cross( a, n ) );
+    let b = cross( n, t );
+    add( add( scale( t, v[0] ), scale( b, v[1] ) ), scale( n, v[2] ) )
+}
+
+fn reflect( wo: [f64;3], n: [f64;3] ) -> [f64;3] {
+    sub( scale( n, 2. * dot( n, wo ) ), wo )
+}
+
+fn luminance( c: [f64;3] ) -> f64 {
+    0.2126 * c[0] + 0.7152 * c[1] + 0.0722 * c[2]
+}
+
+fn clamp_f32( t: f64 ) -> f32 {
+    if t >= ::std::f32::MAX as f64 { ::std::f32::INFINITY } else { t as f32 }
+}
+
+fn to_f64( a: [f32;3] ) -> [f64;3] {
+    [ a[0] as f64, a[1] as f64, a[2] as f64 ]
+}
+
+fn to_f32( a: [f64;3] ) -> [f32;3] {
+    [ a[0] as f32, a[1] as f32, a[2] as f32 ]
+}
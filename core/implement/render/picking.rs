@@ -0,0 +1,172 @@
+extern crate mazth;
+
+use std::cmp::Ordering;
+
+use self::mazth::mat::Mat4x1;
+
+use implement::math::util;
+use implement::math::intersect::to_f64;
+use implement::math::surf_interact;
+use implement::math::surf_interact::{ Ray, SurfInteract };
+use implement::math::bvh::TriMeshBvh;
+use implement::render::camera::Cam;
+use implement::render::culling::{ ICullable, BoundingVolume };
+
+///world space ray with unit direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickRay {
+    pub _origin: [f32;3],
+    pub _dir: [f32;3],
+}
+
+impl PickRay {
+    pub fn point_at( & self, t: f32 ) -> [f32;3] {
+        [ self._origin[0] + self._dir[0] * t,
+          self._origin[1] + self._dir[1] * t,
+          self._origin[2] + self._dir[2] * t ]
+    }
+}
+
+///ray through a window position given in pixels from the top left, starting on the near plane
+pub fn screen_ray( cam: & Cam, x: f32, y: f32, width: f32, height: f32 ) -> PickRay {
+    let ndc_x = 2. * x / width - 1.;
+    let ndc_y = 1. - 2. * y / height;
+    let proj_inv = util::perspective_inverse( cam._fov, cam._aspect, cam._near, cam._far );
+    let view_inv = util::look_at_inverse( cam._pos, cam._focus, cam._up );
+    let unproject = |z: f32| {
+        let v = proj_inv.mul_mat4x1( & Mat4x1 { _val: [ ndc_x, ndc_y, z, 1. ] } ).unwrap();
+        let v = Mat4x1 { _val: [ v[0] / v[3], v[1] / v[3], v[2] / v[3], 1. ] };
+        let w = view_inv.mul_mat4x1( & v ).unwrap();
+        [ w[0], w[1], w[2] ]
+    };
+    let near = unproject( -1. );
+    let far = unproject( 1. );
+    let d = [ far[0] - near[0], far[1] - near[1], far[2] - near[2] ];
+    let l = ( d[0] * d[0] + d[1] * d[1] + d[2] * d[2] ).sqrt();
+    PickRay {
+        _origin: near,
+        _dir: [ d[0] / l, d[1] / l, d[2] / l ],
+    }
+}
+
+///selectable geometry in world space
+pub enum PickShape {
+    Sphere { _center: [f32;3], _radius: f32 },
+    Aabb { _lower: [f32;3], _upper: [f32;3] },
+    ///triangle soup accelerated by a bvh
+    Mesh( TriMeshBvh ),
+}
+
+impl PickShape {
+    pub fn from_bounding_volume( b: & BoundingVolume ) -> Option< PickShape > {
+        match *b {
+            BoundingVolume::Sphere { _center, _radius } => Some( PickShape::Sphere { _center: _center, _radius: _radius } ),
+            BoundingVolume::Aabb { _lower, _upper } => Some( PickShape::Aabb { _lower: _lower, _upper: _upper } ),
+            BoundingVolume::Unbounded => None,
+        }
+    }
+
+    ///nearest hit in front of the ray origin, hits from inside a sphere or box report the exit point
+    pub fn intersect( & self, ray: & PickRay, id: u64 ) -> Option< PickHit > {
+        let r = Ray::init( to_f64( ray._origin ), to_f64( ray._dir ) );
+        match *self {
+            PickShape::Sphere { _center, _radius } => {
+                let s = surf_interact::ray_sphere( & r, to_f64( _center ), _radius as f64, id )?;
+                Some( pick_hit( & s, s._normal_geom._val, None ) )
+            },
+            PickShape::Aabb { _lower, _upper } => {
+                let s = surf_interact::ray_aabb( & r, to_f64( _lower ), to_f64( _upper ), id )?;
+                Some( pick_hit( & s, s._normal_geom._val, None ) )
+            },
+            PickShape::Mesh( ref m ) => {
+                let h = m.ray_first_hit( ray._origin, ray._dir, ::std::f32::INFINITY )?;
+                let v = m.tri( h._tri );
+                let s = surf_interact::ray_triangle( & r, [ to_f64( v[0] ), to_f64( v[1] ), to_f64( v[2] ) ], None, None, id )?;
+                Some( pick_hit( & s, s.normal_facing(), Some( h._tri ) ) )
+            },
+        }
+    }
+}
+
+fn pick_hit( s: & SurfInteract, normal: [f64;3], tri: Option< usize > ) -> PickHit {
+    let p = s._point._val;
+    PickHit {
+        _id: s._prim_id,
+        _t: s._t as f32,
+        _point: [ p[0] as f32, p[1] as f32, p[2] as f32 ],
+        _normal: [ normal[0] as f32, normal[1] as f32, normal[2] as f32 ],
+        _tri: tri,
+        _barycentric: s._barycentric.map( |b| [ b[0] as f32, b[1] as f32, b[2] as f32 ] ),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickHit {
+    ///id of the picked target
+    pub _id: u64,
+    ///distance along the ray
+    pub _t: f32,
+    pub _point: [f32;3],
+    pub _normal: [f32;3],
+    ///triangle index and barycentric weights of its vertices for mesh hits
+    pub _tri: Option< usize >,
+    pub _barycentric: Option< [f32;3] >,
+}
+
+///set of selectable targets by id
+#[derive(Default)]
+pub struct Picker {
+    _targets: Vec< ( u64, PickShape ) >,
+}
+
+impl Picker {
+    pub fn add( & mut self, id: u64, shape: PickShape ) {
+        self._targets.push( ( id, shape ) );
+    }
+    ///adds a triangle soup of 9 floats per triangle
+    pub fn add_mesh( & mut self, id: u64, positions: &[ f32 ] ) -> Result< (), & 'static str > {
+        let m = TriMeshBvh::init( positions, 4 )?;
+        self.add( id, PickShape::Mesh( m ) );
+        Ok( () )
+    }
+    ///adds the bounds of a renderable, returns false for unbounded renderables
+    pub fn add_cullable< T >( & mut self, id: u64, obj: & T ) -> bool where T: ICullable {
+        match PickShape::from_bounding_volume( & obj.bounding_volume() ) {
+            Some( s ) => { self.add( id, s ); true },
+            None => false,
+        }
+    }
+    ///updates the triangles of a mesh target in place, keeping its bvh topology
+    pub fn refit_mesh( & mut self, id: u64, positions: &[ f32 ] ) -> Result< (), & 'static str > {
+        for t in self._targets.iter_mut().filter( |t| t.0 == id ) {
+            if let PickShape::Mesh( ref mut m ) = t.1 {
+                return m.refit( positions )
+            }
+        }
+        Err( "refit mesh target not found." )
+    }
+    pub fn len( & self ) -> usize {
+        self._targets.len()
+    }
+    pub fn is_empty( & self ) -> bool {
+        self._targets.is_empty()
+    }
+    pub fn clear( & mut self ){
+        self._targets.clear();
+    }
+    ///nearest hit along the ray
+    pub fn pick( & self, ray: & PickRay ) -> Option< PickHit > {
+        self._targets.iter()
+            .filter_map( |&( id, ref s )| s.intersect( ray, id ) )
+            .min_by( |a, b| a._t.partial_cmp( &b._t ).unwrap_or( Ordering::Equal ) )
+    }
+    ///all hits along the ray, nearest first
+    pub fn pick_all( & self, ray: & PickRay ) -> Vec< PickHit > {
+        let mut v : Vec< _ > = self._targets.iter().filter_map( |&( id, ref s )| s.intersect( ray, id ) ).collect();
+        v.sort_by( |a, b| a._t.partial_cmp( &b._t ).unwrap_or( Ordering::Equal ) );
+        v
+    }
+    pub fn pick_screen( & self, cam: & Cam, x: f32, y: f32, width: f32, height: f32 ) -> Option< PickHit > {
+        self.pick( & screen_ray( cam, x, y, width, height ) )
+    }
+}
@@ -0,0 +1,394 @@
+///render passes declaring the resources they read and write, ordered by their dependencies and expanded into per frame draw group commands
+
+use interface::i_ele;
+use interface::i_component;
+use interface::i_component::TextureRange;
+use interface::i_renderobj::RenderObjType;
+
+use implement::render::render_commands;
+use implement::render::renderdevice_gl::RenderDrawGroup;
+use implement::render::deferred;
+use implement::render::renderer_gl::Event;
+use implement::render::util_gl::ShaderType;
+
+///render target of the window
+pub const TARGET_DEFAULT : & 'static str = "default";
+
+///position, normal and albedo specular targets written by deferred geometry passes
+pub const TARGET_GBUFFER : & 'static str = "gbuffer";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderPassKind {
+    ///draws its draw group with its uniform groups
+    Draw,
+    ///draws its draw group, a screen quad, once per batch of point lights with the gbuffer as input.
+    ///the light batch is sent as uniform group _light_group, which no other pass may read
+    Lighting { _light_group: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RenderResource {
+    DrawGroup( usize ),
+    RenderTarget( String ),
+    UniformGroup( u64 ),
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderPassDesc {
+    pub _name: String,
+    pub _kind: RenderPassKind,
+    ///index of a loaded shader program, none keeps the current program
+    pub _shader_program: Option< u64 >,
+    ///alpha blending, eg: for transparent objects
+    pub _blend: bool,
+    ///draw group drawn by the pass
+    pub _draw_group: usize,
+    ///uniform groups sent before drawing
+    pub _uniform_groups: Vec< u64 >,
+    ///clears the draw group at the start of each frame before objects are submitted
+    pub _clear_draw_group: bool,
+    ///resources read in addition to the draw group and uniform groups
+    pub _inputs: Vec< RenderResource >,
+    pub _outputs: Vec< RenderResource >,
+}
+
+impl RenderPassDesc {
+    ///pass drawing a draw group into the default render target
+    pub fn init( name: & str, draw_group: usize, uniform_groups: & [ u64 ] ) -> RenderPassDesc {
+        RenderPassDesc {
+            _name: String::from( name ),
+            _kind: RenderPassKind::Draw,
+            _shader_program: None,
+            _blend: false,
+            _draw_group: draw_group,
+            _uniform_groups: uniform_groups.to_vec(),
+            _clear_draw_group: true,
+            _inputs: vec![],
+            _outputs: vec![ RenderResource::RenderTarget( String::from( TARGET_DEFAULT ) ) ],
+        }
+    }
+    ///first render target written, the default target if none
+    pub fn target( & self ) -> & str {
+        self._outputs.iter().filter_map( |x| match x {
+            &RenderResource::RenderTarget( ref t ) => Some( t.as_str() ),
+            _ => None,
+        } ).next().unwrap_or( TARGET_DEFAULT )
+    }
+    pub fn reads( & self ) -> Vec< RenderResource > {
+        let mut r = vec![ RenderResource::DrawGroup( self._draw_group ) ];
+        r.extend( self._uniform_groups.iter().map( |x| RenderResource::UniformGroup( *x ) ) );
+        r.extend( self._inputs.iter().cloned() );
+        r
+    }
+}
+
+///passes and the draw groups they use. objects submitted in a frame are flushed into the draw group of the first dispatched pass, unless added with Event::AddObjDrawGroup
+#[derive(Debug, Clone)]
+pub struct RenderGraph {
+    pub _passes: Vec< RenderPassDesc >,
+    ///primitive type of draw groups by index, created by the renderer if missing
+    pub _draw_groups: Vec< RenderObjType >,
+    _order: Vec< usize >,
+    _is_built: bool,
+}
+
+impl Default for RenderGraph {
+    ///single forward pass drawing draw group 0 with the light and camera uniform groups
+    fn default() -> RenderGraph {
+        let mut g = RenderGraph::init( & [ RenderObjType::TRI ] );
+        g.add_pass( RenderPassDesc::init( "forward", 0, & [ 0, 1 ] ) ).unwrap();
+        g.build().unwrap();
+        g
+    }
+}
+
+impl RenderGraph {
+    pub fn init( draw_groups: & [ RenderObjType ] ) -> RenderGraph {
+        RenderGraph {
+            _passes: vec![],
+            _draw_groups: draw_groups.to_vec(),
+            _order: vec![],
+            _is_built: false,
+        }
+    }
+    pub fn add_pass( & mut self, pass: RenderPassDesc ) -> Result< usize, & 'static str > {
+        if self._passes.iter().any( |x| x._name == pass._name ) {
+            return Err( "render pass name already exists" )
+        }
+        self._is_built = false;
+        self._passes.push( pass );
+        Ok( self._passes.len() - 1 )
+    }
+    pub fn is_built( & self ) -> bool {
+        self._is_built
+    }
+    ///orders passes so that readers of a resource follow its writers, keeping declaration order otherwise.
+    ///passes that both read and write a resource modify it in declaration order
+    pub fn build( & mut self ) -> Result< (), & 'static str > {
+        for p in self._passes.iter() {
+            if p._draw_group >= self._draw_groups.len() {
+                return Err( "render pass draw group out of range" )
+            }
+        }
+        let n = self._passes.len();
+        let mut adj = vec![ vec![]; n ];
+        let mut in_degree = vec![ 0usize; n ];
+        let reads : Vec< Vec< RenderResource > > = self._passes.iter().map( |x| x.reads() ).collect();
+        for ( i, a ) in self._passes.iter().enumerate() {
+            for ( j, b ) in self._passes.iter().enumerate() {
+                let depends = a._outputs.iter().any( |x| {
+                    let modified_by_both = reads[i].contains( x ) && b._outputs.contains( x );
+                    reads[j].contains( x ) && ( !modified_by_both || i < j )
+                } );
+                if i != j && depends {
+                    adj[i].push( j );
+                    in_degree[j] += 1;
+                }
+            }
+        }
+        let mut order = vec![];
+        let mut done = vec![ false; n ];
+        while order.len() < n {
+            let next = match ( 0..n ).find( |&i| !done[i] && in_degree[i] == 0 ) {
+                Some( i ) => i,
+                _ => { return Err( "render graph cycle detected" ) },
+            };
+            done[ next ] = true;
+            order.push( next );
+            for &j in adj[ next ].iter() {
+                in_degree[j] -= 1;
+            }
+        }
+        self._order = order;
+        self._is_built = true;
+        Ok( () )
+    }
+    ///builds the graph if needed and checks it against a backend holding the given number of draw groups and loaded
+    ///shader programs, supporting the given render targets. returns the primitive types of the draw groups to create
+    pub fn prepare( & mut self, draw_groups: usize, shader_programs: usize, targets: & [ & str ] ) -> Result< Vec< RenderObjType >, & 'static str > {
+        if !self._is_built {
+            self.build()?;
+        }
+        for p in self._passes.iter() {
+            for r in p._inputs.iter().chain( p._outputs.iter() ) {
+                match r {
+                    &RenderResource::RenderTarget( ref t ) if !targets.contains( & t.as_str() ) => {
+                        return Err( "render target not supported" )
+                    },
+                    _ => {},
+                }
+            }
+            if let Some( i ) = p._shader_program {
+                if i as usize >= shader_programs {
+                    return Err( "render pass shader program not loaded" )
+                }
+            }
+            if let RenderPassKind::Lighting { _light_group } = p._kind {
+                let collides = _light_group == deferred::UNIFORM_GROUP_CAMERA ||
+                    self._passes.iter().any( |x| x._name != p._name && x.reads().contains( & RenderResource::UniformGroup( _light_group ) ) );
+                if collides {
+                    return Err( "render pass light uniform group already in use" )
+                }
+            }
+        }
+        Ok( self._draw_groups.iter().skip( draw_groups ).cloned().collect() )
+    }
+    ///whether a pass reads or writes the render target
+    pub fn uses_target( & self, target: & str ) -> bool {
+        self._passes.iter().any( |p| p._inputs.iter().chain( p._outputs.iter() ).any( |r| match r {
+            &RenderResource::RenderTarget( ref t ) => t == target,
+            _ => false,
+        } ) )
+    }
+    ///passes in execution order
+    pub fn ordered_passes( & self ) -> Result< Vec< & RenderPassDesc >, & 'static str > {
+        if !self._is_built {
+            return Err( "render graph not built" )
+        }
+        Ok( self._order.iter().map( |&i| & self._passes[i] ).collect() )
+    }
+    ///commands issued before the objects of a frame
+    pub fn commands_frame_begin( & self ) -> Result< Vec< i_ele::Ele >, & 'static str > {
+        let mut cleared = vec![];
+        for p in self.ordered_passes()? {
+            if p._clear_draw_group && !cleared.contains( & p._draw_group ) {
+                cleared.push( p._draw_group );
+            }
+        }
+        Ok( cleared.into_iter().map( |x| i_ele::Ele::init( render_commands::CmdDrawGroupClear::init( x ) ) ).collect() )
+    }
+    ///commands issued after the objects of a frame, one bind, uniform and dispatch sequence per pass
+    pub fn commands_frame_end( & self ) -> Result< Vec< i_ele::Ele >, & 'static str > {
+        let mut ret = vec![];
+        for p in self.ordered_passes()? {
+            ret.extend( RenderGraph::commands_pass( p ) );
+        }
+        Ok( ret )
+    }
+    ///bind, uniform and dispatch commands drawing a pass once
+    pub fn commands_pass( p: & RenderPassDesc ) -> Vec< i_ele::Ele > {
+        vec![ i_ele::Ele::init( render_commands::CmdDrawGroupBind::init( p._draw_group ) ),
+              i_ele::Ele::init( render_commands::CmdDrawGroupDependentUniforms::init( p._draw_group, & p._uniform_groups[..] ) ),
+              i_ele::Ele::init( render_commands::CmdDrawGroupDispatch::init( p._draw_group ) ) ]
+    }
+    ///opaque objects to a gbuffer, full screen lighting, then transparent objects with forward shading.
+    ///draw groups: 0 for objects added with AddObj, 1 for transparent objects, 2 for the screen quad.
+    ///light_group is the uniform group of the light batches and must not be used by the game
+    pub fn deferred( program_geometry: u64, program_lighting: u64, program_forward: u64, light_group: u64 ) -> RenderGraph {
+        let mut g = RenderGraph::init( & [ RenderObjType::TRI, RenderObjType::TRI, RenderObjType::TRI ] );
+
+        let mut geometry = RenderPassDesc::init( "geometry", 0, & [ 1 ] );
+        geometry._shader_program = Some( program_geometry );
+        geometry._outputs = vec![ RenderResource::RenderTarget( String::from( TARGET_GBUFFER ) ) ];
+
+        let mut lighting = RenderPassDesc::init( "lighting", 2, & [ light_group ] );
+        lighting._kind = RenderPassKind::Lighting { _light_group: light_group };
+        lighting._shader_program = Some( program_lighting );
+        lighting._clear_draw_group = false;
+        lighting._inputs = vec![ RenderResource::RenderTarget( String::from( TARGET_GBUFFER ) ) ];
+        lighting._outputs = vec![ RenderResource::RenderTarget( String::from( TARGET_DEFAULT ) ) ];
+
+        let mut transparent = RenderPassDesc::init( "transparent", 1, & [ 0, 1 ] );
+        transparent._shader_program = Some( program_forward );
+        transparent._blend = true;
+        transparent._inputs = vec![ RenderResource::RenderTarget( String::from( TARGET_DEFAULT ) ) ];
+
+        g.add_pass( geometry ).unwrap();
+        g.add_pass( lighting ).unwrap();
+        g.add_pass( transparent ).unwrap();
+        g.build().unwrap();
+        g
+    }
+}
+
+///splits the render graphs out of the events of a frame, together with the shader loads they need ahead of them.
+///shader programs are indexed in load order, so only the first loads up to the highest program referenced by a graph
+///are moved ahead, the remaining events keep their order
+pub fn split_frame_events( e: Vec< Event >, shader_programs: usize ) -> ( Vec< Vec< ( String, ShaderType ) > >, Vec< RenderGraph >, Vec< Event > ) {
+    let mut graphs = vec![];
+    let mut rest = vec![];
+    for i in e {
+        match i {
+            Event::SetRenderGraph( g ) => { graphs.push( g ); },
+            x => { rest.push( x ); },
+        }
+    }
+    let referenced = graphs.iter()
+        .flat_map( |g| g._passes.iter().filter_map( |p| p._shader_program ) )
+        .map( |x| x as usize + 1 )
+        .max().unwrap_or( 0 );
+    let mut hoist = referenced.saturating_sub( shader_programs );
+    let mut shaders = vec![];
+    let mut events = vec![];
+    for i in rest {
+        match i {
+            Event::LoadShader( x ) => {
+                if hoist > 0 {
+                    hoist -= 1;
+                    shaders.push( x );
+                } else {
+                    events.push( Event::LoadShader( x ) );
+                }
+            },
+            x => { events.push( x ); },
+        }
+    }
+    ( shaders, graphs, events )
+}
+
+///renderer specific handling of the commands of submitted objects, see process_objs
+pub trait DrawGroupBackend {
+    fn draw_group_mut( & mut self, group: usize ) -> Result< & mut RenderDrawGroup, & 'static str >;
+    ///texture bindings of a draw group in absolute vertex offsets
+    fn texture_ranges_mut( & mut self, group: usize ) -> Result< & mut Vec< TextureRange >, & 'static str >;
+    fn flush_uniform( & mut self, o: & i_component::ComponentRenderUniform ) -> Result< (), & 'static str >;
+    ///clears the vertices and texture ranges of a draw group
+    fn clear_group( & mut self, group: usize ) -> Result< (), & 'static str >;
+    fn bind_group( & mut self, group: usize ) -> Result< (), & 'static str >;
+    fn set_group_uniforms( & mut self, group: usize, uniform_groups: & [ u64 ] ) -> Result< (), & 'static str >;
+    fn draw_group( & mut self, group: usize ) -> Result< (), & 'static str >;
+    fn dependent_objects( & mut self, _group: usize, _objs: & [ usize ] ) -> Result< (), & 'static str > {
+        Err( "unmatching render command" )
+    }
+    ///called with the relative texture ranges of an object before they apply to its next render buffer
+    fn texture_ranges_submitted( & mut self, _ranges: & [ TextureRange ] ) {}
+    ///called after vertices are appended to a draw group
+    fn buffer_flushed( & mut self, _group: usize, _vert_count: usize ) {}
+}
+
+///flushes the components of objects in order. render buffers go to the draw group of the object's
+///ComponentDrawGroupTarget if any, or group_index, and texture ranges apply to the next render buffer of the same object
+pub fn process_objs< T: DrawGroupBackend >( backend: & mut T, objs: Vec< i_ele::Ele >, group_index: usize ) -> Result< (), & 'static str > {
+    for i in objs {
+        let mut pending_ranges = None;
+        let mut target = group_index;
+        for j in i._components {
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentDrawGroupTarget >() {
+                target = o._group_id;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentRenderTextureRanges >() {
+                backend.texture_ranges_submitted( & o._ranges[..] );
+                pending_ranges = Some( o._ranges.clone() );
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentRenderBuffer >() {
+                let vert_start = backend.draw_group_mut( target )?.vert_count();
+                o.flush_into_render_device( backend.draw_group_mut( target )? )?;
+                let vert_end = backend.draw_group_mut( target )?.vert_count();
+                backend.buffer_flushed( target, vert_end - vert_start );
+                add_texture_ranges( backend.texture_ranges_mut( target )?, vert_start, vert_end, pending_ranges.take() )?;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentRenderUniform >() {
+                backend.flush_uniform( o )?;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentDrawGroupClear >() {
+                backend.clear_group( o._group_id )?;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentDrawGroupDependentObjects >() {
+                backend.dependent_objects( o._group_id, & o._obj_ids[..] )?;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentDrawGroupBind >() {
+                backend.bind_group( o._group_id )?;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentDrawGroupDependentUniforms >() {
+                backend.set_group_uniforms( o._group_id, & o._uniform_ids[..] )?;
+                continue;
+            }
+            if let Some( o ) = j.as_any().downcast_ref::< i_component::ComponentDrawGroupDispatch >() {
+                backend.draw_group( o._group_id )?;
+                continue;
+            }
+            return Err( "unmatching render command" );
+        }
+    }
+    Ok( () )
+}
+
+///appends the texture ranges of a buffer occupying vertices [ vert_start, vert_end ) of a draw group, with ranges
+///relative to vert_start. vertices not covered by a range are bound to the default texture
+pub fn add_texture_ranges( group_ranges: & mut Vec< TextureRange >, vert_start: usize, vert_end: usize, ranges: Option< Vec< TextureRange > > ) -> Result< (), & 'static str > {
+    let mut covered = vert_start;
+    if let Some( r ) = ranges {
+        for i in r {
+            let start = vert_start + i._vert_offset;
+            if start + i._vert_count > vert_end {
+                return Err( "texture range exceeds render buffer" )
+            }
+            if start > covered {
+                group_ranges.push( TextureRange { _texture: None, _vert_offset: covered, _vert_count: start - covered } );
+            }
+            covered = covered.max( start + i._vert_count );
+            group_ranges.push( TextureRange { _texture: i._texture, _vert_offset: start, _vert_count: i._vert_count } );
+        }
+    }
+    if vert_end > covered {
+        group_ranges.push( TextureRange { _texture: None, _vert_offset: covered, _vert_count: vert_end - covered } );
+    }
+    Ok( () )
+}
@@ -40,6 +40,10 @@ impl RenderDrawGroup {
             _primitive_type: primitive_type,
         }
     }
+    ///number of vertices currently stored
+    pub fn vert_count( & self ) -> usize {
+        self._buffer_draw.len() / (self._stride as usize / ::std::mem::size_of::<f32>())
+    }
     pub fn init_with_default_format_triangle( internal_group_handle: u64, buffer_handle: u64 ) -> RenderDrawGroup {
         let stride = 8 * ::std::mem::size_of::<f32>();
         let format = vec![ BufferFormat { _buff_data_type: i_renderobj::BuffDataType::POS, _index: 0, _num_data: 3, _stride_bytes: stride as _, _offset_bytes: 0 },
@@ -114,8 +118,28 @@ impl i_renderobj::RenderDevice for RenderDrawGroup{
         }
         Ok( () )
     }
-    fn draw_buffer_range( & mut self) -> Result< (), & 'static str > {
-        unimplemented!();
+    fn draw_buffer_range( & mut self, start: usize, count: usize ) -> Result< (), & 'static str > {
+        let num_elements = self.vert_count();
+        if start + count > num_elements {
+            return Err( "draw buffer range out of bounds" )
+        }
+        unsafe {
+            gl::BindVertexArray( self._group_handle as _ );
+            match self._primitive_type {
+                i_renderobj::RenderObjType::TRI => {
+                    trace!("draw buffer range: start: {}, num verts: {}", start, count );
+                    gl::DrawArrays(gl::TRIANGLES, start as _, count as _ );
+                },
+                i_renderobj::RenderObjType::POINT => {
+                    trace!("draw buffer range: start: {}, num points: {}", start, count );
+                    gl::PointSize(3f32);
+                    gl::DrawArrays(gl::POINTS, start as _, count as _ );
+                },
+                _=> return Err( "unsupported primite type for drawing detected" )
+            }
+            gl::BindVertexArray( 0 );
+        }
+        Ok( () )
     }
     fn clear_buff_data( & mut self ){
         self._buffer_draw.clear();
@@ -288,19 +312,26 @@ impl RenderUniformCollection {
     pub fn send_uniform_group( & self, group_id: u64 ) -> Result< (), & 'static str > {
         match self._uniforms_groups.get( &group_id ) {
             None => return Err( &"unfound uniform group id" ),
-            Some( &( ref program, ref v ) ) => {
+            Some( &( program, _ ) ) => self.send_uniform_group_to( group_id, program ),
+        }
+    }
+    /// # send the uniform values belonging to the uniform group to a shader program other than the one they were set for
+    pub fn send_uniform_group_to( & self, group_id: u64, shader_program: u64 ) -> Result< (), & 'static str > {
+        match self._uniforms_groups.get( &group_id ) {
+            None => return Err( &"unfound uniform group id" ),
+            Some( &( ref program_set, ref v ) ) => {
                 for name in v.iter() {
-                    match self._uniforms_f.get( &( *program, name.clone()) ) {
+                    match self._uniforms_f.get( &( *program_set, name.clone()) ) {
                         None => return Err( &"unfound uniform name" ),
                         Some( &( ref uniform_type , ref vals) ) => {
                             match *uniform_type {
                                 UniformType::VEC => {
                                     unsafe {
                                         match vals.len() {
-                                            1 => gl::Uniform1f( gl::GetUniformLocation( *program as _, name.as_ptr() as * const i8 ), vals[0] ),
-                                            2 => gl::Uniform2fv( gl::GetUniformLocation( *program as _, name.as_ptr() as * const i8 ), 1, vals.as_ptr() as _ ),
-                                            3 => gl::Uniform3fv( gl::GetUniformLocation( *program as _, name.as_ptr() as * const i8 ), 1, vals.as_ptr() as _ ),
-                                            4 => gl::Uniform4fv( gl::GetUniformLocation( *program as _, name.as_ptr() as * const i8 ), 1, vals.as_ptr() as _ ),
+                                            1 => gl::Uniform1f( gl::GetUniformLocation( shader_program as _, name.as_ptr() as * const i8 ), vals[0] ),
+                                            2 => gl::Uniform2fv( gl::GetUniformLocation( shader_program as _, name.as_ptr() as * const i8 ), 1, vals.as_ptr() as _ ),
+                                            3 => gl::Uniform3fv( gl::GetUniformLocation( shader_program as _, name.as_ptr() as * const i8 ), 1, vals.as_ptr() as _ ),
+                                            4 => gl::Uniform4fv( gl::GetUniformLocation( shader_program as _, name.as_ptr() as * const i8 ), 1, vals.as_ptr() as _ ),
                                             _ => return Err( &"unsupported uniform data length" ),
                                         }
                                     }
@@ -311,7 +342,7 @@ impl RenderUniformCollection {
                                     }
                                     unsafe {
                                         //assumes matrix is row major
-                                        gl::UniformMatrix4fv( gl::GetUniformLocation( *program as _, name.as_ptr() as * const i8 ), 1, gl::TRUE, vals.as_ptr() as _ );
+                                        gl::UniformMatrix4fv( gl::GetUniformLocation( shader_program as _, name.as_ptr() as * const i8 ), 1, gl::TRUE, vals.as_ptr() as _ );
                                     }
                                 },
                                 UniformType::MAT3 => {
@@ -320,7 +351,7 @@ impl RenderUniformCollection {
                                     }
                                     unsafe {
                                         //assumes matrix is row major
-                                        gl::UniformMatrix3fv( gl::GetUniformLocation( *program as _, name.as_ptr() as * const i8 ), 1, gl::TRUE, vals.as_ptr() as _ );
+                                        gl::UniformMatrix3fv( gl::GetUniformLocation( shader_program as _, name.as_ptr() as * const i8 ), 1, gl::TRUE, vals.as_ptr() as _ );
                                     }
                                 },
                                 _ => { unimplemented!(); },
@@ -333,3 +364,95 @@ impl RenderUniformCollection {
         Ok( () )
     }
 }
+
+/// # gbuffer of deferred shading: eye space position, normal, albedo with specular intensity in alpha, and depth
+#[derive(Debug)]
+pub struct GBuffer {
+    pub _fbo: gl::types::GLuint,
+    pub _position: gl::types::GLuint,
+    pub _normal: gl::types::GLuint,
+    pub _albedo_spec: gl::types::GLuint,
+    pub _depth: gl::types::GLuint,
+    pub _w: i32,
+    pub _h: i32,
+}
+
+impl Drop for GBuffer {
+    fn drop( & mut self ) {
+        unsafe {
+            let textures = [ self._position, self._normal, self._albedo_spec ];
+            gl::DeleteTextures( textures.len() as _, textures.as_ptr() );
+            gl::DeleteRenderbuffers( 1, & self._depth );
+            gl::DeleteFramebuffers( 1, & self._fbo );
+        }
+    }
+}
+
+impl GBuffer {
+    pub fn init( w: i32, h: i32 ) -> Result< GBuffer, & 'static str > {
+        let mut g = GBuffer { _fbo: 0, _position: 0, _normal: 0, _albedo_spec: 0, _depth: 0, _w: w, _h: h };
+        unsafe {
+            gl::GenFramebuffers( 1, & mut g._fbo );
+            gl::BindFramebuffer( gl::FRAMEBUFFER, g._fbo );
+            g._position = GBuffer::attach_texture( 0, gl::RGB16F, gl::RGB, gl::FLOAT, w, h );
+            g._normal = GBuffer::attach_texture( 1, gl::RGB16F, gl::RGB, gl::FLOAT, w, h );
+            g._albedo_spec = GBuffer::attach_texture( 2, gl::RGBA8, gl::RGBA, gl::UNSIGNED_BYTE, w, h );
+            let attachments : [ gl::types::GLenum; 3 ] = [ gl::COLOR_ATTACHMENT0, gl::COLOR_ATTACHMENT1, gl::COLOR_ATTACHMENT2 ];
+            gl::DrawBuffers( attachments.len() as _, attachments.as_ptr() );
+
+            gl::GenRenderbuffers( 1, & mut g._depth );
+            gl::BindRenderbuffer( gl::RENDERBUFFER, g._depth );
+            gl::RenderbufferStorage( gl::RENDERBUFFER, gl::DEPTH_COMPONENT32F, w, h );
+            gl::FramebufferRenderbuffer( gl::FRAMEBUFFER, gl::DEPTH_ATTACHMENT, gl::RENDERBUFFER, g._depth );
+            util_gl::check_last_op();
+
+            let status = gl::CheckFramebufferStatus( gl::FRAMEBUFFER );
+            gl::BindFramebuffer( gl::FRAMEBUFFER, 0 );
+            if status != gl::FRAMEBUFFER_COMPLETE {
+                return Err( "gbuffer framebuffer incomplete" )
+            }
+        }
+        Ok( g )
+    }
+    unsafe fn attach_texture( attachment: u32, internal_format: gl::types::GLenum, format: gl::types::GLenum, data_type: gl::types::GLenum, w: i32, h: i32 ) -> gl::types::GLuint {
+        let mut t = 0;
+        gl::GenTextures( 1, & mut t );
+        gl::BindTexture( gl::TEXTURE_2D, t );
+        gl::TexImage2D( gl::TEXTURE_2D, 0, internal_format as _, w, h, 0, format, data_type, ::std::ptr::null() );
+        gl::TexParameteri( gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as _ );
+        gl::TexParameteri( gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as _ );
+        gl::FramebufferTexture2D( gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0 + attachment, gl::TEXTURE_2D, t, 0 );
+        t
+    }
+    ///size of the current viewport
+    pub fn viewport_size() -> ( i32, i32 ) {
+        let mut v = [ 0i32; 4 ];
+        unsafe {
+            gl::GetIntegerv( gl::VIEWPORT, v.as_mut_ptr() );
+        }
+        ( v[2], v[3] )
+    }
+    pub fn bind_write( & self ) {
+        unsafe {
+            gl::BindFramebuffer( gl::FRAMEBUFFER, self._fbo );
+        }
+    }
+    ///binds position, normal and albedo specular to texture units 0, 1 and 2
+    pub fn bind_read( & self ) {
+        util_gl::bind_texture( 0, self._position );
+        util_gl::bind_texture( 1, self._normal );
+        util_gl::bind_texture( 2, self._albedo_spec );
+        unsafe {
+            gl::ActiveTexture( gl::TEXTURE0 );
+        }
+    }
+    ///copies depth to the default framebuffer so that later passes are occluded by the gbuffer geometry
+    pub fn blit_depth( & self ) {
+        unsafe {
+            gl::BindFramebuffer( gl::READ_FRAMEBUFFER, self._fbo );
+            gl::BindFramebuffer( gl::DRAW_FRAMEBUFFER, 0 );
+            gl::BlitFramebuffer( 0, 0, self._w, self._h, 0, 0, self._w, self._h, gl::DEPTH_BUFFER_BIT, gl::NEAREST );
+            gl::BindFramebuffer( gl::FRAMEBUFFER, 0 );
+        }
+    }
+}
@@ -5,14 +5,11 @@ extern crate chrono;
 use self::chrono::prelude::*;
 
 use ::std::str;
-use ::std::ops::DerefMut;
-use std::collections::HashMap;
 use std::cell::RefCell;   
 
 use interface::i_ele;
 use interface::i_renderobj::RenderDevice;
 use interface::i_renderobj;
-use interface::i_renderpass;
 use interface::i_component;
 use interface::i_renderer::IRenderer;
 
@@ -22,11 +19,10 @@ use implement::render::shader_collection;
 use implement::render::texture_collection;
 use implement::render::router;
 use implement::render::renderdevice_gl;
-use implement::render::render_commands;
+use implement::render::render_graph;
+use implement::render::deferred;
 
 pub struct Renderer {
-    _rp: Vec< Box< i_renderpass::IRenderPass > >,
-    _map_string_to_rp: HashMap< String, usize >,
     pub _shader_collection: RefCell< shader_collection::ShaderCollection >,
     _texture_collection: texture_collection::TextureCollection,
     _shader_programs: Vec< u64 >,
@@ -36,11 +32,18 @@ pub struct Renderer {
     _objs: Vec< i_ele::Ele >,
     _uniforms: RefCell< renderdevice_gl::RenderUniformCollection >,
     _draw_group_uniforms: RefCell< Vec< Vec< u64 > > >,
+    ///texture bindings per draw group in absolute vertex offsets
+    _draw_group_texture_ranges: RefCell< Vec< Vec< i_component::TextureRange > > >,
+    ///texture used for ranges without an explicit texture
+    _default_texture: Option< i64 >,
     _shaders_compiled: Vec< gl::types::GLuint >,
     //todo: to be removed
     _current_shader_program: u64,
     _is_init: bool,
-    _draw_group: usize,
+    _render_graph: render_graph::RenderGraph,
+    ///created when the render graph uses the gbuffer render target
+    _gbuffer: Option< renderdevice_gl::GBuffer >,
+    _point_lights: Vec< deferred::PointLight >,
 }
 
 impl Drop for Renderer {
@@ -64,6 +67,12 @@ pub enum Event {
     LoadShader( Vec< ( String, util_gl::ShaderType ) > ),
     LoadTexture( String, Vec< u8 >, usize, usize ),
     CreateDrawGroup( i_renderobj::RenderObjType ),
+    ///replaces the render passes, applied after the shaders of the frame are loaded and before objects are added
+    SetRenderGraph( render_graph::RenderGraph ),
+    ///object whose render buffers go to the given draw group instead of the first dispatched one, eg: transparent objects of the deferred render graph
+    AddObjDrawGroup( usize, i_ele::Ele ),
+    ///replaces the point lights shaded by lighting passes
+    SetPointLights( Vec< deferred::PointLight > ),
 }
 
 pub enum EventResult {
@@ -84,15 +93,26 @@ impl IRenderer for Renderer {
         if !self._is_init {
             info!("renderer: first time initialization.");
             self._is_init = true;
-            let ( _vao, _vbo, draw_group ) = self.create_draw_group( i_renderobj::RenderObjType::TRI ).unwrap();
-            self._draw_group = draw_group;
+            self.set_render_graph( Default::default() )?;
         }
-        
-        //setup hardcoded draw group for now
-        //todo: remove it and put in a configurable hook
+
+        //render graphs apply before the rest of the frame, once the shader programs they use are loaded
+        let ( shaders, graphs, e ) = render_graph::split_frame_events( e, self._shader_programs.len() );
+        for x in shaders {
+            let t0 = Local::now();
+            self.load_shader( x.as_slice() )?;
+            let t1 = Local::now();
+            let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
+            debug!( "t_render_load_shader: {} ms", t_delta );
+        }
+        for g in graphs {
+            self.set_render_graph( g )?;
+        }
+
         let dummy_str = "";
-        let draw_group = self._draw_group;
-        self.add_obj( dummy_str, i_ele::Ele::init( render_commands::CmdDrawGroupClear::init( draw_group ) ) ).is_ok();
+        for i in self._render_graph.commands_frame_begin()? {
+            self.add_obj( dummy_str, i )?;
+        }
 
         //handle events
         //todo: handle return values from calls
@@ -105,11 +125,14 @@ impl IRenderer for Renderer {
                     let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
                     debug!( "t_render_add_obj: {} ms", t_delta );
                 },
-                Event::LoadShader( x ) => {
-                    self.load_shader( x.as_slice() )?;
+                Event::AddObjDrawGroup( g, x ) => {
+                    self.add_obj_to_draw_group( dummy_str, Some( g ), x )?;
                     let t1 = Local::now();
                     let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
-                    debug!( "t_render_load_shader: {} ms", t_delta );
+                    debug!( "t_render_add_obj: {} ms", t_delta );
+                },
+                Event::SetPointLights( x ) => {
+                    self._point_lights = x;
                 },
                 Event::LoadTexture( s, data, w, h ) => {
                     self.load_texture( s, &data, w, h )?;
@@ -123,40 +146,67 @@ impl IRenderer for Renderer {
                     let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
                     debug!( "t_render_create_draw_group: {} ms", t_delta );
                 },
+                Event::LoadShader( x ) => {
+                    self.load_shader( x.as_slice() )?;
+                    let t1 = Local::now();
+                    let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
+                    debug!( "t_render_load_shader: {} ms", t_delta );
+                },
+                Event::SetRenderGraph( _ ) => {},
             }
             util_gl::check_last_op();
         }
 
-        //some hardcoded draw calls in preparation for rendering
-        //todo: put these in a configurable hook
-
+        //draw calls of the render passes in dependency order
         let t0 = Local::now();
 
-        self.add_obj( dummy_str, i_ele::Ele::init( render_commands::CmdDrawGroupBind::init( draw_group ) ) ).is_ok();
+        self.render_passes()?;
 
         let t1 = Local::now();
-        
-        self.add_obj( dummy_str, i_ele::Ele::init( render_commands::CmdDrawGroupDependentUniforms::init( draw_group, &[0u64,1u64] ) ) ).is_ok();
-
-        let t2 = Local::now();
-
-        self.add_obj( dummy_str, i_ele::Ele::init( render_commands::CmdDrawGroupDispatch::init( draw_group ) ) ).is_ok();
+        let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
+        debug!( "t_render_passes: {} ms", t_delta );
 
-        let t3 = Local::now();
+        Ok( () )
+    }
+}
 
-        {
-            let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
-            debug!( "t_draw_group_bind: {} ms", t_delta );
-        }
-        {
-            let t_delta = t2.signed_duration_since(t1).num_milliseconds() as f64;
-            debug!( "t_draw_group_dependent_uniforms: {} ms", t_delta );
+impl render_graph::DrawGroupBackend for Renderer {
+    fn draw_group_mut( & mut self, group: usize ) -> Result< & mut renderdevice_gl::RenderDrawGroup, & 'static str > {
+        match self._draw_groups.get_mut().get_mut( group ) {
+            Some( x ) => Ok( x ),
+            _ => Err( "object index out of range" ),
         }
-        {
-            let t_delta = t3.signed_duration_since(t2).num_milliseconds() as f64;
-            debug!( "t_draw_group_dispatch: {} ms", t_delta );
+    }
+    fn texture_ranges_mut( & mut self, group: usize ) -> Result< & mut Vec< i_component::TextureRange >, & 'static str > {
+        let ranges = self._draw_group_texture_ranges.get_mut();
+        if ranges.len() <= group {
+            ranges.resize( group + 1, vec![] );
         }
-
+        Ok( & mut ranges[ group ] )
+    }
+    fn flush_uniform( & mut self, o: & i_component::ComponentRenderUniform ) -> Result< (), & 'static str > {
+        trace!("uniform flushed");
+        let shader_program = self._shader_collection.borrow_mut().get( self._current_shader_program ).unwrap();
+        o.flush_into_uniform_collection( shader_program, & mut self._uniforms.borrow_mut() )
+    }
+    fn clear_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        trace!("draw group clear");
+        self.reset_draw_group_data( &[ group ] ).is_ok();
+        Ok( () )
+    }
+    fn bind_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        trace!("draw group bind");
+        self.bind_draw_group_data( &[ group ] ).is_ok();
+        Ok( () )
+    }
+    fn set_group_uniforms( & mut self, group: usize, uniform_groups: & [ u64 ] ) -> Result< (), & 'static str > {
+        trace!("draw group dependent uniforms");
+        self.set_draw_group_uniforms( group, uniform_groups ).is_ok();
+        Ok( () )
+    }
+    fn draw_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        trace!("draw group dispatch");
+        Renderer::drawcall_draw_group( self, &[ group ] ).is_ok();
         Ok( () )
     }
 }
@@ -164,8 +214,6 @@ impl IRenderer for Renderer {
 impl Renderer {
     pub fn init() -> Result< Renderer, & 'static str > {
         let rk = Renderer {
-            _rp: vec![],
-            _map_string_to_rp: HashMap::new(),
             _shader_collection: RefCell::new( Default::default() ),
             _texture_collection: Default::default(),
             _shader_programs: vec![],
@@ -175,10 +223,14 @@ impl Renderer {
             _objs: vec![],
             _uniforms: RefCell::new( Default::default() ),
             _draw_group_uniforms: RefCell::new( vec![] ),
+            _draw_group_texture_ranges: RefCell::new( vec![] ),
+            _default_texture: None,
             _shaders_compiled: vec![],
             _current_shader_program: 0,
             _is_init: false,
-            _draw_group: 0usize,
+            _render_graph: Default::default(),
+            _gbuffer: None,
+            _point_lights: vec![],
         };
         let cap = capability_gl::query_gl();
         info!( "GL capability: {}", cap );
@@ -226,6 +278,9 @@ impl Renderer {
             Ok( h ) => h,
             Err( e ) => return Err( e ),
         };
+        if self._default_texture.is_none() {
+            self._default_texture = Some( handle as _ );
+        }
         Ok( h )
     }
     pub fn create_draw_group( & mut self, prim_type: i_renderobj::RenderObjType ) -> Result< ( gl::types::GLuint, gl::types::GLuint, usize ), & 'static str > {
@@ -247,8 +302,12 @@ impl Renderer {
         self._draw_groups.borrow_mut().push( draw_group );
         Ok( ( obj_vao, obj_vbo, self._draw_groups.borrow_mut().len() - 1) )
     }
-    #[allow(unused)]
     pub fn add_obj( & mut self, name: &str, e: i_ele::Ele ) -> Result< ( usize ), & 'static str > {
+        self.add_obj_to_draw_group( name, None, e )
+    }
+    ///adds an object, with its render buffers going to draw_group if given
+    #[allow(unused)]
+    pub fn add_obj_to_draw_group( & mut self, name: &str, draw_group: Option< usize >, e: i_ele::Ele ) -> Result< ( usize ), & 'static str > {
         
         let index = self._objs.len();
         self._objs.push( e );
@@ -261,6 +320,10 @@ impl Renderer {
             _ => (),
         }
 
+        if let Some( g ) = draw_group {
+            self._objs[index]._components.insert( 0, Box::new( i_component::ComponentDrawGroupTarget { _group_id: g } ) );
+        }
+
         let t1 = Local::now();
 
         let t_delta = t1.signed_duration_since(t0).num_milliseconds() as f64;
@@ -303,67 +366,7 @@ impl Renderer {
 
         let drained = renderer._objs.drain(..).collect::<Vec<_>>();
 
-        for i in drained {
-
-            for j in i._components {
-
-                match j.as_any().downcast_ref::< i_component::ComponentRenderBuffer >() {
-                    Some( o ) => {
-                        trace!("buffer flushed");
-                        match o.flush_into_render_device( & mut renderer._draw_groups.get_mut()[ group_index ] ) {
-                            Err( e ) => return Err( e ),
-                            _ => { continue; },
-                        }
-                    },
-                    None => (),
-                }
-                match j.as_any().downcast_ref::< i_component::ComponentRenderUniform >() {
-                    Some( o ) => {
-                        trace!("uniform flushed");
-                        let shader_program = renderer._shader_collection.borrow_mut().get( renderer._current_shader_program ).unwrap();
-                        match o.flush_into_uniform_collection( shader_program, & mut renderer._uniforms.borrow_mut() ) {
-                            Err( e ) => return Err( e ),
-                            _ => { continue; },
-                        }
-                    },
-                    None => (),
-                }
-                match j.as_any().downcast_ref::< i_component::ComponentDrawGroupClear >() {
-                    Some( o ) => {
-                        trace!("draw group clear");
-                        renderer.reset_draw_group_data( &[ o._group_id ] ).is_ok();
-                        continue;
-                    },
-                    None => (),
-                }
-                match j.as_any().downcast_ref::< i_component::ComponentDrawGroupBind >() {
-                    Some( o ) => {
-                        trace!("draw group bind");
-                        renderer.bind_draw_group_data( &[ o._group_id ] ).is_ok();
-                        continue;
-                    },
-                    None => (),
-                }
-                match j.as_any().downcast_ref::< i_component::ComponentDrawGroupDependentUniforms >() {
-                    Some( o ) => {
-                        trace!("draw group dependent uniforms");
-                        renderer.set_draw_group_uniforms( o._group_id, &o._uniform_ids[..] ).is_ok();
-                        continue;
-                    },
-                    None => (),
-                }
-                match j.as_any().downcast_ref::< i_component::ComponentDrawGroupDispatch >() {
-                    Some( o ) => {
-                        trace!("draw group dispatch");
-                        let renderer_immut : & Renderer = & * renderer;
-                        Renderer::drawcall_draw_group( renderer_immut, &[ o._group_id ] ).is_ok();
-                        continue;
-                    },
-                    None => (),
-                }
-                return Err( &"unmatching render command" );
-            }
-        }
+        render_graph::process_objs( renderer, drained, group_index )?;
 
         assert_eq!(renderer._objs.len(), 0 );
 
@@ -376,6 +379,9 @@ impl Renderer {
             }
             let mut dg = self._draw_groups.borrow_mut();
             dg[ i ].clear_buff_data();
+            if let Some( r ) = self._draw_group_texture_ranges.borrow_mut().get_mut( i ) {
+                r.clear();
+            }
         }
         Ok( () )
     }
@@ -391,38 +397,191 @@ impl Renderer {
         }
         Ok( () )
     }
+    ///sends the dependent uniform groups of each draw group to the current shader program, then draws the group once
     pub fn drawcall_draw_group( renderer: & Renderer, group_indices: &[ usize ] ) -> Result< (), & 'static str > {
+        let shader_program = renderer.current_shader_program()?;
         for &i in group_indices {
-            for uniform_group in renderer._draw_group_uniforms.borrow()[i].iter() {
+            if i >= renderer._draw_groups.borrow().len() {
+                return Err( "object index out of range" )
+            }
+            let uniform_groups = renderer._draw_group_uniforms.borrow()[i].clone();
+            for uniform_group in uniform_groups.iter() {
                 trace!("dispatching uniform group: {}", *uniform_group );
-                if i >= renderer._draw_groups.borrow().len() {
-                    return Err( "object index out of range" )
-                }
-                match renderer._uniforms.borrow_mut().send_uniform_group( *uniform_group ){
+                match renderer._uniforms.borrow_mut().send_uniform_group_to( *uniform_group, shader_program ){
                     Err(e) => return Err(e),
                     _ => ()
                 }
-                match renderer._draw_groups.borrow_mut()[ i ].draw_buffer_all() {
-                    Err( e ) => return Err( e ),
-                    _ => (),
+            }
+            if uniform_groups.len() > 0 {
+                Renderer::drawcall_texture_ranges( renderer, i )?;
+            }
+        }
+        Ok( () )
+    }
+    ///draws a group's buffer range by range with each range's texture bound, or all at once if no ranges exist
+    fn drawcall_texture_ranges( renderer: & Renderer, group_index: usize ) -> Result< (), & 'static str > {
+        let ranges = renderer._draw_group_texture_ranges.borrow();
+        match ranges.get( group_index ) {
+            Some( r ) if r.len() > 0 => {
+                for range in r.iter() {
+                    let handle = match range._texture {
+                        Some( ref descrip ) => {
+                            match renderer._texture_collection.get_by_descrip( descrip.as_str() ) {
+                                Some( h ) => Some( h ),
+                                None => {
+                                    warn!( "texture not found: {}, using default", descrip );
+                                    renderer._default_texture
+                                },
+                            }
+                        },
+                        None => renderer._default_texture,
+                    };
+                    if let Some( h ) = handle {
+                        util_gl::bind_texture( 0, h as _ );
+                    }
+                    renderer._draw_groups.borrow_mut()[ group_index ].draw_buffer_range( range._vert_offset, range._vert_count )?;
                 }
+                Ok( () )
+            },
+            _ => {
+                renderer._draw_groups.borrow_mut()[ group_index ].draw_buffer_all()
+            },
+        }
+    }
+    ///builds the graph if needed and creates its missing draw groups and gbuffer, existing draw groups are kept. the draw groups of lighting passes are filled with a screen quad
+    pub fn set_render_graph( & mut self, mut graph: render_graph::RenderGraph ) -> Result< (), & 'static str > {
+        let count = self._draw_groups.borrow().len();
+        let create = graph.prepare( count, self._shader_programs.len(), &[ render_graph::TARGET_DEFAULT, render_graph::TARGET_GBUFFER ] )?;
+        for t in create {
+            self.create_draw_group( t )?;
+        }
+        for p in graph._passes.iter() {
+            if p._kind != render_graph::RenderPassKind::Draw {
+                self.reset_draw_group_data( &[ p._draw_group ] )?;
+                deferred::screen_quad().flush_into_render_device( & mut self._draw_groups.get_mut()[ p._draw_group ] )?;
+            }
+        }
+        self._gbuffer = if graph.uses_target( render_graph::TARGET_GBUFFER ) {
+            let ( w, h ) = renderdevice_gl::GBuffer::viewport_size();
+            Some( renderdevice_gl::GBuffer::init( w, h )? )
+        } else {
+            None
+        };
+        self._render_graph = graph;
+        Ok( () )
+    }
+    ///draws the passes of the render graph in dependency order
+    fn render_passes( & mut self ) -> Result< (), & 'static str > {
+        let dummy_str = "";
+        let passes : Vec< render_graph::RenderPassDesc > = self._render_graph.ordered_passes()?.into_iter().cloned().collect();
+        let mut gbuffer_cleared = false;
+        for p in passes.iter() {
+            self.begin_pass( p, & mut gbuffer_cleared )?;
+            match p._kind {
+                render_graph::RenderPassKind::Draw => {
+                    for i in render_graph::RenderGraph::commands_pass( p ) {
+                        self.add_obj( dummy_str, i )?;
+                    }
+                },
+                render_graph::RenderPassKind::Lighting { _light_group } => {
+                    let shader_program = self.current_shader_program()?;
+                    let view = deferred::view_matrix( & self._uniforms.borrow() )?;
+                    let lights = deferred::to_eye_space( & self._point_lights[..], & view[..] );
+                    for ( n, b ) in deferred::batches( & lights[..] ).into_iter().enumerate() {
+                        if n == 1 {
+                            unsafe {
+                                gl::Enable( gl::BLEND );
+                            }
+                        }
+                        deferred::set_light_uniforms( & mut self._uniforms.borrow_mut(), shader_program, _light_group, b, n == 0 )?;
+                        for i in render_graph::RenderGraph::commands_pass( p ) {
+                            self.add_obj( dummy_str, i )?;
+                        }
+                    }
+                },
             }
+            self.end_pass( p );
+            util_gl::check_last_op();
         }
         Ok( () )
     }
-    pub fn add_renderpass< T > ( & mut self, name: String, rp: T ) -> usize
-    where T: i_renderpass::IRenderPass + 'static {
-        let i = self._rp.len();
-        self._map_string_to_rp.insert( name, i );
-        self._rp.push( Box::new( rp ) );
-        i
+    ///sets the shader program, render target and blending of a pass. the gbuffer is cleared by the first pass writing it in a frame
+    fn begin_pass( & mut self, p: & render_graph::RenderPassDesc, gbuffer_cleared: & mut bool ) -> Result< (), & 'static str > {
+        if let Some( i ) = p._shader_program {
+            let shader_program = match self._shader_programs.get( i as usize ) {
+                Some( x ) => *x,
+                _ => return Err( "render pass shader program not loaded" ),
+            };
+            unsafe {
+                gl::UseProgram( shader_program as _ );
+            }
+            self._current_shader_program = i;
+        }
+        if p.target() == render_graph::TARGET_GBUFFER {
+            let ( w, h ) = renderdevice_gl::GBuffer::viewport_size();
+            let resized = match self._gbuffer {
+                Some( ref g ) => g._w != w || g._h != h,
+                _ => true,
+            };
+            if resized {
+                self._gbuffer = Some( renderdevice_gl::GBuffer::init( w, h )? );
+            }
+            self._gbuffer.as_ref().unwrap().bind_write();
+            if !*gbuffer_cleared {
+                *gbuffer_cleared = true;
+                unsafe {
+                    gl::ClearColor( 0., 0., 0., 0. );
+                    gl::Clear( gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT );
+                }
+            }
+        } else {
+            unsafe {
+                gl::BindFramebuffer( gl::FRAMEBUFFER, 0 );
+            }
+        }
+        match p._kind {
+            render_graph::RenderPassKind::Lighting { .. } => {
+                let shader_program = self.current_shader_program()?;
+                match self._gbuffer {
+                    Some( ref g ) => g.bind_read(),
+                    _ => return Err( "lighting pass without gbuffer" ),
+                }
+                unsafe {
+                    gl::Uniform1i( gl::GetUniformLocation( shader_program as _, "gPosition\0".as_ptr() as * const i8 ), 0 );
+                    gl::Uniform1i( gl::GetUniformLocation( shader_program as _, "gNormal\0".as_ptr() as * const i8 ), 1 );
+                    gl::Uniform1i( gl::GetUniformLocation( shader_program as _, "gAlbedoSpec\0".as_ptr() as * const i8 ), 2 );
+                    //the first light batch replaces the window pixels covered by the gbuffer, later batches add up
+                    gl::Disable( gl::DEPTH_TEST );
+                    gl::Disable( gl::BLEND );
+                    gl::BlendFunc( gl::ONE, gl::ONE );
+                }
+            },
+            render_graph::RenderPassKind::Draw => {
+                unsafe {
+                    if p._blend {
+                        gl::Enable( gl::BLEND );
+                        gl::BlendFunc( gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA );
+                    } else {
+                        gl::Disable( gl::BLEND );
+                    }
+                }
+            },
+        }
+        Ok( () )
     }
-    pub fn get_renderpass( & mut self, name: String ) -> Option< & mut i_renderpass::IRenderPass > {
-        match self._map_string_to_rp.get( &name ){
-            None => None,
-            Some( o ) => {
-                Some( self._rp[*o].deref_mut() )
+    ///restores state changed by a pass. lighting passes copy the gbuffer depth to the window for the passes that follow
+    fn end_pass( & self, p: & render_graph::RenderPassDesc ) {
+        if p._kind != render_graph::RenderPassKind::Draw {
+            if let Some( ref g ) = self._gbuffer {
+                g.blit_depth();
             }
+            unsafe {
+                gl::Enable( gl::DEPTH_TEST );
+            }
+        }
+        unsafe {
+            gl::Disable( gl::BLEND );
+            gl::BindFramebuffer( gl::FRAMEBUFFER, 0 );
         }
     }
     // pub fn uniforms_ref( & mut self ) -> & mut renderdevice_gl::RenderUniformCollection {
@@ -450,6 +609,13 @@ impl Renderer {
         self._draw_group_uniforms.borrow_mut()[ draw_group ].clear();
         Ok( () )
     }
+    ///handle of the shader program in use
+    fn current_shader_program( & self ) -> Result< u64, & 'static str > {
+        match self._shader_programs.get( self._current_shader_program as usize ) {
+            Some( x ) => Ok( *x ),
+            _ => Err( "shader program not loaded" ),
+        }
+    }
     pub fn get_shader_program( & mut self, id: u64 ) -> Option< i64 > {
         self._shader_collection.borrow_mut().get( id )
     }
@@ -0,0 +1,473 @@
+///render backend recording the render events and commands of each frame instead of drawing, for asserting what game logic submits
+
+extern crate pretty_env_logger;
+
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+
+use interface::i_ele;
+use interface::i_renderobj::RenderDevice;
+use interface::i_renderobj;
+use interface::i_component;
+use interface::i_renderer::IRenderer;
+
+use implement::render::renderdevice_gl;
+use implement::render::renderdevice_gl::UniformType;
+use implement::render::renderer_gl::Event;
+use implement::render::render_graph;
+use implement::render::deferred;
+use implement::render::util_gl::ShaderType;
+
+///uniform name, type and values. names keep the trailing nul used by the shaders
+pub type RecordUniform = ( String, UniformType, Vec< f32 > );
+
+///state of a draw group at the time of its dispatch
+#[derive(Debug, Clone)]
+pub struct RecordDispatch {
+    pub _group: usize,
+    pub _vert_count: usize,
+    pub _prim: i_renderobj::RenderObjType,
+    ///values of the dependent uniform groups in group order
+    pub _uniforms: Vec< RecordUniform >,
+    ///dependent uniform groups that could not be resolved
+    pub _missing_uniform_groups: Vec< u64 >,
+    ///texture ranges in absolute vertex offsets as bound by renderer_gl, ranges without a texture use the default texture
+    pub _texture_ranges: Vec< i_component::TextureRange >,
+}
+
+impl RecordDispatch {
+    ///looks up a uniform value, the trailing nul of the name is optional
+    pub fn uniform( & self, name: & str ) -> Option< & [ f32 ] > {
+        let name = name.trim_end_matches( '\0' );
+        self._uniforms.iter()
+            .find( |x| x.0.trim_end_matches( '\0' ) == name )
+            .map( |x| & x.2[..] )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RecordCmd {
+    ///render event carrying an object, with its component count
+    AddObj( usize ),
+    ///render event carrying an object for a draw group, with the draw group and component count
+    AddObjDrawGroup( usize, usize ),
+    ///point lights set for lighting passes
+    SetPointLights( usize ),
+    LoadShader( Vec< ( String, ShaderType ) > ),
+    LoadTexture { _descrip: String, _w: usize, _h: usize },
+    CreateDrawGroup { _group: usize, _prim: i_renderobj::RenderObjType },
+    ///render graph applied, with pass names in execution order
+    SetRenderGraph( Vec< String > ),
+    ///vertices appended to a draw group
+    RenderBuffer { _group: usize, _vert_count: usize },
+    TextureRanges( Vec< i_component::TextureRange > ),
+    ///uniform values and uniform groups, sorted by name and group id
+    Uniform { _values: Vec< RecordUniform >, _groups: Vec< ( u64, Vec< String > ) > },
+    DrawGroupClear( usize ),
+    DrawGroupDependentObjects( usize, Vec< usize > ),
+    DrawGroupBind( usize ),
+    DrawGroupDependentUniforms( usize, Vec< u64 > ),
+    DrawGroupDispatch( RecordDispatch ),
+}
+
+///commands for one call of process_render_events. render events are recorded on submission, components of submitted objects when the renderer processes them on dispatch
+#[derive(Debug, Clone, Default)]
+pub struct RecordFrame {
+    pub _index: u64,
+    pub _cmds: Vec< RecordCmd >,
+    ///error returned for the frame, if any
+    pub _err: Option< & 'static str >,
+}
+
+impl RecordFrame {
+    pub fn dispatches( & self ) -> Vec< & RecordDispatch > {
+        self._cmds.iter().filter_map( |x| match x {
+            &RecordCmd::DrawGroupDispatch( ref d ) => Some( d ),
+            _ => None,
+        } ).collect()
+    }
+    pub fn bound_groups( & self ) -> Vec< usize > {
+        self._cmds.iter().filter_map( |x| match x {
+            &RecordCmd::DrawGroupBind( g ) => Some( g ),
+            _ => None,
+        } ).collect()
+    }
+    ///first dispatch of a draw group
+    pub fn dispatch( & self, group: usize ) -> Option< & RecordDispatch > {
+        self.dispatches().into_iter().find( |x| x._group == group )
+    }
+}
+
+pub struct RendererRecord {
+    pub _frames: Vec< RecordFrame >,
+    ///frames processed so far, including cleared ones
+    pub _frame_count: u64,
+    _draw_groups: Vec< renderdevice_gl::RenderDrawGroup >,
+    _objs: Vec< i_ele::Ele >,
+    _uniforms: renderdevice_gl::RenderUniformCollection,
+    _draw_group_uniforms: Vec< Vec< u64 > >,
+    _draw_group_texture_ranges: Vec< Vec< i_component::TextureRange > >,
+    _cmds: Vec< RecordCmd >,
+    _shader_programs: usize,
+    _point_lights: Vec< deferred::PointLight >,
+    _is_init: bool,
+    _render_graph: render_graph::RenderGraph,
+}
+
+impl IRenderer for RendererRecord {
+
+    type EventRender = Event;
+
+    fn init() -> Result< Self, & 'static str > {
+        Ok( RendererRecord {
+            _frames: vec![],
+            _frame_count: 0,
+            _draw_groups: vec![],
+            _objs: vec![],
+            _uniforms: Default::default(),
+            _draw_group_uniforms: vec![],
+            _draw_group_texture_ranges: vec![],
+            _cmds: vec![],
+            _shader_programs: 0,
+            _point_lights: vec![],
+            _is_init: false,
+            _render_graph: Default::default(),
+        } )
+    }
+    ///records a frame following the same command flow as renderer_gl, the frame is kept even if an error is returned
+    fn process_render_events( & mut self, e: Vec< Self::EventRender > ) -> Result< (), & 'static str > {
+        let ret = self.process_frame( e );
+        if ret.is_err() {
+            //drop commands left pending by the failed frame
+            self._objs.clear();
+        }
+        let frame = RecordFrame {
+            _index: self._frame_count,
+            _cmds: self._cmds.drain(..).collect(),
+            _err: ret.err(),
+        };
+        self._frames.push( frame );
+        self._frame_count += 1;
+        ret
+    }
+}
+
+impl render_graph::DrawGroupBackend for RendererRecord {
+    fn draw_group_mut( & mut self, group: usize ) -> Result< & mut renderdevice_gl::RenderDrawGroup, & 'static str > {
+        self.group_mut( group )
+    }
+    fn texture_ranges_mut( & mut self, group: usize ) -> Result< & mut Vec< i_component::TextureRange >, & 'static str > {
+        match self._draw_group_texture_ranges.get_mut( group ) {
+            Some( x ) => Ok( x ),
+            _ => Err( "object index out of range" ),
+        }
+    }
+    fn flush_uniform( & mut self, o: & i_component::ComponentRenderUniform ) -> Result< (), & 'static str > {
+        o.flush_into_uniform_collection( 0, & mut self._uniforms )?;
+        self._cmds.push( record_uniform( o ) );
+        Ok( () )
+    }
+    fn clear_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        self._cmds.push( RecordCmd::DrawGroupClear( group ) );
+        self.group_mut( group )?.clear_buff_data();
+        self._draw_group_texture_ranges[ group ].clear();
+        Ok( () )
+    }
+    fn dependent_objects( & mut self, group: usize, objs: & [ usize ] ) -> Result< (), & 'static str > {
+        self._cmds.push( RecordCmd::DrawGroupDependentObjects( group, objs.to_vec() ) );
+        Ok( () )
+    }
+    fn bind_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        self._cmds.push( RecordCmd::DrawGroupBind( group ) );
+        Ok( () )
+    }
+    fn set_group_uniforms( & mut self, group: usize, uniform_groups: & [ u64 ] ) -> Result< (), & 'static str > {
+        self._cmds.push( RecordCmd::DrawGroupDependentUniforms( group, uniform_groups.to_vec() ) );
+        self.group_mut( group )?;
+        self._draw_group_uniforms[ group ] = uniform_groups.to_vec();
+        Ok( () )
+    }
+    fn draw_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        let d = self.record_dispatch( group )?;
+        self._cmds.push( RecordCmd::DrawGroupDispatch( d ) );
+        Ok( () )
+    }
+    fn texture_ranges_submitted( & mut self, ranges: & [ i_component::TextureRange ] ) {
+        self._cmds.push( RecordCmd::TextureRanges( ranges.to_vec() ) );
+    }
+    fn buffer_flushed( & mut self, group: usize, vert_count: usize ) {
+        self._cmds.push( RecordCmd::RenderBuffer { _group: group, _vert_count: vert_count } );
+    }
+}
+
+impl RendererRecord {
+    pub fn frame( & self, index: u64 ) -> Option< & RecordFrame > {
+        self._frames.iter().find( |x| x._index == index )
+    }
+    pub fn last_frame( & self ) -> Option< & RecordFrame > {
+        self._frames.last()
+    }
+    ///drops recorded frames, draw group and uniform states are kept
+    pub fn clear( & mut self ) {
+        self._frames.clear();
+    }
+    ///text dump of all recorded frames
+    pub fn dump( & self ) -> String {
+        self._frames.iter().map( |x| format!( "{}", x ) ).collect()
+    }
+    pub fn write_dump( & self, file_path: & str ) -> Result< (), & 'static str > {
+        let mut f = match File::create( file_path ) {
+            Ok( x ) => x,
+            _ => { return Err( "recording dump file creation failed" ) },
+        };
+        match f.write_all( self.dump().as_bytes() ) {
+            Ok( _ ) => Ok( () ),
+            _ => Err( "recording dump write failed" ),
+        }
+    }
+    fn process_frame( & mut self, e: Vec< Event > ) -> Result< (), & 'static str > {
+        //first time initialization
+        if !self._is_init {
+            info!("recording renderer: first time initialization.");
+            self._is_init = true;
+            self.set_render_graph( Default::default() )?;
+        }
+
+        //render graphs apply before the rest of the frame, once the shader programs they use are loaded
+        let ( shaders, graphs, e ) = render_graph::split_frame_events( e, self._shader_programs );
+        for x in shaders {
+            self._cmds.push( RecordCmd::LoadShader( x ) );
+            self._shader_programs += 1;
+        }
+        for g in graphs {
+            self.set_render_graph( g )?;
+            let names = self._render_graph.ordered_passes()?.iter().map( |x| x._name.clone() ).collect();
+            self._cmds.push( RecordCmd::SetRenderGraph( names ) );
+        }
+
+        let dummy_str = "";
+        for i in self._render_graph.commands_frame_begin()? {
+            self.add_obj( dummy_str, i )?;
+        }
+
+        for i in e {
+            match i {
+                Event::AddObj( mut x ) => {
+                    x.update_components_from_impl()?;
+                    self._cmds.push( RecordCmd::AddObj( x._components.len() ) );
+                    self.push_obj( x )?;
+                },
+                Event::AddObjDrawGroup( g, mut x ) => {
+                    x.update_components_from_impl()?;
+                    self._cmds.push( RecordCmd::AddObjDrawGroup( g, x._components.len() ) );
+                    x._components.insert( 0, Box::new( i_component::ComponentDrawGroupTarget { _group_id: g } ) );
+                    self.push_obj( x )?;
+                },
+                Event::SetPointLights( x ) => {
+                    self._cmds.push( RecordCmd::SetPointLights( x.len() ) );
+                    self._point_lights = x;
+                },
+                Event::LoadTexture( s, data, w, h ) => {
+                    if w == 0 || h == 0 || data.len() != w * h * 3 {
+                        return Err( "loading texture failed" )
+                    }
+                    self._cmds.push( RecordCmd::LoadTexture { _descrip: s, _w: w, _h: h } );
+                },
+                Event::CreateDrawGroup( x ) => {
+                    self.create_draw_group( x )?;
+                },
+                Event::LoadShader( x ) => {
+                    self._cmds.push( RecordCmd::LoadShader( x ) );
+                    self._shader_programs += 1;
+                },
+                Event::SetRenderGraph( _ ) => {},
+            }
+        }
+
+        //lighting passes are dispatched once per light batch
+        let passes : Vec< render_graph::RenderPassDesc > = self._render_graph.ordered_passes()?.into_iter().cloned().collect();
+        for p in passes.iter() {
+            match p._kind {
+                render_graph::RenderPassKind::Draw => {
+                    for i in render_graph::RenderGraph::commands_pass( p ) {
+                        self.add_obj( dummy_str, i )?;
+                    }
+                },
+                render_graph::RenderPassKind::Lighting { _light_group } => {
+                    let view = deferred::view_matrix( & self._uniforms )?;
+                    let lights = deferred::to_eye_space( & self._point_lights[..], & view[..] );
+                    for ( n, b ) in deferred::batches( & lights[..] ).into_iter().enumerate() {
+                        deferred::set_light_uniforms( & mut self._uniforms, p._shader_program.unwrap_or( 0 ), _light_group, b, n == 0 )?;
+                        for i in render_graph::RenderGraph::commands_pass( p ) {
+                            self.add_obj( dummy_str, i )?;
+                        }
+                    }
+                },
+            }
+        }
+
+        Ok( () )
+    }
+    ///builds the graph if needed and creates its missing draw groups, render targets are not checked. the draw groups of lighting passes are filled with a screen quad
+    pub fn set_render_graph( & mut self, mut graph: render_graph::RenderGraph ) -> Result< (), & 'static str > {
+        let create = graph.prepare( self._draw_groups.len(), self._shader_programs, &[ render_graph::TARGET_DEFAULT, render_graph::TARGET_GBUFFER ] )?;
+        for t in create {
+            self.create_draw_group( t )?;
+        }
+        for p in graph._passes.iter() {
+            if p._kind != render_graph::RenderPassKind::Draw {
+                let g = self.group_mut( p._draw_group )?;
+                g.clear_buff_data();
+                deferred::screen_quad().flush_into_render_device( g )?;
+            }
+        }
+        self._render_graph = graph;
+        Ok( () )
+    }
+    pub fn create_draw_group( & mut self, prim_type: i_renderobj::RenderObjType ) -> Result< usize, & 'static str > {
+        let i = self._draw_groups.len();
+        let draw_group = match prim_type {
+            i_renderobj::RenderObjType::TRI => renderdevice_gl::RenderDrawGroup::init_with_default_format_triangle( i as _, i as _ ),
+            i_renderobj::RenderObjType::POINT => renderdevice_gl::RenderDrawGroup::init_with_default_format_point( i as _, i as _ ),
+            _ => return Err("unsupported primitive type for draw group detected")
+        };
+        self._draw_groups.push( draw_group );
+        self._draw_group_uniforms.push( vec![] );
+        self._draw_group_texture_ranges.push( vec![] );
+        self._cmds.push( RecordCmd::CreateDrawGroup { _group: i, _prim: prim_type } );
+        Ok( i )
+    }
+    #[allow(unused)]
+    pub fn add_obj( & mut self, name: &str, mut e: i_ele::Ele ) -> Result< usize, & 'static str > {
+        e.update_components_from_impl()?;
+        self.push_obj( e )
+    }
+    ///queues an object with up to date components, processing queued objects on a dispatch command
+    fn push_obj( & mut self, e: i_ele::Ele ) -> Result< usize, & 'static str > {
+        let dispatch = e._components.iter()
+            .filter_map( |j| j.as_any().downcast_ref::< i_component::ComponentDrawGroupDispatch >().map( |o| o._group_id ) )
+            .last();
+        self._objs.push( e );
+        if let Some( group_id ) = dispatch {
+            trace!("detected trigger for draw group dispatch");
+            self.process_objs( group_id )?;
+        }
+        Ok( self._objs.len() )
+    }
+    pub fn process_objs( & mut self, group_index: usize ) -> Result< (), & 'static str > {
+        let drained = self._objs.drain(..).collect::<Vec<_>>();
+        render_graph::process_objs( self, drained, group_index )
+    }
+    fn group_mut( & mut self, group_index: usize ) -> Result< & mut renderdevice_gl::RenderDrawGroup, & 'static str > {
+        match self._draw_groups.get_mut( group_index ) {
+            Some( x ) => Ok( x ),
+            _ => Err( "object index out of range" ),
+        }
+    }
+    fn record_dispatch( & mut self, group_index: usize ) -> Result< RecordDispatch, & 'static str > {
+        let ( vert_count, prim ) = {
+            let g = self.group_mut( group_index )?;
+            ( g.vert_count(), g._primitive_type )
+        };
+        let mut uniforms = vec![];
+        let mut missing = vec![];
+        for &uniform_group in self._draw_group_uniforms[ group_index ].iter() {
+            match self._uniforms.get_group( uniform_group ) {
+                Ok( v ) => { uniforms.extend( v ); },
+                _ => { missing.push( uniform_group ); },
+            }
+        }
+        Ok( RecordDispatch {
+            _group: group_index,
+            _vert_count: vert_count,
+            _prim: prim,
+            _uniforms: uniforms,
+            _missing_uniform_groups: missing,
+            _texture_ranges: self._draw_group_texture_ranges[ group_index ].clone(),
+        } )
+    }
+}
+
+fn record_uniform( o: & i_component::ComponentRenderUniform ) -> RecordCmd {
+    let mut values : Vec< RecordUniform > = vec![];
+    let dicts = [ ( & o._data_dict_vf, UniformType::VEC ),
+                  ( & o._data_dict_mat4f, UniformType::MAT4 ),
+                  ( & o._data_dict_mat3f, UniformType::MAT3 ) ];
+    for &( d, t ) in dicts.iter() {
+        values.extend( d.iter().map( |( k, v )| ( k.clone(), t, v.clone() ) ) );
+    }
+    values.sort_by( |a, b| a.0.cmp( & b.0 ) );
+    let mut groups : Vec< ( u64, Vec< String > ) > = o._data_uniform_group.iter().map( |( k, v )| ( *k, v.clone() ) ).collect();
+    groups.sort_by_key( |x| x.0 );
+    RecordCmd::Uniform { _values: values, _groups: groups }
+}
+
+fn name( s: & str ) -> & str {
+    s.trim_end_matches( '\0' )
+}
+
+fn fmt_uniforms( f: & mut fmt::Formatter, u: & [ RecordUniform ] ) -> fmt::Result {
+    for &( ref n, ref t, ref v ) in u.iter() {
+        writeln!( f, "    {} {:?} {:?}", name( n ), t, v )?;
+    }
+    Ok( () )
+}
+
+fn fmt_ranges( r: & [ i_component::TextureRange ] ) -> String {
+    let v : Vec< String > = r.iter().map( |x| format!( "{}:{}+{}", x._texture.as_ref().map_or( "default", |t| t.as_str() ), x._vert_offset, x._vert_count ) ).collect();
+    v.join( " " )
+}
+
+///one command per line, uniform values on indented lines below their command
+impl fmt::Display for RecordCmd {
+    fn fmt( & self, f: & mut fmt::Formatter ) -> fmt::Result {
+        match self {
+            &RecordCmd::AddObj( n ) => writeln!( f, "  add_obj components={}", n ),
+            &RecordCmd::AddObjDrawGroup( g, n ) => writeln!( f, "  add_obj_draw_group {} components={}", g, n ),
+            &RecordCmd::SetPointLights( n ) => writeln!( f, "  set_point_lights {}", n ),
+            &RecordCmd::LoadShader( ref v ) => {
+                let s : Vec< String > = v.iter().map( |x| match x.1 {
+                    ShaderType::VERTEX => format!( "vertex:{}", x.0 ),
+                    ShaderType::FRAGMENT => format!( "fragment:{}", x.0 ),
+                } ).collect();
+                writeln!( f, "  load_shader {}", s.join( " " ) )
+            },
+            &RecordCmd::LoadTexture { ref _descrip, _w, _h } => writeln!( f, "  load_texture {} {}x{}", _descrip, _w, _h ),
+            &RecordCmd::CreateDrawGroup { _group, _prim } => writeln!( f, "  create_draw_group {} {:?}", _group, _prim ),
+            &RecordCmd::SetRenderGraph( ref v ) => writeln!( f, "  set_render_graph {}", v.join( "," ) ),
+            &RecordCmd::RenderBuffer { _group, _vert_count } => writeln!( f, "  render_buffer group={} verts={}", _group, _vert_count ),
+            &RecordCmd::TextureRanges( ref r ) => writeln!( f, "  texture_ranges {}", fmt_ranges( r ) ),
+            &RecordCmd::Uniform { ref _values, ref _groups } => {
+                let g : Vec< String > = _groups.iter().map( |x| {
+                    let names : Vec< & str > = x.1.iter().map( |n| name( n ) ).collect();
+                    format!( "{}:[{}]", x.0, names.join( "," ) )
+                } ).collect();
+                writeln!( f, "  uniform groups={}", g.join( " " ) )?;
+                fmt_uniforms( f, _values )
+            },
+            &RecordCmd::DrawGroupClear( g ) => writeln!( f, "  draw_group_clear {}", g ),
+            &RecordCmd::DrawGroupDependentObjects( g, ref o ) => writeln!( f, "  draw_group_dependent_objects {} {:?}", g, o ),
+            &RecordCmd::DrawGroupBind( g ) => writeln!( f, "  draw_group_bind {}", g ),
+            &RecordCmd::DrawGroupDependentUniforms( g, ref u ) => writeln!( f, "  draw_group_dependent_uniforms {} {:?}", g, u ),
+            &RecordCmd::DrawGroupDispatch( ref d ) => {
+                writeln!( f, "  draw_group_dispatch {} {:?} verts={} missing_uniform_groups={:?} texture_ranges=[{}]",
+                          d._group, d._prim, d._vert_count, d._missing_uniform_groups, fmt_ranges( & d._texture_ranges ) )?;
+                fmt_uniforms( f, & d._uniforms )
+            },
+        }
+    }
+}
+
+impl fmt::Display for RecordFrame {
+    fn fmt( & self, f: & mut fmt::Formatter ) -> fmt::Result {
+        match self._err {
+            Some( e ) => writeln!( f, "frame {} error={}", self._index, e )?,
+            _ => writeln!( f, "frame {}", self._index )?,
+        }
+        for i in self._cmds.iter() {
+            write!( f, "{}", i )?;
+        }
+        Ok( () )
+    }
+}
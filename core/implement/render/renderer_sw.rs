@@ -0,0 +1,578 @@
+///software rasterizer backend accepting the same render events as renderer_gl, for rendering without a gpu
+
+extern crate pretty_env_logger;
+
+use std::f32;
+use std::collections::HashMap;
+
+use interface::i_ele;
+use interface::i_renderobj::RenderDevice;
+use interface::i_renderobj;
+use interface::i_component;
+use interface::i_renderer::IRenderer;
+
+use implement::file::ppm;
+use implement::math::vec3::{ dot, normalize };
+use implement::render::renderdevice_gl;
+use implement::render::renderer_gl::Event;
+use implement::render::render_graph;
+
+///rgb8 texture, first row at texture coordinate t = 0
+#[derive(Debug, Clone)]
+pub struct TextureSw {
+    pub _descrip: String,
+    pub _w: usize,
+    pub _h: usize,
+    pub _data: Vec< u8 >,
+}
+
+impl TextureSw {
+    ///bilinear filtering with clamp to edge
+    pub fn sample( & self, s: f32, t: f32 ) -> [f32;3] {
+        let x = s * self._w as f32 - 0.5;
+        let y = t * self._h as f32 - 0.5;
+        let ( x0, y0 ) = ( x.floor(), y.floor() );
+        let ( fx, fy ) = ( x - x0, y - y0 );
+        let texel = |i: f32, j: f32| {
+            let i = ( i.max( 0. ) as usize ).min( self._w - 1 );
+            let j = ( j.max( 0. ) as usize ).min( self._h - 1 );
+            let o = ( j * self._w + i ) * 3;
+            [ self._data[o] as f32 / 255., self._data[o+1] as f32 / 255., self._data[o+2] as f32 / 255. ]
+        };
+        let ( a, b, c, d ) = ( texel( x0, y0 ), texel( x0 + 1., y0 ), texel( x0, y0 + 1. ), texel( x0 + 1., y0 + 1. ) );
+        let mut ret = [ 0.; 3 ];
+        for k in 0..3 {
+            ret[k] = ( a[k] * ( 1. - fx ) + b[k] * fx ) * ( 1. - fy ) + ( c[k] * ( 1. - fx ) + d[k] * fx ) * fy;
+        }
+        ret
+    }
+}
+
+///vertex after the built in ads vertex stage
+#[derive(Debug, Clone, Copy)]
+struct VertOut {
+    _clip: [f32;4],
+    _light: [f32;3],
+    _tc: [f32;2],
+}
+
+///uniform values consumed by the built in ads shading, matching ads.vs
+#[derive(Debug, Clone, Copy)]
+struct AdsUniforms {
+    _mvp: [f32;16],
+    _model_view: [f32;16],
+    _normal: [f32;9],
+    _light_pos: [f32;3],
+    _la: [f32;3],
+    _ld: [f32;3],
+    _ls: [f32;3],
+    _ka: [f32;3],
+    _kd: [f32;3],
+    _ks: [f32;3],
+    _shininess: f32,
+}
+
+pub struct RendererSw {
+    pub _width: usize,
+    pub _height: usize,
+    ///rgb8 color buffer, first row at the top of the image
+    pub _color: Vec< u8 >,
+    ///window space depth in [0,1]
+    pub _depth: Vec< f32 >,
+    pub _clear_color: [u8;3],
+    ///counter clockwise triangles are front facing. off by default to match renderer_gl state
+    pub _cull_back_face: bool,
+    ///frames rendered so far
+    pub _frame: u64,
+    _draw_groups: Vec< renderdevice_gl::RenderDrawGroup >,
+    _objs: Vec< i_ele::Ele >,
+    _uniforms: renderdevice_gl::RenderUniformCollection,
+    _draw_group_uniforms: Vec< Vec< u64 > >,
+    ///texture bindings per draw group in absolute vertex offsets
+    _draw_group_texture_ranges: Vec< Vec< i_component::TextureRange > >,
+    _textures: Vec< TextureSw >,
+    _map_descrip_to_texture: HashMap< String, usize >,
+    ///texture used for ranges without an explicit texture
+    _default_texture: Option< usize >,
+    ///shader sources are accepted for compatibility, shading is the built in ads model
+    _shader_programs: usize,
+    _is_init: bool,
+    _render_graph: render_graph::RenderGraph,
+}
+
+impl IRenderer for RendererSw {
+
+    type EventRender = Event;
+
+    ///same size as the default window of the kernel
+    fn init() -> Result< Self, & 'static str > {
+        RendererSw::init_with_size( 500, 500 )
+    }
+    fn process_render_events( & mut self, e: Vec< Self::EventRender > ) -> Result< (), & 'static str > {
+        //first time initialization
+        if !self._is_init {
+            info!("software renderer: first time initialization.");
+            self._is_init = true;
+            self.set_render_graph( Default::default() )?;
+        }
+
+        //render graphs apply before the rest of the frame, once the shader programs they use are loaded
+        let ( shaders, graphs, e ) = render_graph::split_frame_events( e, self._shader_programs );
+        self._shader_programs += shaders.len();
+        for g in graphs {
+            self.set_render_graph( g )?;
+        }
+
+        self.clear_frame();
+
+        let dummy_str = "";
+        for i in self._render_graph.commands_frame_begin()? {
+            self.add_obj( dummy_str, i )?;
+        }
+
+        for i in e {
+            match i {
+                Event::AddObj( x ) => {
+                    self.add_obj( dummy_str, x )?;
+                },
+                Event::LoadShader( _ ) => {
+                    self._shader_programs += 1;
+                },
+                Event::LoadTexture( s, data, w, h ) => {
+                    self.load_texture( s, &data, w, h )?;
+                },
+                Event::CreateDrawGroup( x ) => {
+                    self.create_draw_group( x )?;
+                },
+                Event::AddObjDrawGroup( g, x ) => {
+                    self.add_obj_to_draw_group( dummy_str, Some( g ), x )?;
+                },
+                Event::SetPointLights( _ ) => {
+                    //lighting passes are not supported, shading is the built in ads model
+                },
+                Event::SetRenderGraph( _ ) => {},
+            }
+        }
+
+        for i in self._render_graph.commands_frame_end()? {
+            self.add_obj( dummy_str, i )?;
+        }
+
+        self._frame += 1;
+        Ok( () )
+    }
+}
+
+impl render_graph::DrawGroupBackend for RendererSw {
+    fn draw_group_mut( & mut self, group: usize ) -> Result< & mut renderdevice_gl::RenderDrawGroup, & 'static str > {
+        match self._draw_groups.get_mut( group ) {
+            Some( x ) => Ok( x ),
+            _ => Err( "object index out of range" ),
+        }
+    }
+    fn texture_ranges_mut( & mut self, group: usize ) -> Result< & mut Vec< i_component::TextureRange >, & 'static str > {
+        if self._draw_group_texture_ranges.len() <= group {
+            self._draw_group_texture_ranges.resize( group + 1, vec![] );
+        }
+        Ok( & mut self._draw_group_texture_ranges[ group ] )
+    }
+    fn flush_uniform( & mut self, o: & i_component::ComponentRenderUniform ) -> Result< (), & 'static str > {
+        o.flush_into_uniform_collection( 0, & mut self._uniforms )
+    }
+    fn clear_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        self.reset_draw_group_data( &[ group ] ).is_ok();
+        Ok( () )
+    }
+    ///vertex data is read directly from the draw group
+    fn bind_group( & mut self, _group: usize ) -> Result< (), & 'static str > {
+        Ok( () )
+    }
+    fn set_group_uniforms( & mut self, group: usize, uniform_groups: & [ u64 ] ) -> Result< (), & 'static str > {
+        self.set_draw_group_uniforms( group, uniform_groups );
+        Ok( () )
+    }
+    fn draw_group( & mut self, group: usize ) -> Result< (), & 'static str > {
+        self.drawcall_draw_group( &[ group ] )
+    }
+}
+
+impl RendererSw {
+    pub fn init_with_size( width: usize, height: usize ) -> Result< RendererSw, & 'static str > {
+        if width == 0 || height == 0 {
+            return Err( "software renderer size invalid" )
+        }
+        Ok( RendererSw {
+            _width: width,
+            _height: height,
+            _color: vec![ 0; width * height * 3 ],
+            _depth: vec![ 1.; width * height ],
+            _clear_color: [ 0, 0, 0 ],
+            _cull_back_face: false,
+            _frame: 0,
+            _draw_groups: vec![],
+            _objs: vec![],
+            _uniforms: Default::default(),
+            _draw_group_uniforms: vec![],
+            _draw_group_texture_ranges: vec![],
+            _textures: vec![],
+            _map_descrip_to_texture: HashMap::new(),
+            _default_texture: None,
+            _shader_programs: 0,
+            _is_init: false,
+            _render_graph: Default::default(),
+        } )
+    }
+    pub fn clear_frame( & mut self ) {
+        for p in self._color.chunks_mut( 3 ) {
+            p.copy_from_slice( &self._clear_color[..] );
+        }
+        for d in self._depth.iter_mut() {
+            *d = 1.;
+        }
+    }
+    pub fn frame( & self ) -> &[ u8 ] {
+        &self._color[..]
+    }
+    pub fn pixel( & self, x: usize, y: usize ) -> [u8;3] {
+        let o = ( y * self._width + x ) * 3;
+        [ self._color[o], self._color[o+1], self._color[o+2] ]
+    }
+    pub fn write_ppm( & self, file_path: & str ) -> Result< (), & 'static str > {
+        ppm::write( file_path, self._width as u64, self._height as u64, & self._color )
+    }
+    pub fn load_texture( & mut self, description: String, image: &[u8], w: usize, h: usize ) -> Result< u64, & 'static str > {
+        if w == 0 || h == 0 || image.len() != w * h * 3 {
+            return Err( "loading texture failed" )
+        }
+        let i = self._textures.len();
+        self._textures.push( TextureSw { _descrip: description.clone(), _w: w, _h: h, _data: image.to_vec() } );
+        self._map_descrip_to_texture.insert( description, i );
+        if self._default_texture.is_none() {
+            self._default_texture = Some( i );
+        }
+        Ok( i as u64 )
+    }
+    pub fn get_texture( & self, description: & str ) -> Option< & TextureSw > {
+        self._map_descrip_to_texture.get( description ).map( |&i| & self._textures[i] )
+    }
+    ///builds the graph if needed and creates its missing draw groups, existing draw groups are kept. only draw passes into the window are supported
+    pub fn set_render_graph( & mut self, mut graph: render_graph::RenderGraph ) -> Result< (), & 'static str > {
+        let create = graph.prepare( self._draw_groups.len(), self._shader_programs, &[ render_graph::TARGET_DEFAULT ] )?;
+        if graph._passes.iter().any( |p| p._kind != render_graph::RenderPassKind::Draw ) {
+            return Err( "render pass kind not supported" )
+        }
+        for t in create {
+            self.create_draw_group( t )?;
+        }
+        self._render_graph = graph;
+        Ok( () )
+    }
+    pub fn create_draw_group( & mut self, prim_type: i_renderobj::RenderObjType ) -> Result< usize, & 'static str > {
+        let i = self._draw_groups.len();
+        let draw_group = match prim_type {
+            i_renderobj::RenderObjType::TRI => renderdevice_gl::RenderDrawGroup::init_with_default_format_triangle( i as _, i as _ ),
+            i_renderobj::RenderObjType::POINT => renderdevice_gl::RenderDrawGroup::init_with_default_format_point( i as _, i as _ ),
+            _ => return Err("unsupported primitive type for draw group detected")
+        };
+        self._draw_groups.push( draw_group );
+        Ok( i )
+    }
+    pub fn add_obj( & mut self, name: &str, e: i_ele::Ele ) -> Result< usize, & 'static str > {
+        self.add_obj_to_draw_group( name, None, e )
+    }
+    ///adds an object, with its render buffers going to draw_group if given
+    #[allow(unused)]
+    pub fn add_obj_to_draw_group( & mut self, name: &str, draw_group: Option< usize >, mut e: i_ele::Ele ) -> Result< usize, & 'static str > {
+        e.update_components_from_impl()?;
+        if let Some( g ) = draw_group {
+            e._components.insert( 0, Box::new( i_component::ComponentDrawGroupTarget { _group_id: g } ) );
+        }
+        let dispatch = e._components.iter()
+            .filter_map( |j| j.as_any().downcast_ref::< i_component::ComponentDrawGroupDispatch >().map( |o| o._group_id ) )
+            .last();
+        self._objs.push( e );
+        if let Some( group_id ) = dispatch {
+            trace!("detected trigger for draw group dispatch");
+            self.process_objs( group_id )?;
+        }
+        Ok( self._objs.len() )
+    }
+    pub fn process_objs( & mut self, group_index: usize ) -> Result< (), & 'static str > {
+        let drained = self._objs.drain(..).collect::<Vec<_>>();
+        render_graph::process_objs( self, drained, group_index )
+    }
+    pub fn reset_draw_group_data( & mut self, group_indices: &[ usize ] ) -> Result< (), & 'static str > {
+        for &i in group_indices {
+            if i >= self._draw_groups.len() {
+                return Err( "object index out of range" )
+            }
+            self._draw_groups[ i ].clear_buff_data();
+            if let Some( r ) = self._draw_group_texture_ranges.get_mut( i ) {
+                r.clear();
+            }
+        }
+        Ok( () )
+    }
+    pub fn set_draw_group_uniforms( & mut self, draw_group: usize, uniform_group: &[u64] ) {
+        if self._draw_group_uniforms.len() <= draw_group {
+            self._draw_group_uniforms.resize( draw_group + 1, vec![] );
+        }
+        self._draw_group_uniforms[ draw_group ] = uniform_group.to_vec();
+    }
+    ///applies the draw group's uniform groups then rasterizes its buffer range by range with each range's texture
+    pub fn drawcall_draw_group( & mut self, group_indices: &[ usize ] ) -> Result< (), & 'static str > {
+        for &i in group_indices {
+            if i >= self._draw_groups.len() {
+                return Err( "object index out of range" )
+            }
+            if self._draw_groups[i].vert_count() == 0 {
+                continue
+            }
+            let mut vals = HashMap::new();
+            if let Some( groups ) = self._draw_group_uniforms.get( i ) {
+                for g in groups.iter() {
+                    for ( name, _, v ) in self._uniforms.get_group( *g )? {
+                        vals.insert( name, v );
+                    }
+                }
+            }
+            let u = ads_uniforms( & vals )?;
+            let ranges = match self._draw_group_texture_ranges.get( i ) {
+                Some( r ) if r.len() > 0 => r.clone(),
+                _ => vec![ i_component::TextureRange { _texture: None, _vert_offset: 0, _vert_count: self._draw_groups[i].vert_count() } ],
+            };
+            for range in ranges.iter() {
+                let tex = match range._texture {
+                    Some( ref descrip ) => {
+                        match self._map_descrip_to_texture.get( descrip ) {
+                            Some( &t ) => Some( t ),
+                            None => {
+                                warn!( "texture not found: {}, using default", descrip );
+                                self._default_texture
+                            },
+                        }
+                    },
+                    None => self._default_texture,
+                };
+                self.draw_range( i, & u, tex, range._vert_offset, range._vert_count )?;
+            }
+        }
+        Ok( () )
+    }
+    fn draw_range( & mut self, group_index: usize, u: & AdsUniforms, tex: Option< usize >, start: usize, count: usize ) -> Result< (), & 'static str > {
+        let verts = {
+            let g = & self._draw_groups[ group_index ];
+            if start + count > g.vert_count() {
+                return Err( "draw buffer range out of bounds" )
+            }
+            let stride = g._stride as usize / ::std::mem::size_of::<f32>();
+            ( start..start + count ).map( |v| vertex_stage( u, &g._buffer_draw[ v * stride..v * stride + 8 ] ) ).collect::< Vec< _ > >()
+        };
+        match self._draw_groups[ group_index ]._primitive_type {
+            i_renderobj::RenderObjType::TRI => {
+                for t in verts.chunks( 3 ) {
+                    if t.len() == 3 {
+                        for tri in clip_near( [ t[0], t[1], t[2] ] ) {
+                            self.raster_triangle( & tri, tex );
+                        }
+                    }
+                }
+            },
+            i_renderobj::RenderObjType::POINT => {
+                for v in verts.iter() {
+                    self.raster_point( v, tex );
+                }
+            },
+            _ => return Err( "unsupported primite type for drawing detected" )
+        }
+        Ok( () )
+    }
+    ///fragment color: the bound texture as in ads.fs, or the ads light intensity when no texture is loaded
+    fn shade( & self, light: [f32;3], tc: [f32;2], tex: Option< usize > ) -> [u8;3] {
+        let c = match tex {
+            Some( t ) => self._textures[t].sample( tc[0], tc[1] ),
+            None => light,
+        };
+        [ to_u8( c[0] ), to_u8( c[1] ), to_u8( c[2] ) ]
+    }
+    ///window coordinates with the origin at the top left and depth in [0,1]
+    fn to_window( & self, clip: [f32;4] ) -> [f32;3] {
+        let w = clip[3];
+        [ ( clip[0] / w + 1. ) * 0.5 * self._width as f32,
+          ( 1. - clip[1] / w ) * 0.5 * self._height as f32,
+          ( clip[2] / w + 1. ) * 0.5 ]
+    }
+    fn raster_triangle( & mut self, v: & [ VertOut; 3 ], tex: Option< usize > ) {
+        let p = [ self.to_window( v[0]._clip ), self.to_window( v[1]._clip ), self.to_window( v[2]._clip ) ];
+        //signed area in the y down window, negative for counter clockwise triangles in normalized device coordinates
+        let area = ( p[1][0] - p[0][0] ) * ( p[2][1] - p[0][1] ) - ( p[2][0] - p[0][0] ) * ( p[1][1] - p[0][1] );
+        if area == 0. || !area.is_finite() || ( self._cull_back_face && area > 0. ) {
+            return
+        }
+        let x_min = p.iter().fold( f32::INFINITY, |acc, x| acc.min( x[0] ) ).floor().max( 0. ) as usize;
+        let y_min = p.iter().fold( f32::INFINITY, |acc, x| acc.min( x[1] ) ).floor().max( 0. ) as usize;
+        let x_max = ( p.iter().fold( f32::NEG_INFINITY, |acc, x| acc.max( x[0] ) ).ceil().max( 0. ) as usize ).min( self._width );
+        let y_max = ( p.iter().fold( f32::NEG_INFINITY, |acc, x| acc.max( x[1] ) ).ceil().max( 0. ) as usize ).min( self._height );
+        let inv_w = [ 1. / v[0]._clip[3], 1. / v[1]._clip[3], 1. / v[2]._clip[3] ];
+        let edge = |a: [f32;3], b: [f32;3], x: f32, y: f32| ( b[0] - a[0] ) * ( y - a[1] ) - ( b[1] - a[1] ) * ( x - a[0] );
+        //top left fill rule for pixels exactly on a shared edge
+        let owns = |a: [f32;3], b: [f32;3]| {
+            let ( dx, dy ) = ( b[0] - a[0], b[1] - a[1] );
+            if area < 0. { dy > 0. || ( dy == 0. && dx < 0. ) } else { dy < 0. || ( dy == 0. && dx > 0. ) }
+        };
+        let own = [ owns( p[1], p[2] ), owns( p[2], p[0] ), owns( p[0], p[1] ) ];
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                let ( px, py ) = ( x as f32 + 0.5, y as f32 + 0.5 );
+                let e = [ edge( p[1], p[2], px, py ) / area, edge( p[2], p[0], px, py ) / area, edge( p[0], p[1], px, py ) / area ];
+                if ( 0..3 ).any( |k| e[k] < 0. || ( e[k] == 0. && !own[k] ) ) {
+                    continue
+                }
+                let z = e[0] * p[0][2] + e[1] * p[1][2] + e[2] * p[2][2];
+                let idx = y * self._width + x;
+                if z < 0. || z > 1. || z >= self._depth[idx] {
+                    continue
+                }
+                //perspective correct weights
+                let b = [ e[0] * inv_w[0], e[1] * inv_w[1], e[2] * inv_w[2] ];
+                let s = b[0] + b[1] + b[2];
+                let b = [ b[0] / s, b[1] / s, b[2] / s ];
+                let mut light = [ 0.; 3 ];
+                let mut tc = [ 0.; 2 ];
+                for k in 0..3 {
+                    for c in 0..3 {
+                        light[c] += b[k] * v[k]._light[c];
+                    }
+                    for c in 0..2 {
+                        tc[c] += b[k] * v[k]._tc[c];
+                    }
+                }
+                let color = self.shade( light, tc, tex );
+                self._depth[idx] = z;
+                self._color[ idx * 3..idx * 3 + 3 ].copy_from_slice( &color[..] );
+            }
+        }
+    }
+    ///3 pixel wide square, as the point size used by renderer_gl
+    fn raster_point( & mut self, v: & VertOut, tex: Option< usize > ) {
+        let c = v._clip;
+        if c[3] <= 0. || c[2] < -c[3] || c[2] > c[3] {
+            return
+        }
+        let p = self.to_window( c );
+        let color = self.shade( v._light, v._tc, tex );
+        let ( cx, cy ) = ( p[0].floor() as isize, p[1].floor() as isize );
+        for y in cy - 1..cy + 2 {
+            for x in cx - 1..cx + 2 {
+                if x < 0 || y < 0 || x as usize >= self._width || y as usize >= self._height {
+                    continue
+                }
+                let idx = y as usize * self._width + x as usize;
+                if p[2] >= self._depth[idx] {
+                    continue
+                }
+                self._depth[idx] = p[2];
+                self._color[ idx * 3..idx * 3 + 3 ].copy_from_slice( &color[..] );
+            }
+        }
+    }
+}
+
+fn ads_uniforms( vals: & HashMap< String, Vec< f32 > > ) -> Result< AdsUniforms, & 'static str > {
+    let get = |name: & str, len: usize| {
+        match vals.get( name ) {
+            Some( v ) if v.len() >= len => Ok( v ),
+            _ => {
+                error!( "software renderer uniform not found: {}", name );
+                Err( "software renderer uniform not found" )
+            },
+        }
+    };
+    let v3 = |name: & str| -> Result< [f32;3], & 'static str > { let v = get( name, 3 )?; Ok( [ v[0], v[1], v[2] ] ) };
+    let mut u = AdsUniforms {
+        _mvp: [ 0.; 16 ],
+        _model_view: [ 0.; 16 ],
+        _normal: [ 0.; 9 ],
+        _light_pos: v3( "Light.Position\0" )?,
+        _la: v3( "Light.La\0" )?,
+        _ld: v3( "Light.Ld\0" )?,
+        _ls: v3( "Light.Ls\0" )?,
+        _ka: v3( "Material.Ka\0" )?,
+        _kd: v3( "Material.Kd\0" )?,
+        _ks: v3( "Material.Ks\0" )?,
+        _shininess: get( "Material.Shininess\0", 1 )?[0],
+    };
+    u._mvp.copy_from_slice( &get( "MVP\0", 16 )?[..16] );
+    u._model_view.copy_from_slice( &get( "ModelViewMatrix\0", 16 )?[..16] );
+    u._normal.copy_from_slice( &get( "NormalMatrix\0", 9 )?[..9] );
+    Ok( u )
+}
+
+///ads.vs: phong lighting in eye space evaluated per vertex. matrices are row major
+fn vertex_stage( u: & AdsUniforms, v: &[ f32 ] ) -> VertOut {
+    let p = [ v[0], v[1], v[2], 1. ];
+    let mut clip = [ 0.; 4 ];
+    let mut eye = [ 0.; 4 ];
+    let mut n = [ 0.; 3 ];
+    for r in 0..4 {
+        for c in 0..4 {
+            clip[r] += u._mvp[ r * 4 + c ] * p[c];
+            eye[r] += u._model_view[ r * 4 + c ] * p[c];
+        }
+    }
+    for r in 0..3 {
+        for c in 0..3 {
+            n[r] += u._normal[ r * 3 + c ] * v[ 3 + c ];
+        }
+    }
+    let n = normalize( n );
+    let s = normalize( [ u._light_pos[0] - eye[0], u._light_pos[1] - eye[1], u._light_pos[2] - eye[2] ] );
+    let view = normalize( [ -eye[0], -eye[1], -eye[2] ] );
+    //reflect( -s, n )
+    let sn = dot( s, n );
+    let r = [ 2. * sn * n[0] - s[0], 2. * sn * n[1] - s[1], 2. * sn * n[2] - s[2] ];
+    let s_dot_n = sn.max( 0. );
+    let spec = if s_dot_n > 0. { dot( r, view ).max( 0. ).powf( u._shininess ) } else { 0. };
+    let mut light = [ 0.; 3 ];
+    for k in 0..3 {
+        light[k] = u._la[k] * u._ka[k] + u._ld[k] * u._kd[k] * s_dot_n + u._ls[k] * u._ks[k] * spec;
+    }
+    VertOut {
+        _clip: clip,
+        _light: light,
+        _tc: [ v[6], v[7] ],
+    }
+}
+
+///clips a triangle against the near plane z >= -w, giving up to 2 triangles
+fn clip_near( t: [ VertOut; 3 ] ) -> Vec< [ VertOut; 3 ] > {
+    let dist = |v: & VertOut| v._clip[2] + v._clip[3];
+    let mut poly = vec![];
+    for i in 0..3 {
+        let ( a, b ) = ( t[i], t[ ( i + 1 ) % 3 ] );
+        let ( da, db ) = ( dist( &a ), dist( &b ) );
+        if da >= 0. {
+            poly.push( a );
+        }
+        if ( da >= 0. ) != ( db >= 0. ) {
+            let f = da / ( da - db );
+            let lerp = |x: f32, y: f32| x + ( y - x ) * f;
+            let mut v = a;
+            for k in 0..4 {
+                v._clip[k] = lerp( a._clip[k], b._clip[k] );
+            }
+            for k in 0..3 {
+                v._light[k] = lerp( a._light[k], b._light[k] );
+            }
+            for k in 0..2 {
+                v._tc[k] = lerp( a._tc[k], b._tc[k] );
+            }
+            poly.push( v );
+        }
+    }
+    ( 1..poly.len().max( 2 ) - 1 ).map( |i| [ poly[0], poly[i], poly[ i + 1 ] ] ).collect()
+}
+
+fn to_u8( x: f32 ) -> u8 {
+    ( x.max( 0. ).min( 1. ) * 255. ).round() as u8
+}
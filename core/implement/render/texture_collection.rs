@@ -78,6 +78,17 @@ impl TextureCollection {
         }
         Ok( () )
     }
+    pub fn get_id( & self, descrip: &str ) -> Option< u64 > {
+        self._descrip_to_id.get( descrip ).cloned()
+    }
+    pub fn get_by_descrip( & self, descrip: &str ) -> Option< i64 > {
+        match self._descrip_to_id.get( descrip ) {
+            Some( id ) => {
+                self._textures.get( id ).map( |&( ref _shader_type, handle )| handle )
+            },
+            None => None
+        }
+    }
     pub fn get( & mut self, id: u64 ) -> Option< i64 > {
         match self._textures.get( &id ) {
             Some( &( ref _shader_type, ref handle ) ) => {
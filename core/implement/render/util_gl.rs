@@ -3,6 +3,7 @@ extern crate pretty_env_logger;
 
 use std::str;
 
+#[derive(Debug)]
 #[derive(Clone)]
 #[derive(Copy)]
 pub enum ShaderType {
@@ -144,6 +145,13 @@ pub fn load_texture( program: gl::types::GLuint, texture_number: gl::types::GLui
     Ok( tex )
 }
 
+pub fn bind_texture( texture_number: gl::types::GLuint, handle: gl::types::GLuint ) {
+    unsafe {
+        gl::ActiveTexture(gl::TEXTURE0 + texture_number);
+        gl::BindTexture( gl::TEXTURE_2D, handle );
+    }
+}
+
 pub fn delete_texture( handle: gl::types::GLuint ) -> Result< (), String > {
     unsafe {
         gl::DeleteTextures(1, &handle);
@@ -1 +1,2 @@
 pub mod step_interpolate;
+pub mod tween;
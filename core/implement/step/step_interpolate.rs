@@ -1,6 +1,13 @@
-use interface::i_interpolate::IInterpolate;
+extern crate mazth;
+
+use self::mazth::mat::Mat4x1;
+
+use interface::i_interpolate::{ IInterpolate, IInterpolateEval };
 use interface::i_step::Step;
 
+use implement::math::arclength::ArcLength;
+use implement::math::easing::Ease;
+
 pub fn step_delta< T, V >( s: & mut Step, step_delta: f64, interp: & mut T ) -> V where T : IInterpolate< V >, V: Clone
 {
     if s._current_val >= s._range_val.1 {
@@ -34,3 +41,49 @@ pub fn step_delta< T, V >( s: & mut Step, step_delta: f64, interp: & mut T ) ->
     }
     ret
 }
+
+///advances the step value and returns the point at the matching fraction of path length, giving constant speed along the path
+pub fn step_distance< T >( s: & mut Step, step_delta: f64, path: & ArcLength< T > ) -> Mat4x1< f64 > where T : IInterpolateEval< Mat4x1< f64 > > + Clone
+{
+    let a = s._current_val + step_delta;
+    s._current_val = if a < s._range_val.0 {
+        s._range_val.0
+    } else if a > s._range_val.1 {
+        s._range_val.1
+    } else {
+        a
+    };
+    let range = s._range_val.1 - s._range_val.0;
+    let fraction = if range > 0. { ( s._current_val - s._range_val.0 ) / range } else { 1. };
+    path.point_at_distance( fraction * path.length() )
+}
+
+///advances the step value and moves the interpolator to the step at the eased fraction of the step range.
+///eased fractions outside of [0,1] are clamped to the interpolator ends
+pub fn step_eased< T, V >( s: & mut Step, step_delta: f64, ease: & Ease, interp: & mut T ) -> V where T : IInterpolate< V >, V: Clone
+{
+    let range = s._range_val.1 - s._range_val.0;
+    if range <= 0. {
+        return interp.interp_current()
+    }
+    let total_steps = interp.num_steps() as f64;
+    let lo = s._range_val.0;
+    let steps_at = |v: f64| {
+        let f = ease.apply( ( v - lo ) / range ).max( 0. ).min( 1. );
+        ( f * total_steps ) as i64
+    };
+    let a = s._current_val + step_delta;
+    let b = if a < s._range_val.0 {
+        s._range_val.0
+    } else if a > s._range_val.1 {
+        s._range_val.1
+    } else {
+        a
+    };
+    let delta = steps_at( b ) - steps_at( s._current_val );
+    s._current_val = b;
+    if delta != 0 {
+        interp.interp_delta( delta );
+    }
+    interp.interp_current()
+}
@@ -0,0 +1,324 @@
+extern crate mazth;
+
+use std::rc::Rc;
+use std::cell::Cell;
+
+use self::mazth::mat::{ Mat3x1, Mat4x1 };
+use self::mazth::quat::Quat;
+
+use interface::i_tween::{ ITween, ITweenable };
+
+use implement::math::easing::Ease;
+use implement::math::quat;
+
+impl ITweenable for f32 {
+    fn tween_lerp( & self, other: & f32, t: f64 ) -> f32 {
+        self + ( other - self ) * t as f32
+    }
+}
+
+impl ITweenable for f64 {
+    fn tween_lerp( & self, other: & f64, t: f64 ) -> f64 {
+        self + ( other - self ) * t
+    }
+}
+
+impl ITweenable for [ f32; 3 ] {
+    fn tween_lerp( & self, other: & [ f32; 3 ], t: f64 ) -> [ f32; 3 ] {
+        [ self[0].tween_lerp( &other[0], t ), self[1].tween_lerp( &other[1], t ), self[2].tween_lerp( &other[2], t ) ]
+    }
+}
+
+///rgba colour
+impl ITweenable for [ f32; 4 ] {
+    fn tween_lerp( & self, other: & [ f32; 4 ], t: f64 ) -> [ f32; 4 ] {
+        [ self[0].tween_lerp( &other[0], t ), self[1].tween_lerp( &other[1], t ),
+          self[2].tween_lerp( &other[2], t ), self[3].tween_lerp( &other[3], t ) ]
+    }
+}
+
+impl ITweenable for Mat3x1< f32 > {
+    fn tween_lerp( & self, other: & Mat3x1< f32 >, t: f64 ) -> Mat3x1< f32 > {
+        Mat3x1 { _val: self._val.tween_lerp( &other._val, t ) }
+    }
+}
+
+impl ITweenable for Mat4x1< f64 > {
+    fn tween_lerp( & self, other: & Mat4x1< f64 >, t: f64 ) -> Mat4x1< f64 > {
+        let mut v = *self;
+        for i in 0..4 {
+            v._val[i] = self._val[i].tween_lerp( &other._val[i], t );
+        }
+        v
+    }
+}
+
+///slerp along the shorter arc
+impl ITweenable for Quat< f32 > {
+    fn tween_lerp( & self, other: & Quat< f32 >, t: f64 ) -> Quat< f32 > {
+        quat::slerp( self, other, t as f32 )
+    }
+}
+
+///animates a value from start to end over a duration with easing, delay, repeats and yoyo.
+///the current value is pushed to the update callback on every advance once the delay has passed
+pub struct Tween< T > where T: ITweenable {
+    pub _from: T,
+    pub _to: T,
+    pub _duration: f64,
+    pub _delay: f64,
+    pub _ease: Ease,
+    ///plays back to the start value after reaching the end value
+    pub _yoyo: bool,
+    ///number of additional plays
+    pub _repeat: u32,
+    pub _elapsed: f64,
+    pub _value: T,
+    _on_update: Option< Box< FnMut( & T ) > >,
+    _on_complete: Option< Box< FnMut() > >,
+    _done: bool,
+}
+
+impl < T > Tween< T > where T: ITweenable {
+    pub fn init( from: T, to: T, duration: f64, ease: Ease ) -> Tween< T > {
+        Tween {
+            _value: from.clone(),
+            _from: from,
+            _to: to,
+            _duration: duration.max( 0. ),
+            _delay: 0.,
+            _ease: ease,
+            _yoyo: false,
+            _repeat: 0,
+            _elapsed: 0.,
+            _on_update: None,
+            _on_complete: None,
+            _done: false,
+        }
+    }
+    pub fn delay( mut self, d: f64 ) -> Tween< T > {
+        self._delay = d.max( 0. );
+        self
+    }
+    pub fn yoyo( mut self, b: bool ) -> Tween< T > {
+        self._yoyo = b;
+        self
+    }
+    pub fn repeat( mut self, n: u32 ) -> Tween< T > {
+        self._repeat = n;
+        self
+    }
+    pub fn on_update< F >( mut self, f: F ) -> Tween< T > where F: FnMut( & T ) + 'static {
+        self._on_update = Some( Box::new( f ) );
+        self
+    }
+    pub fn on_complete< F >( mut self, f: F ) -> Tween< T > where F: FnMut() + 'static {
+        self._on_complete = Some( Box::new( f ) );
+        self
+    }
+    ///writes the current value into a shared cell on every update
+    pub fn bind( self, target: Rc< Cell< T > > ) -> Tween< T > where T: Copy + 'static {
+        self.on_update( move |x| target.set( *x ) )
+    }
+    pub fn value( & self ) -> T {
+        self._value.clone()
+    }
+    fn play_duration( & self ) -> f64 {
+        let legs = if self._yoyo { 2. } else { 1. };
+        self._duration * legs * ( self._repeat as f64 + 1. )
+    }
+    fn value_at( & self, t: f64 ) -> T {
+        let total = self.play_duration();
+        let f = if self._duration <= 0. || t >= total {
+            if self._yoyo { 0. } else { 1. }
+        } else {
+            let leg = ( t / self._duration ).floor();
+            let f = ( t - leg * self._duration ) / self._duration;
+            if self._yoyo && ( leg as u64 ) % 2 == 1 { 1. - f } else { f }
+        };
+        self._from.tween_lerp( &self._to, self._ease.apply( f ) )
+    }
+}
+
+impl < T > ITween for Tween< T > where T: ITweenable {
+    fn advance( & mut self, dt: f64 ) -> f64 {
+        if self._done {
+            return dt
+        }
+        let total = self.duration();
+        self._elapsed += dt.max( 0. );
+        let leftover = ( self._elapsed - total ).max( 0. );
+        self._elapsed = self._elapsed.min( total );
+        if self._elapsed >= self._delay {
+            let t = self._elapsed - self._delay;
+            self._value = self.value_at( t );
+            if let Some( ref mut f ) = self._on_update {
+                f( & self._value );
+            }
+        }
+        if self._elapsed >= total {
+            self._done = true;
+            if let Some( ref mut f ) = self._on_complete {
+                f();
+            }
+        }
+        leftover
+    }
+    fn is_done( & self ) -> bool {
+        self._done
+    }
+    fn duration( & self ) -> f64 {
+        self._delay + self.play_duration()
+    }
+    fn reset( & mut self ){
+        self._elapsed = 0.;
+        self._value = self._from.clone();
+        self._done = false;
+    }
+}
+
+///runs tweens one after another, time left over by one is carried into the next
+pub struct Sequence {
+    pub _items: Vec< Box< ITween > >,
+    _current: usize,
+    _on_complete: Option< Box< FnMut() > >,
+}
+
+impl Sequence {
+    pub fn init( items: Vec< Box< ITween > > ) -> Sequence {
+        Sequence {
+            _items: items,
+            _current: 0,
+            _on_complete: None,
+        }
+    }
+    pub fn on_complete< F >( mut self, f: F ) -> Sequence where F: FnMut() + 'static {
+        self._on_complete = Some( Box::new( f ) );
+        self
+    }
+}
+
+impl ITween for Sequence {
+    fn advance( & mut self, dt: f64 ) -> f64 {
+        if self.is_done() {
+            return dt
+        }
+        let mut dt = dt;
+        while self._current < self._items.len() {
+            dt = self._items[ self._current ].advance( dt );
+            if !self._items[ self._current ].is_done() {
+                return 0.
+            }
+            self._current += 1;
+        }
+        if let Some( ref mut f ) = self._on_complete {
+            f();
+        }
+        dt
+    }
+    fn is_done( & self ) -> bool {
+        self._current >= self._items.len()
+    }
+    fn duration( & self ) -> f64 {
+        self._items.iter().fold( 0., |acc, x| acc + x.duration() )
+    }
+    fn reset( & mut self ){
+        for i in self._items.iter_mut() {
+            i.reset();
+        }
+        self._current = 0;
+    }
+}
+
+///runs tweens simultaneously, done when all are done
+pub struct Parallel {
+    pub _items: Vec< Box< ITween > >,
+    _on_complete: Option< Box< FnMut() > >,
+    _done: bool,
+}
+
+impl Parallel {
+    pub fn init( items: Vec< Box< ITween > > ) -> Parallel {
+        Parallel {
+            _items: items,
+            _on_complete: None,
+            _done: false,
+        }
+    }
+    pub fn on_complete< F >( mut self, f: F ) -> Parallel where F: FnMut() + 'static {
+        self._on_complete = Some( Box::new( f ) );
+        self
+    }
+}
+
+impl ITween for Parallel {
+    fn advance( & mut self, dt: f64 ) -> f64 {
+        if self._done {
+            return dt
+        }
+        //time left over after the longest running member completes
+        let leftover = self._items.iter_mut().fold( dt, |acc, x| acc.min( x.advance( dt ) ) );
+        if self._items.iter().all( |x| x.is_done() ) {
+            self._done = true;
+            if let Some( ref mut f ) = self._on_complete {
+                f();
+            }
+            leftover
+        } else {
+            0.
+        }
+    }
+    fn is_done( & self ) -> bool {
+        self._done
+    }
+    fn duration( & self ) -> f64 {
+        self._items.iter().fold( 0., |acc, x| acc.max( x.duration() ) )
+    }
+    fn reset( & mut self ){
+        for i in self._items.iter_mut() {
+            i.reset();
+        }
+        self._done = false;
+    }
+}
+
+///owns running tweens by handle and drops them once done
+#[derive(Default)]
+pub struct TweenManager {
+    _tweens: Vec< ( u64, Box< ITween > ) >,
+    _next_id: u64,
+}
+
+impl TweenManager {
+    pub fn add< T >( & mut self, t: T ) -> u64 where T: ITween + 'static {
+        let id = self._next_id;
+        self._next_id += 1;
+        self._tweens.push( ( id, Box::new( t ) ) );
+        id
+    }
+    ///removes a tween without completing it
+    pub fn cancel( & mut self, id: u64 ) -> bool {
+        let n = self._tweens.len();
+        self._tweens.retain( |x| x.0 != id );
+        n != self._tweens.len()
+    }
+    pub fn is_active( & self, id: u64 ) -> bool {
+        self._tweens.iter().any( |x| x.0 == id )
+    }
+    pub fn len( & self ) -> usize {
+        self._tweens.len()
+    }
+    pub fn is_empty( & self ) -> bool {
+        self._tweens.is_empty()
+    }
+    pub fn clear( & mut self ){
+        self._tweens.clear();
+    }
+    ///advances all tweens by dt seconds
+    pub fn advance( & mut self, dt: f64 ) {
+        for i in self._tweens.iter_mut() {
+            i.1.advance( dt );
+        }
+        self._tweens.retain( |x| !x.1.is_done() );
+    }
+}
@@ -0,0 +1,25 @@
+use interface::i_ui::{ IUi, InputFiltered };
+
+///passes through already filtered events, eg: from a headless window script
+#[derive(Default)]
+pub struct InputHeadless {
+}
+
+impl IUi for InputHeadless {
+    
+    type EventInput = InputFiltered;
+    type EventInputFiltered = InputFiltered;
+
+    fn new() -> Self {
+        Default::default()
+    }
+    
+    fn process_input_events( & mut self, e: & [ Self::EventInput ] ) -> Vec< InputFiltered > {
+        let filtered = e.iter()
+            .cloned()
+            .filter( |x| if let &InputFiltered::Ignored = x { false } else { true } )
+            .inspect( |x| { trace!("{:?}", x); () } )
+            .collect();
+        filtered
+    }
+}
@@ -1,2 +1,3 @@
 pub mod input_default_glutin;
 pub mod ui_cam;
+pub mod input_headless;
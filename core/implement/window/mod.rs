@@ -1 +1,2 @@
 pub mod winglutin;
+pub mod winheadless;
@@ -0,0 +1,105 @@
+///windowing without a display, input events are supplied by a frame indexed script
+
+use std::collections::VecDeque;
+use std::ops::FnMut;
+
+use interface::i_window::IWindow;
+use interface::i_ui::InputFiltered;
+
+pub struct DummySignalRequestType {
+}
+
+pub struct WinHeadless {
+    pub _dim: ( u64, u64 ),
+    ///pending ( frame, event ) in frame order
+    _script: VecDeque< ( u64, InputFiltered ) >,
+    ///( frame, event ) already delivered
+    _recorded: Vec< ( u64, InputFiltered ) >,
+    ///number of frames started
+    _frame_count: u64,
+}
+
+impl WinHeadless {
+    pub fn with_script( w: u64, h: u64, script: & [ ( u64, InputFiltered ) ] ) -> WinHeadless {
+        let mut win = WinHeadless::new( w, h );
+        for &( frame, e ) in script.iter() {
+            win.push_event( frame, e );
+        }
+        win
+    }
+    ///queues an event to become available at the given frame, keeping insertion order within a frame
+    pub fn push_event( & mut self, frame: u64, e: InputFiltered ) {
+        let idx = self._script.iter().position( |x| x.0 > frame ).unwrap_or( self._script.len() );
+        self._script.insert( idx, ( frame, e ) );
+    }
+    ///index of the current frame, none before the first frame is started
+    pub fn frame( & self ) -> Option< u64 > {
+        if self._frame_count == 0 {
+            None
+        } else {
+            Some( self._frame_count - 1 )
+        }
+    }
+    pub fn pending( & self ) -> usize {
+        self._script.len()
+    }
+    ///delivered events tagged with the frame they were delivered in
+    pub fn recorded( & self ) -> & [ ( u64, InputFiltered ) ] {
+        self._recorded.as_slice()
+    }
+    fn pop_due( & mut self ) -> Option< InputFiltered > {
+        let frame = match self.frame() {
+            Some( x ) => x,
+            _ => { return None },
+        };
+        let is_due = match self._script.front() {
+            Some( x ) => x.0 <= frame,
+            _ => false,
+        };
+        if !is_due {
+            return None
+        }
+        let ( _, e ) = self._script.pop_front().unwrap();
+        self._recorded.push( ( frame, e ) );
+        Some( e )
+    }
+}
+
+impl IWindow for WinHeadless {
+
+    type EventType = InputFiltered;
+    type SignalRequestType = DummySignalRequestType;
+
+    fn new( w: u64, h: u64 ) -> WinHeadless {
+        WinHeadless {
+            _dim: ( w, h ),
+            _script: VecDeque::new(),
+            _recorded: vec![],
+            _frame_count: 0,
+        }
+    }
+    fn make_current( & self ) -> Result< (), & 'static str > {
+        Ok( () )
+    }
+    ///drains all events due in the current frame
+    fn handle_events < F > ( & mut self, mut cb: F ) -> ()
+        where F : FnMut( Self::EventType ) -> () {
+        while let Some( e ) = self.pop_due() {
+            cb( e );
+        }
+    }
+    ///returns at most one due event, remaining ones spill over to subsequent frames
+    fn handle_events_pass_thru( & mut self ) -> Option< Self::EventType > {
+        self.pop_due()
+    }
+    fn swap_buf( & self ) -> () {
+        ()
+    }
+    fn handle_signal_request( & mut self, _sig: & [ Self::SignalRequestType ] ) -> Result< (), & 'static str > {
+        Ok( () )
+    }
+    fn per_frame_setup( & mut self ) -> Result< (), & 'static str > {
+        self._frame_count += 1;
+        Ok( () )
+    }
+}
@@ -48,6 +48,38 @@ impl ComponentRenderBuffer {
     }
 }
 
+/// # vertex range of a render buffer drawn with a given texture, none for default texture
+#[derive(Clone, Debug)]
+pub struct TextureRange {
+    pub _texture: Option< String >,
+    pub _vert_offset: usize,
+    pub _vert_count: usize,
+}
+
+/// # texture bindings for ranges of the render buffer that follows it, offsets relative to that buffer
+#[derive(Clone)]
+pub struct ComponentRenderTextureRanges {
+    pub _ranges: Vec< TextureRange >,
+}
+
+impl IComponent for ComponentRenderTextureRanges {
+    fn as_any( & self ) -> & Any {
+        self
+    }
+}
+
+/// # draw group receiving the render buffers that follow it in the same object, instead of the draw group being dispatched
+#[derive(Clone)]
+pub struct ComponentDrawGroupTarget {
+    pub _group_id: usize,
+}
+
+impl IComponent for ComponentDrawGroupTarget {
+    fn as_any( & self ) -> & Any {
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct ComponentRenderUniform {
     /// # stores the uniforms values
@@ -4,3 +4,8 @@ pub trait IParseStr {
 }
 
 
+
+pub trait IParseBytes {
+    type output;
+    fn parse( file_content: &[u8] ) -> Result< Self::output, & 'static str >;
+}
@@ -4,6 +4,9 @@ use std::fmt::Debug;
 
 use interface::i_scheduler::IScheduler;
 
+use implement::math::frustum::Frustum;
+use implement::render::culling::{ self, ICullable, CullStats };
+
 pub trait IGameLogic
 {
     type EventInput : Debug;
@@ -15,12 +18,15 @@ pub trait IGameLogic
     type ComputeSchedule : IScheduler< Item = Self::ComputeUnit > + Iterator<Item = Vec<Self::ComputeUnit> >;
 
     /// transform a high level renderobj representation into render commands / elements
-    type RenderObj : Into< Vec< Self::EventRender > >;
+    type RenderObj : Into< Vec< Self::EventRender > > + ICullable;
     
     fn new() -> Self;
     
     fn run_init_hook( & mut self ) -> Result< (), & 'static str >;
 
+    ///receives the time step in seconds of the upcoming frame from the kernel
+    fn set_frame_delta( & mut self, _dt: f32 ) {}
+
     fn get_states( & mut self ) -> & Self::GameState;
 
     fn get_states_mut( & mut self ) -> & mut Self::GameState;
@@ -42,8 +48,27 @@ pub trait IGameLogic
     ///get all renderable objects from current game state
     fn get_renderable_components( & mut self ) -> Vec< Self::RenderObj >;
 
-    ///do optimization on renderable objects
-    fn filter_renderables( & mut self, r: Vec< Self::RenderObj > ) -> Vec< Self::RenderObj >;
+    ///view frustum of the active camera, none disables culling
+    fn view_frustum( & mut self ) -> Option< Frustum > {
+        None
+    }
+
+    ///receives visible and culled counts of the latest filter pass
+    fn report_cull_stats( & mut self, stats: CullStats ) {
+        trace!( "renderables visible: {}, culled: {}", stats._visible, stats._culled );
+    }
+
+    ///do optimization on renderable objects, defaults to view frustum culling
+    fn filter_renderables( & mut self, r: Vec< Self::RenderObj > ) -> Vec< Self::RenderObj > {
+        match self.view_frustum() {
+            Some( f ) => {
+                let ( visible, stats ) = culling::cull( r, & f );
+                self.report_cull_stats( stats );
+                visible
+            },
+            None => r,
+        }
+    }
 
     fn should_exit( & mut self ) -> bool;
 
@@ -97,7 +122,7 @@ pub trait IGameLogic
         //transform renderable objects to render events
         let render_objects = self.get_renderable_components();
 
-        //do some spatial optimization here
+        //drop renderables outside of view
         let render_objects_filtered = self.filter_renderables( render_objects );
 
         //do further render commands packaging here
@@ -7,3 +7,8 @@ pub trait IInterpolate < T > : DoubleEndedIterator< Item = T >  where T: Clone {
     fn num_steps( & self ) -> u64;
     fn reset( & mut self );
 }
+
+///random access evaluation of an interpolator, fraction in [0,1] spans start to end
+pub trait IInterpolateEval < T > where T: Clone {
+    fn eval( & self, fraction: f64 ) -> T;
+}
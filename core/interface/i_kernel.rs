@@ -32,8 +32,21 @@ pub trait IKernel < W: IWindow,
 
     fn deinit_hook( & mut self ) -> Result< (), & 'static str > { Ok( () ) }
 
+    ///time step in seconds handed to game logic, defaults to the measured time since the previous frame
+    fn frame_delta( & mut self, t_measured: f32 ) -> f32 { t_measured }
+
     ///default implementation of the main control flow
     fn run( & mut self ) -> Result< (), & 'static str > {
+        self.run_loop( None ).map( |_| () )
+    }
+
+    ///runs at most a fixed number of frames, returns the number of frames run
+    fn run_frames( & mut self, frames: u64 ) -> Result< u64, & 'static str > {
+        self.run_loop( Some( frames ) )
+    }
+
+    ///main control flow until exit is signalled or the optional frame limit is reached
+    fn run_loop( & mut self, max_frames: Option< u64 > ) -> Result< u64, & 'static str > {
 
         self.init_hook()?;
             
@@ -42,15 +55,25 @@ pub trait IKernel < W: IWindow,
         //foever loop and process results until exit conditions are caught
         let mut running = true;
 
+        let mut frames = 0u64;
+
         #[allow(unused_mut)]
         let mut sigs_for_window = vec![];
 
         let mut t_cycle_last = Local::now();
 
-        while running {
+        while running && max_frames.map_or( true, |m| frames < m ) {
+
+            frames += 1;
 
             let t0 = Local::now();
 
+            let t_cycle = t0.signed_duration_since(t_cycle_last).num_microseconds().unwrap() as f64;
+            t_cycle_last = t0;
+
+            let dt = self.frame_delta( ( t_cycle / 1_000_000. ) as f32 );
+            (self.as_mut() as & mut G).set_frame_delta( dt );
+
             //process windowing events into buffer
             (self.as_mut() as & W).make_current()?;
 
@@ -87,8 +110,6 @@ pub trait IKernel < W: IWindow,
             let t_1_0 = t1.signed_duration_since(t0).num_microseconds().unwrap() as f64;
             let t_2_1 = t2.signed_duration_since(t1).num_microseconds().unwrap() as f64;
             let t_3_2 = t3.signed_duration_since(t2).num_microseconds().unwrap() as f64;
-            let t_cycle = t0.signed_duration_since(t_cycle_last).num_microseconds().unwrap() as f64;
-            t_cycle_last = t0;
             debug!( "t lapse ui input filter: {} ms", t_1_0 / 1000. );
             debug!( "t lapse game logic: {} ms", t_2_1 / 1000. );
             debug!( "t lapse renderer: {} ms", t_3_2 / 1000. );
@@ -101,6 +122,6 @@ pub trait IKernel < W: IWindow,
 
         self.deinit_hook()?;
 
-        Ok( () )
+        Ok( frames )
     }
 }
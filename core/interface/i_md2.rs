@@ -0,0 +1,27 @@
+///md2 file format, vertices decoded into model space
+pub mod model {
+
+    #[derive(Debug, Clone)]
+    pub struct Md2Tri {
+        pub _vert_indices: [u16;3],
+        pub _tc_indices: [u16;3],
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Md2Frame {
+        pub _name: String,
+        pub _verts: Vec< [f32;3] >,
+        ///index into precomputed normal table of the original format
+        pub _normal_indices: Vec< u8 >,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Md2Root {
+        pub _skin_width: u32,
+        pub _skin_height: u32,
+        pub _skins: Vec< String >,
+        pub _tex_coords: Vec< [i16;2] >,
+        pub _tris: Vec< Md2Tri >,
+        pub _frames: Vec< Md2Frame >,
+    }
+}
@@ -0,0 +1,45 @@
+///md3 file format, vertices and normals decoded into model space
+pub mod model {
+
+    #[derive(Debug, Clone)]
+    pub struct Md3Frame {
+        pub _name: String,
+        pub _bound_min: [f32;3],
+        pub _bound_max: [f32;3],
+        pub _origin: [f32;3],
+        pub _radius: f32,
+    }
+
+    ///attachment point, axis[i] is the i-th basis vector of the tag frame
+    #[derive(Debug, Clone)]
+    pub struct Md3Tag {
+        pub _name: String,
+        pub _origin: [f32;3],
+        pub _axis: [[f32;3];3],
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Md3Vert {
+        pub _pos: [f32;3],
+        pub _normal: [f32;3],
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Md3Surface {
+        pub _name: String,
+        pub _shaders: Vec< String >,
+        pub _tris: Vec< [u32;3] >,
+        pub _tex_coords: Vec< [f32;2] >,
+        ///vertices per frame
+        pub _frames: Vec< Vec< Md3Vert > >,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Md3Root {
+        pub _name: String,
+        pub _frames: Vec< Md3Frame >,
+        ///tags per frame
+        pub _tags: Vec< Vec< Md3Tag > >,
+        pub _surfaces: Vec< Md3Surface >,
+    }
+}
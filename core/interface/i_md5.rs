@@ -164,6 +164,105 @@ pub mod rig {
     }
 }
 
+///compressed md5anim format
+pub mod compress {
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct CompressSettings {
+        ///max allowed per channel position error
+        pub _tolerance_pos: f32,
+        ///max allowed orientation error in radians
+        pub _tolerance_angle: f32,
+    }
+
+    impl Default for CompressSettings {
+        fn default() -> CompressSettings {
+            CompressSettings {
+                _tolerance_pos: 0.001f32,
+                _tolerance_angle: 0.001f32,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct KeyPos {
+        pub _frame: u16,
+        pub _val: f32,
+    }
+
+    ///quaternion stored as the smallest three components quantised to 16 bits,
+    ///with the index of the dropped largest component
+    #[derive(Debug, Clone, Copy)]
+    pub struct KeyRot {
+        pub _frame: u16,
+        pub _largest: u8,
+        pub _val: [u16;3],
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct JointTrack {
+        pub _name: String,
+        pub _parent: i64,
+        ///keys for x, y, z position channels
+        pub _pos: [ Vec< KeyPos >; 3 ],
+        pub _rot: Vec< KeyRot >,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct CompressStat {
+        pub _bytes_original: u64,
+        pub _bytes_compressed: u64,
+        pub _ratio: f32,
+        ///max per channel position error for each joint
+        pub _joint_err_pos: Vec< f32 >,
+        ///max orientation error in radians for each joint
+        pub _joint_err_angle: Vec< f32 >,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Md5AnimCompressed {
+        pub _numframes: u64,
+        pub _framerate: u64,
+        pub _tracks: Vec< JointTrack >,
+        pub _stat: CompressStat,
+    }
+}
+
+///morph targets and animated weights
+pub mod morph {
+
+    ///bind pose displacement of a single vertex
+    #[derive(Debug, Clone)]
+    pub struct MorphDelta {
+        pub _vert_index: u64,
+        pub _pos: [f32;3],
+        ///normal displacement, unused by md5comp which flat shades with face normals of the morphed positions
+        pub _normal: [f32;3],
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct MorphTarget {
+        pub _name: String,
+        ///index of the md5 mesh the deltas apply to
+        pub _mesh_index: usize,
+        pub _deltas: Vec< MorphDelta >,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct MorphKey {
+        ///time in seconds
+        pub _time: f32,
+        pub _weight: f32,
+    }
+
+    ///weight over time for the named morph target
+    #[derive(Debug, Clone)]
+    pub struct MorphTrack {
+        pub _target: String,
+        pub _keys: Vec< MorphKey >,
+    }
+}
+
 pub mod compute {
     ///md5compute format
     #[derive(Debug, Clone)]
@@ -178,6 +277,16 @@ pub mod compute {
         pub _verts: Vec< VertCompute >,
     }
 
+    ///vertex range in batch data originating from a single md5 mesh
+    #[derive(Debug, Clone)]
+    pub struct SubMesh {
+        pub _shader: String,
+        ///texture description resolved from shader name, if any
+        pub _texture: Option< String >,
+        pub _vert_offset: usize,
+        pub _vert_count: usize,
+    }
+
     #[derive(Debug, Clone)]
     pub struct ComputeCollection {
         // pub _meshcomputes: Vec< MeshCompute >, //use batch instead
@@ -187,5 +296,7 @@ pub mod compute {
         pub _batch_vert: Vec< f32 >,
         pub _batch_normal: Vec< f32 >,
         pub _batch_tc: Vec< f32 >,
+
+        pub _submeshes: Vec< SubMesh >,
     }
 }
@@ -39,7 +39,8 @@ pub enum BuffDataType {
 pub trait RenderDevice {
     fn bind_buffer( & mut self ) -> Result< (), & 'static str >;
     fn draw_buffer_all( & mut self ) -> Result< (), & 'static str >;
-    fn draw_buffer_range( & mut self ) -> Result< (), & 'static str >;
+    ///draws vertices in [ start, start + count )
+    fn draw_buffer_range( & mut self, start: usize, count: usize ) -> Result< (), & 'static str >;
     fn store_buff_data( & mut self, data: & HashMap< BuffDataType, Vec< f32 > > ) -> Result< (), & 'static str >;
     fn clear_buff_data( & mut self );
 }
@@ -0,0 +1,15 @@
+///values that can be animated by a tween
+pub trait ITweenable : Clone {
+    ///value at fraction t from self to other, t may lie outside of [0,1] for overshooting easing curves
+    fn tween_lerp( & self, other: & Self, t: f64 ) -> Self;
+}
+
+///time driven animation that can be composed into sequences and parallel groups
+pub trait ITween {
+    ///advances by dt seconds and returns the portion of dt left over after completion
+    fn advance( & mut self, dt: f64 ) -> f64;
+    fn is_done( & self ) -> bool;
+    ///total running time including delays and repeats
+    fn duration( & self ) -> f64;
+    fn reset( & mut self );
+}
@@ -30,8 +30,75 @@ pub mod obj {
 
 }
 
+///mtl file format
+pub mod mtl {
+
+    ///surface description of a newmtl entry, including the pbr extension
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Material {
+        pub _name: String,
+        pub _ka: [ f32; 3 ],
+        pub _kd: [ f32; 3 ],
+        pub _ks: [ f32; 3 ],
+        pub _ke: [ f32; 3 ],
+        ///specular exponent
+        pub _ns: f32,
+        ///dissolve, 1 is opaque
+        pub _d: f32,
+        pub _ni: f32,
+        pub _illum: u32,
+        ///map_Kd arguments, including any texture options
+        pub _map_kd: Option< String >,
+        ///roughness
+        pub _pr: Option< f32 >,
+        ///metallic
+        pub _pm: Option< f32 >,
+        ///sheen
+        pub _ps: Option< f32 >,
+        ///clearcoat thickness and roughness
+        pub _pc: Option< f32 >,
+        pub _pcr: Option< f32 >,
+        pub _aniso: Option< f32 >,
+        pub _anisor: Option< f32 >,
+    }
+
+    impl Material {
+        ///defaults of an entry with only a name
+        pub fn init( name: & str ) -> Material {
+            Material {
+                _name: name.to_string(),
+                _ka: [ 0., 0., 0. ],
+                _kd: [ 0.8, 0.8, 0.8 ],
+                _ks: [ 0., 0., 0. ],
+                _ke: [ 0., 0., 0. ],
+                _ns: 1.,
+                _d: 1.,
+                _ni: 1.,
+                _illum: 2,
+                _map_kd: None,
+                _pr: None,
+                _pm: None,
+                _ps: None,
+                _pc: None,
+                _pcr: None,
+                _aniso: None,
+                _anisor: None,
+            }
+        }
+    }
+}
+
 pub mod compute {
 
+    ///vertex range in batch data originating from a single obj group
+    #[derive(Debug, Clone)]
+    pub struct SubMesh {
+        ///material selected by usemtl, if any
+        pub _material: Option< String >,
+        pub _vert_offset: usize,
+        pub _vert_count: usize,
+    }
+
     #[derive(Debug, Clone)]
     pub struct ComputeCollection {
 
@@ -41,5 +108,7 @@ pub mod compute {
         pub _batch_vert: Vec< f32 >,
         pub _batch_normal: Vec< f32 >,
         pub _batch_tc: Vec< f32 >,
+
+        pub _submeshes: Vec< SubMesh >,
     }
 }
@@ -5,9 +5,17 @@ use self::mazth::mat::Mat4x1;
 use implement::math::spline_bezier::SplineBezier;
 
 use implement::math::piecewise::Piecewise;
+use implement::math::arclength::ArcLength;
 
 pub trait IWaypoint {
     fn set_next( & mut self, pos: Mat4x1<f64> );
     fn get_trajectory( & mut self ) -> & mut Option< Piecewise< SplineBezier, Mat4x1<f64> > >; 
     fn flush( & mut self );
+    ///arc length parameterisation of the current trajectory for constant speed traversal
+    fn get_trajectory_arclength( & mut self, samples: usize ) -> Option< ArcLength< SplineBezier > > {
+        match *self.get_trajectory() {
+            Some( ref t ) => ArcLength::init_from_piecewise( t, samples ).ok(),
+            None => None,
+        }
+    }
 }
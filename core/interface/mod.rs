@@ -3,7 +3,6 @@ pub mod i_threadpool;
 pub mod i_interpolate;
 pub mod i_window;
 // pub mod i_rendernode;
-pub mod i_renderpass;
 pub mod i_renderobj;
 pub mod i_ads;
 pub mod i_ele;
@@ -18,5 +17,8 @@ pub mod i_ui;
 pub mod i_scheduler;
 pub mod i_file;
 pub mod i_md5;
+pub mod i_md2;
+pub mod i_md3;
 pub mod i_wavefront;
 pub mod i_step;
+pub mod i_tween;
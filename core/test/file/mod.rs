@@ -8,6 +8,14 @@ pub mod test_md5anim_nom;
 pub mod test_md5rig_nom;
 // pub mod test_md5comp;
 pub mod test_md5comp_nom;
+pub mod test_md5compress;
+pub mod test_md5player;
+pub mod test_md5skeleton;
+pub mod test_md5morph;
+pub mod test_binread;
+pub mod test_md2;
+pub mod test_md3;
 pub mod test_ppm;
 pub mod test_wavefrontobj;
 pub mod test_wavefrontcomp;
+pub mod test_wavefrontmtl;
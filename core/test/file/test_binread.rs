@@ -0,0 +1,46 @@
+use implement::file::binread::*;
+
+///little endian writers for building binary model fixtures, counterparts of binread
+
+pub fn put_i32( buf: & mut Vec< u8 >, v: i32 ) {
+    for i in 0..4 {
+        buf.push( ( ( v as u32 ) >> ( 8 * i ) ) as u8 );
+    }
+}
+
+pub fn put_u16( buf: & mut Vec< u8 >, v: u16 ) {
+    buf.push( v as u8 );
+    buf.push( ( v >> 8 ) as u8 );
+}
+
+pub fn put_i16( buf: & mut Vec< u8 >, v: i16 ) {
+    put_u16( buf, v as u16 );
+}
+
+pub fn put_f32( buf: & mut Vec< u8 >, v: f32 ) {
+    put_i32( buf, v.to_bits() as i32 );
+}
+
+///zero padded to fixed length
+pub fn put_str( buf: & mut Vec< u8 >, s: &str, len: usize ) {
+    let mut b = s.as_bytes().to_vec();
+    b.resize( len, 0 );
+    buf.extend_from_slice( &b[..] );
+}
+
+#[test]
+fn test_binread(){
+    let mut b = vec![];
+    put_i32( & mut b, -2 );
+    put_u16( & mut b, 0xBEEF );
+    put_i16( & mut b, -3 );
+    put_f32( & mut b, 1.5 );
+    put_str( & mut b, "tag", 8 );
+    assert_eq!( b.len(), 20 );
+    assert_eq!( read_i32( &b[..], 0 ), Ok( -2 ) );
+    assert_eq!( read_u16( &b[..], 4 ), Ok( 0xBEEF ) );
+    assert_eq!( read_i16( &b[..], 6 ), Ok( -3 ) );
+    assert_eq!( read_f32( &b[..], 8 ), Ok( 1.5 ) );
+    assert_eq!( read_str( &b[..], 12, 8 ), Ok( String::from( "tag" ) ) );
+    assert!( read_u32( &b[..], 18 ).is_err() );
+}
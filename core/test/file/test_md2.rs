@@ -0,0 +1,98 @@
+use interface::i_file::IParseBytes;
+
+use implement::file::md2;
+
+use test::file::test_binread::{ put_i32, put_u16, put_f32, put_str };
+
+///single triangle, 2 frames with the triangle translated along z and vertex normals turned from +z to +x in the second frame
+fn synthetic_md2() -> Vec< u8 > {
+    let num_verts = 3;
+    let frame_size = 40 + num_verts * 4;
+    let ofs_skins = 68;
+    let ofs_st = ofs_skins + 64;
+    let ofs_tris = ofs_st + 3 * 4;
+    let ofs_frames = ofs_tris + 12;
+    let ofs_end = ofs_frames + 2 * frame_size;
+
+    let mut b = vec![];
+    b.extend_from_slice( b"IDP2" );
+    for &v in [ 8, 64, 32, frame_size, 1, num_verts, 3, 1, 0, 2,
+                ofs_skins, ofs_st, ofs_tris, ofs_frames, ofs_end, ofs_end ].iter() {
+        put_i32( & mut b, v );
+    }
+    put_str( & mut b, "skin.pcx", 64 );
+    for &( s, t ) in [ ( 0, 0 ), ( 64, 0 ), ( 0, 32 ) ].iter() {
+        put_u16( & mut b, s as u16 );
+        put_u16( & mut b, t as u16 );
+    }
+    for &i in [ 0u16, 1, 2, 0, 1, 2 ].iter() {
+        put_u16( & mut b, i );
+    }
+    for f in 0..2 {
+        for &s in [ 1f32, 1., 1. ].iter() {
+            put_f32( & mut b, s );
+        }
+        for &t in [ 0f32, 0., 10. * f as f32 ].iter() {
+            put_f32( & mut b, t );
+        }
+        put_str( & mut b, &format!( "frame{}", f ), 16 );
+        for &( x, y ) in [ ( 0u8, 0u8 ), ( 0, 4 ), ( 4, 0 ) ].iter() {
+            b.push( x );
+            b.push( y );
+            b.push( 0 );
+            b.push( if f == 0 { 5 } else { 52 } );
+        }
+    }
+    assert_eq!( b.len(), ofs_end as usize );
+    b
+}
+
+#[test]
+fn test_md2_parse(){
+    let b = synthetic_md2();
+    let m = md2::Md2Parser::parse( &b[..] ).expect( "md2 parse" );
+    assert_eq!( m._skins, vec![ String::from( "skin.pcx" ) ] );
+    assert_eq!( m._frames.len(), 2 );
+    assert_eq!( m._frames[1]._name, "frame1" );
+    assert_eq!( m._tris.len(), 1 );
+    assert_eq!( m._frames[1]._verts[1], [ 0., 4., 10. ] );
+    assert_eq!( m._frames[1]._normal_indices, vec![ 52, 52, 52 ] );
+    assert_eq!( md2::ANORMS[ 5 ], [ 0., 0., 1. ] );
+    assert_eq!( md2::ANORMS[ 52 ], [ 1., 0., 0. ] );
+
+    //corrupt ident
+    let mut bad = b.clone();
+    bad[0] = b'X';
+    assert!( md2::Md2Parser::parse( &bad[..] ).is_err() );
+    //truncated
+    assert!( md2::Md2Parser::parse( &b[..b.len()-4] ).is_err() );
+    //normal index past the table
+    let mut bad = b.clone();
+    let last = bad.len() - 1;
+    bad[ last ] = 162;
+    assert_eq!( md2::Md2Parser::parse( &bad[..] ).err(), Some( "md2 normal index out of bounds" ) );
+}
+
+#[test]
+fn test_md2_interpolate(){
+    let b = synthetic_md2();
+    let m = md2::Md2Parser::parse( &b[..] ).expect( "md2 parse" );
+    let cc = md2::interpolate( & m, 0, 1, 0.5 ).expect( "md2 interpolate" );
+    assert_eq!( cc._batch_vert.len(), 9 );
+    assert_eq!( cc._batch_normal.len(), 9 );
+    assert_eq!( cc._batch_tc.len(), 6 );
+    for i in 0..3 {
+        assert!( ( cc._batch_vert[ i * 3 + 2 ] - 5. ).abs() < 1e-5 );
+        //stored vertex normals blended halfway between +z and +x
+        assert!( ( cc._batch_normal[ i * 3 ] - 0.5f32.sqrt() ).abs() < 1e-5 );
+        assert!( cc._batch_normal[ i * 3 + 1 ].abs() < 1e-5 );
+        assert!( ( cc._batch_normal[ i * 3 + 2 ] - 0.5f32.sqrt() ).abs() < 1e-5 );
+    }
+    let cc0 = md2::interpolate( & m, 0, 1, 0. ).expect( "md2 interpolate" );
+    assert_eq!( &cc0._batch_normal[0..3], &md2::ANORMS[ 5 ][..] );
+    assert!( ( cc._batch_tc[2] - 1. ).abs() < 1e-5 );
+    assert!( ( cc._batch_tc[5] - 1. ).abs() < 1e-5 );
+    assert_eq!( cc._submeshes.len(), 1 );
+    assert_eq!( cc._submeshes[0]._vert_count, 3 );
+    assert!( md2::interpolate( & m, 0, 2, 0.5 ).is_err() );
+}
@@ -0,0 +1,109 @@
+use interface::i_file::IParseBytes;
+
+use implement::file::md3;
+use implement::file::md5skeleton;
+
+use test::file::test_binread::{ put_i32, put_i16, put_f32, put_str };
+
+///single surface triangle and one tag over 2 frames, second frame moved along z and tag rotated 90 degrees about z
+fn synthetic_md3() -> Vec< u8 > {
+    let num_frames = 2;
+    let num_verts = 3;
+    let ofs_frames = 108;
+    let ofs_tags = ofs_frames + num_frames * 56;
+    let ofs_surfaces = ofs_tags + num_frames * 112;
+    let surf_ofs_shaders = 108;
+    let surf_ofs_tris = surf_ofs_shaders + 68;
+    let surf_ofs_st = surf_ofs_tris + 12;
+    let surf_ofs_xyz = surf_ofs_st + num_verts * 8;
+    let surf_len = surf_ofs_xyz + num_frames * num_verts * 8;
+    let ofs_eof = ofs_surfaces + surf_len;
+
+    let mut b = vec![];
+    b.extend_from_slice( b"IDP3" );
+    put_i32( & mut b, 15 );
+    put_str( & mut b, "upper", 64 );
+    for &v in [ 0, num_frames, 1, 1, 0, ofs_frames, ofs_tags, ofs_surfaces, ofs_eof ].iter() {
+        put_i32( & mut b, v );
+    }
+    for f in 0..num_frames {
+        for &v in [ -1f32, -1., -1., 1., 1., 1., 0., 0., 0., 2. ].iter() {
+            put_f32( & mut b, v );
+        }
+        put_str( & mut b, &format!( "frame{}", f ), 16 );
+    }
+    for f in 0..num_frames {
+        put_str( & mut b, "tag_head", 64 );
+        let axis = if f == 0 { [ 1f32, 0., 0., 0., 1., 0., 0., 0., 1. ] } else { [ 0f32, 1., 0., -1., 0., 0., 0., 0., 1. ] };
+        for &v in [ 0f32, 0., 3. ].iter().chain( axis.iter() ) {
+            put_f32( & mut b, v );
+        }
+    }
+    //surface
+    b.extend_from_slice( b"IDP3" );
+    put_str( & mut b, "body", 64 );
+    for &v in [ 0, num_frames, 1, num_verts, 1, surf_ofs_tris, surf_ofs_shaders, surf_ofs_st, surf_ofs_xyz, surf_len ].iter() {
+        put_i32( & mut b, v );
+    }
+    put_str( & mut b, "models/body.tga", 64 );
+    put_i32( & mut b, 0 );
+    for &i in [ 0, 1, 2 ].iter() {
+        put_i32( & mut b, i );
+    }
+    for &( s, t ) in [ ( 0f32, 0f32 ), ( 1., 0. ), ( 0., 1. ) ].iter() {
+        put_f32( & mut b, s );
+        put_f32( & mut b, t );
+    }
+    for f in 0..num_frames {
+        for &( x, y ) in [ ( 0i16, 0i16 ), ( 64, 0 ), ( 0, 64 ) ].iter() {
+            put_i16( & mut b, x );
+            put_i16( & mut b, y );
+            put_i16( & mut b, 128 * f as i16 );
+            put_i16( & mut b, 0 ); //normal along +z
+        }
+    }
+    assert_eq!( b.len(), ofs_eof as usize );
+    b
+}
+
+#[test]
+fn test_md3_parse(){
+    let b = synthetic_md3();
+    let m = md3::Md3Parser::parse( &b[..] ).expect( "md3 parse" );
+    assert_eq!( m._name, "upper" );
+    assert_eq!( m._frames.len(), 2 );
+    assert_eq!( m._tags.len(), 2 );
+    assert_eq!( m._tags[0][0]._name, "tag_head" );
+    assert_eq!( m._surfaces.len(), 1 );
+    assert_eq!( m._surfaces[0]._shaders, vec![ String::from( "models/body.tga" ) ] );
+    assert_eq!( m._surfaces[0]._frames[1][1]._pos, [ 1., 0., 2. ] );
+    let n = m._surfaces[0]._frames[0][0]._normal;
+    assert!( n[0].abs() < 1e-5 && n[1].abs() < 1e-5 && ( n[2] - 1. ).abs() < 1e-5 );
+
+    let mut bad = b.clone();
+    bad[4] = 14;
+    assert!( md3::Md3Parser::parse( &bad[..] ).is_err() );
+}
+
+#[test]
+fn test_md3_interpolate_and_tag(){
+    let b = synthetic_md3();
+    let m = md3::Md3Parser::parse( &b[..] ).expect( "md3 parse" );
+    let cc = md3::interpolate( & m, 0, 1, 0.5 ).expect( "md3 interpolate" );
+    assert_eq!( cc._batch_vert.len(), 9 );
+    assert_eq!( cc._batch_normal.len(), 9 );
+    assert_eq!( cc._batch_tc.len(), 6 );
+    assert!( ( cc._batch_vert[2] - 1. ).abs() < 1e-5 );
+    assert_eq!( cc._submeshes[0]._shader, "models/body.tga" );
+
+    //tag rotated 90 degrees about z at full interpolation
+    let x = md3::tag_xform( & m, "tag_head", 0, 1, 1. ).expect( "tag xform" );
+    let p = md5skeleton::xform_point( & x, [ 1., 0., 0. ] );
+    assert!( p[0].abs() < 1e-5 && ( p[1] - 1. ).abs() < 1e-5 && ( p[2] - 3. ).abs() < 1e-5 );
+    //halfway blend remains orthonormal
+    let x = md3::tag_xform( & m, "tag_head", 0, 1, 0.5 ).expect( "tag xform" );
+    let p = md5skeleton::xform_point( & x, [ 1., 0., 0. ] );
+    let l = ( p[0] * p[0] + p[1] * p[1] + ( p[2] - 3. ) * ( p[2] - 3. ) ).sqrt();
+    assert!( ( l - 1. ).abs() < 1e-5 );
+    assert!( md3::tag_xform( & m, "tag_weapon", 0, 1, 0.5 ).is_err() );
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use interface::i_file::IParseStr;
 
 use implement::file::md5common;
@@ -61,3 +63,65 @@ fn test_parse_md5comp(){
         // println!( "nor: {:?}", nor );
     }
 }
+
+#[test]
+fn test_md5comp_submesh(){
+    let file_mesh = md5common::file_open( "core/asset/md5/qshambler.md5mesh" ).expect("md5mesh file open invalid");
+    let file_anim = md5common::file_open( "core/asset/md5/qshamblerattack01.md5anim" ).expect("md5anim file open invalid");
+    let mesh = match Md5MeshParser::parse( &file_mesh ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let anim = match Md5AnimParser::parse( &file_anim ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let posecollection = match md5rig::process( & anim ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let mut comp = match md5comp::process( & posecollection, & mesh, 0, 1, 0.5f32 ){
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+
+    //submesh ranges are contiguous and cover the whole batch
+    assert_eq!( comp._submeshes.len(), mesh._meshes.len() );
+    let mut offset = 0;
+    for ( s, m ) in comp._submeshes.iter().zip( mesh._meshes.iter() ) {
+        assert_eq!( s._shader, m._shader );
+        assert_eq!( s._vert_offset, offset );
+        assert_eq!( s._vert_count, m._tris.len() * 3 );
+        assert!( s._texture.is_none() );
+        offset += s._vert_count;
+    }
+    assert_eq!( offset, comp._batch_vert.len() / 3 );
+
+    let materials : HashMap< String, String > = [ ( mesh._meshes[0]._shader.clone(), String::from( "texture0" ) ) ].iter().cloned().collect();
+    let unresolved = md5comp::map_submesh_textures( & mut comp, & materials );
+    assert_eq!( unresolved, mesh._meshes.len() - 1 );
+    assert_eq!( comp._submeshes[0]._texture, Some( String::from( "texture0" ) ) );
+}
+
+#[test]
+fn test_md5comp_tc(){
+    let file_mesh = md5common::file_open( "core/asset/md5/qshambler.md5mesh" ).expect("md5mesh file open invalid");
+    let file_anim = md5common::file_open( "core/asset/md5/qshamblerattack01.md5anim" ).expect("md5anim file open invalid");
+    let mesh = Md5MeshParser::parse( &file_mesh ).expect( "md5mesh parse" );
+    let anim = Md5AnimParser::parse( &file_anim ).expect( "md5anim parse" );
+    let posecollection = md5rig::process( & anim ).expect( "md5rig process" );
+    let comp = md5comp::process( & posecollection, & mesh, 0, 1, 0.0f32 ).expect( "md5comp process" );
+
+    //each triangle corner carries the texture coordinate of its own vertex
+    let mut offset = 0;
+    for m in mesh._meshes.iter() {
+        for t in m._tris.iter() {
+            for k in 0..3 {
+                let tc = m._verts[ t._vert_indices[ k ] as usize ]._tex_coords;
+                assert_eq!( & comp._batch_tc[ offset .. offset + 2 ], & tc[..] );
+                offset += 2;
+            }
+        }
+    }
+    assert_eq!( offset, comp._batch_tc.len() );
+}
@@ -0,0 +1,127 @@
+use interface::i_file::IParseStr;
+use interface::i_md5::anim::{ Md5AnimRoot, JointHierarchy, Bound, FrameJoint, Frame };
+use interface::i_md5::rig::RigJoint;
+use interface::i_md5::compress::CompressSettings;
+
+use implement::file::md5common;
+use implement::file::md5anim_nom::*;
+use implement::file::md5rig;
+use implement::file::md5compress;
+
+///rotation angle in radians between joint orientations
+fn orient_err( a: & RigJoint, b: & RigJoint ) -> f32 {
+    md5compress::quat_angle( & a._orient, & b._orient )
+}
+
+///number of joints in the longest parent chain, over which per joint orientation errors add up
+fn chain_depth( anim: & Md5AnimRoot ) -> usize {
+    let mut depth = vec![ 0usize; anim._hierarchy.len() ];
+    for ( i, j ) in anim._hierarchy.iter().enumerate() {
+        depth[i] = if j._parent < 0 { 1 } else { depth[ j._parent as usize ] + 1 };
+    }
+    depth.iter().cloned().max().unwrap_or( 0 )
+}
+
+///3 joint chain of unit length bones, all channels animated
+fn synthetic_anim() -> Md5AnimRoot {
+    let mut anim = Md5AnimRoot::init();
+    anim._framerate = 24;
+    for i in 0..3 {
+        anim._hierarchy.push( JointHierarchy { _name: format!( "j{}", i ), _parent: i as i64 - 1, _flags: 63, _start_index: i as u64 * 6 } );
+        anim._baseframe.push( FrameJoint { _index: i as u64, _pos: [ 0., 1., 0. ], _orient: [ 0., 0., 0. ] } );
+    }
+    for k in 0..20 {
+        let t = k as f32 / 19.;
+        let mut data = vec![];
+        for i in 0..3 {
+            let a = 0.3 * ( t * 3. + i as f32 ).sin();
+            data.extend_from_slice( &[ 0.1 * t, 1., 0.5 * t * t, 0.2 * a, -0.1 * a, a ] );
+        }
+        anim._bounds.push( Bound { _min: [ -3., -3., -3. ], _max: [ 3., 3., 3. ] } );
+        anim._frames.push( Frame { _index: k, _data: data } );
+    }
+    anim._numframes = anim._frames.len() as u64;
+    anim._numjoints = anim._hierarchy.len() as u64;
+    anim
+}
+
+#[test]
+fn test_md5compress(){
+    let file_content = md5common::file_open( "core/asset/md5/qshamblerattack01.md5anim" ).expect("file open invalid");
+    let anim = match Md5AnimParser::parse( &file_content ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let rig = match md5rig::process( & anim ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let settings = CompressSettings {
+        _tolerance_pos: 0.01f32,
+        _tolerance_angle: 0.01f32,
+    };
+    let c = match md5compress::compress( & anim, & settings ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    assert!( c._numframes == anim._numframes );
+    assert!( c._tracks.len() == anim._hierarchy.len() );
+    assert!( c._stat._ratio > 1f32 );
+    assert!( c._stat._bytes_compressed < c._stat._bytes_original );
+    for i in 0..c._tracks.len() {
+        assert!( c._stat._joint_err_pos[i] <= settings._tolerance_pos );
+        assert!( c._stat._joint_err_angle[i] <= settings._tolerance_angle );
+    }
+
+    //orientations sampled at keyframe times match the uncompressed rig
+    let settings_fine = CompressSettings {
+        _tolerance_pos: 1e-4f32,
+        _tolerance_angle: 1e-4f32,
+    };
+    let c_fine = md5compress::compress( & anim, & settings_fine ).expect( "compress" );
+    let max_angle = chain_depth( & anim ) as f32 * settings_fine._tolerance_angle;
+    for (k, f) in rig._frames.iter().enumerate() {
+        let p = md5compress::sample( & c_fine, k as f32 / c_fine._framerate as f32 );
+        assert!( p._joints.len() == f._joints.len() );
+        for (a, b) in p._joints.iter().zip( f._joints.iter() ) {
+            assert!( a._name == b._name );
+            assert!( orient_err( a, b ) <= max_angle );
+        }
+    }
+
+    //sampling beyond duration clamps to last frame
+    let last = md5compress::sample_frame( & c, ( c._numframes - 1 ) as f32 );
+    let beyond = md5compress::sample( & c, 1000f32 );
+    for (a, b) in last._joints.iter().zip( beyond._joints.iter() ) {
+        for j in 0..3 {
+            assert!( ( a._pos[j] - b._pos[j] ).abs() < 1e-5f32 );
+        }
+    }
+}
+
+#[test]
+fn test_md5compress_accuracy(){
+    let anim = synthetic_anim();
+    let rig = md5rig::process( & anim ).expect( "rig" );
+    let settings = CompressSettings {
+        _tolerance_pos: 1e-5f32,
+        _tolerance_angle: 1e-5f32,
+    };
+    let c = md5compress::compress( & anim, & settings ).expect( "compress" );
+    //linear position channels reduce to their end keys
+    assert_eq!( c._tracks[0]._pos[0].len(), 2 );
+    assert_eq!( c._tracks[0]._pos[1].len(), 1 );
+
+    //joint positions and orientations at keyframes are reproduced up to quantisation,
+    //which is below 5e-5 radians per joint for 16 bit components
+    let max_angle = chain_depth( & anim ) as f32 * 5e-5f32;
+    for (k, f) in rig._frames.iter().enumerate() {
+        let p = md5compress::sample_frame( & c, k as f32 );
+        for (a, b) in p._joints.iter().zip( f._joints.iter() ) {
+            for j in 0..3 {
+                assert!( ( a._pos[j] - b._pos[j] ).abs() < 1e-4f32 );
+            }
+            assert!( orient_err( a, b ) <= max_angle );
+        }
+    }
+}
@@ -0,0 +1,117 @@
+extern crate mazth;
+
+use std::f32;
+
+use self::mazth::quat::Quat;
+use self::mazth::mat::Mat3x1;
+
+use interface::i_md5::mesh::{ Md5MeshRoot, Md5Mesh, Md5Joint, Md5Vert, Md5Tri, Md5Weight };
+use interface::i_md5::rig::{ PoseJoints, RigJoint };
+use interface::i_md5::morph::{ MorphTarget, MorphDelta, MorphTrack, MorphKey };
+
+use implement::file::md5comp;
+use implement::file::md5morph;
+
+///single joint, single triangle mesh fully weighted to the joint
+fn synthetic_mesh() -> Md5MeshRoot {
+    let mut m = Md5MeshRoot::init();
+    m._numjoints = 1;
+    m._nummeshes = 1;
+    m._joints.push( Md5Joint { _name: String::from( "head" ), _parent_index: -1, _pos: [ 0., 0., 0. ], _orient: [ 0., 0., 0. ], _rot: Default::default() } );
+    let pos = [ [ 0f32, 0., 0. ], [ 1., 0., 0. ], [ 0., 1., 0. ] ];
+    let mut mesh = Md5Mesh { _shader: String::from( "face" ), _numverts: 3, _numtris: 1, _numweights: 3, _verts: vec![], _tris: vec![], _weights: vec![] };
+    for i in 0..3 {
+        mesh._verts.push( Md5Vert { _index: i as u64, _tex_coords: [ 0., 0. ], _weight_start: i as u64, _weight_count: 1, _normal: [ 0., 0., 0. ], _pos: pos[i] } );
+        mesh._weights.push( Md5Weight { _index: i as u64, _joint_index: 0, _weight_bias: 1., _pos: pos[i] } );
+    }
+    mesh._tris.push( Md5Tri { _index: 0, _vert_indices: [ 0, 1, 2 ] } );
+    m._meshes.push( mesh );
+    m
+}
+
+fn pose_rot_z() -> PoseJoints {
+    let rot_z = Quat::<f32>::init_from_axis_angle_radian( ( Mat3x1 { _val: [ 0f32, 0f32, 1f32 ] }, f32::consts::PI / 2. ) );
+    PoseJoints {
+        _joints: vec![ RigJoint { _name: String::from( "head" ), _parent: -1, _pos: [ 0., 0., 0. ], _orient: rot_z } ],
+    }
+}
+
+fn smile() -> MorphTarget {
+    MorphTarget {
+        _name: String::from( "smile" ),
+        _mesh_index: 0,
+        _deltas: vec![ MorphDelta { _vert_index: 0, _pos: [ 1., 0., 0. ], _normal: [ 0., 0., 0. ] } ],
+    }
+}
+
+#[test]
+fn test_md5morph_weights(){
+    let track = MorphTrack {
+        _target: String::from( "smile" ),
+        _keys: vec![ MorphKey { _time: 0., _weight: 0. },
+                     MorphKey { _time: 1., _weight: 1. },
+                     MorphKey { _time: 2., _weight: 0.5 } ],
+    };
+    assert!( ( md5morph::weight_at( & track, -1. ) ).abs() < 1e-5 );
+    assert!( ( md5morph::weight_at( & track, 0.25 ) - 0.25 ).abs() < 1e-5 );
+    assert!( ( md5morph::weight_at( & track, 1.5 ) - 0.75 ).abs() < 1e-5 );
+    assert!( ( md5morph::weight_at( & track, 5. ) - 0.5 ).abs() < 1e-5 );
+    //nan times hold the first key instead of panicking
+    assert!( ( md5morph::weight_at( & track, f32::NAN ) ).abs() < 1e-5 );
+    let nan_key = MorphTrack { _target: String::from( "smile" ), _keys: vec![ MorphKey { _time: 0., _weight: 0. }, MorphKey { _time: f32::NAN, _weight: 1. } ] };
+    md5morph::weight_at( & nan_key, 0.5 );
+
+    let targets = vec![ smile(), MorphTarget { _name: String::from( "blink" ), _mesh_index: 0, _deltas: vec![] } ];
+    let w = md5morph::weights_at( &targets[..], &[ track ], 1. );
+    assert_eq!( w.len(), 2 );
+    assert!( ( w[0] - 1. ).abs() < 1e-5 );
+    assert!( w[1].abs() < 1e-5 );
+}
+
+#[test]
+fn test_md5morph_skinned(){
+    let m = synthetic_mesh();
+    let pose = pose_rot_z();
+    let targets = vec![ smile() ];
+
+    //zero weight matches plain skinning
+    let base = md5comp::interpolate( & m, & pose, & pose, 0. ).expect( "interpolate" );
+    let zero = md5comp::interpolate_with_morphs( & m, & pose, & pose, 0., &targets[..], &[ 0. ] ).expect( "interpolate morph" );
+    assert_eq!( base._batch_vert, zero._batch_vert );
+    assert_eq!( base._batch_normal, zero._batch_normal );
+
+    //bind pose delta along x is carried by the joint rotation onto y
+    let half = md5comp::interpolate_with_morphs( & m, & pose, & pose, 0., &targets[..], &[ 0.5 ] ).expect( "interpolate morph" );
+    assert!( half._batch_vert[0].abs() < 1e-5 );
+    assert!( ( half._batch_vert[1] - 0.5 ).abs() < 1e-5 );
+    assert!( half._batch_vert[2].abs() < 1e-5 );
+
+    assert!( md5comp::interpolate_with_morphs( & m, & pose, & pose, 0., &targets[..], &[] ).is_err() );
+}
+
+#[test]
+fn test_md5morph_normal(){
+    let m = synthetic_mesh();
+    let pose = pose_rot_z();
+    //lifts vert 0 out of the triangle plane, the normal delta does not affect flat shading
+    let lift = MorphTarget {
+        _name: String::from( "lift" ),
+        _mesh_index: 0,
+        _deltas: vec![ MorphDelta { _vert_index: 0, _pos: [ 0., 0., 1. ], _normal: [ 1., 0., 0. ] } ],
+    };
+    let c = md5comp::interpolate_with_morphs( & m, & pose, & pose, 0., &[ lift ], &[ 0.5 ] ).expect( "interpolate morph" );
+    assert_eq!( c._batch_vert.len(), 9 );
+    assert_eq!( c._batch_normal.len(), 9 );
+
+    //every corner carries the face normal of the morphed triangle
+    let v = |i: usize| Mat3x1 { _val: [ c._batch_vert[ i * 3 ], c._batch_vert[ i * 3 + 1 ], c._batch_vert[ i * 3 + 2 ] ] };
+    let v01 = v(1).minus( & v(0) ).expect( "minus" );
+    let v02 = v(2).minus( & v(0) ).expect( "minus" );
+    let n = v02.cross( & v01 ).expect( "cross" ).normalize().expect( "normalize" );
+    assert!( n._val[2].abs() < 0.99 );
+    for corner in 0..3 {
+        for h in 0..3 {
+            assert!( ( c._batch_normal[ corner * 3 + h ] - n._val[h] ).abs() < 1e-5 );
+        }
+    }
+}
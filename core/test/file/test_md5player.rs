@@ -0,0 +1,86 @@
+use interface::i_file::IParseStr;
+
+use implement::file::md5common;
+use implement::file::md5anim_nom::*;
+use implement::file::md5mesh_nom::*;
+use implement::file::md5rig;
+use implement::file::md5player::{ AnimPlayer, LoopMode };
+
+#[test]
+fn test_md5player_loop(){
+    //10 frames at 10 fps, looping duration includes blend back to first frame
+    let mut p = AnimPlayer::init( 10, 10 );
+    assert!( ( p.duration() - 1. ).abs() < 1e-5 );
+    let t = p.update( 0.25 );
+    assert!( ( t - 0.25 ).abs() < 1e-5 );
+    let t = p.update( 1. );
+    assert!( ( t - 0.25 ).abs() < 1e-4 );
+    p.seek( 0.95 );
+    let ( s, e, interp ) = p.frame_sample();
+    assert!( s == 9 );
+    assert!( e == 0 );
+    assert!( ( interp - 0.5 ).abs() < 1e-3 );
+
+    //reverse playback wraps to the end
+    p.seek( 0.1 );
+    p.set_speed( -1. );
+    let t = p.update( 0.2 );
+    assert!( ( t - 0.9 ).abs() < 1e-4 );
+}
+
+#[test]
+fn test_md5player_clamp_pingpong(){
+    let mut p = AnimPlayer::init( 11, 10 );
+    p.set_loop_mode( LoopMode::Clamp );
+    assert!( ( p.duration() - 1. ).abs() < 1e-5 );
+    let t = p.update( 5. );
+    assert!( ( t - 1. ).abs() < 1e-5 );
+    let ( s, e, _ ) = p.frame_sample();
+    assert!( s == 10 );
+    assert!( e == 10 );
+    //reversing after overshoot responds immediately
+    p.set_speed( -1. );
+    let t = p.update( 0.5 );
+    assert!( ( t - 0.5 ).abs() < 1e-4 );
+
+    p.set_loop_mode( LoopMode::PingPong );
+    p.set_speed( 1. );
+    p.seek( 0. );
+    let t = p.update( 1.25 );
+    assert!( ( t - 0.75 ).abs() < 1e-4 );
+    let t = p.update( 1. );
+    assert!( ( t - 0.25 ).abs() < 1e-4 );
+
+    //paused playback does not advance
+    p.pause();
+    let t = p.update( 0.3 );
+    assert!( ( t - 0.25 ).abs() < 1e-4 );
+    p.play();
+    p.seek_normalized( 0.5 );
+    assert!( ( p.normalized_time() - 0.5 ).abs() < 1e-4 );
+}
+
+#[test]
+fn test_md5player_compute(){
+    let file_mesh = md5common::file_open( "core/asset/md5/qshambler.md5mesh" ).expect("file open invalid");
+    let file_anim = md5common::file_open( "core/asset/md5/qshamblerattack01.md5anim" ).expect("file open invalid");
+    let mesh = match Md5MeshParser::parse( &file_mesh ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let anim = match Md5AnimParser::parse( &file_anim ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let pc = match md5rig::process( & anim ) {
+        Ok( o ) => o,
+        Err( e ) => panic!( e ),
+    };
+    let mut p = AnimPlayer::init_from_pose_collection( & pc );
+    p.update( 0.3 );
+    let pose = p.pose( & pc ).expect( "pose invalid" );
+    assert!( pose._joints.len() == pc._frames[0]._joints.len() );
+    let cc = p.compute( & pc, & mesh ).expect( "compute invalid" );
+    assert!( cc._batch_vert.len() > 0 );
+    assert!( cc._batch_vert.len() == cc._batch_normal.len() );
+}
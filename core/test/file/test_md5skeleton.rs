@@ -0,0 +1,76 @@
+extern crate mazth;
+
+use std::f32;
+
+use self::mazth::quat::Quat;
+use self::mazth::mat::{ Mat3x1, Mat4 };
+
+use interface::i_md5::rig::{ PoseJoints, RigJoint };
+
+use implement::file::md5skeleton::{ self, Skeleton, Socket };
+use implement::math::util;
+use implement::render::mesh;
+
+fn synthetic_pose() -> PoseJoints {
+    //root at origin, child rotated 90 degrees about z at x = 1
+    let rot_z = Quat::<f32>::init_from_axis_angle_radian( ( Mat3x1 { _val: [ 0f32, 0f32, 1f32 ] }, f32::consts::PI / 2. ) );
+    PoseJoints {
+        _joints: vec![ RigJoint { _name: String::from( "root" ), _parent: -1, _pos: [ 0., 0., 0. ], _orient: Default::default() },
+                       RigJoint { _name: String::from( "hand_r" ), _parent: 0, _pos: [ 1., 0., 0. ], _orient: rot_z } ],
+    }
+}
+
+#[test]
+fn test_md5skeleton_joint_query(){
+    let pose = synthetic_pose();
+    let skel = Skeleton::init_from_pose( & pose );
+    assert_eq!( skel.joint_index( "hand_r" ), Some( 1 ) );
+    assert_eq!( skel.joint_index( "foot_l" ), None );
+    assert!( skel.joint_xform( & pose, "foot_l" ).is_err() );
+
+    //model translated by ( 0, 0, 5 )
+    let model = util::xform_from_pos_quat( [ 0., 0., 5. ], & Default::default() );
+    let m = skel.joint_world_xform( & pose, "hand_r", & model ).expect( "joint xform" );
+    let p = md5skeleton::xform_point( & m, [ 0., 0., 0. ] );
+    assert!( ( p[0] - 1. ).abs() < 1e-5 && p[1].abs() < 1e-5 && ( p[2] - 5. ).abs() < 1e-5 );
+    //local x axis of joint maps to world y
+    let p = md5skeleton::xform_point( & m, [ 1., 0., 0. ] );
+    assert!( ( p[0] - 1. ).abs() < 1e-5 && ( p[1] - 1. ).abs() < 1e-5 );
+
+    let ( pos, _orient ) = skel.joint_world( & pose, "hand_r", [ 0., 0., 5. ], & Default::default() ).expect( "joint world" );
+    assert!( ( pos[0] - 1. ).abs() < 1e-5 && ( pos[2] - 5. ).abs() < 1e-5 );
+}
+
+#[test]
+fn test_md5skeleton_socket(){
+    let pose = synthetic_pose();
+    let skel = Skeleton::init_from_pose( & pose );
+    let socket = Socket::init( "weapon", "hand_r", [ 2., 0., 0. ], Default::default() );
+    let model = util::identity_mat4();
+    let m = socket.xform( & skel, & pose, & model ).expect( "socket xform" );
+    let p = md5skeleton::xform_point( & m, [ 0., 0., 0. ] );
+    assert!( ( p[0] - 1. ).abs() < 1e-5 && ( p[1] - 2. ).abs() < 1e-5 && p[2].abs() < 1e-5 );
+
+    let ( pos, _ ) = socket.pos_orient( & skel, & pose, [ 0., 0., 0. ], & Default::default() ).expect( "socket pos" );
+    for i in 0..3 {
+        assert!( ( pos[i] - p[i] ).abs() < 1e-5 );
+    }
+
+    //attach a mesh to the socket
+    let mut m_attach = mesh::Mesh::init( 0 );
+    m_attach._batch_pos = vec![ 1., 0., 0. ];
+    m_attach._batch_normal = vec![ 1., 0., 0. ];
+    m_attach._batch_tc = vec![ 0., 0. ];
+    m_attach.apply_xform( & m ).expect( "apply xform" );
+    assert!( ( m_attach._batch_pos[0] - 1. ).abs() < 1e-5 );
+    assert!( ( m_attach._batch_pos[1] - 3. ).abs() < 1e-5 );
+    assert!( m_attach._batch_normal[0].abs() < 1e-5 );
+    assert!( ( m_attach._batch_normal[1] - 1. ).abs() < 1e-5 );
+
+    //degenerate transform leaves no valid normal transform
+    let flat = Mat4 { _val: [ 1., 0., 0., 0.,
+                              0., 1., 0., 0.,
+                              0., 0., 0., 0.,
+                              0., 0., 0., 1. ], _is_row_major: true };
+    assert_eq!( m_attach.apply_xform( & flat ), Err( "transform not invertible" ) );
+}
@@ -22,4 +22,13 @@ fn test_wavefrontobjcomp(){
     for i in 0..3 {
         assert!( obj_compute._bbox_lower[i] < obj_compute._bbox_upper[i] );
     }
+
+    //submeshes cover the batch in order
+    let mut offset = 0;
+    for s in obj_compute._submeshes.iter() {
+        assert_eq!( s._vert_offset, offset );
+        assert!( s._vert_count > 0 );
+        offset += s._vert_count;
+    }
+    assert_eq!( offset * 3, obj_compute._batch_vert.len() );
 }
@@ -0,0 +1,59 @@
+use implement::file::md5common;
+use implement::file::wavefrontmtl;
+
+#[test]
+fn test_parse_wavefrontmtl(){
+    let input = "# two materials\n\
+                 newmtl plastic\n\
+                 Kd 0.5 0.25 0.125\n\
+                 Ks 0.2\n\
+                 Ns 32 # comment\n\
+                 illum 2\n\
+                 map_Kd -s 2 2 1 grid tex.png\n\
+                 \n\
+                 newmtl metal\n\
+                 Ke 1 2 3\n\
+                 Tr 0.25\n\
+                 Pr 0.5\n\
+                 Pm 1\n\
+                 Pc 0.1\n\
+                 map_Bump ignored.png\n";
+    let m = wavefrontmtl::parse( input ).expect( "parse" );
+    assert_eq!( m.len(), 2 );
+    assert_eq!( m[0]._name, "plastic" );
+    assert_eq!( m[0]._kd, [ 0.5, 0.25, 0.125 ] );
+    assert_eq!( m[0]._ks, [ 0.2, 0.2, 0.2 ] );
+    assert_eq!( m[0]._ns, 32. );
+    assert_eq!( m[0]._illum, 2 );
+    assert_eq!( m[0]._map_kd, Some( String::from( "-s 2 2 1 grid tex.png" ) ) );
+    assert_eq!( m[0]._pr, None );
+
+    assert_eq!( m[1]._name, "metal" );
+    assert_eq!( m[1]._ke, [ 1., 2., 3. ] );
+    assert_eq!( m[1]._d, 0.75 );
+    assert_eq!( m[1]._pr, Some( 0.5 ) );
+    assert_eq!( m[1]._pm, Some( 1. ) );
+    assert_eq!( m[1]._pc, Some( 0.1 ) );
+    assert_eq!( m[1]._map_kd, None );
+
+    assert!( wavefrontmtl::parse( "Kd 1 1 1\n" ).is_err() );
+    assert!( wavefrontmtl::parse( "newmtl a\nKd 1 1\n" ).is_err() );
+    assert!( wavefrontmtl::parse( "newmtl a\nNs x\n" ).is_err() );
+    assert!( wavefrontmtl::parse( "newmtl\n" ).is_err() );
+}
+
+#[test]
+fn test_parse_wavefrontmtl_file(){
+    let file_content = md5common::file_open( "core/asset/obj/25-vaz-2108/2108_tri.mtl" ).expect( "file open invalid" );
+    let m = wavefrontmtl::parse( &file_content ).expect( "parse unsuccessful" );
+    assert!( !m.is_empty() );
+
+    let file_content = md5common::file_open( "core/asset/obj/sniper/rifle_mod_2.mtl" ).expect( "file open invalid" );
+    let m = wavefrontmtl::parse( &file_content ).expect( "parse unsuccessful" );
+    assert_eq!( m.len(), 1 );
+    assert_eq!( m[0]._name, "KSR29sniperrifle" );
+    assert!( ( m[0]._ns - 96.078431 ).abs() < 1e-4 );
+    assert!( ( m[0]._kd[1] - 0.250811 ).abs() < 1e-6 );
+    assert_eq!( m[0]._ks, [ 0.5, 0.5, 0.5 ] );
+    assert_eq!( m[0]._map_kd, Some( String::from( "textures/ID.png" ) ) );
+}
@@ -0,0 +1 @@
+pub mod test_kernel_headless;
@@ -0,0 +1,205 @@
+use interface::i_game_logic::IGameLogic;
+use interface::i_kernel::IKernel;
+use interface::i_scheduler::IScheduler;
+use interface::i_ui::{ IUi, InputFiltered, KeyCode, State, Coord };
+use interface::i_window::IWindow;
+
+use implement::kernel::kernel_headless::{ self, KernelHeadless };
+use implement::render::culling::{ ICullable, BoundingVolume };
+use implement::render::mesh::Mesh;
+use implement::render::renderer_gl::Event;
+use implement::ui::input_headless::InputHeadless;
+use implement::window::winheadless::WinHeadless;
+
+use test::render::test_renderer_sw;
+
+#[derive(Clone, Default)]
+struct State0 {
+    _exit: bool,
+    _continue_compute: bool,
+    _frames: u64,
+    _inputs: Vec< ( u64, KeyCode ) >,
+    _dt: Vec< f32 >,
+}
+
+impl From< ( State0, Change ) > for State0 {
+    fn from( ( s, _ ): ( State0, Change ) ) -> State0 {
+        s
+    }
+}
+
+#[derive(Clone, Default)]
+struct Change {}
+
+impl From< Compute > for Change {
+    fn from( _: Compute ) -> Change {
+        Change {}
+    }
+}
+
+struct Compute {}
+
+struct Schedule {}
+
+impl IScheduler for Schedule {
+    type Item = Compute;
+    fn new( _items: & [ Compute ] ) -> Schedule {
+        Schedule {}
+    }
+}
+
+impl Iterator for Schedule {
+    type Item = Vec< Compute >;
+    fn next( & mut self ) -> Option< Vec< Compute > > {
+        None
+    }
+}
+
+///quad facing the camera with its light and camera
+struct Scene0 {
+    _mesh: Mesh,
+}
+
+impl ICullable for Scene0 {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        BoundingVolume::Unbounded
+    }
+}
+
+impl Into< Vec< Event > > for Scene0 {
+    fn into( self ) -> Vec< Event > {
+        test_renderer_sw::frame( vec![ self._mesh ] )
+    }
+}
+
+///records button presses per frame and exits on Q
+struct Logic0 {
+    _state: State0,
+}
+
+impl IGameLogic for Logic0 {
+    type EventInput = InputFiltered;
+    type EventRender = Event;
+    type GameState = State0;
+    type GameStateChangePending = Change;
+    type GameStateChangeApply = Change;
+    type ComputeUnit = Compute;
+    type ComputeSchedule = Schedule;
+    type RenderObj = Scene0;
+
+    fn new() -> Logic0 {
+        Logic0 { _state: Default::default() }
+    }
+    fn run_init_hook( & mut self ) -> Result< (), & 'static str > {
+        Ok( () )
+    }
+    fn get_states( & mut self ) -> & State0 {
+        & self._state
+    }
+    fn get_states_mut( & mut self ) -> & mut State0 {
+        & mut self._state
+    }
+    fn set_frame_delta( & mut self, dt: f32 ) {
+        self._state._dt.push( dt );
+    }
+    fn transition_states( & mut self, inputs: & [ InputFiltered ] ) -> Change {
+        for i in inputs.iter() {
+            match i {
+                &InputFiltered::Button { key: KeyCode::Q, .. } => {
+                    self._state._exit = true;
+                },
+                &InputFiltered::Button { key, state: State::Press } => {
+                    self._state._inputs.push( ( self._state._frames, key ) );
+                },
+                _ => {},
+            }
+        }
+        self._state._frames += 1;
+        Default::default()
+    }
+    fn continue_compute( & mut self ) -> bool {
+        self._state._continue_compute
+    }
+    fn set_continue_compute( & mut self, b: bool ) {
+        self._state._continue_compute = b;
+    }
+    fn get_computations( & mut self, _changed: & Change ) -> Vec< Compute > {
+        vec![]
+    }
+    fn schedule_computes( & mut self, _computes: Vec< Compute > ) -> Vec< Schedule > {
+        vec![]
+    }
+    fn get_renderable_components( & mut self ) -> Vec< Scene0 > {
+        vec![ Scene0 { _mesh: test_renderer_sw::quad( 0, 1., 0., true ) } ]
+    }
+    fn should_exit( & mut self ) -> bool {
+        self._state._exit
+    }
+}
+
+fn press( key: KeyCode ) -> InputFiltered {
+    InputFiltered::Button { key: key, state: State::Press }
+}
+
+#[test]
+fn test_winheadless_script(){
+    let mut w = WinHeadless::with_script( 4, 4, &[ ( 2, press( KeyCode::B ) ),
+                                                   ( 0, press( KeyCode::A ) ),
+                                                   ( 2, press( KeyCode::C ) ) ] );
+    w.push_event( 1, InputFiltered::MouseCoord( Coord::X, 0.5 ) );
+    assert_eq!( w.pending(), 4 );
+    assert_eq!( w.frame(), None );
+    assert!( w.handle_events_pass_thru().is_none() );
+
+    let mut delivered = vec![];
+    for _ in 0..4 {
+        w.per_frame_setup().expect( "frame" );
+        delivered.push( w.handle_events_pass_thru().is_some() );
+    }
+    //one event per frame, the second event of frame 2 spills into frame 3
+    assert_eq!( delivered, vec![ true, true, true, true ] );
+    let frames : Vec< u64 > = w.recorded().iter().map( |x| x.0 ).collect();
+    assert_eq!( frames, vec![ 0, 1, 2, 3 ] );
+    match w.recorded()[2].1 {
+        InputFiltered::Button { key: KeyCode::B, .. } => {},
+        _ => { panic!( "unexpected order" ); },
+    }
+
+    //handle_events drains all due events at once
+    let mut w = WinHeadless::with_script( 4, 4, &[ ( 0, press( KeyCode::A ) ), ( 0, press( KeyCode::B ) ), ( 5, press( KeyCode::C ) ) ] );
+    w.per_frame_setup().expect( "frame" );
+    let mut count = 0;
+    w.handle_events( |_| count += 1 );
+    assert_eq!( count, 2 );
+    assert_eq!( w.pending(), 1 );
+
+    let mut ui = InputHeadless::new();
+    assert_eq!( ui.process_input_events( &[ InputFiltered::Ignored, press( KeyCode::A ) ] ).len(), 1 );
+}
+
+#[test]
+fn test_kernel_headless_run_frames(){
+    let script = [ ( 1, press( KeyCode::A ) ), ( 4, press( KeyCode::B ) ) ];
+    let mut k : KernelHeadless< Logic0 > = KernelHeadless::init( 32, 32, & script ).expect( "kernel" );
+    assert_eq!( k.run_frames( 6 ).expect( "run" ), 6 );
+    assert_eq!( k._game_logic._state._frames, 6 );
+    assert_eq!( k._game_logic._state._inputs.len(), 2 );
+    assert_eq!( k._game_logic._state._inputs[0].0, 1 );
+    assert_eq!( k._game_logic._state._inputs[1].0, 4 );
+    assert_eq!( k._renderer._frame, 6 );
+    //fixed time step regardless of wall clock
+    assert_eq!( k._game_logic._state._dt, vec![ kernel_headless::FRAME_DELTA; 6 ] );
+    //quad rendered at the center of the frame
+    assert!( k._renderer.pixel( 16, 16 ) != [ 0, 0, 0 ] );
+    assert_eq!( k._renderer.pixel( 0, 0 ), [ 0, 0, 0 ] );
+
+    //exit signal stops before the frame limit
+    let script = [ ( 2, press( KeyCode::Q ) ) ];
+    let mut k : KernelHeadless< Logic0 > = KernelHeadless::init( 8, 8, & script ).expect( "kernel" );
+    assert_eq!( k.run_frames( 10 ).expect( "run" ), 3 );
+    assert_eq!( k._windowing.frame(), Some( 2 ) );
+
+    let k : KernelHeadless< Logic0 > = KernelHeadless::new().expect( "kernel" );
+    assert_eq!( k._windowing._dim, ( 500, 500 ) );
+    assert!( KernelHeadless::< Logic0 >::init( 0, 8, &[] ).is_err() );
+}
@@ -3,3 +3,13 @@ pub mod test_spline_bezier;
 pub mod test_linearinterp;
 pub mod test_interpolate;
 pub mod test_sim_anneal;
+pub mod test_frustum;
+pub mod test_clusterbox;
+pub mod test_bvh;
+pub mod test_spline_cubic;
+pub mod test_arclength;
+pub mod test_quat_interp;
+pub mod test_easing;
+pub mod test_simplify;
+pub mod test_convex_hull;
+pub mod test_surf_interact;
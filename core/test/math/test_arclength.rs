@@ -0,0 +1,102 @@
+extern crate mazth;
+
+use self::mazth::mat::Mat4x1;
+
+use interface::i_interpolate::IInterpolate;
+
+use implement::math::arclength::{ ArcLength, ArcLengthInterp };
+use implement::math::linearinterp::LinearInterp;
+use implement::math::spline_bezier::SplineBezier;
+use implement::math::piecewise::Piecewise;
+
+use test::math::test_interpolate;
+
+fn pt( x: f64, y: f64, z: f64 ) -> Mat4x1< f64 > {
+    Mat4x1 { _val: [ x, y, z, 1f64 ] }
+}
+
+///L shaped path of length 3 then 4
+fn l_path() -> ArcLength< LinearInterp > {
+    let segs = vec![ LinearInterp::init( 10, pt( 0., 0., 0. ), pt( 3., 0., 0. ) ),
+                     LinearInterp::init( 10, pt( 3., 0., 0. ), pt( 3., 4., 0. ) ) ];
+    ArcLength::init( segs, 16 ).expect( "arc length init" )
+}
+
+///straight bezier along x with control points bunched at the start
+fn uneven_bezier() -> SplineBezier {
+    SplineBezier::init( 20, pt( 0., 0., 0. ), pt( 0.1, 0., 0. ), pt( 0.2, 0., 0. ), pt( 10., 0., 0. ) )
+}
+
+#[test]
+fn test_arclength_lookup(){
+    let a = l_path();
+    assert!( ( a.length() - 7. ).abs() < 1e-9 );
+    assert!( ( a.segment_length( 1 ) - 4. ).abs() < 1e-9 );
+
+    let ( seg, f ) = a.distance_to_param( 5. );
+    assert_eq!( seg, 1 );
+    assert!( ( f - 0.5 ).abs() < 1e-9 );
+    let p = a.point_at_distance( 5. );
+    assert!( ( p._val[0] - 3. ).abs() < 1e-9 && ( p._val[1] - 2. ).abs() < 1e-9 );
+    //clamped past either end
+    assert_eq!( a.distance_to_param( -1. ), ( 0, 0. ) );
+    assert_eq!( a.distance_to_param( 100. ), ( 1, 1. ) );
+
+    assert!( ( a.param_to_distance( 1, 0.25 ).unwrap() - 4. ).abs() < 1e-9 );
+    assert!( a.param_to_distance( 2, 0.25 ).is_err() );
+    for &d in [ 0.3, 2.9, 3.0, 6.5 ].iter() {
+        let ( s, f ) = a.distance_to_param( d );
+        assert!( ( a.param_to_distance( s, f ).unwrap() - d ).abs() < 1e-9 );
+    }
+
+    assert!( ArcLength::< LinearInterp >::init( vec![], 16 ).is_err() );
+    //non-finite paths are rejected instead of breaking lookups
+    let nan = LinearInterp::init( 10, pt( 0., 0., 0. ), pt( f64::NAN, 0., 0. ) );
+    assert!( ArcLength::init( vec![ nan ], 16 ).is_err() );
+}
+
+#[test]
+fn test_arclength_closest_point(){
+    let a = l_path();
+    let c = a.closest_point( & pt( 1., 1., 0. ) );
+    assert_eq!( c._segment, 0 );
+    assert!( ( c._distance - 1. ).abs() < 1e-6 );
+    assert!( ( c._separation - 1. ).abs() < 1e-6 );
+    let c = a.closest_point( & pt( 5., 3., 0. ) );
+    assert_eq!( c._segment, 1 );
+    assert!( ( c._distance - 6. ).abs() < 1e-6 );
+    assert!( ( c._point._val[1] - 3. ).abs() < 1e-6 );
+
+    //curved path
+    let mut splines = Piecewise::init();
+    splines.add( SplineBezier::init( 10, pt( 0., 0., 0. ), pt( 0., 5., 0. ), pt( 5., 5., 0. ), pt( 5., 0., 0. ) ) );
+    let b = ArcLength::init_from_piecewise( & splines, 64 ).expect( "arc length init" );
+    let c = b.closest_point( & pt( 2.5, 10., 0. ) );
+    assert!( ( c._fraction - 0.5 ).abs() < 1e-4 );
+    assert!( ( c._point._val[1] - 3.75 ).abs() < 1e-4 );
+    assert!( ( c._distance - b.length() * 0.5 ).abs() < 1e-3 );
+}
+
+#[test]
+fn test_arclength_constant_speed(){
+    let b = uneven_bezier();
+    //raw steps speed up along the curve
+    let raw : Vec< _ > = b.clone().collect();
+    let raw_gaps : Vec< f64 > = raw.windows( 2 ).map( |w| w[1]._val[0] - w[0]._val[0] ).collect();
+    let max = raw_gaps.iter().cloned().fold( 0., f64::max );
+    let min = raw_gaps.iter().cloned().fold( 1e9, f64::min );
+    assert!( max / min > 3. );
+
+    let path = ArcLength::init( vec![ b ], 256 ).expect( "arc length init" );
+    assert!( ( path.length() - 10. ).abs() < 1e-6 );
+    let interp = ArcLengthInterp::init( 20, path.clone() );
+    let pts : Vec< _ > = interp.collect();
+    assert_eq!( pts.len(), 20 );
+    for w in pts.windows( 2 ) {
+        assert!( ( w[1]._val[0] - w[0]._val[0] - 10. / 19. ).abs() < 1e-2 );
+    }
+
+    let mut interp = ArcLengthInterp::init( 20, path );
+    assert_eq!( interp.num_steps(), 20 );
+    test_interpolate::test_interp_forward_back( & mut interp, & pt( 0., 0., 0. ), & pt( 10., 0., 0. ) );
+}
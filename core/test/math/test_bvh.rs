@@ -0,0 +1,116 @@
+use interface::i_stat_tree::IStatTree;
+
+use implement::math::bvh::{ Bvh, TriMeshBvh };
+use implement::math::intersect;
+
+///unit boxes on a 4x4x4 lattice with spacing 3
+fn lattice() -> Vec< ( [f32;3], [f32;3] ) > {
+    let mut v = vec![];
+    for x in 0..4 {
+        for y in 0..4 {
+            for z in 0..4 {
+                let l = [ x as f32 * 3., y as f32 * 3., z as f32 * 3. ];
+                v.push( ( l, [ l[0] + 1., l[1] + 1., l[2] + 1. ] ) );
+            }
+        }
+    }
+    v
+}
+
+fn brute_aabb( b: &[ ( [f32;3], [f32;3] ) ], lower: [f32;3], upper: [f32;3] ) -> Vec< usize > {
+    ( 0..b.len() ).filter( |&i| intersect::aabb_overlap( b[i].0, b[i].1, lower, upper ) ).collect()
+}
+
+#[test]
+fn test_bvh_scene_queries(){
+    let b = lattice();
+    let bvh = Bvh::init( &b[..], 2 );
+    assert_eq!( bvh.len(), 64 );
+
+    //every primitive referenced exactly once
+    let mut idx = bvh._indices.clone();
+    idx.sort();
+    assert_eq!( idx, ( 0..64 ).collect::< Vec< _ > >() );
+
+    for &( l, u ) in [ ( [ 0.5f32, 0.5, 0.5 ], [ 3.5f32, 3.5, 3.5 ] ),
+                       ( [ -10., -10., 2.5 ], [ 20., 20., 2.9 ] ),
+                       ( [ 4., 4., 4. ], [ 10., 4.5, 10. ] ) ].iter() {
+        assert_eq!( bvh.query_aabb( l, u ), brute_aabb( &b[..], l, u ) );
+    }
+    //sphere reaching the corner of the first box only
+    assert_eq!( bvh.query_sphere( [ -0.5, -0.5, -0.5 ], 1. ), vec![ 0 ] );
+
+    //ray along x through the row y=z=0
+    let ( hit, t ) = bvh.ray_first_hit_bounds( [ -5., 0.5, 0.5 ], [ 1., 0., 0. ], 100. ).expect( "first hit" );
+    assert_eq!( b[hit].0, [ 0., 0., 0. ] );
+    assert!( ( t - 5. ).abs() < 1e-5 );
+    //from the opposite side
+    let ( hit, t ) = bvh.ray_first_hit_bounds( [ 20., 0.5, 0.5 ], [ -1., 0., 0. ], 100. ).expect( "first hit" );
+    assert_eq!( b[hit].0, [ 9., 0., 0. ] );
+    assert!( ( t - 10. ).abs() < 1e-5 );
+    //between boxes
+    assert!( bvh.ray_first_hit_bounds( [ -5., 2., 2. ], [ 1., 0., 0. ], 100. ).is_none() );
+    //distance limit
+    assert!( bvh.ray_first_hit_bounds( [ -5., 0.5, 0.5 ], [ 1., 0., 0. ], 4. ).is_none() );
+    assert!( bvh.ray_any_hit( [ -5., 0.5, 0.5 ], [ 1., 0., 0. ], 100., |p, _| {
+        intersect::ray_aabb( [ -5., 0.5, 0.5 ], [ 1., 0., 0. ], b[p].0, b[p].1 ).map( |x| x.0 )
+    } ).is_some() );
+}
+
+#[test]
+fn test_bvh_refit_and_stats(){
+    let mut b = lattice();
+    let mut bvh = Bvh::init( &b[..], 1 );
+    assert_eq!( bvh.node_degree(), Some( 2 ) );
+    //single primitive leaves give 2n - 1 nodes
+    assert_eq!( bvh.sum_subtree_child_count(), Some( 2 * 64 - 2 ) );
+    assert_eq!( bvh.min_depth(), Some( 6 ) );
+    assert_eq!( bvh.max_depth(), Some( 6 ) );
+    assert_eq!( bvh.balance_ratio(), Some( 1. ) );
+
+    //move one box far away and refit
+    b[0] = ( [ 100., 100., 100. ], [ 101., 101., 101. ] );
+    bvh.refit( &b[..] ).expect( "refit" );
+    assert_eq!( bvh._nodes[0]._upper, [ 101., 101., 101. ] );
+    assert_eq!( bvh.query_aabb( [ 99., 99., 99. ], [ 102., 102., 102. ] ), vec![ 0 ] );
+    assert!( bvh.query_aabb( [ 0., 0., 0. ], [ 0.5, 0.5, 0.5 ] ).is_empty() );
+    assert!( bvh.refit( &b[1..] ).is_err() );
+
+    let empty = Bvh::init( &[], 4 );
+    assert!( empty.ray_first_hit_bounds( [ 0., 0., 0. ], [ 1., 0., 0. ], 1. ).is_none() );
+    assert_eq!( empty.max_depth(), None );
+}
+
+#[test]
+fn test_bvh_trimesh(){
+    //2 quads facing z, at z = 0 and z = 5
+    let mut pos = vec![];
+    for &z in [ 0f32, 5. ].iter() {
+        pos.extend_from_slice( &[ 0., 0., z, 1., 0., z, 1., 1., z,
+                                  0., 0., z, 1., 1., z, 0., 1., z ] );
+    }
+    let mut m = TriMeshBvh::init( &pos[..], 1 ).expect( "trimesh init" );
+    assert_eq!( m.num_tris(), 4 );
+    assert!( TriMeshBvh::init( &pos[1..], 1 ).is_err() );
+
+    let h = m.ray_first_hit( [ 0.75, 0.25, 10. ], [ 0., 0., -1. ], 100. ).expect( "hit" );
+    assert_eq!( h._tri, 2 );
+    assert!( ( h._t - 5. ).abs() < 1e-5 );
+    //hit point reconstructed from barycentrics
+    let v = m.tri( h._tri );
+    let x = v[0][0] + h._u * ( v[1][0] - v[0][0] ) + h._v * ( v[2][0] - v[0][0] );
+    assert!( ( x - 0.75 ).abs() < 1e-5 );
+
+    assert!( m.ray_any_hit( [ 0.25, 0.75, 10. ], [ 0., 0., -1. ], 100. ) );
+    assert!( !m.ray_any_hit( [ 2., 2., 10. ], [ 0., 0., -1. ], 100. ) );
+    assert_eq!( m.query_sphere( [ 0.5, 0.5, 5.5 ], 0.6 ), vec![ 2, 3 ] );
+
+    //deform the upper quad out of the way
+    for i in 0..6 {
+        pos[ 18 + i * 3 ] += 10.;
+    }
+    m.refit( &pos[..] ).expect( "refit" );
+    let h = m.ray_first_hit( [ 0.75, 0.25, 10. ], [ 0., 0., -1. ], 100. ).expect( "hit" );
+    assert_eq!( h._tri, 0 );
+    assert!( ( h._t - 10. ).abs() < 1e-5 );
+}
@@ -0,0 +1,102 @@
+use std::f32;
+
+use interface::i_stat_tree::IStatTree;
+
+use implement::math::clusterbox::ClusterBox;
+
+///10x10x10 grid of unit cells
+fn grid() -> ClusterBox {
+    ClusterBox::init( [ 0., 0., 0. ], [ 10., 10., 10. ], [ 10, 10, 10 ] ).expect( "clusterbox init" )
+}
+
+#[test]
+fn test_clusterbox_insert_update_remove(){
+    assert!( ClusterBox::init( [ 0., 0., 0. ], [ 10., 10., 10. ], [ 0, 1, 1 ] ).is_err() );
+    assert!( ClusterBox::init( [ 0., 0., 0. ], [ 10., 0., 10. ], [ 1, 1, 1 ] ).is_err() );
+
+    let mut c = grid();
+    c.insert( 0, [ 0.5, 0.5, 0.5 ], [ 1.5, 0.7, 0.7 ] ).expect( "insert" );
+    assert!( c.insert( 0, [ 0., 0., 0. ], [ 1., 1., 1. ] ).is_err() );
+    assert!( c.insert( 1, [ 1., 0., 0. ], [ 0., 1., 1. ] ).is_err() );
+    assert_eq!( c.len(), 1 );
+    assert_eq!( c.cell( [ 0, 0, 0 ] ), &[ 0 ] );
+    assert_eq!( c.cell( [ 1, 0, 0 ] ), &[ 0 ] );
+
+    c.update( 0, [ 5.2, 5.2, 5.2 ], [ 5.4, 5.4, 5.4 ] ).expect( "update" );
+    assert!( c.cell( [ 0, 0, 0 ] ).is_empty() );
+    assert_eq!( c.cell( [ 5, 5, 5 ] ), &[ 0 ] );
+    assert_eq!( c.query_point( [ 5.3, 5.3, 5.3 ] ), vec![ 0 ] );
+    assert!( c.update( 7, [ 0., 0., 0. ], [ 1., 1., 1. ] ).is_err() );
+
+    //out of grid bounds are clamped to boundary cells
+    c.insert( 2, [ -5., -5., -5. ], [ -4., -4., -4. ] ).expect( "insert outside" );
+    assert_eq!( c.cell( [ 0, 0, 0 ] ), &[ 2 ] );
+
+    c.remove( 0 ).expect( "remove" );
+    assert!( c.remove( 0 ).is_err() );
+    assert!( c.cell( [ 5, 5, 5 ] ).is_empty() );
+    assert_eq!( c.len(), 1 );
+}
+
+#[test]
+fn test_clusterbox_queries(){
+    let mut c = grid();
+    c.insert( 0, [ 1., 1., 1. ], [ 2., 2., 2. ] ).unwrap();
+    c.insert( 1, [ 1.5, 1.5, 1.5 ], [ 3., 3., 3. ] ).unwrap();
+    c.insert( 2, [ 8., 8., 8. ], [ 9., 9., 9. ] ).unwrap();
+    c.insert( 3, [ 5., 1., 1. ], [ 6., 2., 2. ] ).unwrap();
+
+    assert_eq!( c.query_point( [ 1.7, 1.7, 1.7 ] ), vec![ 0, 1 ] );
+    assert!( c.query_point( [ 0.5, 0.5, 0.5 ] ).is_empty() );
+
+    assert_eq!( c.query_box( [ 2.5, 2.5, 2.5 ], [ 8.5, 8.5, 8.5 ] ), vec![ 1, 2 ] );
+    assert_eq!( c.query_box( [ 0., 0., 0. ], [ 10., 10., 10. ] ), vec![ 0, 1, 2, 3 ] );
+
+    //sphere reaching box 3 but not box 0 from between them
+    assert_eq!( c.query_sphere( [ 4., 1.5, 1.5 ], 1.1 ), vec![ 1, 3 ] );
+    assert!( c.query_sphere( [ 7., 5., 5. ], 0.5 ).is_empty() );
+
+    //ray along x through boxes 0 and 3, sorted by distance
+    let hits = c.query_ray( [ -5., 1.2, 1.2 ], [ 1., 0., 0. ], 100. );
+    assert_eq!( hits.iter().map( |x| x.0 ).collect::< Vec< _ > >(), vec![ 0, 3 ] );
+    assert!( ( hits[0].1 - 6. ).abs() < 1e-5 );
+    assert!( ( hits[1].1 - 10. ).abs() < 1e-5 );
+    //limited distance
+    assert_eq!( c.query_ray( [ -5., 1.2, 1.2 ], [ 1., 0., 0. ], 8. ).len(), 1 );
+    //diagonal ray
+    let hits = c.query_ray( [ 0., 0., 0. ], [ 1., 1., 1. ], 100. );
+    assert_eq!( hits.iter().map( |x| x.0 ).collect::< Vec< _ > >(), vec![ 0, 1, 2 ] );
+    //missing the grid
+    assert!( c.query_ray( [ -5., 20., 1. ], [ 1., 0., 0. ], 100. ).is_empty() );
+    //unbounded rays terminate, including axis aligned and degenerate directions
+    assert_eq!( c.query_ray( [ -5., 1.2, 1.2 ], [ 1., 0., 0. ], f32::INFINITY ).len(), 2 );
+    assert_eq!( c.query_ray( [ 1.2, 1.2, 1.2 ], [ 0., 0., 0. ], f32::INFINITY ).len(), 0 );
+    assert_eq!( c.query_ray( [ 1.2, 1.2, 1.2 ], [ f32::NAN, 0., 0. ], f32::INFINITY ).len(), 0 );
+}
+
+#[test]
+fn test_clusterbox_pairs_and_stats(){
+    let mut c = grid();
+    assert_eq!( c.max_depth(), Some( 0 ) );
+    assert_eq!( c.mean_depth(), None );
+    assert_eq!( c.balance_ratio(), None );
+
+    c.insert( 0, [ 1., 1., 1. ], [ 2., 2., 2. ] ).unwrap();
+    c.insert( 1, [ 1.5, 1.5, 1.5 ], [ 3., 3., 3. ] ).unwrap();
+    //shares a cell with 1 without overlapping it
+    c.insert( 2, [ 3.2, 3.2, 3.2 ], [ 3.5, 3.5, 3.5 ] ).unwrap();
+    c.insert( 3, [ 1.8, 1.8, 1.8 ], [ 1.9, 1.9, 1.9 ] ).unwrap();
+    assert_eq!( c.overlapping_pairs(), vec![ ( 0, 1 ), ( 0, 3 ), ( 1, 3 ) ] );
+
+    c.update( 3, [ 7.1, 7.1, 7.1 ], [ 7.2, 7.2, 7.2 ] ).unwrap();
+    assert_eq!( c.overlapping_pairs(), vec![ ( 0, 1 ) ] );
+
+    assert_eq!( c.node_degree(), Some( 1000 ) );
+    //0 covers 8 cells inside the 27 cells of 1, 2 and 3 one each
+    assert_eq!( c.sum_subtree_child_count(), Some( 8 + 27 + 1 + 1 ) );
+    assert_eq!( c.occupied_cells(), 28 );
+    assert_eq!( c.max_depth(), Some( 2 ) );
+    assert_eq!( c.min_depth(), Some( 1 ) );
+    assert_eq!( c.mean_depth(), Some( 1 ) );
+    assert!( ( c.balance_ratio().unwrap() - 37. / 28. / 2. ).abs() < 1e-9 );
+}
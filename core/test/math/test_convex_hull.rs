@@ -0,0 +1,178 @@
+extern crate mazth;
+
+use std::collections::HashSet;
+
+use self::mazth::i_shape::IShape;
+use self::mazth::mat::Mat3x1;
+
+use implement::math::convex_hull::{ ConvexHull, HullKind };
+use implement::math::frustum::Frustum;
+
+///deterministic points in [0,1)
+fn lcg( n: usize, seed: u64 ) -> Vec< f64 > {
+    let mut s = seed;
+    ( 0..n ).map( |_| {
+        s = s.wrapping_mul( 6364136223846793005 ).wrapping_add( 1442695040888963407 );
+        ( s >> 11 ) as f64 / ( 1u64 << 53 ) as f64
+    } ).collect()
+}
+
+fn unit_cube_points() -> Vec< [f64;3] > {
+    let mut p = vec![];
+    for i in 0..8 {
+        p.push( [ ( i & 1 ) as f64, ( ( i >> 1 ) & 1 ) as f64, ( ( i >> 2 ) & 1 ) as f64 ] );
+    }
+    let r = lcg( 300, 7 );
+    for c in r.chunks( 3 ) {
+        //interior points
+        p.push( [ c[0], c[1], c[2] ] );
+        //points on the faces and edges
+        p.push( [ c[0], c[1], 1. ] );
+        p.push( [ 0., c[1], c[2] ] );
+        p.push( [ c[0], 1., 0. ] );
+    }
+    p
+}
+
+fn check_closed( h: & ConvexHull ) {
+    let mut edges = HashSet::new();
+    for f in h._faces.iter() {
+        for e in 0..3 {
+            //each directed edge appears once and its twin exists
+            assert!( edges.insert( ( f[e], f[ ( e + 1 ) % 3 ] ) ) );
+        }
+    }
+    for &( a, b ) in edges.iter() {
+        assert!( edges.contains( &( b, a ) ) );
+    }
+    //euler characteristic of a sphere
+    assert_eq!( h._verts.len() as isize - ( edges.len() / 2 ) as isize + h._faces.len() as isize, 2 );
+}
+
+fn brute_support( p: &[ [f64;3] ], d: [f64;3] ) -> f64 {
+    p.iter().fold( ::std::f64::NEG_INFINITY, |acc, x| acc.max( x[0] * d[0] + x[1] * d[1] + x[2] * d[2] ) )
+}
+
+#[test]
+fn test_convex_hull_cube(){
+    let p = unit_cube_points();
+    let h = ConvexHull::init( &p[..] ).expect( "hull" );
+    assert_eq!( h._kind, HullKind::Polyhedron );
+    assert_eq!( h._verts.len(), 8 );
+    assert_eq!( h._faces.len(), 12 );
+    assert_eq!( h._normals.len(), 12 );
+    check_closed( & h );
+    assert!( ( h.volume() - 1. ).abs() < 1e-9 );
+    for ( f, n ) in h._faces.iter().zip( h._normals.iter() ) {
+        //outward unit normals
+        let v = h._verts[ f[0] ];
+        let to_face = [ v[0] - 0.5, v[1] - 0.5, v[2] - 0.5 ];
+        assert!( n[0] * to_face[0] + n[1] * to_face[1] + n[2] * to_face[2] > 0. );
+        assert!( ( n[0] * n[0] + n[1] * n[1] + n[2] * n[2] - 1. ).abs() < 1e-9 );
+    }
+    assert!( p.iter().all( |x| h.contains_point( *x ) ) );
+    assert!( !h.contains_point( [ 0.5, 0.5, 1.1 ] ) );
+
+    //same hull from packed mesh positions
+    let pos : Vec< f32 > = p.iter().flat_map( |x| vec![ x[0] as f32, x[1] as f32, x[2] as f32 ] ).collect();
+    let h = ConvexHull::init_from_positions( &pos[..] ).expect( "hull" );
+    assert_eq!( h._verts.len(), 8 );
+}
+
+#[test]
+fn test_convex_hull_sphere_points(){
+    let r = lcg( 600, 3 );
+    let p : Vec< [f64;3] > = r.chunks( 3 ).map( |c| {
+        let z = c[0] * 2. - 1.;
+        let phi = c[1] * 2. * ::std::f64::consts::PI;
+        let s = ( 1. - z * z ).sqrt();
+        //shrink some points inside
+        let l = if c[2] < 0.3 { 0.5 } else { 1. };
+        [ s * phi.cos() * l, s * phi.sin() * l, z * l ]
+    } ).collect();
+    let h = ConvexHull::init( &p[..] ).expect( "hull" );
+    assert_eq!( h._kind, HullKind::Polyhedron );
+    check_closed( & h );
+    assert_eq!( h._verts.len(), p.iter().filter( |x| x[0] * x[0] + x[1] * x[1] + x[2] * x[2] > 0.9 ).count() );
+    assert!( h.volume() < 4. / 3. * ::std::f64::consts::PI );
+    assert!( h.volume() > 3. );
+    assert!( p.iter().all( |x| h.contains_point( *x ) ) );
+
+    //support mapping agrees with the input points
+    let dirs = lcg( 60, 11 );
+    for d in dirs.chunks( 3 ) {
+        let v = [ d[0] - 0.5, d[1] - 0.5, d[2] - 0.5 ];
+        let s = h.get_support( & Mat3x1 { _val: v } ).expect( "support" );
+        let sd = s[0] * v[0] + s[1] * v[1] + s[2] * v[2];
+        assert!( ( sd - brute_support( &p[..], v ) ).abs() < 1e-12 );
+    }
+}
+
+#[test]
+fn test_convex_hull_degenerate(){
+    assert!( ConvexHull::init( &[] ).is_err() );
+    assert!( ConvexHull::init( &[ [ 0., ::std::f64::NAN, 0. ] ] ).is_err() );
+
+    let h = ConvexHull::init( &[ [ 1., 2., 3. ]; 5 ] ).expect( "hull" );
+    assert_eq!( h._kind, HullKind::Point );
+    assert_eq!( h._verts.len(), 1 );
+    assert!( h._faces.is_empty() );
+    assert!( h.contains_point( [ 1., 2., 3. ] ) );
+
+    let line : Vec< [f64;3] > = ( 0..10 ).map( |i| [ i as f64, 2. * i as f64, 0. ] ).rev().collect();
+    let h = ConvexHull::init( &line[..] ).expect( "hull" );
+    assert_eq!( h._kind, HullKind::Segment );
+    assert_eq!( h._verts.len(), 2 );
+    assert_eq!( h._verts[0]._val, [ 0., 0., 0. ] );
+    assert_eq!( h._verts[1]._val, [ 9., 18., 0. ] );
+    assert!( h.contains_point( [ 4.5, 9., 0. ] ) );
+    assert!( !h.contains_point( [ 10., 20., 0. ] ) );
+    let s = h.get_support( & Mat3x1 { _val: [ 1., 0., 0. ] } ).expect( "support" );
+    assert_eq!( s._val, [ 9., 18., 0. ] );
+
+    //tilted square grid with repeated points
+    let mut plane = vec![];
+    for i in 0..5 {
+        for j in 0..5 {
+            plane.push( [ i as f64, j as f64, i as f64 ] );
+            plane.push( [ i as f64, j as f64, i as f64 ] );
+        }
+    }
+    let h = ConvexHull::init( &plane[..] ).expect( "hull" );
+    assert_eq!( h._kind, HullKind::Polygon );
+    assert_eq!( h._verts.len(), 4 );
+    assert_eq!( h._faces.len(), 4 );
+    assert_eq!( h.volume(), 0. );
+    assert!( plane.iter().all( |x| h.contains_point( *x ) ) );
+    assert!( !h.contains_point( [ 2., 2., 2.5 ] ) );
+    assert!( !h.contains_point( [ 2., 4.5, 2. ] ) );
+    for n in h._normals.iter() {
+        let s = 1. / 2f64.sqrt();
+        assert!( ( n[0].abs() - s ).abs() < 1e-9 && n[1].abs() < 1e-9 && ( n[2].abs() - s ).abs() < 1e-9 );
+    }
+}
+
+#[test]
+fn test_convex_hull_shape(){
+    let p = unit_cube_points();
+    let h = ConvexHull::init( &p[..] ).expect( "hull" );
+
+    assert!( h.intersect_sphere( [ 0.5, 0.5, 1.4 ], 0.5 ) );
+    assert!( !h.intersect_sphere( [ 0.5, 0.5, 1.6 ], 0.5 ) );
+    assert!( h.intersect_aabb( [ 0.9, 0.9, 0.9 ], [ 2., 2., 2. ] ) );
+    assert!( !h.intersect_aabb( [ 1.1, 0., 0. ], [ 2., 1., 1. ] ) );
+
+    //tetrahedron cutting the corner at the origin
+    let tet = |o: f64| ConvexHull::init( &[ [ o, o, o ], [ o - 1., o, o ], [ o, o - 1., o ], [ o, o, o - 1. ] ] ).expect( "hull" );
+    assert!( h.get_intersect( & tet( 0.2 ) ).0 );
+    assert!( !h.get_intersect( & tet( -0.1 ) ).0 );
+    //sliver past the edge at x = z = 1, on the inside of each cube plane taken alone
+    let sliver = ConvexHull::init( &[ [ 1.3, 0.5, 0.9 ], [ 0.9, 0.5, 1.3 ], [ 1.15, 0.6, 1.15 ], [ 1.15, 0.4, 1.15 ] ] ).expect( "hull" );
+    assert!( !h.get_intersect( & sliver ).0 );
+
+    //frustum against the hull through its support mapping
+    let f = Frustum::init( &[ 0.5, 0.5, -3. ], 1., 0.5, 1. );
+    assert!( !f.get_intersect( & h ).0 );
+    let f = Frustum::init( &[ 0.5, 0.5, -0.5 ], 1., 0.5, 1. );
+    assert!( f.get_intersect( & h ).0 );
+}
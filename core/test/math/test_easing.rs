@@ -0,0 +1,47 @@
+use implement::math::easing::{ Ease, EaseMode };
+
+fn all_eases() -> Vec< Ease > {
+    let mut v = vec![ Ease::Linear, Ease::css_ease(), Ease::css_ease_in(), Ease::css_ease_out(), Ease::css_ease_in_out() ];
+    for &m in [ EaseMode::In, EaseMode::Out, EaseMode::InOut ].iter() {
+        v.extend_from_slice( &[ Ease::Quad( m ), Ease::Cubic( m ), Ease::Expo( m ),
+                                Ease::Elastic( m ), Ease::Bounce( m ), Ease::Back( m ) ] );
+    }
+    v
+}
+
+#[test]
+fn test_easing_end_points(){
+    for e in all_eases().iter() {
+        assert!( e.apply( 0. ).abs() < 1e-9, "{:?}", e );
+        assert!( ( e.apply( 1. ) - 1. ).abs() < 1e-9, "{:?}", e );
+        //input is clamped
+        assert!( ( e.apply( 2. ) - 1. ).abs() < 1e-9, "{:?}", e );
+    }
+}
+
+#[test]
+fn test_easing_shapes(){
+    assert!( ( Ease::Quad( EaseMode::In ).apply( 0.5 ) - 0.25 ).abs() < 1e-12 );
+    assert!( ( Ease::Quad( EaseMode::Out ).apply( 0.5 ) - 0.75 ).abs() < 1e-12 );
+    assert!( ( Ease::Quad( EaseMode::InOut ).apply( 0.25 ) - 0.125 ).abs() < 1e-12 );
+    assert!( ( Ease::Cubic( EaseMode::InOut ).apply( 0.75 ) - 0.9375 ).abs() < 1e-12 );
+
+    //back dips below the start, elastic overshoots the end
+    assert!( Ease::Back( EaseMode::In ).apply( 0.2 ) < 0. );
+    assert!( ( 0..100 ).any( |i| Ease::Elastic( EaseMode::Out ).apply( i as f64 / 100. ) > 1. ) );
+    //bounce stays within range
+    assert!( ( 0..101 ).all( |i| { let v = Ease::Bounce( EaseMode::Out ).apply( i as f64 / 100. ); v >= 0. && v <= 1. + 1e-9 } ) );
+
+    //bezier with controls on the diagonal is linear
+    let e = Ease::CubicBezier( 0., 0., 1., 1. );
+    for i in 0..11 {
+        let x = i as f64 / 10.;
+        assert!( ( e.apply( x ) - x ).abs() < 1e-6 );
+    }
+    assert!( ( Ease::css_ease_in_out().apply( 0.5 ) - 0.5 ).abs() < 1e-6 );
+    let e = Ease::css_ease();
+    let v : Vec< f64 > = ( 0..101 ).map( |i| e.apply( i as f64 / 100. ) ).collect();
+    assert!( v.windows( 2 ).all( |w| w[1] >= w[0] ) );
+    //ease starts fast
+    assert!( e.apply( 0.5 ) > 0.75 );
+}
@@ -0,0 +1,53 @@
+extern crate mazth;
+
+use self::mazth::i_shape::{ ShapeType, IShape };
+use self::mazth::mat::Mat3x1;
+
+use implement::math::frustum::Frustum;
+use implement::math::convex_hull::ConvexHull;
+
+#[test]
+fn test_frustum_view(){
+    //90 degree view down -z from origin
+    let f = Frustum::init_from_view( 90., 1., 1., 10., [ 0., 0., 0. ], [ 0., 0., -1. ], [ 0., 1., 0. ] );
+    match f.get_type() {
+        ShapeType::FRUSTUM => {},
+        _ => panic!( "unexpected shape type" ),
+    }
+    assert!( f.contains_point( [ 0., 0., -5. ] ) );
+    assert!( f.contains_point( [ 4.9, 4.9, -5. ] ) );
+    assert!( !f.contains_point( [ 0., 0., 5. ] ) );
+    assert!( !f.contains_point( [ 0., 0., -0.5 ] ) );
+    assert!( !f.contains_point( [ 0., 0., -11. ] ) );
+    assert!( !f.contains_point( [ 6., 0., -5. ] ) );
+
+    assert!( f.intersect_sphere( [ 6., 0., -5. ], 1.5 ) );
+    assert!( !f.intersect_sphere( [ 6., 0., -5. ], 0.5 ) );
+
+    assert!( f.intersect_aabb( [ 4., -1., -6. ], [ 6., 1., -4. ] ) );
+    assert!( !f.intersect_aabb( [ -1., -1., 1. ], [ 1., 1., 2. ] ) );
+
+    //support is the far corner along direction
+    let s = f.get_support( & Mat3x1 { _val: [ 0., 0., -1. ] } ).expect( "support" );
+    assert!( ( s[2] + 10. ).abs() < 1e-9 );
+    let s = f.get_support( & Mat3x1 { _val: [ 1., 1., 0. ] } ).expect( "support" );
+    assert!( ( s[0] - 10. ).abs() < 1e-9 && ( s[1] - 10. ).abs() < 1e-9 );
+
+    //frustum against frustum
+    let g = Frustum::init( &[ 0., 0., -5. ], 1., 1., 2. );
+    assert!( f.intersect_frustum( & g ) );
+    assert!( f.get_intersect( & g ).0 );
+    let h = Frustum::init( &[ 0., 0., 20. ], 1., 1., 2. );
+    assert!( !f.intersect_frustum( & h ) );
+    assert!( !f.get_intersect( & h ).0 );
+}
+
+#[test]
+fn test_frustum_support_outside_edge(){
+    let f = Frustum::init_from_view( 90., 1., 1., 10., [ 0., 0., 0. ], [ 0., 0., -1. ], [ 0., 1., 0. ] );
+    //sliver past the corner of the far cap and right plane, on the inside of each plane taken alone
+    let sliver = ConvexHull::init( &[ [ 11., 0., -9.8 ], [ 9.8, 0., -11. ], [ 10.4, 0.1, -10.4 ], [ 10.4, -0.1, -10.4 ] ] ).expect( "hull" );
+    assert!( !f.get_intersect( & sliver ).0 );
+    let inside = ConvexHull::init( &[ [ 9.5, 0., -9.8 ], [ 9.8, 0., -11. ], [ 9.6, 0.1, -10.4 ], [ 9.6, -0.1, -10.4 ] ] ).expect( "hull" );
+    assert!( f.get_intersect( & inside ).0 );
+}
@@ -0,0 +1,109 @@
+extern crate mazth;
+
+use self::mazth::quat::Quat;
+
+use interface::i_interpolate::{ IInterpolate, IInterpolateEval };
+use interface::i_step::Step;
+
+use implement::math::quat_interp::{ self, QuatInterp, Pose };
+use implement::step::step_interpolate;
+
+fn axis_angle( axis: [ f32; 3 ], angle: f32 ) -> Quat< f32 > {
+    let s = ( angle * 0.5 ).sin();
+    Quat::<f32>::init_from_vals_raw( axis[0] * s, axis[1] * s, axis[2] * s, ( angle * 0.5 ).cos() )
+}
+
+///same rotation regardless of quaternion sign
+fn same_rot( a: & Quat< f32 >, b: & Quat< f32 >, eps: f32 ) -> bool {
+    let d = a._x * b._x + a._y * b._y + a._z * b._z + a._w * b._w;
+    ( d.abs() - 1. ).abs() < eps
+}
+
+fn chord( a: & Quat< f32 >, b: & Quat< f32 > ) -> f32 {
+    ( ( a._x - b._x ).powi( 2 ) + ( a._y - b._y ).powi( 2 ) + ( a._z - b._z ).powi( 2 ) + ( a._w - b._w ).powi( 2 ) ).sqrt()
+}
+
+#[test]
+fn test_quat_interp_slerp_nlerp(){
+    let pi = ::std::f32::consts::PI;
+    let q0 = axis_angle( [ 0., 0., 1. ], 0. );
+    let q1 = axis_angle( [ 0., 0., 1. ], pi / 2. );
+
+    let s = QuatInterp::init_slerp( 5, q0, q1 );
+    assert!( same_rot( & s.eval( 0.25 ), & axis_angle( [ 0., 0., 1. ], pi / 8. ), 1e-6 ) );
+    let n = QuatInterp::init_nlerp( 5, q0, q1 );
+    assert!( same_rot( & n.eval( 0.5 ), & axis_angle( [ 0., 0., 1. ], pi / 4. ), 1e-6 ) );
+    assert!( !same_rot( & n.eval( 0.25 ), & axis_angle( [ 0., 0., 1. ], pi / 8. ), 1e-6 ) );
+
+    //negated end key describes the same rotation and takes the same short arc
+    let neg = Quat::<f32>::init_from_vals_raw( -q1._x, -q1._y, -q1._z, -q1._w );
+    let s_neg = QuatInterp::init_slerp( 5, q0, neg );
+    assert!( same_rot( & s_neg.eval( 0.5 ), & axis_angle( [ 0., 0., 1. ], pi / 4. ), 1e-6 ) );
+
+    let mut s = QuatInterp::init_slerp( 5, q0, q1 );
+    let pts : Vec< _ > = s.by_ref().collect();
+    assert_eq!( pts.len(), 5 );
+    assert!( s.interp_is_end() );
+    assert!( same_rot( & pts[4], & q1, 1e-6 ) );
+    assert!( same_rot( & pts[2], & axis_angle( [ 0., 0., 1. ], pi / 4. ), 1e-6 ) );
+    let back : Vec< _ > = s.by_ref().rev().collect();
+    assert_eq!( back.len(), 5 );
+    assert!( s.interp_is_start() );
+    assert!( same_rot( & back[4], & q0, 1e-6 ) );
+}
+
+#[test]
+fn test_quat_interp_squad(){
+    let pi = ::std::f32::consts::PI;
+    let keys = vec![ axis_angle( [ 0., 0., 1. ], 0. ),
+                     axis_angle( [ 0., 0., 1. ], pi / 2. ),
+                     axis_angle( [ 1., 0., 0. ], pi / 2. ) ];
+    let mut path = quat_interp::squad_path( 10, &keys[..] ).expect( "squad path" );
+    assert_eq!( path.num_steps(), 20 );
+    let pts : Vec< _ > = path.by_ref().collect();
+    assert_eq!( pts.len(), 20 );
+    //passes through every key
+    assert!( same_rot( & pts[0], & keys[0], 1e-5 ) );
+    assert!( same_rot( & pts[9], & keys[1], 1e-5 ) );
+    assert!( same_rot( & pts[10], & keys[1], 1e-5 ) );
+    assert!( same_rot( & pts[19], & keys[2], 1e-5 ) );
+
+    //angular velocity is continuous across the key
+    let h = 1e-3;
+    let a = chord( & path._pieces[0].eval( 1. - h ), & path._pieces[0].eval( 1. ) );
+    let b = chord( & path._pieces[1].eval( 0. ), & path._pieces[1].eval( h ) );
+    assert!( ( a / b - 1. ).abs() < 0.05 );
+
+    assert!( quat_interp::squad_path( 10, &keys[..1] ).is_err() );
+}
+
+#[test]
+fn test_quat_interp_transform_track(){
+    let pi = ::std::f32::consts::PI;
+    let keys = vec![ Pose::init( [ 0., 0., 0. ], axis_angle( [ 0., 1., 0. ], 0. ), [ 1., 1., 1. ] ),
+                     Pose::init( [ 1., 2., 0. ], axis_angle( [ 0., 1., 0. ], pi / 2. ), [ 2., 2., 2. ] ),
+                     Pose::init( [ 4., 2., 1. ], axis_angle( [ 0., 1., 0. ], pi ), [ 3., 1., 1. ] ) ];
+    let mut track = quat_interp::transform_track_from_keys( 10, &keys[..] ).expect( "transform track" );
+    assert_eq!( track.num_steps(), 20 );
+
+    let mut s = Step {
+        _current_val: 0.0,
+        _range_val: ( 0.0, 1.0 ),
+    };
+    let end = step_interpolate::step_delta( & mut s, 1f64, & mut track );
+    for i in 0..3 {
+        assert!( ( end._pos._val[i] - keys[2]._pos._val[i] ).abs() < 1e-9 );
+        assert!( ( end._scale._val[i] - keys[2]._scale._val[i] ).abs() < 1e-9 );
+    }
+    assert!( same_rot( & end._orient, & keys[2]._orient, 1e-5 ) );
+
+    let start = track.interp_delta( -100 ).expect( "backward interpolation" );
+    assert!( track.interp_is_start() );
+    assert!( start._pos._val[0].abs() < 1e-9 );
+    assert!( same_rot( & start._orient, & keys[0]._orient, 1e-5 ) );
+
+    //mismatched step counts are rejected
+    let short = quat_interp::squad_path( 5, & [ keys[0]._orient, keys[1]._orient, keys[2]._orient ] ).expect( "squad path" );
+    let t = track.clone();
+    assert!( quat_interp::TransformTrack::init( t._translation, short, t._scale ).is_err() );
+}
@@ -1,6 +1,12 @@
-use implement::math::sim_anneal;
+extern crate rand;
+
 use std::f32;
 
+use self::rand::{ Rng, StdRng };
+
+use implement::math::sim_anneal;
+use implement::math::sim_anneal::{ AnnealConfig, AnnealProgress, Cooling };
+
 #[test]
 fn test_sim_anneal(){
     let input = (1..100usize).collect::< Vec< usize > >().iter().map(|&x| x as f32 / 100f32 ).collect::< Vec<f32> >();
@@ -14,3 +20,101 @@ fn test_sim_anneal(){
     // println!( "ret: {:?}", ret );
     assert!( ret <= expected_index + (input.len() as f32 * 0.02f32) as usize );
 }
+
+#[test]
+fn test_sim_anneal_discrete_final_state(){
+    //a worse candidate with t * e_diff >= 1 is always taken, and the final state is returned rather than the best
+    let input = [ 0f32, 2f32 ];
+    let max_iter = 10;
+    let ret = sim_anneal::compute_discrete( &input[..],
+                                            |_, i| if i == max_iter - 1 { 1 } else { 0 },
+                                            |_| 1f32,
+                                            |d, i| d[i],
+                                            max_iter, 0 );
+    assert_eq!( ret, 1 );
+}
+
+///bowl centred at (3,-1) with ripples creating local minima around integer offsets
+fn rippled_bowl( s: & Vec< f64 > ) -> f64 {
+    let ( dx, dy ) = ( s[0] - 3., s[1] + 1. );
+    let tau = 2. * ::std::f64::consts::PI;
+    dx * dx + dy * dy + 0.5 * ( 2. - ( tau * dx ).cos() - ( tau * dy ).cos() )
+}
+
+fn step_neighbour( s: & Vec< f64 >, t: f64, rng: & mut StdRng ) -> Vec< f64 > {
+    let w = t.sqrt() + 0.05;
+    s.iter().map( |x| x + rng.gen_range( -w, w ) ).collect()
+}
+
+#[test]
+fn test_sim_anneal_continuous(){
+    let config = AnnealConfig { _max_iter: 4000, _restarts: 0, _seed: 7 };
+    let cooling = Cooling::Exponential { _t0: 10., _alpha: 0.998 };
+    let r = sim_anneal::anneal_cooling( vec![ -8f64, 9. ], step_neighbour, rippled_bowl, cooling, & config );
+    assert!( r._best_energy < 0.05 );
+    assert!( ( r._best[0] - 3. ).abs() < 0.1 && ( r._best[1] + 1. ).abs() < 0.1 );
+    assert_eq!( r._stats._iterations, 4000 );
+    assert_eq!( r._stats._best_trace.len(), 4000 );
+    //best energy never gets worse
+    assert!( r._stats._best_trace.windows( 2 ).all( |w| w[1] <= w[0] ) );
+    let rate = r._stats.acceptance_rate();
+    assert!( rate > 0. && rate < 1. );
+    assert!( r._stats._improved > 0 && r._stats._improved <= r._stats._accepted );
+}
+
+#[test]
+fn test_sim_anneal_seed_and_restarts(){
+    let config = AnnealConfig { _max_iter: 500, _restarts: 3, _seed: 42 };
+    let cooling = Cooling::Logarithmic { _t0: 2. };
+    let a = sim_anneal::anneal_cooling( vec![ 0f64, 0. ], step_neighbour, rippled_bowl, cooling, & config );
+    let b = sim_anneal::anneal_cooling( vec![ 0f64, 0. ], step_neighbour, rippled_bowl, cooling, & config );
+    assert_eq!( a._best, b._best );
+    assert_eq!( a._stats._best_trace, b._stats._best_trace );
+
+    let c = sim_anneal::anneal_cooling( vec![ 0f64, 0. ], step_neighbour, rippled_bowl, cooling, & AnnealConfig { _seed: 43, .. config.clone() } );
+    assert!( a._stats._best_trace != c._stats._best_trace );
+
+    assert_eq!( a._stats._iterations, 2000 );
+    assert_eq!( a._stats._restart_best.len(), 4 );
+    assert!( a._stats._restart_best.windows( 2 ).all( |w| w[1] <= w[0] ) );
+    assert_eq!( a._best_energy, a._stats._restart_best[3] );
+}
+
+#[test]
+fn test_sim_anneal_cooling(){
+    let p = |iter: usize, t: f64, rate: f64| AnnealProgress { _iter: iter, _max_iter: 100, _restart: 0, _temperature: t, _accept_rate: rate };
+
+    let e = Cooling::Exponential { _t0: 4., _alpha: 0.5 };
+    assert_eq!( e.temperature( & p( 0, 0., 0. ) ), 4. );
+    assert_eq!( e.temperature( & p( 3, 0., 0. ) ), 0.5 );
+
+    let l = Cooling::Logarithmic { _t0: 4. };
+    assert!( ( l.temperature( & p( 0, 0., 0. ) ) - 4. ).abs() < 1e-12 );
+    assert!( l.temperature( & p( 50, 0., 0. ) ) < l.temperature( & p( 10, 0., 0. ) ) );
+
+    let a = Cooling::Adaptive { _t0: 4., _target_accept: 0.4, _rate: 0.1 };
+    assert_eq!( a.temperature( & p( 0, ::std::f64::INFINITY, 1. ) ), 4. );
+    //cools when accepting too often, heats when rejecting too often
+    assert!( ( a.temperature( & p( 10, 2., 0.8 ) ) - 1.8 ).abs() < 1e-12 );
+    assert!( ( a.temperature( & p( 10, 2., 0.1 ) ) - 2.2 ).abs() < 1e-12 );
+    //bounded by linear decay
+    assert!( ( a.temperature( & p( 90, 2., 0.1 ) ) - 0.4 ).abs() < 1e-9 );
+}
+
+#[test]
+fn test_sim_anneal_permutation(){
+    //orders values by annealing over swaps of a permutation
+    let values = [ 5f64, 1., 4., 2., 3., 0. ];
+    let energy = |s: & Vec< usize >| s.windows( 2 ).filter( |w| values[ w[0] ] > values[ w[1] ] ).count() as f64;
+    let neighbour = |s: & Vec< usize >, _t: f64, rng: & mut StdRng| {
+        let mut n = s.clone();
+        let i = rng.gen_range( 0, n.len() );
+        let j = rng.gen_range( 0, n.len() );
+        n.swap( i, j );
+        n
+    };
+    let config = AnnealConfig { _max_iter: 3000, _restarts: 1, _seed: 3 };
+    let r = sim_anneal::anneal( ( 0..6 ).collect(), neighbour, energy, |p: & AnnealProgress| 2. * ( 1. - p.fraction() ), & config );
+    assert_eq!( r._best_energy, 0. );
+    assert_eq!( r._best, vec![ 5, 1, 3, 4, 2, 0 ] );
+}
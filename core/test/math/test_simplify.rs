@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use implement::math::simplify::{ self, Quadric, SimplifyConfig };
+
+///n by n quad grid on the xy plane spanning [x0,x0+1]x[0,1] with texture coordinates offset by tc_offset
+fn grid( n: usize, x0: f32, tc_offset: f32 ) -> ( Vec< f32 >, Vec< f32 >, Vec< f32 > ) {
+    let mut pos = vec![];
+    let mut normal = vec![];
+    let mut tc = vec![];
+    let p = |i: usize, j: usize| [ x0 + i as f32 / n as f32, j as f32 / n as f32 ];
+    for i in 0..n {
+        for j in 0..n {
+            let quad = [ p( i, j ), p( i + 1, j ), p( i + 1, j + 1 ), p( i, j ), p( i + 1, j + 1 ), p( i, j + 1 ) ];
+            for v in quad.iter() {
+                pos.extend_from_slice( &[ v[0], v[1], 0. ] );
+                normal.extend_from_slice( &[ 0., 0., 1. ] );
+                tc.extend_from_slice( &[ v[0] - x0 + tc_offset, v[1] ] );
+            }
+        }
+    }
+    ( pos, normal, tc )
+}
+
+///latitude longitude sphere of unit radius with normals and no texture coordinates
+fn sphere( rings: usize, segments: usize ) -> ( Vec< f32 >, Vec< f32 > ) {
+    use std::f32::consts::PI;
+    let p = |r: usize, s: usize| {
+        let theta = PI * r as f32 / rings as f32;
+        let phi = 2. * PI * ( s % segments ) as f32 / segments as f32;
+        if r == 0 {
+            [ 0., 0., 1. ]
+        } else if r == rings {
+            [ 0., 0., -1. ]
+        } else {
+            [ theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos() ]
+        }
+    };
+    let mut pos = vec![];
+    for r in 0..rings {
+        for s in 0..segments {
+            let tris = [ [ p( r, s ), p( r + 1, s ), p( r + 1, s + 1 ) ],
+                         [ p( r, s ), p( r + 1, s + 1 ), p( r, s + 1 ) ] ];
+            for t in tris.iter() {
+                //skip the collapsed triangles at the poles
+                if ( r == 0 && t[0] == t[2] ) || ( r + 1 == rings && t[1] == t[2] ) || t[0] == t[1] || t[0] == t[2] || t[1] == t[2] {
+                    continue;
+                }
+                for v in t.iter() {
+                    pos.extend_from_slice( & v[..] );
+                }
+            }
+        }
+    }
+    let normal = pos.clone();
+    ( pos, normal )
+}
+
+fn area( pos: &[ f32 ] ) -> f32 {
+    pos.chunks( 9 ).fold( 0., |acc, t| {
+        let e0 = [ t[3] - t[0], t[4] - t[1], t[5] - t[2] ];
+        let e1 = [ t[6] - t[0], t[7] - t[1], t[8] - t[2] ];
+        let c = [ e0[1] * e1[2] - e0[2] * e1[1], e0[2] * e1[0] - e0[0] * e1[2], e0[0] * e1[1] - e0[1] * e1[0] ];
+        acc + ( c[0] * c[0] + c[1] * c[1] + c[2] * c[2] ).sqrt() * 0.5
+    } )
+}
+
+#[test]
+fn test_simplify_quadric(){
+    //distance to z = 2 plane
+    let q = Quadric::from_plane( [ 0., 0., 1. ], -2., 1. );
+    assert!( ( q.error( [ 5., 3., 5. ] ) - 9. ).abs() < 1e-9 );
+    assert!( q.optimal().is_none() );
+
+    //three planes meet at a point
+    let q = q.add( & Quadric::from_plane( [ 1., 0., 0. ], 1., 1. ) )
+        .add( & Quadric::from_plane( [ 0., 1., 0. ], -3., 2. ) );
+    let p = q.optimal().expect( "solvable" );
+    assert!( ( p[0] + 1. ).abs() < 1e-9 && ( p[1] - 3. ).abs() < 1e-9 && ( p[2] - 2. ).abs() < 1e-9 );
+    assert!( q.error( p ).abs() < 1e-9 );
+}
+
+#[test]
+fn test_simplify_planar_grid(){
+    let ( pos, normal, tc ) = grid( 8, 0., 0. );
+    let config = SimplifyConfig { _target_tris: 2, .. Default::default() };
+    let r = simplify::simplify( &pos[..], &normal[..], &tc[..], & config ).expect( "simplify" );
+    assert_eq!( r._tri_count, 2 );
+    assert_eq!( r._batch_pos.len(), 2 * 9 );
+    assert_eq!( r._batch_tc.len(), 2 * 6 );
+    assert!( r._error < 1e-9 );
+    //boundary kept, so the square is still fully covered
+    assert!( ( area( &r._batch_pos[..] ) - 1. ).abs() < 1e-5 );
+    for i in 0..r._batch_pos.len() / 3 {
+        assert!( r._batch_pos[ i*3 + 2 ].abs() < 1e-6 );
+        assert!( ( r._batch_normal[ i*3 + 2 ] - 1. ).abs() < 1e-5 );
+        //planar mapping is preserved
+        assert!( ( r._batch_tc[ i*2 ] - r._batch_pos[ i*3 ] ).abs() < 1e-5 );
+        assert!( ( r._batch_tc[ i*2 + 1 ] - r._batch_pos[ i*3 + 1 ] ).abs() < 1e-5 );
+    }
+}
+
+#[test]
+fn test_simplify_error_limit(){
+    let ( pos, normal, tc ) = grid( 6, 0., 0. );
+    let ( sp, sn ) = sphere( 12, 24 );
+    let input_tris = sp.len() / 9;
+
+    //flat regions collapse at no cost
+    let config = SimplifyConfig { _max_error: 1e-9, .. Default::default() };
+    let r = simplify::simplify( &pos[..], &normal[..], &tc[..], & config ).expect( "simplify" );
+    assert!( r._tri_count < 6 * 6 * 2 / 4 );
+
+    //curved surfaces stop once the limit is reached
+    let r = simplify::simplify( &sp[..], &sn[..], &[], & config ).expect( "simplify" );
+    assert_eq!( r._tri_count, input_tris );
+    assert!( r._batch_tc.is_empty() );
+
+    let loose = SimplifyConfig { _max_error: 1e-3, .. Default::default() };
+    let tight = SimplifyConfig { _max_error: 1e-5, .. Default::default() };
+    let r_loose = simplify::simplify( &sp[..], &sn[..], &[], & loose ).expect( "simplify" );
+    let r_tight = simplify::simplify( &sp[..], &sn[..], &[], & tight ).expect( "simplify" );
+    assert!( r_loose._tri_count < r_tight._tri_count );
+    assert!( r_tight._tri_count < input_tris );
+    assert!( r_loose._error <= 1e-3 );
+
+    assert!( simplify::simplify( &sp[0..10], &[], &[], & config ).is_err() );
+    assert!( simplify::simplify( &sp[..], &sn[0..9], &[], & config ).is_err() );
+}
+
+#[test]
+fn test_simplify_closed_mesh(){
+    let ( pos, normal ) = sphere( 16, 32 );
+    let config = SimplifyConfig { _target_tris: 100, .. Default::default() };
+    let r = simplify::simplify( &pos[..], &normal[..], &[], & config ).expect( "simplify" );
+    assert!( r._tri_count <= 100 );
+    assert!( r._tri_count >= 4 );
+
+    //still watertight: every edge is shared by exactly two triangles
+    let key = |i: usize| [ r._batch_pos[ i*3 ].to_bits(), r._batch_pos[ i*3 + 1 ].to_bits(), r._batch_pos[ i*3 + 2 ].to_bits() ];
+    let mut edges = HashMap::new();
+    for t in 0..r._tri_count {
+        for c in 0..3 {
+            let ( a, b ) = ( key( t*3 + c ), key( t*3 + ( c + 1 ) % 3 ) );
+            let e = if a < b { ( a, b ) } else { ( b, a ) };
+            *edges.entry( e ).or_insert( 0 ) += 1;
+        }
+    }
+    assert!( edges.values().all( |&x| x == 2 ) );
+
+    //stays close to the surface with unit normals
+    for i in 0..r._batch_pos.len() / 3 {
+        let p = &r._batch_pos[ i*3 .. i*3 + 3 ];
+        let n = &r._batch_normal[ i*3 .. i*3 + 3 ];
+        assert!( ( ( p[0] * p[0] + p[1] * p[1] + p[2] * p[2] ).sqrt() - 1. ).abs() < 0.2 );
+        assert!( ( ( n[0] * n[0] + n[1] * n[1] + n[2] * n[2] ).sqrt() - 1. ).abs() < 1e-4 );
+    }
+}
+
+#[test]
+fn test_simplify_uv_seam(){
+    //two charts meeting at x = 1 with discontinuous texture coordinates
+    let ( mut pos, mut normal, mut tc ) = grid( 6, 0., 0. );
+    let ( p1, n1, t1 ) = grid( 6, 1., 5. );
+    pos.extend( p1 );
+    normal.extend( n1 );
+    tc.extend( t1 );
+
+    let config = SimplifyConfig { _target_tris: 4, .. Default::default() };
+    let r = simplify::simplify( &pos[..], &normal[..], &tc[..], & config ).expect( "simplify" );
+    assert!( r._tri_count <= 8 );
+    assert!( ( area( &r._batch_pos[..] ) - 2. ).abs() < 1e-5 );
+
+    for t in 0..r._tri_count {
+        let xs : Vec< f32 > = ( 0..3 ).map( |c| r._batch_pos[ ( t*3 + c ) * 3 ] ).collect();
+        let right = xs.iter().any( |&x| x > 1. + 1e-5 );
+        let left = xs.iter().any( |&x| x < 1. - 1e-5 );
+        //no triangle crosses the seam
+        assert!( !( left && right ) );
+        let offset = if right { 4. } else { 0. };
+        for c in 0..3 {
+            let i = t*3 + c;
+            assert!( ( r._batch_tc[ i*2 ] - ( r._batch_pos[ i*3 ] + offset ) ).abs() < 1e-5 );
+        }
+    }
+}
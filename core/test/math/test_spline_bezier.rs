@@ -9,11 +9,13 @@ use std::cmp::Ordering;
 #[allow(unused_imports)]
 use self::mazth::mat::Mat4x1;
 #[allow(unused_imports)]
-use interface::i_interpolate::IInterpolate;
+use interface::i_interpolate::{ IInterpolate, IInterpolateEval };
 
 use implement::math::spline_bezier::SplineBezier;
 use implement::math::piecewise::Piecewise;
 
+use self::mazth::i_comparable::IComparableError;
+
 use test::math::test_interpolate;
 
 #[test]
@@ -43,19 +45,19 @@ fn test_spline_iterator(){
     test_interpolate::test_iterator( & mut spline, & cp0, & cp3 );
 }
 
-//todo
-// #[test]
-// fn test_spline_reverse_iterator(){
-//     let cp0 = Mat4x1 { _val: [ 0f64, 1f64, 2f64, 3f64 ] };
-//     let cp1 = Mat4x1 { _val: [ 10f64, 16f64, 17f64, 18f64 ] };
-    
-//     let mut interp = Spline::init( 10, cp0, cp1 );
-//     let count_interpolated = interp.num_steps();
-//     assert!( count_interpolated == 10 );
+#[test]
+fn test_spline_reverse_iterator(){
+    let cp0 = Mat4x1 { _val: [ 0f64, 1f64, 2f64, 3f64 ] };
+    let cp1 = Mat4x1 { _val: [ 5f64, 6f64, 7f64, 8f64 ] };
+    let cp2 = Mat4x1 { _val: [ 10f64, 16f64, 17f64, 18f64 ] };
+    let cp3 = Mat4x1 { _val: [ 0f64, 1f64, -2f64, -3f64 ] };
 
-//     test_interpolate::test_reverse_iterator( & mut interp, & cp0, & cp1 );
-// }
+    let mut spline = SplineBezier::init( 10, cp0, cp1, cp2, cp3 );
+    let count_interpolated = spline.num_steps();
+    assert!( count_interpolated == 10 );
 
+    test_interpolate::test_reverse_iterator( & mut spline, & cp0, & cp3 );
+}
 
 #[test]
 fn test_spline_piecewise(){
@@ -105,3 +107,41 @@ fn test_spline_piecewise_reset(){
     let total_steps = 100u64;
     test_interpolate::test_piecewise_reset( & mut splines, & cp0, & cp3, piece_count, total_steps );
 }
+
+#[test]
+fn test_spline_bezier_reverse(){
+    let cp0 = Mat4x1 { _val: [ 0f64, 1f64, 2f64, 3f64 ] };
+    let cp1 = Mat4x1 { _val: [ 5f64, 6f64, 7f64, 8f64 ] };
+    let cp2 = Mat4x1 { _val: [ 10f64, 16f64, 17f64, 18f64 ] };
+    let cp3 = Mat4x1 { _val: [ 0f64, 1f64, -2f64, -3f64 ] };
+    {
+        let mut spline = SplineBezier::init( 10, cp0, cp1, cp2, cp3 );
+        test_interpolate::test_interp_forward_back( & mut spline, & cp0, & cp3 );
+    }
+    //forward stepping resumes correctly after stepping back
+    {
+        let mut a = SplineBezier::init( 10, cp0, cp1, cp2, cp3 );
+        let b = SplineBezier::init( 10, cp0, cp1, cp2, cp3 );
+        a.interp_delta( 7 );
+        a.interp_delta( -4 );
+        let p = a.interp_delta( 2 ).unwrap();
+        let q = b.eval( 4. / 9. );
+        assert!( ( 0..4 ).all( |i| ( p._val[i] - q._val[i] ).abs() < 1e-6 ) );
+    }
+    //piecewise backward iteration mirrors forward iteration
+    let mut splines = Piecewise::init();
+    for x in 0..3 {
+        splines.add( SplineBezier::init( 10 * ( x + 1 ), cp0, cp1, cp2, cp3 ) );
+    }
+    let fwd : Vec< _ > = splines.by_ref().collect();
+    let mut bwd = vec![];
+    while let Some( p ) = splines.next_back() {
+        bwd.push( p );
+    }
+    bwd.reverse();
+    assert_eq!( fwd.len(), 60 );
+    assert_eq!( bwd.len(), 60 );
+    for ( a, b ) in fwd.iter().zip( bwd.iter() ) {
+        assert!( a.is_equal( b, 0.00001f64 ).expect("is_equal invalid") );
+    }
+}
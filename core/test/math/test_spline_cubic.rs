@@ -0,0 +1,110 @@
+extern crate mazth;
+
+use self::mazth::mat::Mat4x1;
+
+use interface::i_interpolate::{ IInterpolate, IInterpolateEval };
+
+use implement::math::spline_cubic::{ self, SplineCubic, CatmullRomParam };
+
+use test::math::test_interpolate;
+
+fn close( a: & Mat4x1< f64 >, b: & Mat4x1< f64 > ) -> bool {
+    ( 0..4 ).all( |i| ( a._val[i] - b._val[i] ).abs() < 1e-6 )
+}
+
+fn waypoints() -> Vec< Mat4x1< f64 > > {
+    vec![ Mat4x1 { _val: [ 0f64, 0f64, 0f64, 1f64 ] },
+          Mat4x1 { _val: [ 1f64, 2f64, 0f64, 1f64 ] },
+          Mat4x1 { _val: [ 1.5f64, 2f64, 0f64, 1f64 ] },
+          Mat4x1 { _val: [ 8f64, -1f64, 3f64, 1f64 ] } ]
+}
+
+#[test]
+fn test_spline_hermite(){
+    let p0 = Mat4x1 { _val: [ 0f64, 1f64, 2f64, 3f64 ] };
+    let m0 = Mat4x1 { _val: [ 5f64, 0f64, 0f64, 0f64 ] };
+    let p1 = Mat4x1 { _val: [ 10f64, 16f64, 17f64, 18f64 ] };
+    let m1 = Mat4x1 { _val: [ 0f64, -5f64, 0f64, 0f64 ] };
+    {
+        let mut s = SplineCubic::init_hermite( 10, p0, m0, p1, m1 );
+        test_interpolate::test_interp_forward_back( & mut s, & p0, & p1 );
+    }
+    {
+        let mut s = SplineCubic::init_hermite( 10, p0, m0, p1, m1 );
+        test_interpolate::test_iterator( & mut s, & p0, & p1 );
+    }
+    let s = SplineCubic::init_hermite( 10, p0, m0, p1, m1 );
+    assert!( close( & s.eval_tangent( 0. ), & m0 ) );
+    assert!( close( & s.eval_tangent( 1. ), & m1 ) );
+}
+
+#[test]
+fn test_spline_catmull_rom(){
+    let w = waypoints();
+    //uniform tangent is half the difference of the neighbours
+    let s = SplineCubic::init_catmull_rom( 10, w[0], w[1], w[2], w[3], CatmullRomParam::Uniform );
+    assert!( close( & s.eval( 0. ), & w[1] ) );
+    assert!( close( & s.eval( 1. ), & w[2] ) );
+    let t = s.eval_tangent( 0. );
+    for i in 0..4 {
+        assert!( ( t._val[i] - ( w[2]._val[i] - w[0]._val[i] ) / 2. ).abs() < 1e-9 );
+    }
+
+    for &param in [ CatmullRomParam::Uniform, CatmullRomParam::Centripetal, CatmullRomParam::Chordal ].iter() {
+        let mut path = spline_cubic::catmull_rom_path( 10, &w[..], param ).expect( "catmull-rom path" );
+        assert_eq!( path._pieces.len(), 3 );
+        assert_eq!( path.num_steps(), 30 );
+        let pts : Vec< _ > = path.by_ref().collect();
+        assert_eq!( pts.len(), 30 );
+        //passes through every waypoint
+        for k in 0..3 {
+            assert!( close( & pts[ k * 10 ], & w[k] ) );
+            assert!( close( & pts[ k * 10 + 9 ], & w[ k + 1 ] ) );
+        }
+        //tangent direction is continuous across segments
+        for k in 0..2 {
+            let a = path._pieces[k].eval_tangent( 1. );
+            let b = path._pieces[ k + 1 ].eval_tangent( 0. );
+            let cross = [ a._val[1] * b._val[2] - a._val[2] * b._val[1],
+                          a._val[2] * b._val[0] - a._val[0] * b._val[2],
+                          a._val[0] * b._val[1] - a._val[1] * b._val[0] ];
+            let dot = ( 0..3 ).fold( 0., |acc, i| acc + a._val[i] * b._val[i] );
+            assert!( cross.iter().all( |x| x.abs() < 1e-6 ) && dot > 0. );
+        }
+    }
+    assert!( spline_cubic::catmull_rom_path( 10, &w[..1], CatmullRomParam::Centripetal ).is_err() );
+}
+
+#[test]
+fn test_spline_catmull_rom_centripetal(){
+    //closely spaced middle points between far neighbours, uniform knots make the segment loop back past its start
+    let p0 = Mat4x1 { _val: [ 0f64, 0f64, 0f64, 0f64 ] };
+    let p1 = Mat4x1 { _val: [ 1f64, 1f64, 0f64, 0f64 ] };
+    let p2 = Mat4x1 { _val: [ 1.1f64, 1f64, 0f64, 0f64 ] };
+    let p3 = Mat4x1 { _val: [ 10f64, 0f64, 0f64, 0f64 ] };
+    let min_x = |s: & SplineCubic| ( 0..101 ).fold( 1e9f64, |acc, i| acc.min( s.eval( i as f64 / 100. )._val[0] ) );
+    let uniform = SplineCubic::init_catmull_rom( 10, p0, p1, p2, p3, CatmullRomParam::Uniform );
+    let centripetal = SplineCubic::init_catmull_rom( 10, p0, p1, p2, p3, CatmullRomParam::Centripetal );
+    assert!( min_x( & uniform ) < 1. - 1e-3 );
+    assert!( min_x( & centripetal ) > 1. - 1e-9 );
+}
+
+#[test]
+fn test_spline_bspline(){
+    let w = waypoints();
+    let s = SplineCubic::init_bspline( 10, w[0], w[1], w[2], w[3] );
+    let start = s.eval( 0. );
+    for i in 0..4 {
+        assert!( ( start._val[i] - ( w[0]._val[i] + 4. * w[1]._val[i] + w[2]._val[i] ) / 6. ).abs() < 1e-9 );
+    }
+    let mut path = spline_cubic::bspline_path( 10, &w[..] ).expect( "bspline path" );
+    assert_eq!( path._pieces.len(), 5 );
+    assert_eq!( path.num_steps(), 50 );
+    let pts : Vec< _ > = path.by_ref().collect();
+    assert!( close( & pts[0], & w[0] ) );
+    assert!( close( & pts[ pts.len() - 1 ], & w[3] ) );
+    //consecutive segments join
+    for k in 0..4 {
+        assert!( close( & path._pieces[k].eval( 1. ), & path._pieces[ k + 1 ].eval( 0. ) ) );
+    }
+}
@@ -0,0 +1,112 @@
+use std::f64;
+
+use implement::math::surf_interact::{ self, Ray, Primitive };
+
+fn close( a: [f64;3], b: [f64;3] ) -> bool {
+    ( 0..3 ).all( |i| ( a[i] - b[i] ).abs() < 1e-9 )
+}
+
+#[test]
+fn test_surf_interact_triangle(){
+    let v = [ [ 0., 0., 0. ], [ 2., 0., 0. ], [ 0., 2., 0. ] ];
+    let r = Ray::init( [ 0.5, 0.5, 3. ], [ 0., 0., -1. ] );
+    let h = surf_interact::ray_triangle( & r, v, None, None, 4 ).expect( "hit" );
+    assert!( ( h._t - 3. ).abs() < 1e-12 );
+    assert!( close( h._point._val, [ 0.5, 0.5, 0. ] ) );
+    assert!( close( h._normal_geom._val, [ 0., 0., 1. ] ) );
+    assert!( h._front_face );
+    assert_eq!( h._prim_id, 4 );
+    let b = h._barycentric.expect( "barycentric" );
+    assert!( close( b, [ 0.5, 0.25, 0.25 ] ) );
+    //uv defaults to barycentrics of the 2nd and 3rd vertex
+    assert!( ( h._uv[0] - 0.25 ).abs() < 1e-12 && ( h._uv[1] - 0.25 ).abs() < 1e-12 );
+
+    //texture coordinates and vertex normals are interpolated
+    let uv = [ [ 0., 0. ], [ 1., 0. ], [ 0., 1. ] ];
+    let n = [ [ 0., 0., 1. ], [ 1., 0., 0. ], [ 0., 1., 0. ] ];
+    let h = surf_interact::ray_triangle( & r, v, Some( n ), Some( uv ), 0 ).expect( "hit" );
+    assert!( close( h._dpdu._val, [ 2., 0., 0. ] ) );
+    assert!( close( h._dpdv._val, [ 0., 2., 0. ] ) );
+    let s = 1. / ( 0.5f64 * 0.5 + 0.25 * 0.25 * 2. ).sqrt();
+    assert!( close( h._normal_shading._val, [ 0.25 * s, 0.25 * s, 0.5 * s ] ) );
+
+    //back face, outside the interval and misses
+    let back = Ray::init( [ 0.5, 0.5, -1. ], [ 0., 0., 1. ] );
+    assert!( !surf_interact::ray_triangle( & back, v, None, None, 0 ).expect( "hit" )._front_face );
+    assert!( surf_interact::ray_triangle( & r.with_range( 0., 2. ), v, None, None, 0 ).is_none() );
+    assert!( surf_interact::ray_triangle( & Ray::init( [ 1.5, 1.5, 3. ], [ 0., 0., -1. ] ), v, None, None, 0 ).is_none() );
+    assert!( surf_interact::ray_triangle( & Ray::init( [ 0.5, 0.5, 3. ], [ 1., 0., 0. ] ), v, None, None, 0 ).is_none() );
+}
+
+#[test]
+fn test_surf_interact_sphere(){
+    let r = Ray::init( [ 5., 0., 0. ], [ -2., 0., 0. ] );
+    let h = surf_interact::ray_sphere( & r, [ 0., 0., 0. ], 1., 1 ).expect( "hit" );
+    //distance in units of the direction length
+    assert!( ( h._t - 2. ).abs() < 1e-12 );
+    assert!( close( h._point._val, [ 1., 0., 0. ] ) );
+    assert!( close( h._normal_geom._val, [ 1., 0., 0. ] ) );
+    assert!( h._front_face );
+    assert!( ( h._uv[0] - 0. ).abs() < 1e-12 && ( h._uv[1] - 0.5 ).abs() < 1e-12 );
+    assert!( close( h._dpdu._val, [ 0., 2. * f64::consts::PI, 0. ] ) );
+    assert!( close( h._dpdv._val, [ 0., 0., -f64::consts::PI ] ) );
+
+    //from inside the exit point is reported against the outward normal
+    let r = Ray::init( [ 0., 0., 0. ], [ 0., 1., 0. ] );
+    let h = surf_interact::ray_sphere( & r, [ 0., 0., 0. ], 2., 0 ).expect( "hit" );
+    assert!( ( h._t - 2. ).abs() < 1e-12 );
+    assert!( !h._front_face );
+    assert!( close( h.normal_facing(), [ 0., -1., 0. ] ) );
+    assert!( ( h._uv[0] - 0.25 ).abs() < 1e-12 );
+
+    assert!( surf_interact::ray_sphere( & Ray::init( [ 5., 2., 0. ], [ -1., 0., 0. ] ), [ 0., 0., 0. ], 1., 0 ).is_none() );
+    assert!( surf_interact::ray_sphere( & Ray::init( [ 5., 0., 0. ], [ 1., 0., 0. ] ), [ 0., 0., 0. ], 1., 0 ).is_none() );
+}
+
+#[test]
+fn test_surf_interact_plane_and_box(){
+    let r = Ray::init( [ 1., 2., 5. ], [ 0., 0., -1. ] );
+    let h = surf_interact::ray_plane( & r, [ 0., 0., 1. ], [ 0., 0., 2. ], 0 ).expect( "hit" );
+    assert!( ( h._t - 4. ).abs() < 1e-12 );
+    assert!( close( h._normal_geom._val, [ 0., 0., 1. ] ) );
+    assert!( h._front_face );
+    //uv measures distance within the plane
+    assert!( ( ( h._uv[0] * h._uv[0] + h._uv[1] * h._uv[1] ) - 5. ).abs() < 1e-9 );
+    assert!( surf_interact::ray_plane( & Ray::init( [ 1., 2., 5. ], [ 1., 0., 0. ] ), [ 0., 0., 1. ], [ 0., 0., 1. ], 0 ).is_none() );
+
+    let r = Ray::init( [ -3., 0.5, 0.25 ], [ 1., 0., 0. ] );
+    let h = surf_interact::ray_aabb( & r, [ 0., 0., 0. ], [ 2., 1., 1. ], 0 ).expect( "hit" );
+    assert!( ( h._t - 3. ).abs() < 1e-12 );
+    assert!( close( h._normal_geom._val, [ -1., 0., 0. ] ) );
+    assert!( ( h._uv[0] - 0.5 ).abs() < 1e-12 && ( h._uv[1] - 0.25 ).abs() < 1e-12 );
+    //inside reports the exit face
+    let r = Ray::init( [ 1., 0.5, 0.5 ], [ 0., 0., 1. ] );
+    let h = surf_interact::ray_aabb( & r, [ 0., 0., 0. ], [ 2., 1., 1. ], 0 ).expect( "hit" );
+    assert!( ( h._t - 0.5 ).abs() < 1e-12 );
+    assert!( close( h._normal_geom._val, [ 0., 0., 1. ] ) );
+    assert!( !h._front_face );
+    assert!( surf_interact::ray_aabb( & Ray::init( [ -3., 2., 0.5 ], [ 1., 0., 0. ] ), [ 0., 0., 0. ], [ 2., 1., 1. ], 0 ).is_none() );
+}
+
+#[test]
+fn test_surf_interact_closest(){
+    let prims = vec![ Primitive::Plane { _point: [ 0., 0., -10. ], _normal: [ 0., 0., 1. ] },
+                      Primitive::Sphere { _center: [ 0., 0., -5. ], _radius: 1. },
+                      Primitive::Aabb { _lower: [ -1., -1., -3. ], _upper: [ 1., 1., -2. ] },
+                      Primitive::Triangle { _v: [ [ -1., -1., -8. ], [ 1., -1., -8. ], [ 0., 1., -8. ] ], _n: None, _uv: None } ];
+    let r = Ray::init( [ 0., 0., 0. ], [ 0., 0., -1. ] );
+    let h = surf_interact::closest_hit( &prims[..], & r ).expect( "hit" );
+    assert_eq!( h._prim_id, 2 );
+    assert!( ( h._t - 2. ).abs() < 1e-12 );
+
+    //continuing from the hit skips the surface just hit
+    let h = surf_interact::closest_hit( &prims[..], & h.spawn_ray( [ 0., 0., -1. ] ) ).expect( "hit" );
+    assert_eq!( h._prim_id, 2 );
+    assert!( !h._front_face );
+    let h = surf_interact::closest_hit( &prims[..], & h.spawn_ray( [ 0., 0., -1. ] ) ).expect( "hit" );
+    assert_eq!( h._prim_id, 1 );
+
+    assert_eq!( prims[0].bounds(), None );
+    assert_eq!( prims[3].bounds(), Some( ( [ -1., -1., -8. ], [ 1., 1., -8. ] ) ) );
+    assert!( surf_interact::closest_hit( &prims[..], & Ray::init( [ 0., 0., 0. ], [ 0., 0., 1. ] ) ).is_none() );
+}
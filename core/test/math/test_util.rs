@@ -39,3 +39,22 @@ fn test_math_util(){
                                                 0.0, 0.0, -1.0, 0.0 ], _is_row_major: true }, 0.01f32 ).expect("perspective result unexpected") );
     }
 }
+
+#[test]
+fn test_math_util_inverse(){
+    let identity = Mat4{ _val: [ 1.0, 0.0, 0.0, 0.0,
+                                 0.0, 1.0, 0.0, 0.0,
+                                 0.0, 0.0, 1.0, 0.0,
+                                 0.0, 0.0, 0.0, 1.0 ], _is_row_major: true };
+    {
+        let eye : Mat3x1<f32> = Mat3x1 { _val: [5.0,5.0,5.0] };
+        let center : Mat3x1<f32> = Mat3x1 { _val: [0.0,1.0,0.0] };
+        let up : Mat3x1<f32> = Mat3x1 { _val: [0.0,1.0,0.0] };
+        let m = util::look_at( eye, center, up ).mul( &util::look_at_inverse( eye, center, up ) ).unwrap();
+        assert!( m.is_equal( &identity, 0.0001f32 ).expect("look_at inverse result unexpected") );
+    }
+    {
+        let m = util::perspective( 70.0, 1.5, 0.1, 100.0 ).mul( &util::perspective_inverse( 70.0, 1.5, 0.1, 100.0 ) ).unwrap();
+        assert!( m.is_equal( &identity, 0.0001f32 ).expect("perspective inverse result unexpected") );
+    }
+}
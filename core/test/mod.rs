@@ -6,3 +6,4 @@ pub mod render;
 pub mod step;
 // pub mod motion;
 // pub mod window;
+pub mod kernel;
@@ -1 +1,9 @@
 pub mod test_texture;
+pub mod test_culling;
+pub mod test_picking;
+pub mod test_lod;
+pub mod test_pathtracer;
+pub mod test_renderer_sw;
+pub mod test_renderer_record;
+pub mod test_render_graph;
+pub mod test_deferred;
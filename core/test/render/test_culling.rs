@@ -0,0 +1,92 @@
+extern crate mazth;
+
+use self::mazth::mat::Mat3x1;
+
+use interface::i_md5::compute::ComputeCollection;
+
+use implement::math::frustum::Frustum;
+use implement::render::camera::Cam;
+use implement::render::primitive::SphereIcosahedron;
+use implement::render::culling::{ self, ICullable, BoundingVolume };
+
+///camera at origin looking down -z
+fn cam_frustum() -> Frustum {
+    Frustum::init_from_view( 90., 1., 0.1, 100., [ 0., 0., 0. ], [ 0., 0., -1. ], [ 0., 1., 0. ] )
+}
+
+fn md5_box( lower: [f32;3], upper: [f32;3] ) -> ComputeCollection {
+    ComputeCollection {
+        _bbox_lower: lower,
+        _bbox_upper: upper,
+        _batch_vert: vec![],
+        _batch_normal: vec![],
+        _batch_tc: vec![],
+        _submeshes: vec![],
+    }
+}
+
+#[test]
+fn test_culling_spheres(){
+    let f = cam_frustum();
+    let scene = vec![ SphereIcosahedron::init( Mat3x1 { _val: [ 0., 0., -10. ] }, 1. ), //in front
+                      SphereIcosahedron::init( Mat3x1 { _val: [ 0., 0., 10. ] }, 1. ), //behind
+                      SphereIcosahedron::init( Mat3x1 { _val: [ 50., 0., -10. ] }, 1. ), //far right
+                      SphereIcosahedron::init( Mat3x1 { _val: [ 10.5, 0., -10. ] }, 1. ), //straddles right plane
+                      SphereIcosahedron::init( Mat3x1 { _val: [ 0., 0., -150. ] }, 1. ) ]; //past far plane
+    let ( visible, stats ) = culling::cull( scene, & f );
+    assert_eq!( stats._visible, 2 );
+    assert_eq!( stats._culled, 3 );
+    assert_eq!( visible[0]._pos[2], -10. );
+    assert_eq!( visible[1]._pos[0], 10.5 );
+}
+
+#[test]
+fn test_culling_bbox(){
+    let f = cam_frustum();
+    let scene = vec![ md5_box( [ -1., -1., -6. ], [ 1., 1., -4. ] ),
+                      md5_box( [ -1., -1., 4. ], [ 1., 1., 6. ] ),
+                      //large box enclosing the camera
+                      md5_box( [ -100., -100., -100. ], [ 100., 100., 100. ] ),
+                      md5_box( [ -20., 30., -10. ], [ 20., 40., -5. ] ) ];
+    let ( visible, stats ) = culling::cull( scene, & f );
+    assert_eq!( stats._visible, 2 );
+    assert_eq!( stats._culled, 2 );
+    assert_eq!( visible[0]._bbox_upper[2], -4. );
+
+    //camera derived frustum agrees, default cam looks from +z toward origin
+    let cam = Cam::init( 0, 90., 1., 0.1, 100.,
+                         Mat3x1 { _val: [ 0., 0., 10. ] },
+                         Mat3x1 { _val: [ 0., 0., 0. ] },
+                         Mat3x1 { _val: [ 0., 1., 0. ] } );
+    let ( _, stats ) = culling::cull_cam( vec![ md5_box( [ -1., -1., -1. ], [ 1., 1., 1. ] ),
+                                                md5_box( [ -1., -1., 20. ], [ 1., 1., 22. ] ) ], & cam );
+    assert_eq!( stats._visible, 1 );
+    assert_eq!( stats._culled, 1 );
+}
+
+#[test]
+fn test_culling_cam_frustum(){
+    let cam = Cam::init( 0, 90., 1., 1., 10.,
+                         Mat3x1 { _val: [ 0f32, 0., 0. ] },
+                         Mat3x1 { _val: [ 1f32, 0., 0. ] },
+                         Mat3x1 { _val: [ 0f32, 0., 1. ] } );
+    let f = cam.frustum();
+    assert!( f.contains_point( [ 5., 0., 0. ] ) );
+    assert!( !f.contains_point( [ -5., 0., 0. ] ) );
+    assert!( !f.contains_point( [ 5., 0., 6. ] ) );
+}
+
+#[test]
+fn test_culling_volumes(){
+    let f = cam_frustum();
+    assert!( BoundingVolume::Unbounded.is_visible( & f ) );
+    let s = SphereIcosahedron::init( Mat3x1 { _val: [ 1., 2., 3. ] }, 2. );
+    assert_eq!( s.bounding_volume().aabb(), Some( ( [ -1., 0., 1. ], [ 3., 4., 5. ] ) ) );
+    let u = s.bounding_volume().union( & BoundingVolume::Aabb { _lower: [ 0., 0., 0. ], _upper: [ 10., 1., 1. ] } );
+    assert_eq!( u, BoundingVolume::Aabb { _lower: [ -1., 0., 0. ], _upper: [ 10., 4., 5. ] } );
+    assert_eq!( culling::bound_positions( &[ 1., 2., 3., -1., 5., 0. ] ),
+                BoundingVolume::Aabb { _lower: [ -1., 2., 0. ], _upper: [ 1., 5., 3. ] } );
+    let ( v, stats ) = culling::cull_by( vec![ 0, 1, 2 ], & f, |_| BoundingVolume::Unbounded );
+    assert_eq!( v.len(), 3 );
+    assert_eq!( stats._culled, 0 );
+}
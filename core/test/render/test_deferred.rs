@@ -0,0 +1,140 @@
+use interface::i_ele;
+use interface::i_renderer::IRenderer;
+
+use implement::render::culling::{ ICullable, BoundingVolume };
+use implement::render::deferred;
+use implement::render::deferred::PointLight;
+use implement::render::renderer_gl::Event;
+use implement::render::renderer_record::{ RendererRecord, RecordCmd };
+use implement::render::renderer_sw::RendererSw;
+use implement::render::render_graph::{ RenderGraph, RenderPassKind, TARGET_DEFAULT, TARGET_GBUFFER };
+use implement::render::util_gl::ShaderType;
+
+use test::render::test_renderer_sw::{ self, tri };
+
+fn frame( lights: Option< Vec< PointLight > > ) -> Vec< Event > {
+    let mut e = test_renderer_sw::frame( vec![ tri( 0 ) ] );
+    e.push( Event::AddObjDrawGroup( 1, i_ele::Ele::init( tri( 0 ) ) ) );
+    if let Some( x ) = lights {
+        e.push( Event::SetPointLights( x ) );
+    }
+    e
+}
+
+fn shader( name: & str ) -> Vec< ( String, ShaderType ) > {
+    vec![ ( format!( "{}.vs", name ), ShaderType::VERTEX ), ( format!( "{}.fs", name ), ShaderType::FRAGMENT ) ]
+}
+
+#[test]
+fn test_deferred_lights(){
+    let l = PointLight::init( [ 0., 0., 0. ], [ 1., 0.5, 0. ] );
+    let r = l.radius();
+    let intensity = 1. / ( 1. + l._linear * r + l._quadratic * r * r );
+    assert!( ( intensity - deferred::LIGHT_CUTOFF ).abs() < 1e-5 );
+    assert_eq!( PointLight::init( [ 0., 0., 0. ], [ 0.01, 0.01, 0.01 ] ).radius(), 0. );
+    match l.bounding_volume() {
+        BoundingVolume::Sphere { _center, _radius } => { assert_eq!( _radius, r ); },
+        _ => { panic!( "light volume not a sphere" ); },
+    }
+
+    let lights : Vec< PointLight > = ( 0..40 ).map( |i| PointLight::init( [ i as f32, 0., 0. ], [ 1., 1., 1. ] ) ).collect();
+    let sizes : Vec< usize > = deferred::batches( & lights[..] ).iter().map( |x| x.len() ).collect();
+    assert_eq!( sizes, vec![ 32, 8 ] );
+    assert_eq!( deferred::batches( &[] ).len(), 1 );
+
+    //camera at z = 5 looking down -z
+    let view = [ 1., 0., 0., 0.,
+                 0., 1., 0., 0.,
+                 0., 0., 1., -5.,
+                 0., 0., 0., 1. ];
+    let front = PointLight::init( [ 1., 2., 0. ], [ 1., 1., 1. ] );
+    let behind = PointLight::init( [ 0., 0., 20. ], [ 1., 1., 1. ] );
+    let eye = deferred::to_eye_space( &[ front, behind ], &view[..] );
+    assert_eq!( eye.len(), 1 );
+    assert_eq!( eye[0]._pos, [ 1., 2., -5. ] );
+}
+
+#[test]
+fn test_deferred_graph(){
+    let g = RenderGraph::deferred( 1, 2, 0, 2 );
+    let p = g.ordered_passes().expect( "ordered" );
+    let names : Vec< & str > = p.iter().map( |x| x._name.as_str() ).collect();
+    assert_eq!( names, vec![ "geometry", "lighting", "transparent" ] );
+    assert_eq!( p[0].target(), TARGET_GBUFFER );
+    assert_eq!( p[1]._kind, RenderPassKind::Lighting { _light_group: 2 } );
+    assert_eq!( p[1].target(), TARGET_DEFAULT );
+    assert!( !p[1]._clear_draw_group );
+    assert!( p[2]._blend );
+    let programs : Vec< Option< u64 > > = p.iter().map( |x| x._shader_program ).collect();
+    assert_eq!( programs, vec![ Some( 1 ), Some( 2 ), Some( 0 ) ] );
+
+    //ordered by the targets the passes read and write rather than by declaration
+    let mut r = RenderGraph::init( & g._draw_groups[..] );
+    for x in g._passes.iter().rev() {
+        r.add_pass( x.clone() ).expect( "add" );
+    }
+    r.build().expect( "build" );
+    let names : Vec< & str > = r.ordered_passes().expect( "ordered" ).iter().map( |x| x._name.as_str() ).collect();
+    assert_eq!( names, vec![ "geometry", "lighting", "transparent" ] );
+}
+
+#[test]
+fn test_deferred_renderer(){
+    let lights : Vec< PointLight > = ( 0..40 ).map( |i| PointLight::init( [ i as f32 * 0.1, 0., 0. ], [ 1., 1., 1. ] ) ).collect();
+    let mut e = vec![ Event::LoadShader( shader( "ads" ) ) ];
+    e.extend( deferred::events_pipeline( shader( "geometry" ), shader( "lighting" ), 1, 0, 2 ) );
+    e.extend( frame( Some( lights ) ) );
+
+    let mut r = RendererRecord::init().expect( "renderer" );
+    r.process_render_events( e ).expect( "render" );
+    r.process_render_events( frame( None ) ).expect( "render" );
+
+    let f = r.frame( 0 ).unwrap();
+    assert!( f.to_string().contains( "  set_render_graph geometry,lighting,transparent\n" ) );
+    assert!( f.to_string().contains( "  add_obj_draw_group 1 components=1\n" ) );
+    //opaque objects go to the gbuffer, transparent ones to their own draw group
+    let buffers : Vec< usize > = f._cmds.iter().filter_map( |x| match x {
+        &RecordCmd::RenderBuffer { _group, .. } => Some( _group ),
+        _ => None,
+    } ).collect();
+    assert_eq!( buffers, vec![ 0, 1 ] );
+    //one screen quad per light batch
+    let d : Vec< ( usize, usize ) > = f.dispatches().iter().map( |x| ( x._group, x._vert_count ) ).collect();
+    assert_eq!( d, vec![ ( 0, 3 ), ( 2, 6 ), ( 2, 6 ), ( 1, 3 ) ] );
+    let lighting : Vec< _ > = f.dispatches().into_iter().filter( |x| x._group == 2 ).collect();
+    assert_eq!( lighting[0].uniform( "LightCount" ), Some( &[ 32. ][..] ) );
+    assert_eq!( lighting[1].uniform( "LightCount" ), Some( &[ 8. ][..] ) );
+    assert_eq!( lighting[0].uniform( "Ambient" ), Some( & deferred::AMBIENT[..] ) );
+    assert_eq!( lighting[1].uniform( "Ambient" ), Some( &[ 0., 0., 0. ][..] ) );
+    //light positions are moved to eye space
+    let p = lighting[0].uniform( "lights[1].Position" ).expect( "light position" );
+    assert!( ( p[0] - 0.1 ).abs() < 1e-4 && p[1].abs() < 1e-4 && ( p[2] + 5. ).abs() < 1e-4 );
+    assert!( lighting[1].uniform( "lights[8].Position" ).is_none() );
+    assert_eq!( f.dispatch( 1 ).unwrap()._uniforms.len(), 12 );
+
+    //lights and graph persist, transparent objects are cleared every frame
+    let d : Vec< ( usize, usize ) > = r.frame( 1 ).unwrap().dispatches().iter().map( |x| ( x._group, x._vert_count ) ).collect();
+    assert_eq!( d, vec![ ( 0, 3 ), ( 2, 6 ), ( 2, 6 ), ( 1, 3 ) ] );
+
+    //programs of the passes must be loaded first
+    let mut r = RendererRecord::init().expect( "renderer" );
+    assert_eq!( r.process_render_events( vec![ Event::SetRenderGraph( RenderGraph::deferred( 1, 2, 0, 2 ) ) ] ), Err( "render pass shader program not loaded" ) );
+
+    let mut sw = RendererSw::init_with_size( 16, 16 ).expect( "renderer" );
+    assert_eq!( sw.process_render_events( vec![ Event::SetRenderGraph( RenderGraph::deferred( 1, 2, 0, 2 ) ) ] ), Err( "render target not supported" ) );
+
+    //light batches may not overwrite uniform groups read by other passes or the camera
+    for &g in [ 0, 1 ].iter() {
+        let mut e = vec![ Event::LoadShader( shader( "ads" ) ) ];
+        e.extend( deferred::events_pipeline( shader( "geometry" ), shader( "lighting" ), 1, 0, g ) );
+        let mut r = RendererRecord::init().expect( "renderer" );
+        assert_eq!( r.process_render_events( e ), Err( "render pass light uniform group already in use" ) );
+    }
+
+    //lights are not shaded without a camera to move them to eye space
+    let mut e = vec![ Event::LoadShader( shader( "ads" ) ) ];
+    e.extend( deferred::events_pipeline( shader( "geometry" ), shader( "lighting" ), 1, 0, 2 ) );
+    e.push( Event::AddObj( i_ele::Ele::init( tri( 0 ) ) ) );
+    let mut r = RendererRecord::init().expect( "renderer" );
+    assert_eq!( r.process_render_events( e ), Err( "camera view matrix not found" ) );
+}
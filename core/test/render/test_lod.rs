@@ -0,0 +1,102 @@
+extern crate mazth;
+
+use self::mazth::mat::Mat3x1;
+
+use implement::render::camera::Cam;
+use implement::render::culling::{ ICullable, BoundingVolume };
+use implement::render::lod::{ LodChain, LodConfig, LodPolicy };
+
+///unit sphere tessellated by latitude and longitude with a planar texture mapping
+fn sphere( rings: usize, segments: usize ) -> ( Vec< f32 >, Vec< f32 >, Vec< f32 > ) {
+    use std::f32::consts::PI;
+    let p = |r: usize, s: usize| {
+        let theta = PI * r as f32 / rings as f32;
+        let phi = 2. * PI * ( s % segments ) as f32 / segments as f32;
+        [ theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos() ]
+    };
+    let mut pos = vec![];
+    let mut tc = vec![];
+    for r in 1..rings - 1 {
+        for s in 0..segments {
+            for v in [ p( r, s ), p( r + 1, s ), p( r + 1, s + 1 ), p( r, s ), p( r + 1, s + 1 ), p( r, s + 1 ) ].iter() {
+                pos.extend_from_slice( & v[..] );
+                tc.extend_from_slice( &[ v[0], v[1] ] );
+            }
+        }
+    }
+    let normal = pos.clone();
+    ( pos, normal, tc )
+}
+
+fn cam_at( z: f32 ) -> Cam {
+    Cam::init( 0, 90., 1., 0.1, 1000., Mat3x1 { _val: [ 0., 0., z ] }, Mat3x1 { _val: [ 0., 0., 0. ] }, Mat3x1 { _val: [ 0., 1., 0. ] } )
+}
+
+#[test]
+fn test_lod_build(){
+    let ( pos, normal, tc ) = sphere( 16, 32 );
+    let full = pos.len() / 9;
+    let chain = LodChain::build( &pos[..], &normal[..], &tc[..], & LodConfig::default() ).expect( "lod chain" );
+    assert_eq!( chain.len(), 4 );
+    assert_eq!( chain._levels[0]._tri_count, full );
+    assert_eq!( chain._levels[0]._error, 0. );
+    for i in 1..chain.len() {
+        let ( prev, cur ) = ( & chain._levels[ i - 1 ], & chain._levels[i] );
+        assert!( cur._tri_count < prev._tri_count );
+        assert!( cur._tri_count <= full >> i );
+        assert!( cur._error >= prev._error );
+        assert_eq!( cur._batch_pos.len(), cur._tri_count * 9 );
+        assert_eq!( cur._batch_normal.len(), cur._batch_pos.len() );
+        assert_eq!( cur._batch_tc.len(), cur._tri_count * 6 );
+    }
+
+    let m = chain.mesh( 2, 7 );
+    assert_eq!( m._id, 7 );
+    assert_eq!( m._batch_pos, chain._levels[2]._batch_pos );
+    //out of range levels clamp to the coarsest
+    assert_eq!( chain.mesh( 10, 0 )._batch_pos.len(), chain._levels[3]._batch_pos.len() );
+
+    match chain.bounding_volume() {
+        BoundingVolume::Sphere { _center, _radius } => {
+            assert!( _center.iter().all( |x| x.abs() < 1e-5 ) );
+            assert!( _radius >= 1. && _radius < 3f32.sqrt() );
+        },
+        _ => panic!( "unexpected bounding volume" ),
+    }
+
+    //reduction limited by the minimum triangle count
+    let c = LodConfig { _levels: 10, _min_tris: full / 3, .. Default::default() };
+    let chain = LodChain::build( &pos[..], &normal[..], &tc[..], & c ).expect( "lod chain" );
+    assert_eq!( chain.len(), 3 );
+    //a collapse removes up to two triangles at a time
+    assert!( chain._levels[2]._tri_count + 1 >= full / 3 );
+
+    assert!( LodChain::build( &pos[..], &normal[..], &tc[..], & LodConfig { _levels: 0, .. Default::default() } ).is_err() );
+    assert!( LodChain::build( &pos[..], &normal[..], &tc[..], & LodConfig { _reduction: 1., .. Default::default() } ).is_err() );
+    assert!( LodChain::build( &[], &[], &[], & LodConfig::default() ).is_err() );
+}
+
+#[test]
+fn test_lod_select(){
+    let ( pos, normal, tc ) = sphere( 12, 24 );
+    let chain = LodChain::build( &pos[..], &normal[..], &tc[..], & LodConfig::default() ).expect( "lod chain" );
+    assert_eq!( chain.len(), 4 );
+
+    let by_distance = LodPolicy::Distance( vec![ 5., 10., 20. ] );
+    assert_eq!( chain.select( & cam_at( 3. ), & by_distance ), 0 );
+    assert_eq!( chain.select( & cam_at( 7. ), & by_distance ), 1 );
+    assert_eq!( chain.select( & cam_at( 15. ), & by_distance ), 2 );
+    assert_eq!( chain.select( & cam_at( 500. ), & by_distance ), 3 );
+
+    //radius of the bounding sphere over the viewport half height at a 90 degree field of view
+    let r = chain._radius;
+    assert!( ( chain.screen_size( & cam_at( 10. ) ) - r / 10. ).abs() < 1e-4 );
+    assert!( chain.screen_size( & cam_at( 0.5 ) ).is_infinite() );
+
+    let by_size = LodPolicy::ScreenSize( vec![ 0.5, 0.1 ] );
+    assert_eq!( chain.select( & cam_at( 0.5 ), & by_size ), 0 );
+    assert_eq!( chain.select( & cam_at( r / 0.3 ), & by_size ), 1 );
+    assert_eq!( chain.select( & cam_at( r / 0.05 ), & by_size ), 2 );
+    //fewer thresholds than levels never reach the coarsest level
+    assert_eq!( chain.select( & cam_at( 1000. ), & by_size ), 2 );
+}
@@ -0,0 +1,207 @@
+extern crate mazth;
+
+use std::sync::Arc;
+
+use self::mazth::mat::Mat3x1;
+
+use interface::i_wavefront;
+use interface::i_wavefront::mtl;
+
+use implement::file::ppm;
+use implement::render::camera::Cam;
+use implement::render::light::{ LightAdsPoint, LightAdsDir };
+use implement::render::mesh::Mesh;
+use implement::render::pathtracer::{ Scene, Material, PtLight, PathTracer, PathTracerConfig };
+
+///square in the z = h plane facing +z
+fn quad( half: f32, h: f32 ) -> Vec< f32 > {
+    vec![ -half, -half, h,   half, -half, h,   half, half, h,
+          -half, -half, h,   half, half, h,   -half, half, h ]
+}
+
+fn cam( pos: [f32;3], fov: f32 ) -> Cam {
+    Cam::init( 0, fov, 1., 0.1, 100., Mat3x1 { _val: pos }, Mat3x1 { _val: [ 0., 0., 0. ] }, Mat3x1 { _val: [ 0., 1., 0. ] } )
+}
+
+fn config( w: usize, h: usize, depth: u32, threads: usize ) -> PathTracerConfig {
+    PathTracerConfig { _width: w, _height: h, _max_depth: depth, _seed: 17, _threads: threads }
+}
+
+///floor, a floating occluder and a sky
+fn room() -> Arc< Scene > {
+    let mut s = Scene::init();
+    let floor = s.add_material( Material { _kd: [ 0.6, 0.5, 0.4 ], .. Default::default() } );
+    let shiny = s.add_material( Material { _kd: [ 0.2, 0.2, 0.2 ], _ks: [ 0.5, 0.5, 0.5 ], _ns: 20., .. Default::default() } );
+    s.add_triangles( & quad( 10., 0. ), &[], floor ).expect( "floor" );
+    let mut m = Mesh::init( 1 );
+    m._batch_pos = quad( 0.5, 1. );
+    m._batch_normal = ( 0..6 ).flat_map( |_| vec![ 0., 0., 1. ] ).collect();
+    s.add_mesh( & m, shiny ).expect( "occluder" );
+    s.add_light( PtLight::Point { _pos: [ 1., 1., 3. ], _intensity: [ 5., 5., 5. ] } );
+    s._ambient = [ 0.2, 0.3, 0.4 ];
+    s.build();
+    Arc::new( s )
+}
+
+#[test]
+fn test_pathtracer_emission(){
+    let mut s = Scene::init();
+    let m = s.add_material( Material { _kd: [ 0., 0., 0. ], _ke: [ 0.5, 0.5, 0.5 ], .. Default::default() } );
+    s.add_triangles( & quad( 100., 0. ), &[], m ).expect( "triangles" );
+    s.build();
+    let s = Arc::new( s );
+    let mut pt = PathTracer::init( config( 8, 6, 3, 2 ) ).expect( "path tracer" );
+    pt.render_pass( & s, & cam( [ 0., 0., 5. ], 60. ), 2 ).expect( "render" );
+    assert_eq!( pt.samples(), 2 );
+    assert!( pt.image().iter().all( |x| ( x - 0.5 ).abs() < 1e-12 ) );
+    //gamma encoded mid grey
+    assert!( pt.to_rgb8().iter().all( |x| *x == 186 ) );
+
+    //back faces do not emit
+    let mut pt = PathTracer::init( config( 4, 4, 3, 1 ) ).expect( "path tracer" );
+    pt.render_pass( & s, & cam( [ 0., 0., -5. ], 60. ), 1 ).expect( "render" );
+    assert!( pt.image().iter().all( |x| *x == 0. ) );
+}
+
+#[test]
+fn test_pathtracer_direct(){
+    //diffuse floor lit from above, radiance is kd / pi * intensity * cos / d^2
+    let light = LightAdsPoint { _id: 0,
+                                _pos: Mat3x1 { _val: [ 0., 0., 2. ] },
+                                _ads_val_spec: Mat3x1 { _val: [ 0., 0., 0. ] },
+                                _ads_val_diff: Mat3x1 { _val: [ 4., 4., 4. ] },
+                                _ads_val_amb: Mat3x1 { _val: [ 0., 0., 0. ] } };
+    let mut s = Scene::init();
+    let m = s.add_material( Material { _kd: [ 0.5, 0.5, 0.5 ], .. Default::default() } );
+    s.add_triangles( & quad( 10., 0. ), &[], m ).expect( "triangles" );
+    s.add_light( PtLight::from_point( & light ) );
+    s.build();
+    let mut pt = PathTracer::init( config( 9, 9, 1, 3 ) ).expect( "path tracer" );
+    pt.render_pass( & Arc::new( s ), & cam( [ 0., 0., 5. ], 2. ), 4 ).expect( "render" );
+    let expect = 0.5 / ::std::f64::consts::PI;
+    let center = pt.image()[ ( 4 * 9 + 4 ) * 3 ];
+    assert!( ( center - expect ).abs() < 1e-4 );
+    //falls off away from the light
+    assert!( pt.image()[0] < center );
+
+    //directional light at 60 degrees from the normal
+    let dir = LightAdsDir { _id: 0,
+                            _pos: Mat3x1 { _val: [ 0., 0., 0. ] },
+                            _ads_val_spec: Mat3x1 { _val: [ 0., 0., 0. ] },
+                            _ads_val_diff: Mat3x1 { _val: [ 2., 2., 2. ] },
+                            _ads_val_amb: Mat3x1 { _val: [ 0., 0., 0. ] },
+                            _dir: Mat3x1 { _val: [ 3f32.sqrt(), 0., -1. ] },
+                            _light_view_xform: Default::default() };
+    let mut s = Scene::init();
+    let m = s.add_material( Material { _kd: [ 0.5, 0.5, 0.5 ], .. Default::default() } );
+    s.add_triangles( & quad( 10., 0. ), &[], m ).expect( "triangles" );
+    s.add_light( PtLight::from_dir( & dir ) );
+    s.build();
+    let mut pt = PathTracer::init( config( 3, 3, 1, 1 ) ).expect( "path tracer" );
+    pt.render_pass( & Arc::new( s ), & cam( [ 0., 0., 5. ], 10. ), 1 ).expect( "render" );
+    assert!( pt.image().iter().all( |x| ( x - expect ).abs() < 1e-6 ) );
+}
+
+#[test]
+fn test_pathtracer_deterministic(){
+    let s = room();
+    let c = cam( [ 3., -4., 4. ], 50. );
+
+    let mut a = PathTracer::init( config( 16, 12, 4, 1 ) ).expect( "path tracer" );
+    a.render_pass( & s, & c, 4 ).expect( "render" );
+
+    //same image regardless of thread count and pass schedule
+    let mut b = PathTracer::init( config( 16, 12, 4, 5 ) ).expect( "path tracer" );
+    b.render_pass( & s, & c, 1 ).expect( "render" );
+    b.render_pass( & s, & c, 3 ).expect( "render" );
+    assert_eq!( b.samples(), 4 );
+    assert_eq!( a._accum, b._accum );
+    assert_eq!( a.to_rgb8(), b.to_rgb8() );
+    assert!( a.image().iter().all( |x| x.is_finite() && *x >= 0. ) );
+
+    //a different seed gives a different image
+    let mut d = PathTracer::init( PathTracerConfig { _seed: 18, .. config( 16, 12, 4, 2 ) } ).expect( "path tracer" );
+    d.render_pass( & s, & c, 4 ).expect( "render" );
+    assert!( a._accum != d._accum );
+
+    b.reset();
+    assert_eq!( b.samples(), 0 );
+    assert!( b.image().iter().all( |x| *x == 0. ) );
+}
+
+#[test]
+fn test_pathtracer_ppm(){
+    let mut pt = PathTracer::init( config( 12, 8, 3, 2 ) ).expect( "path tracer" );
+    pt.render_pass( & room(), & cam( [ 3., -4., 4. ], 50. ), 2 ).expect( "render" );
+    let path = ::std::env::temp_dir().join( "e2r_test_pathtracer.ppm" );
+    let path = path.to_str().expect( "path" );
+    pt.write_ppm( path ).expect( "write" );
+    let ( w, h, img ) = ppm::read( path ).expect( "read" );
+    assert_eq!( ( w, h ), ( 12, 8 ) );
+    assert_eq!( img, pt.to_rgb8() );
+}
+
+#[test]
+fn test_pathtracer_invalid(){
+    assert!( PathTracer::init( config( 0, 8, 3, 1 ) ).is_err() );
+    let mut s = Scene::init();
+    assert!( s.add_triangles( & quad( 1., 0. ), &[], 0 ).is_err() );
+    let m = s.add_material( Default::default() );
+    assert!( s.add_triangles( &[ 0.; 8 ], &[], m ).is_err() );
+    assert!( s.add_triangles( & quad( 1., 0. ), &[ 0.; 9 ], m ).is_err() );
+    s.add_triangles( & quad( 1., 0. ), &[], m ).expect( "triangles" );
+    //not built
+    let mut pt = PathTracer::init( config( 4, 4, 3, 1 ) ).expect( "path tracer" );
+    assert!( pt.render_pass( & Arc::new( s ), & cam( [ 0., 0., 5. ], 60. ), 1 ).is_err() );
+}
+
+#[test]
+fn test_pathtracer_mtl(){
+    let mut plastic = mtl::Material::init( "plastic" );
+    plastic._kd = [ 0.5, 0.5, 0.5 ];
+    plastic._ks = [ 0.1, 0.1, 0.1 ];
+    plastic._ns = 10.;
+    let m = Material::from_mtl( & plastic );
+    assert_eq!( m._kd, [ 0.5, 0.5, 0.5 ] );
+    assert_eq!( m._ns, 10. );
+
+    //full roughness gives a cosine lobe, metals have no diffuse term and a tinted specular
+    let mut metal = mtl::Material::init( "metal" );
+    metal._kd = [ 1., 0.5, 0. ];
+    metal._ke = [ 2., 2., 2. ];
+    metal._pr = Some( 1. );
+    metal._pm = Some( 1. );
+    let m = Material::from_mtl( & metal );
+    assert!( m._ns.abs() < 1e-12 );
+    assert_eq!( m._kd, [ 0., 0., 0. ] );
+    assert_eq!( m._ks, [ 1., 0.5, 0. ] );
+    assert_eq!( m._ke, [ 2., 2., 2. ] );
+    metal._pr = Some( 0.1 );
+    assert!( Material::from_mtl( & metal )._ns > 1000. );
+
+    //usemtl groups pick their material by name, unknown and missing names use the default
+    let mut s = Scene::init();
+    let default_material = s.add_material( Default::default() );
+    let names = s.add_mtl( &[ plastic, metal ] );
+    assert_eq!( s._materials.len(), 3 );
+    let tri = |g: usize| ( 0..9 ).map( |i| ( g * 9 + i ) as f32 ).collect::< Vec< f32 > >();
+    let mut pos = vec![];
+    for g in 0..4 {
+        pos.extend( tri( g ) );
+    }
+    let submesh = |m: Option< & str >, g: usize| i_wavefront::compute::SubMesh { _material: m.map( String::from ), _vert_offset: g * 3, _vert_count: 3 };
+    let c = i_wavefront::compute::ComputeCollection {
+        _bbox_lower: [ 0., 0., 0. ],
+        _bbox_upper: [ 35., 35., 35. ],
+        _batch_normal: vec![ 0.; pos.len() ],
+        _batch_tc: vec![ 0.; pos.len() / 3 * 2 ],
+        _batch_vert: pos,
+        _submeshes: vec![ submesh( Some( "metal" ), 0 ), submesh( Some( "plastic" ), 1 ), submesh( Some( "unknown" ), 2 ), submesh( None, 3 ) ],
+    };
+    s.add_wavefront( & c, & names, default_material ).expect( "wavefront" );
+    assert_eq!( s._tri_material, vec![ names[ "metal" ], names[ "plastic" ], default_material, default_material ] );
+
+    let mut bad = c.clone();
+    bad._submeshes[3]._vert_count = 6;
+    assert!( s.add_wavefront( & bad, & names, default_material ).is_err() );
+}
@@ -0,0 +1,83 @@
+extern crate mazth;
+
+use self::mazth::mat::Mat3x1;
+
+use implement::render::camera::Cam;
+use implement::render::culling::BoundingVolume;
+use implement::render::picking::{ self, Picker, PickShape, PickRay };
+
+fn close( a: [f32;3], b: [f32;3] ) -> bool {
+    ( 0..3 ).all( |i| ( a[i] - b[i] ).abs() < 1e-4 )
+}
+
+///camera at z = 10 looking at the origin with a 90 degree view over a 500 pixel window
+fn cam() -> Cam {
+    Cam::init( 0, 90., 1., 1., 100., Mat3x1 { _val: [ 0., 0., 10. ] }, Mat3x1 { _val: [ 0., 0., 0. ] }, Mat3x1 { _val: [ 0., 1., 0. ] } )
+}
+
+#[test]
+fn test_picking_screen_ray(){
+    let c = cam();
+    let r = picking::screen_ray( & c, 250., 250., 500., 500. );
+    assert!( close( r._origin, [ 0., 0., 9. ] ) );
+    assert!( close( r._dir, [ 0., 0., -1. ] ) );
+    //top right corner lies on the near plane at unit half extent
+    let r = picking::screen_ray( & c, 500., 0., 500., 500. );
+    assert!( close( r._origin, [ 1., 1., 9. ] ) );
+    let s = 1. / 3f32.sqrt();
+    assert!( close( r._dir, [ s, s, -s ] ) );
+}
+
+#[test]
+fn test_picking_nearest(){
+    let mut p = Picker::default();
+    p.add( 1, PickShape::Sphere { _center: [ 0., 0., 0. ], _radius: 1. } );
+    p.add( 2, PickShape::Aabb { _lower: [ -1., -1., -6. ], _upper: [ 1., 1., -4. ] } );
+    p.add_mesh( 3, &[ -1., -1., -10., 1., -1., -10., 0., 1., -10. ] ).expect( "add mesh" );
+    assert!( p.add_mesh( 4, &[ 0., 0. ] ).is_err() );
+    assert!( PickShape::from_bounding_volume( & BoundingVolume::Unbounded ).is_none() );
+    assert_eq!( p.len(), 3 );
+
+    let h = p.pick_screen( & cam(), 250., 250., 500., 500. ).expect( "hit" );
+    assert_eq!( h._id, 1 );
+    assert!( ( h._t - 8. ).abs() < 1e-4 );
+    assert!( close( h._normal, [ 0., 0., 1. ] ) );
+    assert!( h._tri.is_none() );
+
+    let all = p.pick_all( & PickRay { _origin: [ 0., 0., 9. ], _dir: [ 0., 0., -1. ] } );
+    assert_eq!( all.iter().map( |x| x._id ).collect::< Vec< _ > >(), vec![ 1, 2, 3 ] );
+    assert!( ( all[1]._t - 13. ).abs() < 1e-4 );
+    assert!( close( all[1]._normal, [ 0., 0., 1. ] ) );
+    assert!( close( all[2]._point, [ 0., 0., -10. ] ) );
+    assert_eq!( all[2]._tri, Some( 0 ) );
+    let b = all[2]._barycentric.expect( "barycentric" );
+    assert!( close( b, [ 0.25, 0.25, 0.5 ] ) );
+
+    //from inside the sphere the exit point is reported
+    let h = p.pick( & PickRay { _origin: [ 0., 0., 0. ], _dir: [ 1., 0., 0. ] } ).expect( "hit" );
+    assert_eq!( h._id, 1 );
+    assert!( close( h._point, [ 1., 0., 0. ] ) );
+
+    assert!( p.pick( & PickRay { _origin: [ 5., 5., 9. ], _dir: [ 0., 0., -1. ] } ).is_none() );
+    //targets behind the ray are ignored
+    assert!( p.pick( & PickRay { _origin: [ 0., 0., 9. ], _dir: [ 0., 0., 1. ] } ).is_none() );
+}
+
+#[test]
+fn test_picking_refit_mesh(){
+    let mut p = Picker::default();
+    p.add( 1, PickShape::Sphere { _center: [ 0., 0., 0. ], _radius: 1. } );
+    p.add_mesh( 2, &[ -1., -1., -10., 1., -1., -10., 0., 1., -10. ] ).expect( "add mesh" );
+    let r = PickRay { _origin: [ 5., 0., 9. ], _dir: [ 0., 0., -1. ] };
+    assert!( p.pick( & r ).is_none() );
+
+    //moved triangle is hit at its new location
+    p.refit_mesh( 2, &[ 4., -1., -4., 6., -1., -4., 5., 1., -4. ] ).expect( "refit" );
+    let h = p.pick( & r ).expect( "hit" );
+    assert_eq!( h._id, 2 );
+    assert!( close( h._point, [ 5., 0., -4. ] ) );
+
+    assert!( p.refit_mesh( 2, &[ 0., 0. ] ).is_err() );
+    assert!( p.refit_mesh( 1, &[ 4., -1., -4., 6., -1., -4., 5., 1., -4. ] ).is_err() );
+    assert!( p.refit_mesh( 3, &[ 4., -1., -4., 6., -1., -4., 5., 1., -4. ] ).is_err() );
+}
@@ -0,0 +1,181 @@
+use interface::i_renderer::IRenderer;
+use interface::i_renderobj::RenderObjType;
+
+use implement::render::renderer_gl::Event;
+use implement::render::renderer_record::RendererRecord;
+use implement::render::renderer_sw::RendererSw;
+use implement::render::render_graph::{ self, RenderGraph, RenderPassDesc, RenderResource, TARGET_DEFAULT };
+use implement::render::util_gl::ShaderType;
+
+use test::render::test_renderer_sw;
+
+fn names( g: & RenderGraph ) -> Vec< String > {
+    g.ordered_passes().expect( "ordered" ).iter().map( |x| x._name.clone() ).collect()
+}
+
+fn target( s: & str ) -> RenderResource {
+    RenderResource::RenderTarget( String::from( s ) )
+}
+
+#[test]
+fn test_render_graph_order(){
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI, RenderObjType::TRI ] );
+    //declared out of dependency order
+    let mut light = RenderPassDesc::init( "light", 1, &[ 0 ] );
+    light._inputs = vec![ target( "gbuffer" ) ];
+    let mut geometry = RenderPassDesc::init( "geometry", 0, &[ 1 ] );
+    geometry._outputs = vec![ target( "gbuffer" ) ];
+    let mut transparent = RenderPassDesc::init( "transparent", 1, &[ 0, 1 ] );
+    transparent._inputs = vec![ target( TARGET_DEFAULT ) ];
+    transparent._clear_draw_group = false;
+    let sky = RenderPassDesc::init( "sky", 0, &[] );
+    g.add_pass( transparent ).expect( "add" );
+    g.add_pass( light ).expect( "add" );
+    g.add_pass( sky ).expect( "add" );
+    g.add_pass( geometry ).expect( "add" );
+    assert!( g.ordered_passes().is_err() );
+    g.build().expect( "build" );
+    assert_eq!( names( & g ), vec![ "sky", "geometry", "light", "transparent" ] );
+
+    //clears once per draw group, then bind, uniforms and dispatch per pass
+    assert_eq!( g.commands_frame_begin().expect( "begin" ).len(), 2 );
+    assert_eq!( g.commands_frame_end().expect( "end" ).len(), 12 );
+
+    assert!( g.add_pass( RenderPassDesc::init( "light", 0, &[] ) ).is_err() );
+    assert!( g.is_built() );
+    g.add_pass( RenderPassDesc::init( "extra", 0, &[] ) ).expect( "add" );
+    assert!( !g.is_built() );
+
+    let g = RenderGraph::default();
+    assert_eq!( names( & g ), vec![ "forward" ] );
+    assert_eq!( g._draw_groups.len(), 1 );
+}
+
+#[test]
+fn test_render_graph_invalid(){
+    //passes reading each other's outputs
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI ] );
+    let mut a = RenderPassDesc::init( "a", 0, &[] );
+    a._inputs = vec![ target( "t1" ) ];
+    a._outputs = vec![ target( "t0" ) ];
+    let mut b = RenderPassDesc::init( "b", 0, &[] );
+    b._inputs = vec![ target( "t0" ) ];
+    b._outputs = vec![ target( "t1" ) ];
+    g.add_pass( a ).expect( "add" );
+    g.add_pass( b ).expect( "add" );
+    assert_eq!( g.build(), Err( "render graph cycle detected" ) );
+
+    //a pass reading its own output is not a cycle
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI ] );
+    let mut a = RenderPassDesc::init( "a", 0, &[] );
+    a._inputs = vec![ target( TARGET_DEFAULT ) ];
+    g.add_pass( a ).expect( "add" );
+    g.build().expect( "build" );
+
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI ] );
+    g.add_pass( RenderPassDesc::init( "a", 1, &[] ) ).expect( "add" );
+    assert!( g.build().is_err() );
+}
+
+#[test]
+fn test_render_graph_read_modify_write(){
+    //passes blending into the window target run in declaration order after the pass producing it
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI ] );
+    for name in [ "post1", "post0" ].iter() {
+        let mut p = RenderPassDesc::init( name, 0, &[] );
+        p._inputs = vec![ target( TARGET_DEFAULT ) ];
+        g.add_pass( p ).expect( "add" );
+    }
+    let mut scene = RenderPassDesc::init( "scene", 0, &[] );
+    scene._inputs = vec![ target( "gbuffer" ) ];
+    g.add_pass( scene ).expect( "add" );
+    let mut geometry = RenderPassDesc::init( "geometry", 0, &[] );
+    geometry._outputs = vec![ target( "gbuffer" ) ];
+    g.add_pass( geometry ).expect( "add" );
+    g.build().expect( "build" );
+    assert_eq!( names( & g ), vec![ "geometry", "scene", "post1", "post0" ] );
+
+    //draw groups to create and backend support
+    assert!( g.uses_target( "gbuffer" ) );
+    assert!( !g.uses_target( "shadow" ) );
+    assert_eq!( g.prepare( 0, 0, &[ TARGET_DEFAULT, "gbuffer" ] ).expect( "prepare" ).len(), 1 );
+    assert!( g.prepare( 1, 0, &[ TARGET_DEFAULT, "gbuffer" ] ).expect( "prepare" ).is_empty() );
+    assert_eq!( g.prepare( 1, 0, &[ TARGET_DEFAULT ] ).err(), Some( "render target not supported" ) );
+    g._passes[0]._shader_program = Some( 2 );
+    assert_eq!( g.prepare( 1, 2, &[ TARGET_DEFAULT, "gbuffer" ] ).err(), Some( "render pass shader program not loaded" ) );
+    assert!( g.prepare( 1, 3, &[ TARGET_DEFAULT, "gbuffer" ] ).is_ok() );
+}
+
+#[test]
+fn test_render_graph_split_frame_events(){
+    let shader = |name: & str| Event::LoadShader( vec![ ( String::from( name ), ShaderType::VERTEX ) ] );
+    let shader_names = |v: & Vec< Vec< ( String, ShaderType ) > >| v.iter().map( |x| x[0].0.clone() ).collect::< Vec< _ > >();
+    let kinds = |v: & Vec< Event >| v.iter().map( |x| match x {
+        &Event::LoadShader( ref s ) => s[0].0.clone(),
+        &Event::CreateDrawGroup( _ ) => String::from( "create" ),
+        _ => String::from( "other" ),
+    } ).collect::< Vec< _ > >();
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI ] );
+    let mut p = RenderPassDesc::init( "a", 0, &[] );
+    p._shader_program = Some( 1 );
+    g.add_pass( p ).expect( "add" );
+
+    //only the loads of the programs the graph uses move ahead
+    let e = vec![ Event::CreateDrawGroup( RenderObjType::TRI ), shader( "s0" ), shader( "s1" ), Event::SetRenderGraph( g.clone() ), shader( "s2" ) ];
+    let ( shaders, graphs, rest ) = render_graph::split_frame_events( e, 0 );
+    assert_eq!( shader_names( & shaders ), vec![ "s0", "s1" ] );
+    assert_eq!( graphs.len(), 1 );
+    assert_eq!( kinds( & rest ), vec![ "create", "s2" ] );
+
+    let e = vec![ Event::CreateDrawGroup( RenderObjType::TRI ), shader( "s2" ), Event::SetRenderGraph( g ) ];
+    let ( shaders, _, rest ) = render_graph::split_frame_events( e, 2 );
+    assert!( shaders.is_empty() );
+    assert_eq!( kinds( & rest ), vec![ "create", "s2" ] );
+
+    //without a graph events keep their order
+    let ( shaders, graphs, rest ) = render_graph::split_frame_events( vec![ Event::CreateDrawGroup( RenderObjType::TRI ), shader( "s0" ) ], 0 );
+    assert!( shaders.is_empty() && graphs.is_empty() );
+    assert_eq!( kinds( & rest ), vec![ "create", "s0" ] );
+}
+
+fn frame( g: Option< RenderGraph > ) -> Vec< Event > {
+    let mut e = test_renderer_sw::frame( vec![ test_renderer_sw::tri( 0 ) ] );
+    //applies before the other events regardless of position
+    if let Some( x ) = g {
+        e.push( Event::SetRenderGraph( x ) );
+    }
+    e
+}
+
+#[test]
+fn test_render_graph_renderer(){
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI, RenderObjType::POINT ] );
+    let mut overlay = RenderPassDesc::init( "overlay", 1, &[ 1 ] );
+    overlay._inputs = vec![ target( TARGET_DEFAULT ) ];
+    g.add_pass( overlay ).expect( "add" );
+    g.add_pass( RenderPassDesc::init( "forward", 0, &[ 0, 1 ] ) ).expect( "add" );
+
+    let mut r = RendererRecord::init().expect( "renderer" );
+    r.process_render_events( frame( None ) ).expect( "render" );
+    r.process_render_events( frame( Some( g.clone() ) ) ).expect( "render" );
+    r.process_render_events( frame( None ) ).expect( "render" );
+
+    assert_eq!( r.frame( 0 ).unwrap().bound_groups(), vec![ 0 ] );
+    let f = r.frame( 1 ).unwrap();
+    assert!( f.to_string().starts_with( "frame 1\n  create_draw_group 1 POINT\n  set_render_graph forward,overlay\n" ) );
+    //submitted objects go to the draw group of the first pass
+    let d : Vec< ( usize, usize ) > = f.dispatches().iter().map( |x| ( x._group, x._vert_count ) ).collect();
+    assert_eq!( d, vec![ ( 0, 3 ), ( 1, 0 ) ] );
+    assert_eq!( f.dispatch( 1 ).unwrap()._uniforms.len(), 4 );
+    //graph persists across frames
+    assert_eq!( r.frame( 2 ).unwrap().bound_groups(), vec![ 0, 1 ] );
+
+    //software renderer draws into the window only
+    let mut sw = RendererSw::init_with_size( 16, 16 ).expect( "renderer" );
+    sw.process_render_events( frame( Some( g ) ) ).expect( "render" );
+    let mut g = RenderGraph::init( &[ RenderObjType::TRI ] );
+    let mut p = RenderPassDesc::init( "offscreen", 0, &[ 0, 1 ] );
+    p._outputs = vec![ target( "gbuffer" ) ];
+    g.add_pass( p ).expect( "add" );
+    assert_eq!( sw.process_render_events( frame( Some( g ) ) ), Err( "render target not supported" ) );
+}
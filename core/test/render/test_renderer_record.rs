@@ -0,0 +1,121 @@
+use interface::i_ele;
+use interface::i_component::TextureRange;
+use interface::i_renderer::IRenderer;
+
+use implement::render::mesh::Mesh;
+use implement::render::renderer_gl::Event;
+use implement::render::renderer_record::{ RendererRecord, RecordCmd };
+use implement::render::util_gl::ShaderType;
+
+use test::render::test_renderer_sw::{ quad, frame_with_light };
+
+fn frame( meshes: Vec< Mesh > ) -> Vec< Event > {
+    frame_with_light( [ 1., 2., 3. ], meshes )
+}
+
+#[test]
+fn test_renderer_record_frames(){
+    let mut r = RendererRecord::init().expect( "renderer" );
+    let mut e = vec![ Event::LoadShader( vec![ ( String::from( "a.vs" ), ShaderType::VERTEX ), ( String::from( "a.fs" ), ShaderType::FRAGMENT ) ] ),
+                      Event::LoadTexture( String::from( "texture0" ), vec![ 0; 12 ], 2, 2 ) ];
+    e.extend( frame( vec![ quad( 0, 1., 0., true ) ] ) );
+    r.process_render_events( e ).expect( "render" );
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., true ), quad( 1, 1., 0., true ) ] ) ).expect( "render" );
+    assert_eq!( r._frames.len(), 2 );
+
+    let f0 = r.frame( 0 ).expect( "frame 0" );
+    match f0._cmds[0] {
+        RecordCmd::CreateDrawGroup { _group: 0, .. } => {},
+        _ => { panic!( "draw group not created first" ); },
+    }
+    assert_eq!( f0.bound_groups(), vec![ 0 ] );
+    let d = f0.dispatch( 0 ).expect( "dispatch" );
+    assert_eq!( d._vert_count, 6 );
+    assert!( d._missing_uniform_groups.is_empty() );
+    assert_eq!( d.uniform( "Light.Position" ), Some( &[ 1., 2., 3. ][..] ) );
+    assert_eq!( d.uniform( "MVP" ).expect( "mvp" ).len(), 16 );
+    assert_eq!( d.uniform( "NormalMatrix\0" ).expect( "normal matrix" ).len(), 9 );
+    assert!( d.uniform( "Unknown" ).is_none() );
+
+    //uniform values submitted by the camera are the ones dispatched
+    let mvp = f0._cmds.iter().filter_map( |x| match x {
+        &RecordCmd::Uniform { ref _values, .. } => _values.iter().find( |v| v.0 == "MVP\0" ).map( |v| v.2.clone() ),
+        _ => None,
+    } ).next().expect( "camera uniforms" );
+    assert_eq!( d.uniform( "MVP" ), Some( &mvp[..] ) );
+
+    //draw group is cleared every frame
+    let f1 = r.last_frame().expect( "frame 1" );
+    assert_eq!( f1._index, 1 );
+    assert_eq!( f1.dispatch( 0 ).expect( "dispatch" )._vert_count, 12 );
+    let buffers : Vec< usize > = f1._cmds.iter().filter_map( |x| match x {
+        &RecordCmd::RenderBuffer { _vert_count, .. } => Some( _vert_count ),
+        _ => None,
+    } ).collect();
+    assert_eq!( buffers, vec![ 6, 6 ] );
+}
+
+#[test]
+fn test_renderer_record_dump(){
+    let mut r = RendererRecord::init().expect( "renderer" );
+    let mut m = quad( 0, 1., 0., true );
+    m._submesh_textures.push( TextureRange { _texture: Some( String::from( "texture0" ) ), _vert_offset: 3, _vert_count: 3 } );
+    r.process_render_events( frame( vec![ quad( 1, 1., 0., true ), m ] ) ).expect( "render" );
+    let d = r.frame( 0 ).expect( "frame" ).dispatch( 0 ).expect( "dispatch" );
+    //uncovered vertices are bound to the default texture as in renderer_gl
+    let ranges : Vec< ( Option< & str >, usize, usize ) > = d._texture_ranges.iter().map( |x| ( x._texture.as_ref().map( |t| t.as_str() ), x._vert_offset, x._vert_count ) ).collect();
+    assert_eq!( ranges, vec![ ( None, 0, 6 ), ( None, 6, 3 ), ( Some( "texture0" ), 9, 3 ) ] );
+
+    let dump = r.dump();
+    //objects are recorded on submission, their components when processed at dispatch
+    assert!( dump.starts_with( "frame 0\n  create_draw_group 0 TRI\n  add_obj components=1\n  add_obj components=1\n  add_obj components=1\n  add_obj components=2\n  draw_group_clear 0\n" ) );
+    assert!( dump.contains( "  texture_ranges texture0:3+3\n" ) );
+    assert!( dump.contains( "  draw_group_dispatch 0 TRI verts=12 missing_uniform_groups=[] texture_ranges=[default:0+6 default:6+3 texture0:9+3]\n" ) );
+    assert!( dump.contains( "    Light.Position VEC [1.0, 2.0, 3.0]\n" ) );
+    //identical submissions dump identically
+    let mut r2 = RendererRecord::init().expect( "renderer" );
+    let mut m = quad( 0, 1., 0., true );
+    m._submesh_textures.push( TextureRange { _texture: Some( String::from( "texture0" ) ), _vert_offset: 3, _vert_count: 3 } );
+    r2.process_render_events( frame( vec![ quad( 1, 1., 0., true ), m ] ) ).expect( "render" );
+    assert_eq!( r2.dump(), dump );
+
+    let path = ::std::env::temp_dir().join( "e2r_test_renderer_record.txt" );
+    let path = path.to_str().expect( "path" );
+    r.write_dump( path ).expect( "write" );
+    let mut s = String::new();
+    use std::io::Read;
+    ::std::fs::File::open( path ).expect( "open" ).read_to_string( & mut s ).expect( "read" );
+    assert_eq!( s, dump );
+}
+
+#[test]
+fn test_renderer_record_errors(){
+    //geometry without camera and light records unresolved uniform groups
+    let mut r = RendererRecord::init().expect( "renderer" );
+    r.process_render_events( vec![ Event::AddObj( i_ele::Ele::init( quad( 0, 1., 0., true ) ) ) ] ).expect( "render" );
+    assert_eq!( r.frame( 0 ).unwrap().dispatch( 0 ).unwrap()._missing_uniform_groups, vec![ 0, 1 ] );
+
+    //failed frame is still recorded
+    assert!( r.process_render_events( vec![ Event::LoadTexture( String::from( "bad" ), vec![ 0; 5 ], 2, 1 ) ] ).is_err() );
+    let f = r.frame( 1 ).expect( "frame" );
+    assert_eq!( f._err, Some( "loading texture failed" ) );
+    assert!( f.dispatches().is_empty() );
+    assert!( r.dump().contains( "frame 1 error=loading texture failed\n" ) );
+
+    r.clear();
+    assert!( r.last_frame().is_none() );
+    //frame indices continue after clearing and pending commands of the failed frame are dropped
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., true ) ] ) ).expect( "render" );
+    let f = r.last_frame().expect( "frame" );
+    assert_eq!( f._index, 2 );
+    assert!( r.frame( 0 ).is_none() );
+    assert_eq!( f.dispatch( 0 ).unwrap()._vert_count, 6 );
+    let clears = f._cmds.iter().filter( |x| if let &RecordCmd::DrawGroupClear( _ ) = *x { true } else { false } ).count();
+    assert_eq!( clears, 1 );
+
+    //texture ranges past the render buffer are rejected as by the other backends
+    let mut m = quad( 0, 1., 0., true );
+    m._submesh_textures.push( TextureRange { _texture: Some( String::from( "texture0" ) ), _vert_offset: 3, _vert_count: 4 } );
+    assert_eq!( r.process_render_events( frame( vec![ m ] ) ), Err( "texture range exceeds render buffer" ) );
+    assert_eq!( r.last_frame().unwrap()._err, Some( "texture range exceeds render buffer" ) );
+}
@@ -0,0 +1,182 @@
+extern crate mazth;
+
+use self::mazth::mat::Mat3x1;
+
+use interface::i_ele;
+use interface::i_renderer::IRenderer;
+
+use implement::file::ppm;
+use implement::render::camera::Cam;
+use implement::render::light::LightAdsPoint;
+use implement::render::mesh::Mesh;
+use implement::render::renderer_gl::Event;
+use implement::render::renderer_sw::RendererSw;
+
+///square in the z = h plane, counter clockwise seen from +z, texture coordinates spanning [0,1]
+pub fn quad( id: u64, half: f32, h: f32, ccw: bool ) -> Mesh {
+    let mut corners = vec![ [ -1., -1. ], [ 1., -1. ], [ 1., 1. ], [ -1., -1. ], [ 1., 1. ], [ -1., 1. ] ];
+    if !ccw {
+        corners.reverse();
+    }
+    let mut m = Mesh::init( id );
+    for c in corners.iter() {
+        m._batch_pos.extend_from_slice( &[ c[0] * half, c[1] * half, h ] );
+        m._batch_normal.extend_from_slice( &[ 0., 0., 1. ] );
+        m._batch_tc.extend_from_slice( &[ ( c[0] + 1. ) * 0.5, ( c[1] + 1. ) * 0.5 ] );
+    }
+    m
+}
+
+///first triangle of quad( id, 1., 0., true )
+pub fn tri( id: u64 ) -> Mesh {
+    let mut m = quad( id, 1., 0., true );
+    m._batch_pos.truncate( 9 );
+    m._batch_normal.truncate( 9 );
+    m._batch_tc.truncate( 6 );
+    m
+}
+
+///looking down -z from ( 0, 0, 5 ) with a 90 degree field of view
+pub fn cam() -> Cam {
+    Cam::init( 0, 90., 1., 0.1, 100., Mat3x1 { _val: [ 0., 0., 5. ] }, Mat3x1 { _val: [ 0., 0., 0. ] }, Mat3x1 { _val: [ 0., 1., 0. ] } )
+}
+
+///position is interpreted in eye space as in ads.vs
+pub fn light( pos: [ f32; 3 ] ) -> LightAdsPoint {
+    LightAdsPoint { _id: 0,
+                    _pos: Mat3x1 { _val: pos },
+                    _ads_val_spec: Mat3x1 { _val: [ 0.2, 0.2, 0.2 ] },
+                    _ads_val_diff: Mat3x1 { _val: [ 0.5, 0.5, 0.5 ] },
+                    _ads_val_amb: Mat3x1 { _val: [ 0.1, 0.1, 0.1 ] } }
+}
+
+///light at the eye and camera events followed by the meshes
+pub fn frame( meshes: Vec< Mesh > ) -> Vec< Event > {
+    frame_with_light( [ 0., 0., 0. ], meshes )
+}
+
+pub fn frame_with_light( light_pos: [ f32; 3 ], meshes: Vec< Mesh > ) -> Vec< Event > {
+    let mut e = vec![ Event::AddObj( i_ele::Ele::init( light( light_pos ) ) ),
+                      Event::AddObj( i_ele::Ele::init( cam() ) ) ];
+    for m in meshes {
+        e.push( Event::AddObj( i_ele::Ele::init( m ) ) );
+    }
+    e
+}
+
+fn close( a: [u8;3], b: [u8;3], tol: i32 ) -> bool {
+    ( 0..3 ).all( |i| ( a[i] as i32 - b[i] as i32 ).abs() <= tol )
+}
+
+#[test]
+fn test_renderer_sw_ads(){
+    let mut r = RendererSw::init_with_size( 64, 64 ).expect( "renderer" );
+    r._clear_color = [ 10, 20, 30 ];
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., true ) ] ) ).expect( "render" );
+    assert_eq!( r._frame, 1 );
+
+    //all corners see the light at the same angle so the quad is evenly lit
+    let s_dot_n = 5. / 27f32.sqrt();
+    let spec = ( 23f32 / 27. ).powf( 3. );
+    let material = [ ( 0.2, 0.1, 0.9 ), ( 0.2, 0.1, 0.1 ), ( 0.2, 0.9, 0.1 ) ];
+    let mut expect = [ 0u8; 3 ];
+    for k in 0..3 {
+        let ( ka, kd, ks ) = material[k];
+        expect[k] = ( ( 0.1 * ka + 0.5 * kd * s_dot_n + 0.2 * ks * spec ) * 255. ).round() as u8;
+    }
+    assert!( close( r.pixel( 32, 32 ), expect, 1 ) );
+    assert!( close( r.pixel( 27, 37 ), expect, 1 ) );
+    //the quad projects to a fifth of the viewport
+    assert_eq!( r.pixel( 20, 32 ), [ 10, 20, 30 ] );
+    assert_eq!( r.pixel( 32, 44 ), [ 10, 20, 30 ] );
+    assert!( r._depth[ 32 * 64 + 32 ] < 1. );
+    assert_eq!( r._depth[0], 1. );
+
+    //next frame starts from a cleared buffer
+    r.process_render_events( frame( vec![] ) ).expect( "render" );
+    assert_eq!( r._frame, 2 );
+    assert_eq!( r.pixel( 32, 32 ), [ 10, 20, 30 ] );
+}
+
+#[test]
+fn test_renderer_sw_texture(){
+    let mut r = RendererSw::init_with_size( 64, 64 ).expect( "renderer" );
+    //first row at t = 0, the bottom of the quad
+    let tex = vec![ 255, 0, 0,   0, 255, 0,
+                    0, 0, 255,   255, 255, 255 ];
+    let mut e = vec![ Event::LoadTexture( String::from( "texture0" ), tex, 2, 2 ) ];
+    e.extend( frame( vec![ quad( 0, 1., 0., true ) ] ) );
+    r.process_render_events( e ).expect( "render" );
+    assert!( r.get_texture( "texture0" ).is_some() );
+    //pixels a quarter of the quad in from the corners sample single texels
+    assert!( close( r.pixel( 28, 35 ), [ 255, 0, 0 ], 1 ) );
+    assert!( close( r.pixel( 35, 35 ), [ 0, 255, 0 ], 1 ) );
+    assert!( close( r.pixel( 28, 28 ), [ 0, 0, 255 ], 1 ) );
+    assert!( close( r.pixel( 35, 28 ), [ 255, 255, 255 ], 1 ) );
+    //bilinear blend at a pixel center just off the middle of the quad
+    assert!( close( r.pixel( 32, 32 ), [ 124, 147, 108 ], 1 ) );
+
+    assert!( r.load_texture( String::from( "bad" ), &[ 0; 5 ], 2, 1 ).is_err() );
+}
+
+#[test]
+fn test_renderer_sw_depth_and_culling(){
+    let mut r = RendererSw::init_with_size( 64, 64 ).expect( "renderer" );
+    //large far quad drawn after the small near one
+    r.process_render_events( frame( vec![ quad( 0, 1., 1., true ), quad( 1, 2., -1., true ) ] ) ).expect( "render" );
+    let near = r.pixel( 32, 32 );
+    let d_near = r._depth[ 32 * 64 + 32 ];
+    let far = r.pixel( 32, 22 );
+    assert!( near != far );
+    assert!( r._depth[ 22 * 64 + 32 ] > d_near );
+
+    r.process_render_events( frame( vec![ quad( 1, 2., -1., true ), quad( 0, 1., 1., true ) ] ) ).expect( "render" );
+    assert_eq!( r.pixel( 32, 32 ), near );
+    assert_eq!( r._depth[ 32 * 64 + 32 ], d_near );
+
+    //clockwise quad is drawn unless culling is on
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., false ) ] ) ).expect( "render" );
+    assert!( r.pixel( 32, 32 ) != [ 0, 0, 0 ] );
+    r._cull_back_face = true;
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., false ) ] ) ).expect( "render" );
+    assert_eq!( r.pixel( 32, 32 ), [ 0, 0, 0 ] );
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., true ) ] ) ).expect( "render" );
+    assert!( r.pixel( 32, 32 ) != [ 0, 0, 0 ] );
+}
+
+#[test]
+fn test_renderer_sw_near_clip(){
+    //floor extending behind the camera
+    let mut m = Mesh::init( 0 );
+    let c = [ [ -20., 20. ], [ 20., 20. ], [ 20., -50. ], [ -20., 20. ], [ 20., -50. ], [ -20., -50. ] ];
+    for p in c.iter() {
+        m._batch_pos.extend_from_slice( &[ p[0], -1., p[1] ] );
+        m._batch_normal.extend_from_slice( &[ 0., 1., 0. ] );
+        m._batch_tc.extend_from_slice( &[ 0., 0. ] );
+    }
+    let mut r = RendererSw::init_with_size( 32, 32 ).expect( "renderer" );
+    r.process_render_events( frame( vec![ m ] ) ).expect( "render" );
+    //bottom half covered up to the horizon
+    assert!( ( 0..32 ).all( |x| r.pixel( x, 31 ) != [ 0, 0, 0 ] ) );
+    assert!( ( 0..32 ).all( |x| r.pixel( x, 17 ) != [ 0, 0, 0 ] ) );
+    assert!( ( 0..32 ).all( |x| r.pixel( x, 14 ) == [ 0, 0, 0 ] ) );
+    assert!( r._depth.iter().all( |d| *d >= 0. && *d <= 1. ) );
+}
+
+#[test]
+fn test_renderer_sw_output(){
+    let mut r = RendererSw::init().expect( "renderer" );
+    assert_eq!( ( r._width, r._height ), ( 500, 500 ) );
+    r.process_render_events( frame( vec![ quad( 0, 1., 0., true ) ] ) ).expect( "render" );
+    let path = ::std::env::temp_dir().join( "e2r_test_renderer_sw.ppm" );
+    let path = path.to_str().expect( "path" );
+    r.write_ppm( path ).expect( "write" );
+    let ( w, h, img ) = ppm::read( path ).expect( "read" );
+    assert_eq!( ( w, h ), ( 500, 500 ) );
+    assert_eq!( &img[..], r.frame() );
+
+    //geometry without camera and light uniforms
+    let mut r = RendererSw::init_with_size( 8, 8 ).expect( "renderer" );
+    assert!( r.process_render_events( vec![ Event::AddObj( i_ele::Ele::init( quad( 0, 1., 0., true ) ) ) ] ).is_err() );
+    assert!( RendererSw::init_with_size( 0, 8 ).is_err() );
+}
@@ -1 +1,2 @@
 pub mod test_step_interpolate;
+pub mod test_tween;
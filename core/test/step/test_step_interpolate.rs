@@ -15,6 +15,9 @@ use implement::step::step_interpolate;
 
 use implement::math::spline_bezier::SplineBezier;
 use implement::math::piecewise::Piecewise;
+use implement::math::linearinterp::LinearInterp;
+use implement::math::arclength::ArcLength;
+use implement::math::easing::{ Ease, EaseMode };
 
 #[test]
 fn test_step_interpolate(){
@@ -108,3 +111,49 @@ fn test_step_interpolate_start(){
 
     assert!( val.is_equal( &cp0, 0.00001f64 ).expect("is_equal invalid") );
 }
+
+#[test]
+fn test_step_distance(){
+    //segments of length 1 and 3
+    let cp0 = Mat4x1 { _val: [ 0f64, 0f64, 0f64, 1f64 ] };
+    let cp1 = Mat4x1 { _val: [ 1f64, 0f64, 0f64, 1f64 ] };
+    let cp2 = Mat4x1 { _val: [ 4f64, 0f64, 0f64, 1f64 ] };
+    let path = ArcLength::init( vec![ LinearInterp::init( 10, cp0, cp1 ),
+                                      LinearInterp::init( 10, cp1, cp2 ) ], 8 ).expect( "arc length init" );
+
+    let mut s = Step {
+        _current_val: 10.0,
+        _range_val: ( 10.0, 20.0 ),
+    };
+
+    let val = step_interpolate::step_distance( & mut s, 5f64, & path );
+    assert!( val.is_equal( &Mat4x1 { _val: [ 2f64, 0f64, 0f64, 1f64 ] }, 0.00001f64 ).expect("is_equal invalid") );
+
+    let val = step_interpolate::step_distance( & mut s, 15f64, & path );
+    assert!( s._current_val == 20.0 );
+    assert!( val.is_equal( &cp2, 0.00001f64 ).expect("is_equal invalid") );
+}
+
+#[test]
+fn test_step_eased(){
+    let cp0 = Mat4x1 { _val: [ 0f64, 0f64, 0f64, 1f64 ] };
+    let cp1 = Mat4x1 { _val: [ 10f64, 0f64, 0f64, 1f64 ] };
+    let mut interp = LinearInterp::init( 11, cp0, cp1 );
+
+    let mut s = Step {
+        _current_val: 0.0,
+        _range_val: ( 0.0, 1.0 ),
+    };
+    let ease = Ease::Quad( EaseMode::In );
+
+    //half way through the range is a quarter of the way along the interpolator
+    let val = step_interpolate::step_eased( & mut s, 0.5f64, & ease, & mut interp );
+    assert!( val.is_equal( &Mat4x1 { _val: [ 1f64, 0f64, 0f64, 1f64 ] }, 0.00001f64 ).expect("is_equal invalid") );
+
+    let val = step_interpolate::step_eased( & mut s, 5f64, & ease, & mut interp );
+    assert!( s._current_val == 1.0 );
+    assert!( val.is_equal( &cp1, 0.00001f64 ).expect("is_equal invalid") );
+
+    let val = step_interpolate::step_eased( & mut s, -1f64, & ease, & mut interp );
+    assert!( val.is_equal( &cp0, 0.00001f64 ).expect("is_equal invalid") );
+}
@@ -0,0 +1,109 @@
+extern crate mazth;
+
+use std::rc::Rc;
+use std::cell::Cell;
+
+use self::mazth::quat::Quat;
+
+use interface::i_tween::ITween;
+
+use implement::math::easing::{ Ease, EaseMode };
+use implement::step::tween::{ Tween, Sequence, Parallel, TweenManager };
+
+#[test]
+fn test_tween_delay_and_complete(){
+    let target = Rc::new( Cell::new( -1f32 ) );
+    let completed = Rc::new( Cell::new( 0 ) );
+    let c = completed.clone();
+    let mut t = Tween::init( 0f32, 10f32, 1., Ease::Linear )
+        .delay( 0.5 )
+        .bind( target.clone() )
+        .on_complete( move || c.set( c.get() + 1 ) );
+    assert!( ( t.duration() - 1.5 ).abs() < 1e-12 );
+
+    //still delayed
+    assert_eq!( t.advance( 0.25 ), 0. );
+    assert_eq!( target.get(), -1. );
+    t.advance( 0.5 );
+    assert!( ( target.get() - 2.5 ).abs() < 1e-5 );
+    //completes with time left over
+    let left = t.advance( 1. );
+    assert!( ( left - 0.25 ).abs() < 1e-12 );
+    assert!( t.is_done() );
+    assert_eq!( target.get(), 10. );
+    assert_eq!( completed.get(), 1 );
+    t.advance( 1. );
+    assert_eq!( completed.get(), 1 );
+
+    t.reset();
+    assert!( !t.is_done() );
+    assert_eq!( t.value(), 0. );
+}
+
+#[test]
+fn test_tween_yoyo_repeat(){
+    let mut t = Tween::init( 0f64, 1f64, 1., Ease::Quad( EaseMode::In ) ).yoyo( true ).repeat( 1 );
+    assert!( ( t.duration() - 4. ).abs() < 1e-12 );
+    t.advance( 0.5 );
+    assert!( ( t.value() - 0.25 ).abs() < 1e-12 );
+    //heading back
+    t.advance( 1. );
+    assert!( ( t.value() - 0.25 ).abs() < 1e-12 );
+    //second play
+    t.advance( 1. );
+    assert!( ( t.value() - 0.25 ).abs() < 1e-12 );
+    t.advance( 10. );
+    assert!( t.is_done() );
+    assert_eq!( t.value(), 0. );
+}
+
+#[test]
+fn test_tween_sequence_parallel(){
+    let a = Rc::new( Cell::new( 0f32 ) );
+    let b = Rc::new( Cell::new( 0f32 ) );
+    let mut s = Sequence::init( vec![ Box::new( Tween::init( 0f32, 1f32, 1., Ease::Linear ).bind( a.clone() ) ) as Box< ITween >,
+                                      Box::new( Tween::init( 0f32, 1f32, 1., Ease::Linear ).bind( b.clone() ) ) ] );
+    assert!( ( s.duration() - 2. ).abs() < 1e-12 );
+    //leftover of the first carries into the second
+    s.advance( 1.5 );
+    assert_eq!( a.get(), 1. );
+    assert!( ( b.get() - 0.5 ).abs() < 1e-6 );
+    assert!( !s.is_done() );
+    assert!( ( s.advance( 1. ) - 0.5 ).abs() < 1e-12 );
+    assert!( s.is_done() );
+
+    let done = Rc::new( Cell::new( false ) );
+    let d = done.clone();
+    let q = Rc::new( Cell::new( Quat::<f32>::init_from_vals_raw( 0., 0., 0., 1. ) ) );
+    let half = ( ::std::f32::consts::PI / 4. ).sin();
+    let mut p = Parallel::init( vec![ Box::new( Tween::init( [ 0f32, 0., 0., 1. ], [ 1f32, 1., 1., 1. ], 2., Ease::Linear ) ) as Box< ITween >,
+                                      Box::new( Tween::init( Quat::<f32>::init_from_vals_raw( 0., 0., 0., 1. ),
+                                                             Quat::<f32>::init_from_vals_raw( 0., 0., half, half ),
+                                                             1., Ease::Linear ).bind( q.clone() ) ) ] )
+        .on_complete( move || d.set( true ) );
+    assert!( ( p.duration() - 2. ).abs() < 1e-12 );
+    p.advance( 0.5 );
+    //quarter turn halfway
+    let expect = ( ::std::f32::consts::PI / 8. ).sin();
+    assert!( ( q.get()._z - expect ).abs() < 1e-5 );
+    p.advance( 1. );
+    assert!( !p.is_done() );
+    assert!( ( p.advance( 1. ) - 0.5 ).abs() < 1e-12 );
+    assert!( p.is_done() && done.get() );
+}
+
+#[test]
+fn test_tween_manager(){
+    let a = Rc::new( Cell::new( 0f32 ) );
+    let mut m = TweenManager::default();
+    let short = m.add( Tween::init( 0f32, 1f32, 1., Ease::Linear ).bind( a.clone() ) );
+    let long = m.add( Tween::init( 0f32, 1f32, 5., Ease::Linear ) );
+    assert_eq!( m.len(), 2 );
+    m.advance( 2. );
+    assert_eq!( a.get(), 1. );
+    assert!( !m.is_active( short ) );
+    assert!( m.is_active( long ) );
+    assert!( m.cancel( long ) );
+    assert!( !m.cancel( long ) );
+    assert!( m.is_empty() );
+}
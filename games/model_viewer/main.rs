@@ -18,10 +18,11 @@ use std::f32;
 
 use self::e2rcore::interface::i_ele;
 use self::e2rcore::interface::i_game_logic::IGameLogic;
-use self::e2rcore::interface::i_ui::{ InputFiltered, KeyCode, /*State, Coord*/ };
+use self::e2rcore::interface::i_ui::{ InputFiltered, KeyCode, State, /*Coord*/ };
 use self::e2rcore::interface::i_scheduler::IScheduler;
 use self::e2rcore::interface::i_file::IParseStr;
 use self::e2rcore::interface::i_md5;
+use self::e2rcore::interface::i_component;
 
 use self::e2rcore::implement::render::renderer_gl;
 use self::e2rcore::implement::render::util_gl;
@@ -30,6 +31,11 @@ use self::e2rcore::implement::render::camera;
 use self::e2rcore::implement::render::light;
 use self::e2rcore::implement::render::mesh;
 // use self::e2rcore::implement::render::primitive;
+use self::e2rcore::implement::render::culling::{ ICullable, BoundingVolume, CullStats };
+use self::e2rcore::implement::render::picking::Picker;
+use self::e2rcore::implement::render::lod::{ LodChain, LodConfig, LodPolicy };
+
+use self::e2rcore::implement::math::frustum::Frustum;
 
 use self::e2rcore::implement::ui::ui_cam::UiCam;
 
@@ -44,7 +50,7 @@ use self::image::GenericImage;
 
 use std::env;
 
-// use std::collections::{ HashSet, HashMap };
+use std::collections::HashMap;
 
 use self::e2rcore::interface::i_kernel::IKernel;
 
@@ -52,12 +58,13 @@ use self::e2rcore::implement::kernel::kernel_impl_001::Kernel;
 
 use self::e2rcore::implement::file::*;
 
-use self::e2rcore::interface::i_wavefront;
-
 use self::e2rcore::implement::file::md5common;
 use self::e2rcore::implement::file::wavefrontobj;
 use self::e2rcore::implement::file::wavefrontcomp;
 
+///window dimension created by the kernel
+const WINDOW_SIZE : ( f32, f32 ) = ( 500., 500. );
+
 //todo: put this somewhere else
 pub fn file_open( file_path: & str ) -> Option<String> {
     let path = File::open( file_path ).expect("file path open invalid");
@@ -186,17 +193,28 @@ pub enum RenderObj {
         _time_game: f32,
         _light: light::LightAdsPoint,
         _camera: camera::Cam,
-        _md5_precompute: Rc< Vec<i_md5::compute::ComputeCollection> >,
+        _md5_compute: i_md5::compute::ComputeCollection,
     },
     TestGeometryObj {
         _time_game: f32,
         _light: light::LightAdsPoint,
         _camera: camera::Cam,
-        _obj_compute: Rc< i_wavefront::compute::ComputeCollection >,
+        _obj_lod: Rc< LodChain >,
+        _lod_level: usize,
     },
 }
 
 
+impl ICullable for RenderObj {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        match *self {
+            RenderObj::InitialRender{..} => BoundingVolume::Unbounded,
+            RenderObj::TestGeometry{ ref _md5_compute, .. } => _md5_compute.bounding_volume(),
+            RenderObj::TestGeometryObj{ ref _obj_lod, .. } => _obj_lod.bounding_volume(),
+        }
+    }
+}
+
 impl From< RenderObj > for Vec< renderer_gl::Event > {
     fn from( _r: RenderObj ) -> Self {
         match _r {
@@ -214,31 +232,38 @@ impl From< RenderObj > for Vec< renderer_gl::Event > {
                     ] );
                 render_events.push( event_load_shader );
 
-                let img = image::open( &Path::new( "core/asset/images/texture0.jpg" ) ).unwrap();
-                debug!( "image dimension: {:?}", img.dimensions() );
-                debug!( "image type: {:?}", img.color() );
+                for name in [ "texture0", "texture1" ].iter() {
+                    let img = image::open( &Path::new( format!( "core/asset/images/{}.jpg", name ).as_str() ) ).unwrap();
+                    debug!( "image dimension: {:?}", img.dimensions() );
+                    debug!( "image type: {:?}", img.color() );
                 
-                let texture0 = texture::Texture::from( &img );
-                let texture_data = Vec::from( texture0 );
-                let ( w, h ) = img.dimensions();
-                let event_load_texture = renderer_gl::Event::LoadTexture( String::from("texture0"), texture_data, w as _, h as _ );
-                render_events.push( event_load_texture );
+                    let texture = texture::Texture::from( &img );
+                    let texture_data = Vec::from( texture );
+                    let ( w, h ) = img.dimensions();
+                    let event_load_texture = renderer_gl::Event::LoadTexture( String::from( *name ), texture_data, w as _, h as _ );
+                    render_events.push( event_load_texture );
+                }
 
                 info!( "press q to quit." );
+                info!( "press p to pause/play, r to reverse, l to cycle loop mode, 0 to rewind." );
+                info!( "press o to toggle the obj model." );
 
                 render_events
             },
-            RenderObj::TestGeometry{ _time_game, _light, _camera, _md5_precompute } =>{
+            RenderObj::TestGeometry{ _time_game, _light, _camera, _md5_compute } =>{
 
                 let mut render_events = vec![];
                 
                 let mut mesh = mesh::Mesh::init( 0 );
 
-                let frame = _time_game as usize % _md5_precompute.len();
-
-                mesh._batch_pos = _md5_precompute[frame]._batch_vert.clone();
-                mesh._batch_normal = _md5_precompute[frame]._batch_normal.clone();
-                mesh._batch_tc = _md5_precompute[frame]._batch_tc.clone();
+                mesh._submesh_textures = _md5_compute._submeshes.iter()
+                    .map( |x| i_component::TextureRange { _texture: x._texture.clone(),
+                                                          _vert_offset: x._vert_offset,
+                                                          _vert_count: x._vert_count } )
+                    .collect();
+                mesh._batch_pos = _md5_compute._batch_vert;
+                mesh._batch_normal = _md5_compute._batch_normal;
+                mesh._batch_tc = _md5_compute._batch_tc;
                 
                 assert!( mesh._batch_pos.len() % 3 == 0 );
                 assert!( mesh._batch_pos.len() == mesh._batch_normal.len() );
@@ -259,17 +284,11 @@ impl From< RenderObj > for Vec< renderer_gl::Event > {
 
                 render_events
             },
-            RenderObj::TestGeometryObj{ _time_game, _light, _camera, _obj_compute } =>{
+            RenderObj::TestGeometryObj{ _time_game, _light, _camera, _obj_lod, _lod_level } =>{
 
                 let mut render_events = vec![];
                 
-                let mut mesh = mesh::Mesh::init( 0 );
-
-                // let frame = _time_game as usize % _obj_compute.len();
-
-                mesh._batch_pos = _obj_compute._batch_vert.clone();
-                mesh._batch_normal = _obj_compute._batch_normal.clone();
-                mesh._batch_tc = _obj_compute._batch_tc.clone();
+                let mesh = _obj_lod.mesh( _lod_level, 0 );
                 
                 assert!( mesh._batch_pos.len() % 3 == 0 );
                 assert!( mesh._batch_pos.len() == mesh._batch_normal.len() );
@@ -304,8 +323,21 @@ pub struct GameLogic {
     _state: GameState,
     _uicam: UiCam,
     _md5: ( i_md5::rig::PoseCollection, i_md5::mesh::Md5MeshRoot ),
-    _md5_precompute: Rc< Vec< i_md5::compute::ComputeCollection > >,
-    _obj_compute: Rc< i_wavefront::compute::ComputeCollection >,
+    _anim_player: md5player::AnimPlayer,
+    ///md5 shader name to texture description
+    _md5_materials: HashMap< String, String >,
+    ///simplified versions of the obj model
+    _obj_lod: Rc< LodChain >,
+    _lod_policy: LodPolicy,
+    _lod_level: usize,
+    ///renders the obj model instead of the md5 model
+    _show_obj: bool,
+    ///playback time of the last rendered md5 pose, skinned again when picking
+    _md5_pose_last: Option< md5player::AnimPlayer >,
+    ///per submesh bvhs built from the first frame, refitted to the last rendered pose when picking
+    _md5_picker: Picker,
+    ///seconds elapsed since the previous frame, provided by the kernel
+    _frame_delta: f32,
 }
 
 impl IGameLogic for GameLogic {
@@ -346,9 +378,7 @@ impl IGameLogic for GameLogic {
         };
         assert!( 5 < posecollection._frames.len() );
 
-        //prcompute animations
-        let mut animation = vec![];
-
+        //find animation extent for camera placement
         let mut bbox_lower = [ 0f32; 3 ];
         let mut bbox_upper = [ 0f32; 3 ];
         
@@ -360,7 +390,6 @@ impl IGameLogic for GameLogic {
                             bbox_upper[h] = bbox_upper[h].max( o._bbox_upper[h] );
                             bbox_lower[h] = bbox_lower[h].min( o._bbox_lower[h] );
                         }
-                        animation.push( o );
                     },
                     Err( e ) => panic!( e ),
                 }
@@ -372,6 +401,11 @@ impl IGameLogic for GameLogic {
 
         let wavefront_obj = wavefrontobj::parse( &file_content ).expect("parse unsuccessful");
         let obj_compute = wavefrontcomp::process( & wavefront_obj ).expect("obj compute unsuccessful");
+        let obj_lod = LodChain::build( &obj_compute._batch_vert[..], &obj_compute._batch_normal[..], &obj_compute._batch_tc[..],
+                                       & LodConfig::default() ).expect("obj lod chain unsuccessful");
+        for ( i, l ) in obj_lod._levels.iter().enumerate() {
+            info!( "obj lod {}: triangles: {}, error: {}", i, l._tri_count, l._error );
+        }
 
         // let bbox_upper = obj_compute._bbox_upper.clone();
         // let bbox_lower = obj_compute._bbox_lower.clone();
@@ -394,6 +428,18 @@ impl IGameLogic for GameLogic {
         let cam_id = 0;
         let cam = camera::Cam::init( cam_id, fov, aspect, near, far, cam_pos, cam_foc_pos, cam_up );
 
+        let anim_player = md5player::AnimPlayer::init_from_pose_collection( & posecollection );
+
+        //submesh triangle counts are fixed, so the picking bvhs are built once and refitted per pick
+        let mut md5_picker = Picker::default();
+        let md5_compute_first = md5comp::process( & posecollection, & mesh, 0, 0, 0. ).expect( "md5 compute unsuccessful" );
+        for ( i, s ) in md5_compute_first._submeshes.iter().enumerate() {
+            let pos = &md5_compute_first._batch_vert[ s._vert_offset * 3 .. ( s._vert_offset + s._vert_count ) * 3 ];
+            if let Err( e ) = md5_picker.add_mesh( i as u64, pos ) {
+                warn!( "submesh {} not pickable: {}", s._shader, e );
+            }
+        }
+
         let mut ret = GameLogic {
 
             _is_init: false,
@@ -404,12 +450,21 @@ impl IGameLogic for GameLogic {
             _path_shader_fs: String::new(),
             _state: Default::default(),
             _uicam: UiCam {
-                _trackball: TrackBall::new(500.,500.),
+                _trackball: TrackBall::new( WINDOW_SIZE.0, WINDOW_SIZE.1 ),
                 .. Default::default()
             },
             _md5: ( posecollection , mesh ),
-            _md5_precompute: Rc::new( animation ),
-            _obj_compute: Rc::new( obj_compute ),
+            _anim_player: anim_player,
+            _md5_materials: [ ( String::from( "models/obihb/qshambler/qshambler" ), String::from( "texture0" ) ),
+                              ( String::from( "models/obihb/qshambler/qshambler_teeth" ), String::from( "texture1" ) ) ]
+                .iter().cloned().collect(),
+            _obj_lod: Rc::new( obj_lod ),
+            _lod_policy: LodPolicy::ScreenSize( vec![ 0.5, 0.25, 0.1 ] ),
+            _lod_level: 0,
+            _show_obj: false,
+            _md5_pose_last: None,
+            _md5_picker: md5_picker,
+            _frame_delta: 0f32,
         };
         
         //lights
@@ -441,6 +496,10 @@ impl IGameLogic for GameLogic {
         Ok( () )
     }
 
+    fn set_frame_delta( & mut self, dt: f32 ) {
+        self._frame_delta = dt;
+    }
+
     ///computes changed game state given user inputs and current game state
     fn transition_states( & mut self, inputs: & [ InputFiltered ] ) -> GameStateChangePending {
         //todo
@@ -450,6 +509,34 @@ impl IGameLogic for GameLogic {
                 &InputFiltered::Button { key: KeyCode::Q, .. } => {
                     self._state._exit = true;
                 },
+                &InputFiltered::Button { key: KeyCode::P, state: State::Press } => {
+                    if self._anim_player.is_playing() {
+                        self._anim_player.pause();
+                    } else {
+                        self._anim_player.play();
+                    }
+                },
+                &InputFiltered::Button { key: KeyCode::R, state: State::Press } => {
+                    let speed = self._anim_player._speed;
+                    self._anim_player.set_speed( -speed );
+                },
+                &InputFiltered::Button { key: KeyCode::L, state: State::Press } => {
+                    let mode = match self._anim_player._loop_mode {
+                        md5player::LoopMode::Loop => md5player::LoopMode::PingPong,
+                        md5player::LoopMode::PingPong => md5player::LoopMode::Clamp,
+                        md5player::LoopMode::Clamp => md5player::LoopMode::Loop,
+                    };
+                    self._anim_player.set_loop_mode( mode );
+                },
+                &InputFiltered::Button { key: KeyCode::Num0, state: State::Press } => {
+                    self._anim_player.seek( 0f32 );
+                },
+                &InputFiltered::Button { key: KeyCode::MouseL, state: State::Press } => {
+                    self.select_part();
+                },
+                &InputFiltered::Button { key: KeyCode::O, state: State::Press } => {
+                    self._show_obj = !self._show_obj;
+                },
                 _ => {},
             };
             self._uicam.process( i );
@@ -534,26 +621,48 @@ impl IGameLogic for GameLogic {
 
         self._camera._pos_orig = pos_new;
 
-        // println!( "self._obj_compute: {:?}", self._obj_compute );
+        //advance animation by the time elapsed since the previous frame
+        self._anim_player.update( self._frame_delta );
         
         //dummy geometry to render
-        v.push( RenderObj::TestGeometry { _time_game: self._state._time_game,
-                                           _light: self._lights[0].clone(),
-                                           _camera: self._camera.clone(),
-                                           _md5_precompute: self._md5_precompute.clone(),
-        // v.push( RenderObj::TestGeometryObj { _time_game: self._state._time_game,
-        //                                       _light: self._lights[0].clone(),
-        //                                       _camera: self._camera.clone(),
-        //                                       _obj_compute: self._obj_compute.clone(),
-        } );
+        if self._show_obj {
+            let level = self._obj_lod.select( & self._camera, & self._lod_policy );
+            if level != self._lod_level {
+                debug!( "obj lod level: {}, triangles: {}", level, self._obj_lod._levels[ level ]._tri_count );
+                self._lod_level = level;
+            }
+            v.push( RenderObj::TestGeometryObj { _time_game: self._state._time_game,
+                                                  _light: self._lights[0].clone(),
+                                                  _camera: self._camera.clone(),
+                                                  _obj_lod: self._obj_lod.clone(),
+                                                  _lod_level: level,
+            } );
+        } else {
+            let mut md5_compute = match self._anim_player.compute( & self._md5.0, & self._md5.1 ) {
+                Ok( o ) => o,
+                Err( e ) => panic!( e ),
+            };
+            md5comp::map_submesh_textures( & mut md5_compute, & self._md5_materials );
+            self._md5_pose_last = Some( self._anim_player.clone() );
+            v.push( RenderObj::TestGeometry { _time_game: self._state._time_game,
+                                               _light: self._lights[0].clone(),
+                                               _camera: self._camera.clone(),
+                                               _md5_compute: md5_compute,
+            } );
+        }
         
         self._state._time_game += 1.;
 
         v
     }
-    fn filter_renderables( & mut self, _r: Vec< RenderObj > ) -> Vec< RenderObj > {
-        //todo: add spatial accelerator algo here
-        _r
+    fn view_frustum( & mut self ) -> Option< Frustum > {
+        Some( self._camera.frustum() )
+    }
+
+    fn report_cull_stats( & mut self, stats: CullStats ) {
+        if stats._culled > 0 {
+            debug!( "renderables culled: {}, visible: {}", stats._culled, stats._visible );
+        }
     }
 
     fn should_exit( & mut self ) -> bool {
@@ -561,6 +670,42 @@ impl IGameLogic for GameLogic {
     }
 }
 
+impl GameLogic {
+    ///reports the md5 submesh under the mouse cursor. only the md5 model is pickable
+    fn select_part( & mut self ) {
+        if self._show_obj {
+            return
+        }
+        let cc = match self._md5_pose_last {
+            Some( ref x ) => x.compute( & self._md5.0, & self._md5.1 ),
+            None => { return },
+        };
+        let cc = match cc {
+            Ok( o ) => o,
+            Err( e ) => {
+                warn!( "md5 compute for picking unsuccessful: {}", e );
+                return
+            },
+        };
+        for ( i, s ) in cc._submeshes.iter().enumerate() {
+            let pos = &cc._batch_vert[ s._vert_offset * 3 .. ( s._vert_offset + s._vert_count ) * 3 ];
+            if let Err( e ) = self._md5_picker.refit_mesh( i as u64, pos ) {
+                warn!( "submesh {} not pickable: {}", s._shader, e );
+            }
+        }
+        let ( x, y ) = self._uicam._mouse_pos;
+        match self._md5_picker.pick_screen( & self._camera, x, y, WINDOW_SIZE.0, WINDOW_SIZE.1 ) {
+            Some( h ) => {
+                info!( "selected part: {}, triangle: {:?}, point: {:?}, normal: {:?}",
+                       cc._submeshes[ h._id as usize ]._shader, h._tri, h._point, h._normal );
+            },
+            None => {
+                info!( "selected part: none" );
+            },
+        }
+    }
+}
+
 #[main]
 fn main() {
 
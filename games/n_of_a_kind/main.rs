@@ -15,9 +15,10 @@ use std::path::Path;
 
 use self::e2rcore::interface::i_ele;
 use self::e2rcore::interface::i_game_logic::IGameLogic;
-use self::e2rcore::interface::i_ui::{ InputFiltered, KeyCode, /*State, Coord*/ };
+use self::e2rcore::interface::i_ui::{ InputFiltered, KeyCode, State, /*Coord*/ };
 // use interface::i_camera::ICamera;
 use self::e2rcore::interface::i_scheduler::IScheduler;
+use self::e2rcore::interface::i_tween::ITween;
 
 use self::e2rcore::implement::render::renderer_gl;
 use self::e2rcore::implement::render::util_gl;
@@ -26,6 +27,13 @@ use self::e2rcore::implement::render::camera;
 use self::e2rcore::implement::render::light;
 // use self::e2rcore::implement::render::mesh;
 use self::e2rcore::implement::render::primitive;
+use self::e2rcore::implement::render::culling::{ ICullable, BoundingVolume, CullStats };
+use self::e2rcore::implement::render::picking::Picker;
+
+use self::e2rcore::implement::math::frustum::Frustum;
+use self::e2rcore::implement::math::easing::{ Ease, EaseMode };
+
+use self::e2rcore::implement::step::tween::{ Tween, Sequence, TweenManager };
 
 use self::e2rcore::implement::ui::ui_cam::UiCam;
 
@@ -41,12 +49,18 @@ use self::rand::distributions::{IndependentSample, Range};
 use std::env;
 
 use std::collections::{ HashSet, HashMap };
+use std::rc::Rc;
+use std::cell::Cell;
 
 use self::e2rcore::interface::i_kernel::IKernel;
 
 use self::e2rcore::implement::kernel::kernel_impl_001::Kernel;
+use self::e2rcore::implement::kernel::kernel_headless::KernelHeadless;
 
 //todo: put this somewhere else
+///window dimension created by the kernel
+const WINDOW_SIZE : ( f32, f32 ) = ( 500., 500. );
+
 pub fn file_open( file_path: & str ) -> Option<String> {
     let path = File::open( file_path ).expect("file path open invalid");
     let mut buf_reader = BufReader::new(path);
@@ -74,6 +88,8 @@ pub struct GameState {
     pub _piece_gen_distr: HashMap< GamePiece, f32 >,
 
     pub _is_player_turn: bool,
+    ///board cell last selected with the mouse
+    pub _selected: Option< (isize,isize) >,
 }
 
 impl Default for GameState {
@@ -114,6 +130,7 @@ impl Default for GameState {
             _piece_gen_distr: piece_distr,
 
             _is_player_turn: false,
+            _selected: None,
         }
     }
 }
@@ -207,9 +224,37 @@ impl From< (GameState, GameStateChangeApply) > for GameState {
 
 pub enum RenderObj {
     InitialRender { _path_shader_vs: String, _path_shader_fs: String },
-    BoardGeometry { _time_game: f32, _light: light::LightAdsPoint, _camera: camera::Cam, _gamestate: GameState },
+    BoardGeometry { _time_game: f32, _light: light::LightAdsPoint, _camera: camera::Cam, _gamestate: GameState, _tile_height: f32 },
+}
+
+
+impl ICullable for RenderObj {
+    fn bounding_volume( & self ) -> BoundingVolume {
+        match *self {
+            RenderObj::InitialRender{..} => BoundingVolume::Unbounded,
+            RenderObj::BoardGeometry{ ref _gamestate, _tile_height, .. } => {
+                let mut bv : Option< BoundingVolume > = None;
+                for i in 0.._gamestate._row {
+                    for j in 0.._gamestate._col {
+                        let b = board_tile( i, j, _tile_height ).bounding_volume();
+                        bv = Some( match bv {
+                            Some( x ) => x.union( & b ),
+                            None => b,
+                        } );
+                    }
+                }
+                bv.unwrap_or( BoundingVolume::Unbounded )
+            },
+        }
+    }
 }
 
+fn board_tile( i: isize, j: isize, height: f32 ) -> primitive::Poly6 {
+    primitive::Poly6 {
+        _pos: mat::Mat3x1 { _val: [ 5. * i as f32, 5. * j as f32, 0. ] },
+        _scale: mat::Mat3x1 { _val: [ 1., 1., height ] },
+        _radius: 5f32 }
+}
 
 impl From< RenderObj > for Vec< renderer_gl::Event > {
     fn from( _r: RenderObj ) -> Self {
@@ -242,15 +287,12 @@ impl From< RenderObj > for Vec< renderer_gl::Event > {
 
                 render_events
             },
-            RenderObj::BoardGeometry{ _time_game, _light, _camera, _gamestate } =>{
+            RenderObj::BoardGeometry{ _time_game, _light, _camera, _gamestate, _tile_height } =>{
                 let mut render_events = vec![];
 
                 for i in 0.._gamestate._row {
                     for j in 0.._gamestate._col {
-                        let prim_box = primitive::Poly6 {
-                            _pos: mat::Mat3x1 { _val: [ 5. * i as f32, 5. * j as f32, 0. ] },
-                            _scale: mat::Mat3x1 { _val: [ 1., 1., 0.05 ] },
-                            _radius: 5f32 };
+                        let prim_box = board_tile( i, j, _tile_height );
                         render_events.push( renderer_gl::Event::AddObj( i_ele::Ele::init( prim_box ) ) );
                     }
                 }
@@ -281,6 +323,11 @@ pub struct GameLogic {
     _path_shader_fs: String,
     _state: GameState,
     _uicam: UiCam,
+    _tweens: TweenManager,
+    ///camera distance multiplier for the intro zoom
+    _cam_zoom: Rc< Cell< f32 > >,
+    _tile_height: Rc< Cell< f32 > >,
+    _frame_delta: f32,
 }
 
 //todo
@@ -309,7 +356,18 @@ impl IGameLogic for GameLogic {
                 _trackball: TrackBall::new(500.,500.),
                 .. Default::default()
             },
+            _tweens: Default::default(),
+            _cam_zoom: Rc::new( Cell::new( 3. ) ),
+            _tile_height: Rc::new( Cell::new( 0.05 ) ),
+            _frame_delta: 0f32,
         };
+
+        //intro: zoom in on the board then pop the tiles
+        let intro = Sequence::init( vec![
+            Box::new( Tween::init( 3f32, 1f32, 2., Ease::Cubic( EaseMode::Out ) ).bind( ret._cam_zoom.clone() ) ) as Box< ITween >,
+            Box::new( Tween::init( 0.05f32, 0.3f32, 0.25, Ease::Back( EaseMode::Out ) ).yoyo( true ).bind( ret._tile_height.clone() ) ),
+        ] ).on_complete( || info!( "board ready." ) );
+        ret._tweens.add( intro );
         
         //lights
         let mut rng = rand::thread_rng();
@@ -352,6 +410,10 @@ impl IGameLogic for GameLogic {
         Ok( () )
     }
 
+    fn set_frame_delta( & mut self, dt: f32 ) {
+        self._frame_delta = dt;
+    }
+
     ///computes changed game state given user inputs and current game state
     fn transition_states( & mut self, inputs: & [ InputFiltered ] ) -> GameStateChangePending {
         //todo
@@ -361,6 +423,9 @@ impl IGameLogic for GameLogic {
                 &InputFiltered::Button { key: KeyCode::Q, .. } => {
                     self._state._exit = true;
                 },
+                &InputFiltered::Button { key: KeyCode::MouseL, state: State::Press } => {
+                    self.select_cell();
+                },
                 _ => {},
             };
             self._uicam.process( i );
@@ -419,6 +484,9 @@ impl IGameLogic for GameLogic {
         }
 
         
+        //advance animations by the kernel's frame time
+        self._tweens.advance( self._frame_delta as f64 );
+
         //update camera
         
         let focus = self._cameras[0]._focus.clone();
@@ -445,7 +513,9 @@ impl IGameLogic for GameLogic {
         let pos_update = rot_matrix.mul_mat4x1( & offset ).unwrap();
 
         let pos_new = focus.plus( & mat::Mat3x1 { _val: [ pos_update[0], pos_update[1], pos_update[2] ] } ).unwrap();
-        self._cameras[0].update_pos( pos_new, focus );
+        let zoom = self._cam_zoom.get();
+        let pos_view = focus.plus( & mat::Mat3x1 { _val: [ pos_update[0] * zoom, pos_update[1] * zoom, pos_update[2] * zoom ] } ).unwrap();
+        self._cameras[0].update_pos( pos_view, focus );
 
         self._cameras[0]._pos_orig = pos_new;
 
@@ -454,16 +524,22 @@ impl IGameLogic for GameLogic {
             _time_game: self._state._time_game,
             _light: self._lights[0].clone(),
             _camera: self._cameras[0].clone(),
-            _gamestate: self._state.clone()
+            _gamestate: self._state.clone(),
+            _tile_height: self._tile_height.get(),
         } );
         
         self._state._time_game -= 0.01;
 
         v
     }
-    fn filter_renderables( & mut self, _r: Vec< RenderObj > ) -> Vec< RenderObj > {
-        //todo: add spatial accelerator algo here
-        _r
+    fn view_frustum( & mut self ) -> Option< Frustum > {
+        Some( self._cameras[0].frustum() )
+    }
+
+    fn report_cull_stats( & mut self, stats: CullStats ) {
+        if stats._culled > 0 {
+            debug!( "renderables culled: {}, visible: {}", stats._culled, stats._visible );
+        }
     }
 
     fn should_exit( & mut self ) -> bool {
@@ -487,6 +563,20 @@ pub enum GamePiece {
 }
 
 impl GameLogic {
+    ///selects the board cell under the mouse cursor
+    pub fn select_cell( & mut self ) {
+        let mut picker = Picker::default();
+        for i in 0..self._state._row {
+            for j in 0..self._state._col {
+                picker.add_cullable( ( i * self._state._col + j ) as u64, & board_tile( i, j, self._tile_height.get() ) );
+            }
+        }
+        let ( x, y ) = self._uicam._mouse_pos;
+        self._state._selected = picker.pick_screen( & self._cameras[0], x, y, WINDOW_SIZE.0, WINDOW_SIZE.1 ).map( |h| {
+            ( h._id as isize / self._state._col, h._id as isize % self._state._col )
+        } );
+        info!( "selected cell: {:?}", self._state._selected );
+    }
     pub fn place_piece( & mut self, piece: GamePiece, to: (isize,isize) ) -> bool {
         if to.0 as usize >= self._state._board[0].len() ||
             to.1 as usize >= self._state._board[0].len() {
@@ -678,6 +768,19 @@ fn main() {
     
     pretty_env_logger::init_custom_env( "LOG_SETTING" );
     
+    //usage: n_of_a_kind [--headless <frames> [output.ppm]]
+    let args : Vec< String > = env::args().collect();
+    if args.len() > 1 && args[1] == "--headless" {
+        let frames = args.get( 2 ).and_then( |x| x.parse::< u64 >().ok() ).unwrap_or( 300 );
+        let mut k : KernelHeadless<GameLogic> = KernelHeadless::init( WINDOW_SIZE.0 as _, WINDOW_SIZE.1 as _, &[] ).unwrap();
+        let n = k.run_frames( frames ).unwrap();
+        info!( "headless run: {} frame(s)", n );
+        if let Some( path ) = args.get( 3 ) {
+            k._renderer.write_ppm( path ).unwrap();
+        }
+        return
+    }
+    
     let mut k : Kernel<GameLogic> = Kernel::new().unwrap();
     
     k.run().is_ok();